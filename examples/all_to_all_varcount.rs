@@ -0,0 +1,65 @@
+#![deny(warnings)]
+
+use std::mem::size_of;
+use std::panic::{self, AssertUnwindSafe};
+
+use mpi::datatype::{HeterogeneousPartition, HeterogeneousPartitionMut, Partition, PartitionMut};
+use mpi::traits::*;
+use mpi::Count;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let rank = world.rank();
+    let size = world.size();
+
+    // Process `rank` sends `r + 1` copies of its rank to process `r`.
+    let send_counts: Vec<Count> = (1..=size).collect();
+    let send_displs: Vec<Count> = (0..size).map(|r| r * (r + 1) / 2).collect();
+    let send_buf = vec![rank; (size * (size + 1) / 2) as usize];
+
+    let recv_counts: Vec<Count> = vec![rank + 1; size as usize];
+    let recv_displs: Vec<Count> = (0..size).map(|r| r * (rank + 1)).collect();
+    let expected: Vec<Count> = (0..size)
+        .flat_map(|r| std::iter::repeat(r).take((rank + 1) as usize))
+        .collect();
+
+    let mut recv_buf = vec![-1; (size * (rank + 1)) as usize];
+    {
+        let send_partition = Partition::new(&send_buf[..], &send_counts[..], &send_displs[..]);
+        let mut recv_partition =
+            PartitionMut::new(&mut recv_buf[..], &recv_counts[..], &recv_displs[..]);
+        world.all_to_all_varcount_into(&send_partition, &mut recv_partition);
+    }
+    assert_eq!(recv_buf, expected);
+
+    let elem_size = size_of::<Count>() as Count;
+    let send_byte_displs: Vec<Count> = send_displs.iter().map(|d| d * elem_size).collect();
+    let recv_byte_displs: Vec<Count> = recv_displs.iter().map(|d| d * elem_size).collect();
+    let datatypes = vec![Count::equivalent_datatype(); size as usize];
+
+    let mut recv_buf = vec![-1; (size * (rank + 1)) as usize];
+    {
+        let send_partition = HeterogeneousPartition::new(
+            &send_buf[..],
+            &send_counts[..],
+            &send_byte_displs[..],
+            &datatypes[..],
+        );
+        let mut recv_partition = HeterogeneousPartitionMut::new(
+            &mut recv_buf[..],
+            &recv_counts[..],
+            &recv_byte_displs[..],
+            &datatypes[..],
+        );
+        world.all_to_all_heterogeneous_into(&send_partition, &mut recv_partition);
+    }
+    assert_eq!(recv_buf, expected);
+    // Partitions reaching past the end of the buffer are rejected.
+    let past_end = [send_buf.len() as Count * elem_size];
+    let partition = panic::catch_unwind(AssertUnwindSafe(|| {
+        HeterogeneousPartition::new(&send_buf[..], &[1][..], &past_end[..], &datatypes[..1]);
+    }));
+    assert!(partition.is_err());
+}
@@ -0,0 +1,41 @@
+#![deny(warnings)]
+
+use mpi::environment::MpiAllocated;
+use mpi::point_to_point as p2p;
+use mpi::traits::*;
+use mpi::window::Window;
+use mpi::Rank;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    // Send and receive directly from memory allocated by MPI.
+    let data = MpiAllocated::from_elem(rank, 8).unwrap();
+    let mut received = MpiAllocated::from_slice(&[-1 as Rank; 8]).unwrap();
+    assert_eq!(data.len(), 8);
+    p2p::send_receive_into(&data, &next_process, &mut received, &previous_process);
+    assert!(received.iter().all(|&x| x == previous_process.rank()));
+
+    // Expose it to remote memory access, every process writes its rank into its slot on every
+    // other process.
+    let mut memory = MpiAllocated::from_elem(-1 as Rank, size as usize).unwrap();
+    mpi::request::scope(|scope| {
+        let mut window = Window::create(&world, scope, &mut memory[..]);
+        window.fence_epoch(|epoch| {
+            for target in 0..size {
+                epoch.put(&rank, target, rank as mpi::Address);
+            }
+        });
+    });
+    assert_eq!((0..size).collect::<Vec<_>>(), &memory[..]);
+
+    // Empty allocations do not call MPI.
+    let empty = MpiAllocated::<f64>::from_slice(&[]).unwrap();
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,100 @@
+#![deny(warnings)]
+//! Awaiting nonblocking operations
+use std::future::IntoFuture;
+
+use futures::executor::block_on;
+use futures::future::join_all;
+use mpi::request::StaticScope;
+use mpi::topology::ThreadSafe;
+use mpi::traits::*;
+use mpi::Threading;
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
+    let Some(world) = universe.thread_safe_world() else {
+        assert_ne!(threading, Threading::Multiple);
+        return;
+    };
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    // Awaited requests are completed by a progress thread, so their buffers have to outlive them.
+    // The futures return the buffers together with the status.
+    let x: &'static i32 = Box::leak(Box::new(rank));
+    let send = thread_safe(next_process.immediate_send(StaticScope, x)).into_future();
+    let receive =
+        thread_safe(previous_process.immediate_receive_into(StaticScope, Box::leak(Box::new(-1))))
+            .into_future();
+    let (_, (y, status)) = block_on(async { futures::join!(send, receive) });
+    assert_eq!(status.source_rank(), previous_process.rank());
+    assert_eq!(*y, previous_process.rank());
+
+    // The futures are `Send`, so they can be awaited on another thread.
+    let send = thread_safe(next_process.immediate_send(StaticScope, x)).into_future();
+    let receive =
+        thread_safe(previous_process.immediate_receive_into(StaticScope, Box::leak(Box::new(-1))))
+            .into_future();
+    let (_, (y, _)) = std::thread::spawn(move || block_on(async { futures::join!(send, receive) }))
+        .join()
+        .unwrap();
+    assert_eq!(*y, previous_process.rank());
+
+    // Futures of many requests can be combined like any other futures
+    let sends: Vec<_> = world
+        .processes()
+        .map(|process| thread_safe(process.immediate_send(StaticScope, x)).into_future())
+        .collect();
+    let receives: Vec<_> = (0..size)
+        .map(|_| {
+            let value: &'static mut i32 = Box::leak(Box::new(-1));
+            thread_safe(
+                world
+                    .any_process()
+                    .immediate_receive_into(StaticScope, value),
+            )
+            .into_future()
+        })
+        .collect();
+    let mut values: Vec<i32> = block_on(async {
+        let values = join_all(receives).await;
+        join_all(sends).await;
+        values.into_iter().map(|(value, _)| *value).collect()
+    });
+    values.sort_unstable();
+    assert_eq!(values, (0..size).collect::<Vec<_>>());
+
+    // Messages of unknown length can be awaited as well
+    let xs: &'static [i32] = Vec::leak(vec![rank; rank as usize + 1]);
+    let send = thread_safe(next_process.immediate_send(StaticScope, xs)).into_future();
+    let receive = previous_process.immediate_receive_vec::<i32>();
+    let (_, (msg, _)) = block_on(async { futures::join!(send, receive) });
+    assert_eq!(
+        msg,
+        vec![previous_process.rank(); previous_process.rank() as usize + 1]
+    );
+
+    block_on(thread_safe(world.immediate_barrier()).into_future());
+
+    // A future that is dropped before its operation has completed has the operation cancelled.
+    // The progress thread is stopped before MPI is finalized when `universe` is dropped.
+    let unmatched: &'static mut i32 = Box::leak(Box::new(-1));
+    let receive = thread_safe(previous_process.immediate_receive_into_with_tag(
+        StaticScope,
+        unmatched,
+        UNMATCHED_TAG,
+    ))
+    .into_future();
+    drop(receive);
+}
+
+/// A tag that no process sends with
+const UNMATCHED_TAG: mpi::Tag = 99;
+
+/// Wrap a request of the static scope so that it can be awaited.
+fn thread_safe<T>(request: T) -> ThreadSafe<T> {
+    ThreadSafe::new(request)
+        .unwrap_or_else(|_| unreachable!("MPI has been initialized with `Threading::Multiple`"))
+}
@@ -63,4 +63,6 @@ fn main() {
     println!("Got from comm: {:?}", comm.get_attr::<MyData2>());
     let mut comm2 = comm.duplicate();
     with_trait_object(&mut comm2);
+    comm2.delete_attr::<MyData2>();
+    assert_eq!(comm2.get_attr::<MyData2>(), None);
 }
@@ -0,0 +1,44 @@
+#![deny(warnings)]
+use mpi::datatype::UserDatatype;
+use mpi::traits::*;
+use mpi::window::Window;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Layout(&'static str);
+
+impl DatatypeAttribute for Layout {
+    const CLONE_ON_DUP: bool = true;
+}
+
+#[derive(Debug, PartialEq)]
+struct Generation(u64);
+
+impl WindowAttribute for Generation {}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let mut pair = UserDatatype::contiguous(2, &i32::equivalent_datatype());
+    assert_eq!(pair.get_attr::<Layout>(), None);
+    pair.set_attr(Layout("pair"));
+    assert_eq!(pair.get_attr::<Layout>(), Some(&Layout("pair")));
+
+    let copy = pair.clone();
+    assert_eq!(copy.get_attr::<Layout>(), Some(&Layout("pair")));
+
+    pair.delete_attr::<Layout>();
+    assert_eq!(pair.get_attr::<Layout>(), None);
+    assert_eq!(copy.get_attr::<Layout>(), Some(&Layout("pair")));
+
+    let mut buf = vec![0i32; 4];
+    mpi::request::scope(|scope| {
+        let mut window = Window::create(&world, scope, &mut buf[..]);
+        window.set_attr(Generation(1));
+        assert_eq!(window.get_attr::<Generation>(), Some(&Generation(1)));
+        window.set_attr(Generation(2));
+        assert_eq!(window.get_attr::<Generation>(), Some(&Generation(2)));
+        window.delete_attr::<Generation>();
+        assert_eq!(window.get_attr::<Generation>(), None);
+    });
+}
@@ -31,4 +31,48 @@ fn main() {
     root_process.broadcast_into(&mut a);
     println!("Rank {} received value: {:?}.", world.rank(), &a[..]);
     assert_eq!(&a[..], &[2, 4, 8, 16]);
+    println!();
+
+    let mut b: Box<[u64]>;
+    if world.rank() == root_rank {
+        b = vec![3, 5, 7].into_boxed_slice();
+        println!("Root broadcasting value: {:?}.", &b);
+    } else {
+        b = vec![0; 3].into_boxed_slice();
+    }
+    root_process.broadcast_into(&mut b);
+    println!("Rank {} received value: {:?}.", world.rank(), &b);
+    assert_eq!(&b[..], &[3, 5, 7]);
+    println!();
+
+    let mut v;
+    let mut s;
+    if world.rank() == root_rank {
+        v = vec![1_i32, 1, 2, 3, 5, 8];
+        s = String::from("Fibonacci");
+        println!("Root broadcasting value: {:?} {:?}.", s, &v[..]);
+    } else {
+        v = Vec::new();
+        s = String::from("overwritten");
+    }
+    root_process.broadcast_owned(&mut v);
+    root_process.broadcast_owned(&mut s);
+    println!(
+        "Rank {} received value: {:?} {:?}.",
+        world.rank(),
+        s,
+        &v[..]
+    );
+    assert_eq!(&v[..], &[1, 1, 2, 3, 5, 8]);
+    assert_eq!(s, "Fibonacci");
+
+    let mut greeting = if world.rank() == root_rank {
+        String::from("Grüß Gott")
+    } else {
+        String::new()
+    };
+    root_process
+        .broadcast_string(&mut greeting)
+        .expect("the root sends valid UTF-8");
+    assert_eq!(greeting, "Grüß Gott");
 }
@@ -1,5 +1,6 @@
 #![deny(warnings)]
 
+use mpi::environment::{BufferAttachment, BSEND_OVERHEAD};
 use mpi::request::WaitGuard;
 use mpi::traits::*;
 
@@ -29,4 +30,23 @@ fn main() {
         world.this_process().buffered_send(&x[..]);
     });
     assert_eq!(x, y);
+
+    // Attach a buffer sized for exactly one message instead.
+    universe.detach_buffer();
+    let world = universe.world();
+    let size = world.pack_size(1024, &f32::equivalent_datatype()) as usize + BSEND_OVERHEAD;
+    let attachment = BufferAttachment::new(size).unwrap();
+    assert_eq!(attachment.size(), size);
+
+    let mut z = vec![0.0; 1024];
+    mpi::request::scope(|scope| {
+        let _rreq = WaitGuard::from(
+            world
+                .any_process()
+                .immediate_receive_into(scope, &mut z[..]),
+        );
+        world.this_process().buffered_send(&x[..]);
+    });
+    assert_eq!(x, z);
+    drop(attachment);
 }
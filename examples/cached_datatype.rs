@@ -0,0 +1,89 @@
+#![deny(warnings)]
+#![allow(clippy::float_cmp)]
+
+#[macro_use]
+extern crate memoffset;
+
+use std::mem;
+
+use mpi::datatype::{self, DatatypeRef, MutView, UncommittedUserDatatype, UserDatatype};
+use mpi::traits::*;
+use mpi::Address;
+
+#[derive(Default, Clone, Copy)]
+struct Particle {
+    position: [f64; 3],
+    id: u32,
+}
+
+// The datatype is committed once and reused for every message.
+unsafe impl Equivalence for Particle {
+    type Out = DatatypeRef<'static>;
+    fn equivalent_datatype() -> Self::Out {
+        datatype::cached_datatype::<Self>(|| {
+            UserDatatype::resized(
+                &UncommittedUserDatatype::structured(
+                    &[3, 1],
+                    &[
+                        offset_of!(Particle, position) as Address,
+                        offset_of!(Particle, id) as Address,
+                    ],
+                    &[f64::equivalent_datatype(), u32::equivalent_datatype()],
+                ),
+                0,
+                mem::size_of::<Particle>() as Address,
+            )
+        })
+    }
+}
+
+/// Marker for the datatype of the first column of a 4 x 4 matrix
+struct FirstColumn;
+
+fn first_column() -> DatatypeRef<'static> {
+    datatype::cached_datatype::<FirstColumn>(|| {
+        UserDatatype::vector(4, 1, 4, &i32::equivalent_datatype())
+    })
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let root_process = world.process_at_rank(0);
+
+    assert_eq!(
+        Particle::equivalent_datatype().as_raw(),
+        Particle::equivalent_datatype().as_raw()
+    );
+
+    for step in 0..10 {
+        let mut particles = if rank == 0 {
+            [Particle {
+                position: [step as f64, 1.0, 2.0],
+                id: step,
+            }; 3]
+        } else {
+            [Particle::default(); 3]
+        };
+        root_process.broadcast_into(&mut particles[..]);
+        for particle in &particles {
+            assert_eq!(particle.position, [step as f64, 1.0, 2.0]);
+            assert_eq!(particle.id, step);
+        }
+    }
+
+    for step in 0..10 {
+        let mut matrix = [0; 16];
+        if rank == 0 {
+            for (i, row) in matrix.chunks_mut(4).enumerate() {
+                row[0] = step * 10 + i as i32;
+            }
+        }
+        let mut column = MutView::new(&mut matrix[..], 1, &first_column());
+        root_process.broadcast_into(&mut column);
+        for (i, row) in matrix.chunks(4).enumerate() {
+            assert_eq!(row[0], step * 10 + i as i32);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#![deny(warnings)]
+//! Point to point communication with datatype checks
+use std::panic::{self, AssertUnwindSafe};
+
+use mpi::datatype::{UserDatatype, View};
+use mpi::environment::{BufferAttachment, BSEND_OVERHEAD};
+use mpi::point_to_point as p2p;
+use mpi::request;
+use mpi::traits::*;
+
+fn main() {
+    // Usually set when launching the job, e.g. `RSMPI_CHECKED=1 mpiexec ...`
+    std::env::set_var("RSMPI_CHECKED", "1");
+
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    // A message may be shorter than the receive buffer
+    let x = [rank as u64; 3];
+    let mut y = [0_u64; 4];
+    let status = p2p::send_receive_into(&x[..], &next_process, &mut y[..], &previous_process);
+    assert_eq!(status.count(u64::equivalent_datatype()), 3);
+    assert_eq!(y[..3], [previous_process.rank() as u64; 3]);
+
+    // Derived datatypes match if they consist of the same sequence of predefined datatypes
+    let pair = UserDatatype::contiguous(2, &u64::equivalent_datatype());
+    let view = unsafe { View::with_count_and_datatype(&x[..2], 1, &pair) };
+    next_process.send(&view);
+    let (pairs, _) = previous_process.receive_vec::<u64>();
+    assert_eq!(pairs, [previous_process.rank() as u64; 2]);
+
+    // Buffered mode sends buffer the header as well, which is sent before the message is received.
+    let message_size = world.pack_size(3, &u64::equivalent_datatype()) as usize + BSEND_OVERHEAD;
+    let header_size = world.pack_size(2, &u64::equivalent_datatype()) as usize + BSEND_OVERHEAD;
+    let attachment = BufferAttachment::new(message_size + header_size).unwrap();
+    next_process.buffered_send(&x[..]);
+    let (buffered, _) = previous_process.receive_vec::<u64>();
+    assert_eq!(buffered, [previous_process.rank() as u64; 3]);
+    drop(attachment);
+
+    // Immediate operations would not match the headers of the blocking ones, so they are
+    // rejected before anything is sent or received.
+    let mut z = [0_u64; 3];
+    let send = panic::catch_unwind(AssertUnwindSafe(|| {
+        request::scope(|scope| next_process.immediate_send(scope, &x[..]).wait());
+    }));
+    assert!(send.is_err());
+    let receive = panic::catch_unwind(AssertUnwindSafe(|| {
+        request::scope(|scope| {
+            previous_process
+                .immediate_receive_into(scope, &mut z[..])
+                .wait()
+        });
+    }));
+    assert!(receive.is_err());
+
+    // Blocking messages sent afterwards still match up
+    next_process.send(&x[..]);
+    previous_process.receive_into(&mut z[..]);
+    assert_eq!(z, [previous_process.rank() as u64; 3]);
+}
@@ -24,8 +24,8 @@ fn main() {
     let t = UserDatatype::contiguous(3, &Rank::equivalent_datatype());
     let status;
     {
-        let v1 = unsafe { View::with_count_and_datatype(&b1[..], 1, &t) };
-        let mut v2 = unsafe { MutView::with_count_and_datatype(&mut b2[..], 1, &t) };
+        let v1 = View::new(&b1[..], 1, &t);
+        let mut v2 = MutView::new(&mut b2[..], 1, &t);
         status = p2p::send_receive_into(&v1, &next_process, &mut v2, &previous_process);
     }
 
@@ -0,0 +1,32 @@
+#![deny(warnings)]
+//! The buffer counts of collectives with equally sized parts are checked before MPI is called
+use std::panic::{self, AssertUnwindSafe};
+
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size() as usize;
+
+    // The receive buffer holds one element more than there are processes.
+    let x = 1_u32;
+    let mut too_long = vec![0_u32; size + 1];
+    assert!(rejected(|| world.all_gather_into(&x, &mut too_long[..])));
+
+    // The parts sent and received differ in size.
+    let send = vec![1_u32; size];
+    let mut wider = vec![0_u64; size];
+    assert!(rejected(|| world.all_to_all_into(&send[..], &mut wider[..])));
+
+    // The matching buffers still work afterwards.
+    let mut received = vec![0_u32; size];
+    world.all_to_all_into(&send[..], &mut received[..]);
+    assert_eq!(received, send);
+}
+
+/// Whether `f` panics. The checks panic on all processes alike, so no process is left waiting in
+/// a collective.
+fn rejected(f: impl FnOnce()) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(f)).is_err()
+}
@@ -0,0 +1,52 @@
+#![deny(warnings)]
+//! Passing device memory to a CUDA-aware MPI library
+use mpi::cuda::{self, Device, DeviceBuffer, DeviceBufferMut, DeviceMut};
+use mpi::point_to_point as p2p;
+use mpi::traits::*;
+
+/// Stands in for a wrapper around the device allocation of a CUDA binding like `cust` or `cudarc`,
+/// for which this crate does not implement `DeviceBuffer` itself.
+///
+/// CUDA-aware MPI libraries check where the memory behind a pointer lives, so host memory is
+/// accepted as well and the example runs without a GPU.
+struct Allocation(Vec<i32>);
+
+unsafe impl DeviceBuffer for Allocation {
+    type Item = i32;
+    fn device_ptr(&self) -> *const i32 {
+        self.0.as_ptr()
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+unsafe impl DeviceBufferMut for Allocation {
+    fn device_ptr_mut(&mut self) -> *mut i32 {
+        self.0.as_mut_ptr()
+    }
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    if !cuda::is_cuda_aware() {
+        // Device pointers would be treated as host pointers.
+        return;
+    }
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    let data = Allocation(vec![rank; 4]);
+    let mut received = Allocation(vec![-1; 4]);
+    p2p::send_receive_into(
+        &Device::new(&data),
+        &next_process,
+        &mut DeviceMut::new(&mut received),
+        &previous_process,
+    );
+    assert!(received.0.iter().all(|&x| x == previous_process.rank()));
+}
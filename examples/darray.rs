@@ -0,0 +1,50 @@
+#![deny(warnings)]
+
+use mpi::datatype::{Distribution, Order, UserDatatype, View};
+use mpi::point_to_point as p2p;
+use mpi::topology::Rank;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let this_process = world.this_process();
+
+    let global = (0..4 * size).collect::<Vec<_>>();
+
+    // Each process owns a contiguous block of four elements.
+    let block = UserDatatype::distributed_array(
+        size,
+        rank,
+        &[4 * size],
+        &[Distribution::Block(None)],
+        &[size],
+        Order::C,
+        &Rank::equivalent_datatype(),
+    );
+    let mut local = vec![-1; 4];
+    {
+        let v = unsafe { View::with_count_and_datatype(&global[..], 1, &block) };
+        p2p::send_receive_into(&v, &this_process, &mut local[..], &this_process);
+    }
+    assert_eq!(local, (4 * rank..4 * rank + 4).collect::<Vec<_>>());
+
+    // The elements are dealt out to the processes one by one.
+    let cyclic = UserDatatype::distributed_array(
+        size,
+        rank,
+        &[4 * size],
+        &[Distribution::Cyclic(None)],
+        &[size],
+        Order::C,
+        &Rank::equivalent_datatype(),
+    );
+    let mut local = vec![-1; 4];
+    {
+        let v = unsafe { View::with_count_and_datatype(&global[..], 1, &cyclic) };
+        p2p::send_receive_into(&v, &this_process, &mut local[..], &this_process);
+    }
+    assert_eq!(local, (0..4).map(|i| rank + i * size).collect::<Vec<_>>());
+}
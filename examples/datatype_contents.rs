@@ -0,0 +1,49 @@
+#![deny(warnings)]
+
+use mpi::datatype::{Combiner, ConstituentDatatype, Contents, UserDatatype};
+use mpi::traits::*;
+
+fn main() {
+    let _universe = mpi::initialize().unwrap();
+
+    let int_type = i32::equivalent_datatype();
+    assert_eq!(int_type.get_envelope().combiner, Combiner::Named);
+    assert!(matches!(int_type.get_contents(), Contents::Named));
+
+    let vector = UserDatatype::vector(2, 3, 4, &int_type);
+    let resized = UserDatatype::resized(&vector, 0, 64);
+
+    let envelope = resized.get_envelope();
+    assert_eq!(envelope.combiner, Combiner::Resized);
+    assert_eq!(envelope.num_addresses, 2);
+    assert_eq!(envelope.num_datatypes, 1);
+
+    match resized.get_contents() {
+        Contents::Resized {
+            lb,
+            extent,
+            oldtype,
+        } => {
+            assert_eq!(lb, 0);
+            assert_eq!(extent, 64);
+            assert!(matches!(oldtype, ConstituentDatatype::Derived(_)));
+            match oldtype.get_contents() {
+                Contents::Vector {
+                    count,
+                    blocklength,
+                    stride,
+                    oldtype,
+                } => {
+                    assert_eq!((count, blocklength, stride), (2, 3, 4));
+                    assert!(matches!(oldtype, ConstituentDatatype::Named(_)));
+                    assert_eq!(oldtype.as_raw(), int_type.as_raw());
+                }
+                _ => panic!("Expected a vector datatype."),
+            }
+        }
+        _ => panic!("Expected a resized datatype."),
+    }
+
+    let dup = vector.dup();
+    assert_eq!(dup.get_envelope().combiner, Combiner::Dup);
+}
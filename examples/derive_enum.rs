@@ -0,0 +1,56 @@
+#![deny(warnings)]
+//! Fieldless enums are sent as their integer representation and checked when they are received
+use mpi::request::WaitGuard;
+use mpi::traits::*;
+
+#[derive(Equivalence, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+enum Color {
+    Red = 1,
+    Green = 2,
+    Blue = 4,
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    let colors = [Color::Red, Color::Green, Color::Blue];
+    let color = colors[rank as usize % 3];
+    let (received, _) = mpi::request::scope(|scope| {
+        let _send = WaitGuard::from(next_process.immediate_send(scope, &color));
+        previous_process.receive::<u8>()
+    });
+    assert_eq!(
+        Color::try_from(received),
+        Ok(colors[previous_process.rank() as usize % 3])
+    );
+
+    // Slices of enums are sent as slices of their representation.
+    let sent: Vec<u8> = colors[rank as usize % 3..]
+        .iter()
+        .map(|&color| color as u8)
+        .collect();
+    let received = mpi::request::scope(|scope| {
+        let _send = WaitGuard::from(next_process.immediate_send(scope, &sent[..]));
+        previous_process.receive_vec::<u8>().0
+    });
+    let received: Vec<Color> = received
+        .into_iter()
+        .map(|color| Color::try_from(color).unwrap())
+        .collect();
+    assert_eq!(received, colors[previous_process.rank() as usize % 3..]);
+
+    // Values that are not a discriminant of the enum are rejected.
+    let invalid = 3_u8;
+    let (received, _) = mpi::request::scope(|scope| {
+        let _send = WaitGuard::from(next_process.immediate_send(scope, &invalid));
+        previous_process.receive::<u8>()
+    });
+    assert_eq!(Color::try_from(received), Err(3));
+}
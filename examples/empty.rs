@@ -0,0 +1,46 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::datatype::Empty;
+use mpi::point_to_point as p2p;
+use mpi::request::WaitGuard;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    // A message without contents
+    let status = p2p::send_receive_into(&Empty, &next_process, &mut Empty, &previous_process);
+    assert_eq!(status.source_rank(), previous_process.rank());
+    assert_eq!(status.count(u8::equivalent_datatype()), 0);
+
+    // Empty slices of any element type work as well
+    let nothing: [f64; 0] = [];
+    let status = p2p::send_receive_into(&nothing[..], &next_process, &mut Empty, &previous_process);
+    assert_eq!(status.count(u8::equivalent_datatype()), 0);
+
+    // Messages of unknown length may turn out to be empty
+    mpi::request::scope(|scope| {
+        let _sreq = WaitGuard::from(next_process.immediate_send(scope, &nothing[..]));
+        let (msg, _) = previous_process.receive_vec::<f64>();
+        assert!(msg.is_empty());
+    });
+
+    // Collectives on empty buffers
+    let root_process = world.process_at_rank(0);
+    root_process.broadcast_into(&mut Empty);
+    world.all_gather_into(&Empty, &mut Empty);
+
+    let mut received: Vec<f64> = Vec::new();
+    world.all_to_all_into(&nothing[..], &mut received[..]);
+    assert!(received.is_empty());
+
+    let mut sum = [0.0f64; 0];
+    world.all_reduce_into(&nothing[..], &mut sum[..], SystemOperation::sum());
+}
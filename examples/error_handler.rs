@@ -0,0 +1,57 @@
+#![deny(warnings)]
+
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use mpi::traits::*;
+use mpi::{ffi, Error};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let errors = Arc::new(AtomicUsize::new(0));
+    {
+        let errors = errors.clone();
+        // Only the raw `MPI_Send()`s below fail, and their error codes are checked.
+        unsafe {
+            world.set_error_handler(move |error| {
+                assert_eq!(error.class().raw(), ffi::MPI_ERR_RANK as Error);
+                errors.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+    }
+
+    // Sending to a rank outside of the communicator is an error.
+    let x = 0i32;
+    let code = unsafe {
+        ffi::MPI_Send(
+            &x as *const i32 as *const c_void,
+            1,
+            i32::equivalent_datatype().as_raw(),
+            world.size(),
+            0,
+            world.as_raw(),
+        )
+    };
+    assert_ne!(code, ffi::MPI_SUCCESS as Error);
+    assert_eq!(errors.load(Ordering::SeqCst), 1);
+
+    // Duplicates keep the handler.
+    let dup = world.duplicate();
+    let code = unsafe {
+        ffi::MPI_Send(
+            &x as *const i32 as *const c_void,
+            1,
+            i32::equivalent_datatype().as_raw(),
+            dup.size(),
+            0,
+            dup.as_raw(),
+        )
+    };
+    assert_ne!(code, ffi::MPI_SUCCESS as Error);
+    assert_eq!(errors.load(Ordering::SeqCst), 2);
+
+    world.set_errors_are_fatal();
+}
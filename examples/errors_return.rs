@@ -0,0 +1,51 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::traits::*;
+use mpi::{ffi, Error, MpiError};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    // Only the `try_` variants below fail, and they return their error codes.
+    unsafe { world.set_errors_return() };
+
+    // Tags have to be non-negative.
+    let x = 0i32;
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let error = next_process.try_send_with_tag(&x, -5).unwrap_err();
+    assert_eq!(error.class().raw(), ffi::MPI_ERR_TAG as Error);
+    assert!(!error.message().is_empty());
+
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+    let error = previous_process
+        .try_receive_with_tag::<i32>(-5)
+        .unwrap_err();
+    assert_eq!(error.class().raw(), ffi::MPI_ERR_TAG as Error);
+
+    let error = MpiError::from(error);
+    assert!(!error.to_string().is_empty());
+
+    // The bitwise operations are not defined for floating point numbers.
+    let mut sum = 0.0f64;
+    assert!(world
+        .try_all_reduce_into(&1.0f64, &mut sum, SystemOperation::bitwise_and())
+        .is_err());
+
+    // Operations that succeed return their results as usual.
+    world
+        .try_all_reduce_into(&1.0f64, &mut sum, SystemOperation::sum())
+        .unwrap();
+    assert_eq!(sum, f64::from(size));
+    let (received, _) = mpi::request::scope(|scope| {
+        let _send = mpi::request::WaitGuard::from(next_process.immediate_send(scope, &rank));
+        previous_process.try_receive::<i32>().unwrap()
+    });
+    assert_eq!(received, previous_process.rank());
+    world.try_barrier().unwrap();
+
+    world.set_errors_are_fatal();
+}
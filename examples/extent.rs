@@ -0,0 +1,31 @@
+#![deny(warnings)]
+
+use std::mem;
+
+use mpi::datatype::UserDatatype;
+use mpi::traits::*;
+use mpi::Address;
+
+fn main() {
+    let _universe = mpi::initialize().unwrap();
+
+    let int_size = mem::size_of::<i32>() as Address;
+
+    let int_type = i32::equivalent_datatype();
+    assert_eq!(int_type.size() as Address, int_size);
+    assert_eq!(int_type.extent(), int_size);
+    assert_eq!(int_type.lower_bound(), 0);
+
+    // Two blocks of two integers with the starts of the blocks three integers apart.
+    let t = UserDatatype::vector(2, 2, 3, &int_type);
+    assert_eq!(t.size() as Address, 4 * int_size);
+    assert_eq!(t.extent(), 5 * int_size);
+    assert_eq!(t.lower_bound(), 0);
+    assert_eq!(t.true_extent(), 5 * int_size);
+    assert_eq!(t.true_lower_bound(), 0);
+
+    // A receive buffer for `count` elements of `t` has to span `count` extents.
+    let count = 3;
+    let buffer = vec![0i32; (count * t.extent() / int_size) as usize];
+    assert_eq!(buffer.len(), 15);
+}
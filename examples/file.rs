@@ -0,0 +1,42 @@
+#![deny(warnings)]
+
+use std::mem::size_of;
+
+use mpi::io::{AccessMode, Offset};
+use mpi::traits::*;
+use mpi::{File, Rank};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let path = std::env::temp_dir().join("rsmpi_file_example.dat");
+    let file = File::open(
+        &world,
+        &path,
+        AccessMode::CREATE | AccessMode::READ_WRITE | AccessMode::DELETE_ON_CLOSE,
+    )
+    .unwrap();
+
+    // Every process writes two copies of its rank into its block of the file.
+    let block = 2 * size_of::<Rank>() as Offset;
+    let status = file
+        .write_at_all(rank as Offset * block, &[rank, rank][..])
+        .unwrap();
+    assert_eq!(2, status.count(Rank::equivalent_datatype()));
+    file.sync().unwrap();
+    world.barrier();
+    file.sync().unwrap();
+    assert_eq!(size as Offset * block, file.size().unwrap());
+
+    // Every process reads the block of its right neighbor.
+    let right = (rank + 1) % size;
+    let mut neighbor = [0 as Rank; 2];
+    file.read_at(right as Offset * block, &mut neighbor[..])
+        .unwrap();
+    assert_eq!([right, right], neighbor);
+
+    file.close().unwrap();
+}
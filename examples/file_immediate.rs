@@ -0,0 +1,59 @@
+#![deny(warnings)]
+
+use std::mem::size_of;
+
+use mpi::io::{AccessMode, Offset};
+use mpi::request::WaitGuard;
+use mpi::traits::*;
+use mpi::{File, Rank};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let path = std::env::temp_dir().join("rsmpi_file_immediate_example.dat");
+    let file = File::open(
+        &world,
+        &path,
+        AccessMode::CREATE | AccessMode::READ_WRITE | AccessMode::DELETE_ON_CLOSE,
+    )
+    .unwrap();
+
+    let block = 2 * size_of::<Rank>() as Offset;
+    let offset = rank as Offset * block;
+
+    // Write the block of the calling process while doing other work.
+    let local = [rank, 2 * rank];
+    mpi::request::scope(|scope| {
+        let _guard = WaitGuard::from(file.immediate_write_at(scope, offset, &local[..]).unwrap());
+        // ... other work, the write completes when the guard is dropped
+    });
+
+    file.sync().unwrap();
+    world.barrier();
+    file.sync().unwrap();
+
+    // Read the block of the right neighbor as a split collective operation.
+    let right = (rank + 1) % size;
+    let mut neighbor = [0 as Rank; 2];
+    mpi::request::scope(|scope| {
+        let split = file
+            .read_at_all_begin(scope, right as Offset * block, &mut neighbor[..])
+            .unwrap();
+        let status = split.end().unwrap();
+        assert_eq!(2, status.count(Rank::equivalent_datatype()));
+    });
+    assert_eq!([right, 2 * right], neighbor);
+
+    // Read the block of the calling process via the individual file pointer.
+    let mut own = [0 as Rank; 2];
+    file.seek(std::io::SeekFrom::Start(offset as u64)).unwrap();
+    mpi::request::scope(|scope| {
+        file.immediate_read(scope, &mut own[..]).unwrap().wait();
+    });
+    assert_eq!(local, own);
+
+    file.close().unwrap();
+}
@@ -0,0 +1,52 @@
+#![deny(warnings)]
+
+use mpi::datatype::{Order, UserDatatype};
+use mpi::io::AccessMode;
+use mpi::traits::*;
+use mpi::{File, Rank};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let path = std::env::temp_dir().join("rsmpi_file_view_example.dat");
+    let file = File::open(
+        &world,
+        &path,
+        AccessMode::CREATE | AccessMode::READ_WRITE | AccessMode::DELETE_ON_CLOSE,
+    )
+    .unwrap();
+
+    // A 2 x (2 * size) global array in row-major order, every process owns a 2 x 2 block.
+    let block = UserDatatype::subarray(
+        &[2, 2 * size],
+        &[2, 2],
+        &[0, 2 * rank],
+        Order::C,
+        &Rank::equivalent_datatype(),
+    );
+    file.set_view(0, Rank::equivalent_datatype(), &block)
+        .unwrap();
+    let local = [10 * rank, 10 * rank + 1, 10 * rank + 2, 10 * rank + 3];
+    file.write_all(&local[..]).unwrap();
+
+    // Make the writes of all processes visible to all other processes.
+    file.sync().unwrap();
+    world.barrier();
+    file.sync().unwrap();
+
+    // Read the whole global array back with a contiguous view.
+    file.set_view(0, Rank::equivalent_datatype(), Rank::equivalent_datatype())
+        .unwrap();
+    let mut global = vec![0 as Rank; 4 * size as usize];
+    file.read_at_all(0, &mut global[..]).unwrap();
+
+    let expected: Vec<Rank> = (0..2)
+        .flat_map(|row| (0..2 * size).map(move |column| 10 * (column / 2) + 2 * row + column % 2))
+        .collect();
+    assert_eq!(expected, global);
+
+    file.close().unwrap();
+}
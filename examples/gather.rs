@@ -25,6 +25,29 @@ fn main() {
         root_process.gather_into(&i);
     }
 
+    if let Some(a) = root_process.gather(&i) {
+        assert_eq!(world.rank(), root_rank);
+        assert_eq!(a.len(), count);
+        assert!(a
+            .iter()
+            .enumerate()
+            .all(|(a, &b)| b == 2u64.pow(a as u32 + 1)));
+    }
+
+    // `char`s are sent as `u32`s, which are validated after receiving them.
+    let c = char::from_u32('α' as u32 + world.rank() as u32).unwrap();
+    if world.rank() == root_rank {
+        let mut a = vec![0u32; count];
+        root_process.gather_into_root(&c, &mut a[..]);
+        let a = a
+            .into_iter()
+            .map(|x| char::from_u32(x).unwrap())
+            .collect::<String>();
+        assert!(a.starts_with("αβ"));
+    } else {
+        root_process.gather_into(&c);
+    }
+
     let factor = world.rank() as u64 + 1;
     let a = (1_u64..)
         .take(count)
@@ -45,6 +68,41 @@ fn main() {
         root_process.gather_into(&a[..]);
     }
 
+    if let Some(t) = root_process.gather_vec(&a[..]) {
+        assert_eq!(world.rank(), root_rank);
+        assert!((0_u64..)
+            .zip(t.iter())
+            .all(|(a, &b)| b == (a / count as u64 + 1) * (a % count as u64 + 1)));
+    } else {
+        assert_ne!(world.rank(), root_rank);
+    }
+
+    // Every process contributes as many bytes as its rank
+    let bytes = "x".repeat(world.rank() as usize);
+    if let Some(all) = root_process.gather_varcount_vec(bytes.as_bytes()) {
+        assert_eq!(all.len(), count);
+        for (rank, bytes) in all.into_iter().enumerate() {
+            assert_eq!(String::from_utf8(bytes).unwrap(), "x".repeat(rank));
+        }
+    } else {
+        assert_ne!(world.rank(), root_rank);
+    }
+
+    // Strings of different lengths, e.g. for reporting on the root process
+    let report = format!(
+        "rank {} says {}",
+        world.rank(),
+        "ß".repeat(world.rank() as usize)
+    );
+    if let Some(reports) = root_process.gather_strings(&report) {
+        assert_eq!(reports.len(), count);
+        for (rank, report) in reports.iter().enumerate() {
+            assert_eq!(*report, format!("rank {} says {}", rank, "ß".repeat(rank)));
+        }
+    } else {
+        assert_ne!(world.rank(), root_rank);
+    }
+
     let d = UserDatatype::contiguous(count as Count, &u64::equivalent_datatype());
     let sv = unsafe { View::with_count_and_datatype(&a[..], 1, &d) };
 
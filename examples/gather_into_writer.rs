@@ -0,0 +1,34 @@
+#![deny(warnings)]
+
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let rank = world.rank();
+    let size = world.size();
+
+    let root_rank = 0;
+    let root_process = world.process_at_rank(root_rank);
+
+    // Repeated calls reuse the private duplicate of the communicator created by the first one.
+    for round in 0..3 {
+        let msg = format!("Hello from rank {} in round {}!\n", rank, round);
+
+        if rank == root_rank {
+            let mut out = Vec::new();
+            root_process
+                .gather_into_writer_root(msg.as_bytes(), &mut out)
+                .unwrap();
+
+            let expected: String = (0..size)
+                .map(|r| format!("Hello from rank {} in round {}!\n", r, round))
+                .collect();
+            assert_eq!(String::from_utf8(out).unwrap(), expected);
+            print!("{}", expected);
+        } else {
+            root_process.gather_into_writer(msg.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#![deny(warnings)]
+
+use mpi::topology::{DistributedGraphNeighbors, GraphLayout, Rank};
+use mpi::traits::*;
+use mpi::Count;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let left = (rank + size - 1) % size;
+    let right = (rank + 1) % size;
+
+    // Every process is connected to its left and right neighbor on a ring.
+    let index: Vec<Count> = (1..=size).map(|i| 2 * i).collect();
+    let edges: Vec<Rank> = (0..size)
+        .flat_map(|i| vec![(i + size - 1) % size, (i + 1) % size])
+        .collect();
+
+    let graph = world
+        .create_graph_communicator(&index, &edges, false)
+        .expect("every process is a node of the graph");
+    assert_eq!(2, graph.neighbors_count(rank));
+    assert_eq!(vec![left, right], graph.neighbors(rank));
+
+    let GraphLayout {
+        index: graph_index,
+        edges: graph_edges,
+    } = graph.get_layout();
+    assert_eq!(index, graph_index);
+    assert_eq!(edges, graph_edges);
+
+    // MPI may suggest other ranks for the nodes of the graph, but every process gets one.
+    let new_rank = world
+        .graph_map(&index, &edges)
+        .expect("every process is a node of the graph");
+    assert!((0..size).contains(&new_rank));
+    let reordered = world
+        .create_graph_communicator(&index, &edges, true)
+        .expect("every process is a node of the graph");
+    assert_eq!(2, reordered.neighbors_count(reordered.rank()));
+
+    // The same ring, but with only the local edges specified on each process.
+    let dist_graph =
+        world.create_distributed_graph_communicator(&[left, right], &[left, right], false);
+    assert_eq!((2, 2, false), dist_graph.neighbors_count());
+
+    let DistributedGraphNeighbors {
+        sources,
+        source_weights,
+        destinations,
+        destination_weights,
+    } = dist_graph.neighbors();
+    assert_eq!(vec![left, right], sources);
+    assert_eq!(vec![left, right], destinations);
+    assert!(source_weights.is_none());
+    assert!(destination_weights.is_none());
+
+    let weighted = world.create_weighted_distributed_graph_communicator(
+        &[left, right],
+        &[1, 2],
+        &[left, right],
+        &[2, 1],
+        false,
+    );
+    let neighbors = weighted.neighbors();
+    assert_eq!(Some(vec![1, 2]), neighbors.source_weights);
+    assert_eq!(Some(vec![2, 1]), neighbors.destination_weights);
+}
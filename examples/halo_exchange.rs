@@ -0,0 +1,66 @@
+#![deny(warnings)]
+
+use mpi::traits::*;
+use mpi::Count;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+
+    // A periodic grid of `size` x 1 processes, each one owning 2 x 3 cells surrounded by one
+    // layer of ghost cells.
+    let cart = world
+        .create_cartesian_communicator(&[size, 1], &[true, true], false)
+        .expect("every process is part of the grid");
+    let rank = cart.rank();
+    let lower = (rank + size - 1) % size;
+    let upper = (rank + 1) % size;
+
+    let shape: [Count; 2] = [4, 5];
+    let mut cells = vec![-1; 20];
+    for i in 1..=2 {
+        for j in 1..=3 {
+            cells[(i * 5 + j) as usize] = value(rank, i, j);
+        }
+    }
+
+    cart.halo_exchange(&mut cells, &shape, 1);
+    check(&cells, rank, lower, upper);
+
+    // A plan exchanges arrays of the same shape repeatedly without rebuilding the datatypes.
+    let plan = cart.halo_exchange_plan::<Count>(&shape, 1);
+    for _ in 0..3 {
+        cells.iter_mut().for_each(|cell| *cell = -1);
+        for i in 1..=2 {
+            for j in 1..=3 {
+                cells[(i * 5 + j) as usize] = value(rank, i, j);
+            }
+        }
+        plan.exchange(&mut cells);
+        check(&cells, rank, lower, upper);
+    }
+}
+
+fn value(rank: Count, i: Count, j: Count) -> Count {
+    rank * 100 + i * 10 + j
+}
+
+fn check(cells: &[Count], rank: Count, lower: Count, upper: Count) {
+    for i in 0..4 {
+        for j in 0..5 {
+            let (source, row) = match i {
+                0 => (lower, 2),
+                3 => (upper, 1),
+                _ => (rank, i),
+            };
+            // The second dimension has a single process, which is its own neighbor.
+            let column = match j {
+                0 => 3,
+                4 => 1,
+                _ => j,
+            };
+            assert_eq!(cells[(i * 5 + j) as usize], value(source, row, column));
+        }
+    }
+}
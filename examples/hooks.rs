@@ -0,0 +1,126 @@
+#![deny(warnings)]
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use mpi::collective::SystemOperation;
+use mpi::hooks::{self, CallInfo, Hook};
+use mpi::topology::SimpleCommunicator;
+use mpi::traits::*;
+
+/// Counts the calls and bytes per MPI function
+#[derive(Default)]
+struct Statistics {
+    calls: Mutex<BTreeMap<&'static str, (u64, u64, Duration)>>,
+}
+
+impl Hook for Statistics {
+    fn after(&self, call: &CallInfo, elapsed: Duration) {
+        let mut calls = self.calls.lock().unwrap();
+        let entry = calls.entry(call.operation()).or_default();
+        entry.0 += 1;
+        entry.1 += call.bytes().unwrap_or(0);
+        entry.2 += elapsed;
+    }
+}
+
+/// Counts the processes entering a barrier, calling MPI from within a hook
+struct Collect;
+
+impl Hook for Collect {
+    fn before(&self, call: &CallInfo) {
+        if call.operation() == "MPI_Barrier" {
+            let world = SimpleCommunicator::world();
+            let mut sum = 0;
+            world.all_reduce_into(&1, &mut sum, SystemOperation::sum());
+            assert_eq!(sum, world.size());
+        }
+    }
+}
+
+/// Remembers the last MPI call that returned
+struct Last(Arc<Mutex<Option<CallInfo>>>);
+
+impl Hook for Last {
+    fn after(&self, call: &CallInfo, _elapsed: Duration) {
+        *self.0.lock().unwrap() = Some(*call);
+    }
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+    let world_id = unsafe { mpi::ffi::RSMPI_Comm_c2f(world.as_raw()) };
+
+    let statistics = Arc::new(Statistics::default());
+    hooks::register(statistics.clone());
+    let collect = Arc::new(Collect);
+    hooks::register(collect.clone());
+
+    let previous_rank = (rank - 1 + size) % size;
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank(previous_rank);
+    let mut received = [0u64; 8];
+    for _ in 0..3 {
+        mpi::point_to_point::send_receive_into(
+            &[rank as u64; 8],
+            &next_process,
+            &mut received,
+            &previous_process,
+        );
+    }
+    assert_eq!(received, [previous_rank as u64; 8]);
+
+    let mut sum = 0;
+    world.all_reduce_into(&rank, &mut sum, SystemOperation::sum());
+    assert_eq!(sum, size * (size - 1) / 2);
+    world.barrier();
+
+    // Hooks that are no longer registered are not called anymore.
+    assert!(hooks::unregister(&statistics));
+    assert!(hooks::unregister(&collect));
+    assert!(!hooks::unregister(&statistics));
+    world.barrier();
+
+    let calls = statistics.calls.lock().unwrap();
+    let operations: Vec<_> = calls
+        .iter()
+        .map(|(&operation, &(count, bytes, _))| (operation, count, bytes))
+        .collect();
+    // The reduction of the `Collect` hook is not recorded.
+    assert_eq!(
+        operations,
+        [
+            ("MPI_Allreduce", 1, 4),
+            ("MPI_Barrier", 1, 0),
+            ("MPI_Sendrecv", 3, 3 * 64),
+        ]
+    );
+
+    // The description of a call identifies its communicator.
+    let last = Arc::new(Mutex::new(None));
+    let hook = Arc::new(Last(last.clone()));
+    hooks::register(hook.clone());
+    world.process_at_rank(0).broadcast_into(&mut received[..]);
+    hooks::unregister(&hook);
+    let last = last.lock().unwrap().unwrap();
+    assert_eq!(last.operation(), "MPI_Bcast");
+    assert_eq!(last.comm_id(), Some(world_id));
+    assert_eq!(last.peer(), Some(0));
+    assert_eq!(last.count(), Some(8));
+    assert_eq!(last.bytes(), Some(64));
+
+    // Calls besides communication, like managing communicators, are described as well.
+    let last = Arc::new(Mutex::new(None));
+    let hook = Arc::new(Last(last.clone()));
+    hooks::register(hook.clone());
+    let duplicate = world.duplicate();
+    assert_eq!(last.lock().unwrap().unwrap().operation(), "MPI_Comm_dup");
+    assert_eq!(last.lock().unwrap().unwrap().comm_id(), Some(world_id));
+    drop(duplicate);
+    assert_eq!(last.lock().unwrap().unwrap().operation(), "MPI_Comm_free");
+    hooks::unregister(&hook);
+}
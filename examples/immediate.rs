@@ -10,6 +10,7 @@ fn main() {
 
     let x = std::f32::consts::PI;
     let mut y: f32 = 0.0;
+    let mut z: f32 = 0.0;
 
     mpi::request::scope(|scope| {
         let mut sreq = world.this_process().immediate_send(scope, &x);
@@ -85,6 +86,19 @@ fn main() {
         }
     }
 
+    // Messages of unknown length are received into a newly allocated `Vec`.
+    let xs = vec![x; 3];
+    let source = world.any_process();
+    let future = source.immediate_receive_vec::<f32>().r#try();
+    assert!(future.is_err());
+    let future = future.err().unwrap();
+    mpi::request::scope(|scope| {
+        let _sreq = WaitGuard::from(world.this_process().immediate_send(scope, &xs[..]));
+        let (msg, status) = future.get();
+        assert_eq!(xs, msg);
+        assert_eq!(world.rank(), status.source_rank());
+    });
+
     mpi::request::scope(|scope| {
         let sreq = world.this_process().immediate_send(scope, &x);
         sreq.cancel();
@@ -92,4 +106,13 @@ fn main() {
 
         let _sreq = CancelGuard::from(world.this_process().immediate_receive_into(scope, &mut y));
     });
+
+    mpi::request::scope(|scope| {
+        // Nothing is ever sent with this tag, so the receive can always be cancelled.
+        let rreq = world
+            .any_process()
+            .immediate_receive_into_with_tag(scope, &mut z, 42);
+        let status = rreq.cancel_and_wait();
+        assert!(status.is_cancelled());
+    });
 }
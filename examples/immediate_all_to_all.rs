@@ -1,6 +1,8 @@
 #![deny(warnings)]
 
+use mpi::datatype::{HeterogeneousPartition, HeterogeneousPartitionMut};
 use mpi::traits::*;
+use mpi::Count;
 
 fn main() {
     let universe = mpi::initialize().unwrap();
@@ -21,4 +23,33 @@ fn main() {
     println!("v: {:?}", v);
 
     assert!(v.into_iter().zip(0..size).all(|(i, j)| i == j));
+
+    let counts = vec![1; size as usize];
+    let byte_displs: Vec<Count> = (0..size)
+        .map(|r| r * std::mem::size_of::<Count>() as Count)
+        .collect();
+    let datatypes = vec![Count::equivalent_datatype(); size as usize];
+    let mut w = vec![0; size as usize];
+
+    {
+        let send_partition =
+            HeterogeneousPartition::new(&u[..], &counts[..], &byte_displs[..], &datatypes[..]);
+        let mut recv_partition = HeterogeneousPartitionMut::new(
+            &mut w[..],
+            &counts[..],
+            &byte_displs[..],
+            &datatypes[..],
+        );
+        mpi::request::scope(|scope| {
+            world
+                .immediate_all_to_all_heterogeneous_into(
+                    scope,
+                    &send_partition,
+                    &mut recv_partition,
+                )
+                .wait();
+        });
+    }
+
+    assert!(w.into_iter().zip(0..size).all(|(i, j)| i == j));
 }
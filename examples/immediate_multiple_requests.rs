@@ -120,6 +120,33 @@ fn main() {
         assert_eq!(recv_count, COUNT);
     });
 
+    let mut result: Vec<i32> = vec![0; COUNT];
+    // Test completions()
+    mpi::request::multiple_scope(2 * COUNT, |scope, coll| {
+        for _ in 0..result.len() {
+            let sreq = world
+                .process_at_rank(next_proc)
+                .immediate_send(scope, &rank);
+            coll.add(sreq);
+        }
+        for val in result.iter_mut() {
+            let rreq = world
+                .process_at_rank(prev_proc)
+                .immediate_receive_into(scope, val);
+            coll.add(rreq);
+        }
+        let mut recv_count = 0;
+        for (i, status, result) in coll.completions() {
+            if i >= COUNT {
+                assert_eq!(status.source_rank(), prev_proc);
+                assert_eq!(*result, prev_proc);
+                recv_count += 1;
+            }
+        }
+        assert_eq!(recv_count, COUNT);
+        assert_eq!(coll.incomplete(), 0);
+    });
+
     // Check wait_*() with a buffer of increasing values
     let x: Vec<i32> = (0..COUNT as i32).collect();
     let mut result: Vec<i32> = vec![0; COUNT];
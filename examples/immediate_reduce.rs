@@ -6,9 +6,11 @@ use std::os::raw::{c_int, c_void};
 #[cfg(feature = "user-operations")]
 use mpi::collective::UserOperation;
 use mpi::collective::{SystemOperation, UnsafeUserOperation};
+use mpi::datatype::Partition;
 use mpi::ffi::MPI_Datatype;
 use mpi::topology::Rank;
 use mpi::traits::*;
+use mpi::Count;
 
 #[cfg(feature = "user-operations")]
 fn test_user_operations<C: Communicator>(comm: C) {
@@ -108,6 +110,20 @@ fn main() {
     });
     assert_eq!(b, rank.wrapping_pow(size as u32));
 
+    // Process `r` receives `r + 1` elements of the reduction result.
+    let counts: Vec<Count> = (1..=size).collect();
+    let displs: Vec<Count> = (0..size).map(|r| r * (r + 1) / 2).collect();
+    let c: Vec<Rank> = (0..size).flat_map(|r| vec![r; (r + 1) as usize]).collect();
+    let mut e = vec![0; (rank + 1) as usize];
+
+    let partition = Partition::new(&c[..], &counts[..], &displs[..]);
+    mpi::request::scope(|scope| {
+        world
+            .immediate_reduce_scatter_into(scope, &partition, &mut e[..], SystemOperation::sum())
+            .wait();
+    });
+    assert!(e.iter().all(|&x| x == rank * size));
+
     test_user_operations(universe.world());
 
     let mut d = 0;
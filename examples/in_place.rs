@@ -0,0 +1,59 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::topology::Rank;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let root_rank = 0;
+    let root_process = world.process_at_rank(root_rank);
+
+    let mut x = rank + 1;
+    world.all_reduce_in_place(&mut x, SystemOperation::sum());
+    assert_eq!(x, size * (size + 1) / 2);
+
+    let mut x = rank + 1;
+    world.scan_in_place(&mut x, SystemOperation::sum());
+    assert_eq!(x, (rank + 1) * (rank + 2) / 2);
+
+    let mut x = rank + 1;
+    world.exclusive_scan_in_place(&mut x, SystemOperation::sum());
+    if rank > 0 {
+        assert_eq!(x, rank * (rank + 1) / 2);
+    }
+
+    let mut all: Vec<Rank> = vec![-1; size as usize];
+    all[rank as usize] = rank;
+    world.all_gather_in_place(&mut all[..]);
+    assert!(all.iter().copied().eq(0..size));
+
+    let mut all = (0..size).map(|i| rank * size + i).collect::<Vec<_>>();
+    world.all_to_all_in_place(&mut all[..]);
+    assert!(all.iter().copied().eq((0..size).map(|i| i * size + rank)));
+
+    if rank == root_rank {
+        let mut x = rank + 1;
+        root_process.reduce_in_place_root(&mut x, SystemOperation::sum());
+        assert_eq!(x, size * (size + 1) / 2);
+
+        let mut all: Vec<Rank> = vec![-1; size as usize];
+        all[rank as usize] = rank;
+        root_process.gather_in_place_root(&mut all[..]);
+        assert!(all.iter().copied().eq(0..size));
+
+        let all = (0..size).map(|i| 10 * i).collect::<Vec<_>>();
+        root_process.scatter_in_place_root(&all[..]);
+    } else {
+        root_process.reduce_into(&(rank + 1), SystemOperation::sum());
+
+        root_process.gather_into(&rank);
+
+        let mut x: Rank = -1;
+        root_process.scatter_into(&mut x);
+        assert_eq!(x, 10 * rank);
+    }
+}
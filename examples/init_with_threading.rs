@@ -1,9 +1,19 @@
 #![deny(warnings)]
 
+use std::thread;
+
 use mpi::Threading;
 
 fn main() {
     let (_universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
     assert_eq!(threading, mpi::environment::threading_support());
     println!("Supported level of threading: {:?}", threading);
+
+    assert!(mpi::environment::is_thread_main());
+    if threading == Threading::Multiple {
+        let on_main = thread::spawn(mpi::environment::is_thread_main)
+            .join()
+            .unwrap();
+        assert!(!on_main);
+    }
 }
@@ -0,0 +1,34 @@
+#![deny(warnings)]
+
+use std::sync::Arc;
+
+use mpi::traits::*;
+use mpi::Threading;
+
+fn world_size() -> i32 {
+    // Libraries can fetch the universe without coordinating with the application.
+    mpi::initialize_shared().unwrap().world().size()
+}
+
+fn main() {
+    assert!(!mpi::environment::is_initialized());
+
+    let universe = mpi::initialize_shared().unwrap();
+    assert!(mpi::environment::is_initialized());
+    assert!(Arc::ptr_eq(&universe, &mpi::initialize_shared().unwrap()));
+    assert_eq!(universe.world().size(), world_size());
+
+    // Threads can share the universe if the library supports `Threading::Multiple`.
+    if mpi::environment::threading_support() == Threading::Multiple {
+        std::thread::spawn(world_size).join().unwrap();
+    }
+
+    // MPI is already initialized, so it can not be initialized again.
+    assert!(mpi::initialize().is_none());
+    assert!(!mpi::environment::is_finalized());
+
+    // Dropping the last handle finalizes MPI.
+    drop(universe);
+    assert!(mpi::environment::is_finalized());
+    assert!(mpi::initialize_shared().is_none());
+}
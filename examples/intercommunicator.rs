@@ -0,0 +1,50 @@
+#![deny(warnings)]
+
+use mpi::topology::{Color, Rank};
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    // Split the world into the processes with even and odd ranks.
+    let is_even = rank % 2 == 0;
+    let local = world
+        .split_by_color(Color::with_value(rank % 2))
+        .expect("Every process has a color.");
+
+    // The leaders are the processes with world rank 0 and 1.
+    let remote_leader = world.process_at_rank(if is_even { 1 } else { 0 });
+    let inter = local.create_intercommunicator(0, &remote_leader);
+
+    assert!(inter.test_inter());
+    let odd = size / 2;
+    let even = size - odd;
+    assert_eq!(inter.remote_size(), if is_even { odd } else { even });
+
+    // Broadcast from the even leader to all odd processes.
+    let mut x: Rank = if is_even { 42 } else { 0 };
+    if !is_even {
+        inter.process_at_rank(0).broadcast_into(&mut x);
+        assert_eq!(x, 42);
+    } else if local.rank() == 0 {
+        inter.local_root().broadcast_into(&mut x);
+    } else {
+        inter.local_non_root().broadcast_into(&mut x);
+    }
+
+    // Gather the world ranks of all odd processes on the even leader.
+    if !is_even {
+        inter.process_at_rank(0).gather_into(&rank);
+    } else if local.rank() == 0 {
+        let mut odd_ranks = vec![0 as Rank; odd as usize];
+        inter
+            .local_root()
+            .gather_into_root(&[][..], &mut odd_ranks[..]);
+        assert_eq!(odd_ranks, (0..odd).map(|i| 2 * i + 1).collect::<Vec<_>>());
+    } else {
+        inter.local_non_root().gather_into(&rank);
+    }
+}
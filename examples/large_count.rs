@@ -0,0 +1,39 @@
+#![deny(warnings)]
+
+use mpi::datatype::{LargeSlice, LargeSliceMut};
+use mpi::request::WaitGuard;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let next_rank = (rank + 1) % size;
+    let next_process = world.process_at_rank(next_rank);
+    let previous_rank = (rank + size - 1) % size;
+    let previous_process = world.process_at_rank(previous_rank);
+
+    // The datatype of a large slice does not depend on whether its length fits into a `Count`, so
+    // short slices exercise the same code path without allocating gigabytes of memory.
+    let len = 1000 + rank as usize;
+    let msg = (0..len as u64).collect::<Vec<_>>();
+    let msg = LargeSlice::new(&msg[..]);
+
+    mpi::request::scope(|scope| {
+        let _sreq = WaitGuard::from(next_process.immediate_send(scope, &msg));
+        let mut buf = vec![0_u64; 1000 + previous_rank as usize];
+        previous_process.receive_into(&mut LargeSliceMut::new(&mut buf[..]));
+        assert!(buf.iter().enumerate().all(|(i, &x)| x == i as u64));
+    });
+
+    let root_process = world.process_at_rank(0);
+    let mut values = if rank == 0 {
+        (0..1 << 12).map(f64::from).collect::<Vec<_>>()
+    } else {
+        vec![0.0; 1 << 12]
+    };
+    root_process.broadcast_into(&mut LargeSliceMut::new(&mut values[..]));
+    assert!(values.iter().enumerate().all(|(i, &x)| x == i as f64));
+}
@@ -0,0 +1,29 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::datatype::ValueIndex;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    // The distance to the middle of the ranks, ties are resolved by the smallest rank.
+    let distance = (2 * rank - (size - 1)).abs() as f64;
+    let local = ValueIndex(distance, rank);
+
+    let mut farthest = ValueIndex::default();
+    world.all_reduce_into(&local, &mut farthest, SystemOperation::max_loc());
+    assert_eq!(farthest, ValueIndex((size - 1) as f64, 0));
+
+    let mut nearest = ValueIndex::default();
+    world.all_reduce_into(&local, &mut nearest, SystemOperation::min_loc());
+    assert_eq!(nearest, ValueIndex(((size - 1) % 2) as f64, (size - 1) / 2));
+
+    let values = [ValueIndex(rank, rank), ValueIndex(-rank, rank)];
+    let mut extrema = [ValueIndex::default(); 2];
+    world.all_reduce_into(&values[..], &mut extrema[..], SystemOperation::max_loc());
+    assert_eq!(extrema, [ValueIndex(size - 1, size - 1), ValueIndex(0, 0)]);
+}
@@ -0,0 +1,47 @@
+#![deny(warnings)]
+
+use mpi::point_to_point as p2p;
+use mpi::traits::*;
+use ndarray::{s, Array2};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    // A 4x4 interior with one ghost column on either side.
+    let (rows, cols) = (4, 6);
+    let mut a = Array2::from_shape_fn((rows, cols), |(i, j)| {
+        if j == 0 || j == cols - 1 {
+            -1
+        } else {
+            100 * rank + 10 * i as i32 + j as i32
+        }
+    });
+
+    // Columns are not contiguous, they are sent and received in place.
+    let last_interior = a.slice(s![.., cols - 2]).to_owned();
+    let (last, mut ghost) = a.multi_slice_mut((s![.., cols - 2], s![.., 0]));
+    p2p::send_receive_into(&last, &next_process, &mut ghost, &previous_process);
+    let previous = (rank - 1 + size) % size;
+    for i in 0..rows {
+        assert_eq!(
+            a[[i, 0]],
+            100 * previous + 10 * i as i32 + (cols - 2) as i32
+        );
+        assert_eq!(a[[i, cols - 2]], last_interior[i]);
+    }
+
+    // Rows are contiguous and sent as plain elements.
+    let mut row = Array2::zeros((1, cols));
+    p2p::send_receive_into(
+        &a.slice(s![1..2, ..]),
+        &next_process,
+        &mut row,
+        &previous_process,
+    );
+    assert_eq!(row[[0, 1]], 100 * previous + 10 + 1);
+}
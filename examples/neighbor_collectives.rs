@@ -0,0 +1,57 @@
+#![deny(warnings)]
+
+use mpi::datatype::{Partition, PartitionMut};
+use mpi::traits::*;
+use mpi::{Count, Rank};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let left = (rank + size - 1) % size;
+    let right = (rank + 1) % size;
+
+    // A periodic one-dimensional cartesian topology has the left and right neighbor as sources
+    // and destinations.
+    let ring = world
+        .create_cartesian_communicator(&[size], &[true], false)
+        .expect("every process is part of the ring");
+    assert_eq!((2, 2), ring.neighbor_degrees());
+
+    let mut gathered = [0 as Rank; 2];
+    ring.neighbor_all_gather_into(&rank, &mut gathered[..]);
+    assert_eq!([left, right], gathered);
+
+    // Send `10 * rank` to the left neighbor and `10 * rank + 1` to the right neighbor.
+    let send = [10 * rank, 10 * rank + 1];
+    let mut received = [0 as Rank; 2];
+    ring.neighbor_all_to_all_into(&send[..], &mut received[..]);
+    assert_eq!([10 * left + 1, 10 * right], received);
+
+    // A directed ring where every process sends to its right neighbor and receives from its left
+    // neighbor, with a varying number of elements per process.
+    let graph = world.create_distributed_graph_communicator(&[left], &[right], false);
+    assert_eq!((1, 1), graph.neighbor_degrees());
+
+    let send = vec![rank; (rank + 1) as usize];
+    let counts: [Count; 1] = [left + 1];
+    let displs: [Count; 1] = [0];
+
+    let mut gathered = vec![0 as Rank; (left + 1) as usize];
+    {
+        let mut partition = PartitionMut::new(&mut gathered[..], &counts[..], &displs[..]);
+        graph.neighbor_all_gather_varcount_into(&send[..], &mut partition);
+    }
+    assert!(gathered.iter().all(|&x| x == left));
+
+    let mut received = vec![0 as Rank; (left + 1) as usize];
+    {
+        let send_counts: [Count; 1] = [rank + 1];
+        let partition = Partition::new(&send[..], &send_counts[..], &displs[..]);
+        let mut recv_partition = PartitionMut::new(&mut received[..], &counts[..], &displs[..]);
+        graph.neighbor_all_to_all_varcount_into(&partition, &mut recv_partition);
+    }
+    assert_eq!(gathered, received);
+}
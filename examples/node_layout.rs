@@ -0,0 +1,36 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::topology::NodeLayout;
+use mpi::traits::*;
+use mpi::Rank;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let NodeLayout {
+        node,
+        leaders,
+        node_index,
+        num_nodes,
+    } = world.node_layout();
+
+    assert!((0..num_nodes).contains(&node_index));
+    assert_eq!(leaders.is_some(), node.rank() == 0);
+    if let Some(leaders) = &leaders {
+        assert_eq!(leaders.size(), num_nodes);
+        assert_eq!(leaders.rank(), node_index);
+    }
+
+    // Every node has one leader and every process is on exactly one node.
+    let mut nodes: Rank = 0;
+    let is_leader = Rank::from(leaders.is_some());
+    world.all_reduce_into(&is_leader, &mut nodes, SystemOperation::sum());
+    assert_eq!(nodes, num_nodes);
+
+    let mut total: Rank = 0;
+    let local_size = if node.rank() == 0 { node.size() } else { 0 };
+    world.all_reduce_into(&local_size, &mut total, SystemOperation::sum());
+    assert_eq!(total, world.size());
+}
@@ -0,0 +1,27 @@
+#![deny(warnings)]
+
+use mpi::datatype::{pack_external, unpack_external_into};
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let root_process = world.process_at_rank(0);
+
+    // external32 stores a 32 bit integer in four big-endian bytes.
+    let ints = [3i32, 2, 1];
+    let packed = pack_external(&ints[..]);
+    assert_eq!(packed, [0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 1]);
+
+    let mut received = vec![0u8; packed.len()];
+    if world.rank() == 0 {
+        received.copy_from_slice(&packed);
+    }
+    root_process.broadcast_into(&mut received[..]);
+
+    let mut new_ints = [0, 0, 0];
+    let position = unsafe { unpack_external_into(&received, &mut new_ints[..], 0) };
+
+    assert_eq!(position as usize, received.len());
+    assert_eq!([3, 2, 1], new_ints);
+}
@@ -0,0 +1,81 @@
+#![deny(warnings)]
+
+use std::thread;
+
+use mpi::traits::*;
+use mpi::Threading;
+
+const PARTITIONS: usize = 4;
+const PARTITION_LEN: usize = 8;
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_rank = (rank + size - 1) % size;
+    let previous_process = world.process_at_rank(previous_rank);
+
+    let mut send_buffer = [0_i32; PARTITIONS * PARTITION_LEN];
+    let mut receive_buffer = [0_i32; PARTITIONS * PARTITION_LEN];
+    mpi::request::scope(|scope| {
+        let mut receive = match previous_process.partitioned_receive_init(
+            scope,
+            &mut receive_buffer[..],
+            PARTITIONS,
+        ) {
+            Ok(receive) => receive,
+            // Partitioned communication requires an MPI 4.0 library.
+            Err(_) => return,
+        };
+        let mut send = next_process
+            .partitioned_send_init(scope, &mut send_buffer[..], PARTITIONS)
+            .unwrap();
+
+        for step in 0..3 {
+            let value = |rank: i32, partition: usize| 100 * rank + 10 * partition as i32 + step;
+            if threading != Threading::Multiple {
+                // The whole message is prepared in advance and marked ready at once.
+                for (partition, data) in send.data_mut().chunks_mut(PARTITION_LEN).enumerate() {
+                    data.fill(value(rank, partition));
+                }
+            }
+
+            receive.start();
+            send.start();
+
+            if threading == Threading::Multiple {
+                // Every thread fills its own partition, which is sent as soon as it is dropped.
+                thread::scope(|s| {
+                    for mut partition in send.partitions_mut() {
+                        s.spawn(move || {
+                            let x = value(rank, partition.index());
+                            partition.fill(x);
+                        });
+                    }
+                });
+            } else {
+                send.ready_all();
+            }
+
+            let mut arrived = [false; PARTITIONS];
+            while !arrived.iter().all(|&a| a) {
+                for (partition, arrived) in arrived.iter_mut().enumerate() {
+                    if *arrived {
+                        continue;
+                    }
+                    if let Some(data) = receive.arrived(partition) {
+                        assert!(data.iter().all(|&x| x == value(previous_rank, partition)));
+                        *arrived = true;
+                    }
+                }
+            }
+
+            send.wait();
+            let status = receive.wait();
+            assert_eq!(status.source_rank(), previous_rank);
+        }
+    });
+}
@@ -0,0 +1,78 @@
+#![deny(warnings)]
+
+use mpi::request::start_all;
+use mpi::topology::Rank;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_rank = (rank - 1 + size) % size;
+    let previous_process = world.process_at_rank(previous_rank);
+
+    let mut send_buffer = [0 as Rank; 4];
+    let mut receive_buffer = [0 as Rank; 4];
+    let mut other_send_buffer = [0 as Rank; 4];
+    let mut other_receive_buffer = [0 as Rank; 4];
+    let mut ready_send_buffer = [0 as Rank; 4];
+    let mut ready_receive_buffer = [0 as Rank; 4];
+    mpi::request::scope(|scope| {
+        // Send to the next process and receive from the previous one.
+        let mut send = next_process.send_init(scope, &mut send_buffer[..]);
+        let mut receive = previous_process.receive_init(scope, &mut receive_buffer[..]);
+
+        for step in 0..3 {
+            send.data_mut()
+                .iter_mut()
+                .for_each(|x| *x = 10 * rank + step);
+            receive.start();
+            send.start();
+            send.wait();
+            let status = receive.wait();
+            assert_eq!(status.source_rank(), previous_rank);
+            assert!(receive
+                .data()
+                .iter()
+                .all(|&x| x == 10 * previous_rank + step));
+        }
+
+        // Send to the previous process and receive from the next one, starting both at once.
+        let mut requests = vec![
+            next_process.receive_init(scope, &mut other_receive_buffer[..]),
+            previous_process.send_init(scope, &mut other_send_buffer[..]),
+        ];
+        for step in 0..3 {
+            requests[1]
+                .data_mut()
+                .iter_mut()
+                .for_each(|x| *x = -rank - step);
+            start_all(&mut requests);
+            for request in &mut requests {
+                request.wait_without_status();
+            }
+            let next_rank = (rank + 1) % size;
+            assert!(requests[0].data().iter().all(|&x| x == -next_rank - step));
+        }
+
+        // In ready mode, the matching receive has to be started before every run of the send.
+        let mut ready_send =
+            unsafe { next_process.ready_send_init(scope, &mut ready_send_buffer[..]) };
+        let mut ready_receive = previous_process.receive_init(scope, &mut ready_receive_buffer[..]);
+        for step in 0..3 {
+            ready_send.data_mut().fill(rank + step);
+            ready_receive.start();
+            world.barrier();
+            ready_send.start();
+            ready_send.wait();
+            ready_receive.wait();
+            assert!(ready_receive
+                .data()
+                .iter()
+                .all(|&x| x == previous_rank + step));
+        }
+    });
+}
@@ -0,0 +1,61 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::topology::Rank;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let root_rank = 0;
+    let root_process = world.process_at_rank(root_rank);
+
+    let mut barrier = match world.barrier_init() {
+        Ok(barrier) => barrier,
+        // Persistent collective operations require an MPI 4.0 library.
+        Err(_) => return,
+    };
+
+    let mut sum: [Rank; 2] = [0; 2];
+    let mut parameter = 0.0_f64;
+    let mut contribution: Rank = 0;
+    let mut product: Rank = 0;
+    mpi::request::scope(|scope| {
+        let mut all_reduce = world
+            .all_reduce_in_place_init(scope, &mut sum[..], SystemOperation::sum())
+            .unwrap();
+        let mut broadcast = root_process.broadcast_init(scope, &mut parameter).unwrap();
+        let mut reduce = if rank == root_rank {
+            root_process.reduce_in_place_init_root(scope, &mut product, SystemOperation::product())
+        } else {
+            root_process.reduce_init(scope, &mut contribution, SystemOperation::product())
+        }
+        .unwrap();
+
+        for step in 0..5 {
+            all_reduce.data_mut().copy_from_slice(&[rank, step]);
+            all_reduce.start();
+            all_reduce.wait();
+            assert_eq!(all_reduce.data(), &[size * (size - 1) / 2, size * step]);
+
+            if rank == root_rank {
+                *broadcast.data_mut() = 0.5 * step as f64;
+            }
+            broadcast.start();
+            broadcast.wait();
+            assert_eq!(*broadcast.data(), 0.5 * step as f64);
+
+            *reduce.data_mut() = step + rank;
+            reduce.start();
+            reduce.wait();
+            if rank == root_rank {
+                assert_eq!(*reduce.data(), (0..size).map(|r| step + r).product());
+            }
+
+            barrier.start();
+            barrier.wait();
+        }
+    });
+}
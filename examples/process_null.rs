@@ -0,0 +1,34 @@
+#![deny(warnings)]
+//! Exchanging with the null process at the boundary of a non-periodic chain
+use mpi::point_to_point as p2p;
+use mpi::traits::*;
+use mpi::Rank;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let chain = world
+        .create_cartesian_communicator(&[size], &[false], false)
+        .expect("every process is part of the chain");
+    let (previous, next) = chain.shift(0, 1);
+    let previous_process = chain.process_at_rank_or_null(previous);
+    let next_process = chain.process_at_rank_or_null(next);
+    assert_eq!(previous_process.is_null(), rank == 0);
+    assert_eq!(next_process.is_null(), rank == size - 1);
+
+    // Shift the ranks to the right, the first process keeps its initial value
+    let mut received: Rank = -1;
+    let status = p2p::send_receive_into(&rank, &next_process, &mut received, &previous_process);
+    if previous_process.is_null() {
+        assert_eq!(received, -1);
+        assert_eq!(status.source_rank(), chain.null_process().rank());
+    } else {
+        assert_eq!(received, rank - 1);
+    }
+
+    // Sends to the null process complete without a matching receive
+    chain.null_process().send(&rank);
+}
@@ -0,0 +1,44 @@
+#![deny(warnings)]
+//! Iterating over the processes of a communicator
+use mpi::traits::*;
+use mpi::Rank;
+
+const TASKS: Rank = 10;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    assert_eq!(world.processes().len(), size as usize);
+    assert!(world.processes().map(|process| process.rank()).eq(0..size));
+    assert_eq!(world.other_processes().len(), size as usize - 1);
+    assert!(world.other_processes().all(|process| !process.is_self()));
+
+    let root_rank = 0;
+    if rank == root_rank {
+        if size == 1 {
+            return;
+        }
+        // Hand out the tasks round-robin, a negative task tells the workers to stop
+        for (task, worker) in (0..TASKS).zip(world.other_processes().cycle()) {
+            worker.send(&task);
+        }
+        for worker in world.other_processes() {
+            worker.send(&-1);
+        }
+    } else {
+        let root = world.process_at_rank(root_rank);
+        let mut expected = rank - 1;
+        loop {
+            let (task, _) = root.receive::<Rank>();
+            if task < 0 {
+                break;
+            }
+            assert_eq!(task, expected);
+            expected += size - 1;
+        }
+        assert!(expected >= TASKS);
+    }
+}
@@ -0,0 +1,51 @@
+#![deny(warnings)]
+#![allow(clippy::float_cmp)]
+//! Overlapping communication with computations on a Rayon thread pool
+use mpi::funneled::FunneledGuard;
+use mpi::request::WaitGuard;
+use mpi::traits::*;
+use mpi::Threading;
+use rayon::prelude::*;
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Funneled).unwrap();
+    if threading == Threading::Single {
+        // Other threads are not allowed at all.
+        return;
+    }
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+    let guard = FunneledGuard::new(&universe);
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    // Fill a buffer in parallel
+    let mut data = vec![0.0f64; 1 << 16];
+    data.par_iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = (rank as usize + i) as f64);
+
+    // Send it around the ring on the main thread, while the Rayon threads sum it up
+    let mut received = vec![0.0f64; data.len()];
+    let (_, sum) = mpi::request::scope(|scope| {
+        guard.join(
+            |_| {
+                let _receive = WaitGuard::from(
+                    previous_process.immediate_receive_into(scope, &mut received[..]),
+                );
+                let _send = WaitGuard::from(next_process.immediate_send(scope, &data[..]));
+            },
+            || data.par_iter().sum::<f64>(),
+        )
+    });
+
+    let previous = previous_process.rank() as usize;
+    assert!(received
+        .iter()
+        .enumerate()
+        .all(|(i, &x)| x == (previous + i) as f64));
+    let n = data.len() as f64;
+    assert_eq!(sum, n * rank as f64 + n * (n - 1.0) / 2.0);
+}
@@ -7,9 +7,11 @@ use std::os::raw::{c_int, c_void};
 #[cfg(feature = "user-operations")]
 use mpi::collective::UserOperation;
 use mpi::collective::{self, SystemOperation, UnsafeUserOperation};
+use mpi::datatype::Partition;
 use mpi::ffi::MPI_Datatype;
 use mpi::topology::Rank;
 use mpi::traits::*;
+use mpi::Count;
 
 #[cfg(feature = "user-operations")]
 fn test_user_operations<C: Communicator>(comm: C) {
@@ -110,6 +112,32 @@ fn main() {
     world.reduce_scatter_block_into(&f[..], &mut g, SystemOperation::product());
     assert_eq!(g, rank.wrapping_pow(size as u32));
 
+    // Process `r` receives `r + 1` elements of the reduction result.
+    let counts: Vec<Count> = (1..=size).collect();
+    let displs: Vec<Count> = (0..size).map(|r| r * (r + 1) / 2).collect();
+    let h: Vec<Rank> = (0..size).flat_map(|r| vec![r; (r + 1) as usize]).collect();
+    let mut j = vec![0; (rank + 1) as usize];
+    {
+        let partition = Partition::new(&h[..], &counts[..], &displs[..]);
+        world.reduce_scatter_into(&partition, &mut j[..], SystemOperation::sum());
+    }
+    assert!(j.iter().all(|&x| x == rank * size));
+
+    // The same, but with the partitions stored in reverse rank order.
+    let displs: Vec<Count> = (0..size)
+        .map(|r| (r + 1..size).map(|q| q + 1).sum())
+        .collect();
+    let h: Vec<Rank> = (0..size)
+        .rev()
+        .flat_map(|r| vec![r; (r + 1) as usize])
+        .collect();
+    let mut k = vec![0; (rank + 1) as usize];
+    {
+        let partition = Partition::new(&h[..], &counts[..], &displs[..]);
+        world.segmented_reduce_into(&partition, &mut k[..], SystemOperation::sum());
+    }
+    assert!(k.iter().all(|&x| x == rank * size));
+
     test_user_operations(universe.world());
 
     let mut i = 0;
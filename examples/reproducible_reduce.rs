@@ -0,0 +1,64 @@
+#![deny(warnings)]
+#![allow(clippy::float_cmp)]
+
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let root_rank = 0;
+    let root_process = world.process_at_rank(root_rank);
+
+    // Large values of alternating sign with ones in between cancel out, which loses the ones when
+    // summed in rank order, e.g. `((2^60 + 1) - 2^60) + 1 == 1`.
+    let big = 2f64.powi(60);
+    let contribution = |r| match r % 4 {
+        0 => [big, 0.1],
+        2 => [-big, 0.1],
+        _ => [1.0, 0.1],
+    };
+    let x = contribution(rank);
+
+    // The exact sums, each rounded once.
+    let bigs = (0..size).filter(|r| r % 4 == 0).count() - (0..size).filter(|r| r % 4 == 2).count();
+    let ones = (0..size).filter(|r| r % 2 == 1).count();
+    let expected = [
+        bigs as f64 * big + ones as f64,
+        exact_sum_of_tenths(size as usize),
+    ];
+
+    let mut sum = [0.0; 2];
+    world.reproducible_all_sum_into(&x[..], &mut sum[..]);
+    assert_eq!(sum, expected);
+
+    // The same values contributed by other processes give the same result.
+    let mut reversed_sum = [0.0; 2];
+    world.reproducible_all_sum_into(&contribution(size - 1 - rank)[..], &mut reversed_sum[..]);
+    assert_eq!(reversed_sum.map(f64::to_bits), sum.map(f64::to_bits));
+
+    // `f32`s are summed exactly as well.
+    let mut sum_f32 = 0.0f32;
+    world.reproducible_all_sum_into(&[0.1f32][..], std::slice::from_mut(&mut sum_f32));
+    assert_eq!(sum_f32, (f64::from(0.1f32) * f64::from(size)) as f32);
+
+    if rank == root_rank {
+        let mut sum = [0.0; 2];
+        root_process.reproducible_sum_into_root(&x[..], &mut sum[..]);
+        assert_eq!(sum, expected);
+    } else {
+        root_process.reproducible_sum_into(&x[..]);
+    }
+}
+
+/// The sum of `n` times the `f64` closest to `0.1`, rounded once
+///
+/// `0.1` is `m * 2^-56` with a 53 bit integer `m`, so `n * m` is exact as a `u128`.
+fn exact_sum_of_tenths(n: usize) -> f64 {
+    let bits = 0.1f64.to_bits();
+    let m = (bits & ((1 << 52) - 1)) | (1 << 52);
+    let exponent = ((bits >> 52) & 0x7ff) as i32 - 1075;
+    (m as u128 * n as u128) as f64 * 2f64.powi(exponent)
+}
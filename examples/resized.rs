@@ -0,0 +1,37 @@
+#![deny(warnings)]
+
+use std::mem;
+
+use mpi::datatype::{UserDatatype, View};
+use mpi::topology::Rank;
+use mpi::traits::*;
+use mpi::Address;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let root_rank = 0;
+    let root_process = world.process_at_rank(root_rank);
+
+    let rows = 3;
+
+    // A column of a `rows` x `size` matrix stored in row-major order. Resizing the extent to a
+    // single element makes consecutive columns start one element apart.
+    let column = UserDatatype::vector(rows, 1, size, &Rank::equivalent_datatype());
+    let column = UserDatatype::resized(&column, 0, mem::size_of::<Rank>() as Address);
+    assert_eq!(column.extent(), mem::size_of::<Rank>() as Address);
+
+    let mut received = vec![-1 as Rank; rows as usize];
+    if rank == root_rank {
+        let matrix = (0..rows * size).collect::<Vec<_>>();
+        let v = unsafe { View::with_count_and_datatype(&matrix[..], size, &column) };
+        root_process.scatter_into_root(&v, &mut received[..]);
+    } else {
+        root_process.scatter_into(&mut received[..]);
+    }
+
+    let expected = (0..rows).map(|row| row * size + rank).collect::<Vec<_>>();
+    assert_eq!(received, expected);
+}
@@ -19,4 +19,18 @@ fn main() {
         root_process.scatter_into(&mut x);
     }
     assert_eq!(x, rank);
+
+    // Every process receives two columns of a matrix with three rows.
+    let ncols = 2 * size;
+    let matrix = if rank == root_rank {
+        (0..3 * ncols).collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let mut columns = vec![-1 as Rank; 6];
+    root_process.scatter_columns_into(&matrix, ncols, &mut columns);
+    let expected = (0..3)
+        .flat_map(|row| [row * ncols + 2 * rank, row * ncols + 2 * rank + 1])
+        .collect::<Vec<_>>();
+    assert_eq!(columns, expected);
 }
@@ -0,0 +1,20 @@
+#![deny(warnings)]
+//! Deriving per-process seeds for random number generators
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let root_process = world.process_at_rank(0);
+
+    // Only the seed of the root matters
+    let seed = root_process.seed_per_rank(42 + world.rank() as u64);
+    assert_eq!(seed, root_process.seed_per_rank(42));
+    assert_ne!(seed, root_process.seed_per_rank(43));
+
+    if let Some(mut seeds) = root_process.gather(&seed) {
+        seeds.sort_unstable();
+        seeds.dedup();
+        assert_eq!(seeds.len(), world.size() as usize);
+    }
+}
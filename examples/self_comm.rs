@@ -0,0 +1,50 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::io::{AccessMode, Offset};
+use mpi::traits::*;
+use mpi::window::Window;
+use mpi::{File, Rank};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+
+    let local = universe.self_comm();
+    assert_eq!(1, local.size());
+    assert_eq!(0, local.rank());
+
+    // Collectives only involve the current process.
+    let mut sum: Rank = 0;
+    local.all_reduce_into(&rank, &mut sum, SystemOperation::sum());
+    assert_eq!(rank, sum);
+
+    // Every process opens its own file.
+    let path = std::env::temp_dir().join(format!("rsmpi_self_comm_example_{}.dat", rank));
+    let file = File::open(
+        &local,
+        &path,
+        AccessMode::CREATE | AccessMode::READ_WRITE | AccessMode::DELETE_ON_CLOSE,
+    )
+    .unwrap();
+    file.write_at(0, &[rank, rank][..]).unwrap();
+    let mut read = [0 as Rank; 2];
+    file.read_at(0, &mut read[..]).unwrap();
+    assert_eq!([rank, rank], read);
+    assert_eq!(
+        2 * std::mem::size_of::<Rank>() as Offset,
+        file.size().unwrap()
+    );
+    file.close().unwrap();
+
+    // Every process creates its own window.
+    let mut window = Window::<Rank>::allocate(&local, 1);
+    window.fence_epoch(|epoch| {
+        epoch.put(&rank, 0, 0);
+    });
+    // The window is private to this process and the fence has completed the put.
+    assert_eq!([rank], unsafe { window.local() });
+
+    world.barrier();
+}
@@ -1,6 +1,7 @@
 #![deny(warnings)]
 
 use mpi::point_to_point as p2p;
+use mpi::request::WaitGuard;
 use mpi::topology::Rank;
 use mpi::traits::*;
 
@@ -44,4 +45,23 @@ fn main() {
     let mut x = rank;
     p2p::send_receive_replace_into(&mut x, &next_process, &previous_process);
     assert_eq!(x, previous_rank);
+
+    // `char`s are sent as `u32`s and validated when they are received.
+    let c = char::from_u32('α' as u32 + rank as u32).unwrap();
+    let (received, _) = mpi::request::scope(|scope| {
+        let _send = WaitGuard::from(next_process.immediate_send(scope, &c));
+        previous_process.receive_char()
+    });
+    assert_eq!(
+        received,
+        Ok(char::from_u32('α' as u32 + previous_rank as u32).unwrap())
+    );
+
+    // A surrogate is a valid `u32` but not a `char`.
+    let surrogate = 0xd800_u32;
+    let (received, _) = mpi::request::scope(|scope| {
+        let _send = WaitGuard::from(next_process.immediate_send(scope, &surrogate));
+        previous_process.receive_char()
+    });
+    assert_eq!(received, Err(surrogate));
 }
@@ -0,0 +1,50 @@
+#![deny(warnings)]
+
+use std::collections::HashMap;
+
+use mpi::traits::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Shape {
+    Circle { radius: f64 },
+    Polygon(Vec<(f64, f64)>),
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let inventory: HashMap<String, Vec<u32>> = [
+        ("apples".to_string(), vec![1, 2, 3]),
+        ("pears".to_string(), vec![]),
+    ]
+    .into_iter()
+    .collect();
+    let shapes = vec![
+        Shape::Circle { radius: 1.5 },
+        Shape::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]),
+    ];
+
+    if world.rank() == 0 {
+        for rank in 1..world.size() {
+            let process = world.process_at_rank(rank);
+            process.send_serialized(&inventory).unwrap();
+            process.send_serialized(&shapes[..]).unwrap();
+            process
+                .send_serialized(&format!("hello rank {rank}"))
+                .unwrap();
+        }
+    } else {
+        let root = world.process_at_rank(0);
+        let (received, status) = root
+            .receive_deserialized::<HashMap<String, Vec<u32>>>()
+            .unwrap();
+        assert_eq!(received, inventory);
+        assert_eq!(status.source_rank(), 0);
+        let (received, _) = root.receive_deserialized::<Vec<Shape>>().unwrap();
+        assert_eq!(received, shapes);
+        let (received, _) = root.receive_deserialized::<String>().unwrap();
+        assert_eq!(received, format!("hello rank {}", world.rank()));
+    }
+}
@@ -0,0 +1,37 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::session::Session;
+use mpi::topology::Rank;
+use mpi::traits::*;
+
+fn main() {
+    // Sessions require an MPI 4.0 library.
+    let session = match Session::init() {
+        Ok(session) => session,
+        Err(_) => return,
+    };
+
+    let process_sets = session.process_sets().unwrap();
+    assert!(process_sets.iter().any(|name| name == "mpi://WORLD"));
+    assert!(process_sets.iter().any(|name| name == "mpi://SELF"));
+
+    {
+        let world = session
+            .communicator("mpi://WORLD", "org.rsmpi.examples.session")
+            .unwrap();
+        let rank = world.rank();
+        let size = world.size();
+
+        let mut sum: Rank = 0;
+        world.all_reduce_into(&rank, &mut sum, SystemOperation::sum());
+        assert_eq!(sum, size * (size - 1) / 2);
+
+        let single = session
+            .communicator("mpi://SELF", "org.rsmpi.examples.session.self")
+            .unwrap();
+        assert_eq!(single.size(), 1);
+    }
+
+    session.finalize().unwrap();
+}
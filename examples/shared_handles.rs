@@ -0,0 +1,56 @@
+#![deny(warnings)]
+
+use std::rc::Rc;
+
+use mpi::collective::SystemOperation;
+use mpi::datatype::{MutView, UserDatatype};
+use mpi::traits::*;
+use mpi::Rank;
+
+/// A stage of a computation that keeps its communicator and datatype
+struct Stage {
+    comm: Rc<dyn Communicator>,
+    pairs: Rc<UserDatatype>,
+}
+
+impl Stage {
+    fn run(&self, data: &mut [Rank]) -> Rank {
+        let mut pairs = MutView::new(data, 2, &self.pairs);
+        self.comm.process_at_rank(0).broadcast_into(&mut pairs);
+        sum(&self.comm, data[0])
+    }
+}
+
+fn sum<C: Communicator + ?Sized>(comm: &C, value: Rank) -> Rank {
+    let mut sum = 0;
+    comm.all_reduce_into(&value, &mut sum, SystemOperation::sum());
+    sum
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    // Both stages share the same handles, which are freed with the last stage.
+    let comm: Rc<dyn Communicator> = Rc::new(world.duplicate());
+    let pairs = Rc::new(UserDatatype::contiguous(2, &Rank::equivalent_datatype()));
+    let first = Stage {
+        comm: comm.clone(),
+        pairs: pairs.clone(),
+    };
+    let second = Stage { comm, pairs };
+    assert_eq!(Rc::strong_count(&first.comm), 2);
+    assert_eq!(Rc::strong_count(&first.pairs), 2);
+
+    let mut data = if rank == 0 { [1, 2, 3, 4] } else { [0; 4] };
+    assert_eq!(first.run(&mut data), size);
+    assert_eq!(data, [1, 2, 3, 4]);
+    drop(first);
+
+    let mut data = if rank == 0 { [5, 6, 7, 8] } else { [0; 4] };
+    assert_eq!(second.run(&mut data), 5 * size);
+    assert_eq!(data, [5, 6, 7, 8]);
+    assert_eq!(Rc::strong_count(&second.comm), 1);
+}
@@ -0,0 +1,41 @@
+#![deny(warnings)]
+
+use mpi::shared_memory::SharedMemoryTransport;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let node = world.split_shared(world.rank());
+
+    let rank = node.rank();
+    let size = node.size();
+
+    let transport = SharedMemoryTransport::new(&node, 64 * std::mem::size_of::<u64>());
+
+    let root = 0;
+    let mut data: Vec<u64> = if rank == root {
+        (0..64).collect()
+    } else {
+        vec![0; 64]
+    };
+    transport.broadcast_into(root, &mut data[..]);
+    assert!(data.iter().copied().eq(0..64));
+
+    let contribution = vec![rank as u64; 16];
+    let mut gathered = vec![0u64; 16 * size as usize];
+    transport.all_gather_into(&contribution[..], &mut gathered[..]);
+    assert!(gathered
+        .chunks(16)
+        .enumerate()
+        .all(|(r, chunk)| chunk.iter().all(|&x| x == r as u64)));
+
+    // Too large for the shared-memory window, handled by the regular collectives.
+    let mut large: Vec<u64> = if rank == root {
+        (0..1024).collect()
+    } else {
+        vec![0; 1024]
+    };
+    transport.broadcast_into(root, &mut large[..]);
+    assert!(large.iter().copied().eq(0..1024));
+}
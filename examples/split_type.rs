@@ -0,0 +1,33 @@
+#![deny(warnings)]
+
+use mpi::topology::{Color, SplitType};
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let node = world
+        .split_by_type(SplitType::Shared, world.rank())
+        .expect("every process can share memory with itself");
+    assert_eq!(node.size(), world.split_shared(world.rank()).size());
+    assert!(node.size() <= world.size());
+
+    // One leader per node communicates across nodes.
+    let color = if node.rank() == 0 {
+        Color::with_value(0)
+    } else {
+        Color::undefined()
+    };
+    let leaders = world.split_by_color(color);
+    assert_eq!(leaders.is_some(), node.rank() == 0);
+
+    // "mpi_shared_memory" is equivalent to `SplitType::Shared`, older implementations do not
+    // support hardware guided splits.
+    if let Some(shared) = world.split_by_type(
+        SplitType::Hardware("mpi_shared_memory".into()),
+        world.rank(),
+    ) {
+        assert_eq!(shared.size(), node.size());
+    }
+}
@@ -0,0 +1,53 @@
+#![deny(warnings)]
+
+use mpi::datatype::{Order, UserDatatype, View};
+use mpi::point_to_point as p2p;
+use mpi::topology::Rank;
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_rank = (rank - 1 + size) % size;
+    let previous_process = world.process_at_rank(previous_rank);
+
+    // A 4x4 matrix in row-major order, send the 2x2 block in its center.
+    let matrix = (0..16).map(|x| 100 * rank + x).collect::<Vec<_>>();
+    let block = UserDatatype::subarray(
+        &[4, 4],
+        &[2, 2],
+        &[1, 1],
+        Order::C,
+        &Rank::equivalent_datatype(),
+    );
+    let mut received = vec![-1; 4];
+    {
+        let v = unsafe { View::with_count_and_datatype(&matrix[..], 1, &block) };
+        p2p::send_receive_into(&v, &next_process, &mut received[..], &previous_process);
+    }
+
+    let expected = [5, 6, 9, 10]
+        .iter()
+        .map(|x| 100 * previous_rank + x)
+        .collect::<Vec<_>>();
+    assert_eq!(received, expected);
+
+    // The same block of the matrix in column-major order.
+    let block = UserDatatype::subarray(
+        &[4, 4],
+        &[2, 2],
+        &[1, 1],
+        Order::Fortran,
+        &Rank::equivalent_datatype(),
+    );
+    let mut received = vec![-1; 4];
+    {
+        let v = unsafe { View::with_count_and_datatype(&matrix[..], 1, &block) };
+        p2p::send_receive_into(&v, &next_process, &mut received[..], &previous_process);
+    }
+    assert_eq!(received, expected);
+}
@@ -0,0 +1,49 @@
+#![deny(warnings)]
+//! Sending and receiving with tags attached to processes
+use mpi::point_to_point as p2p;
+use mpi::traits::*;
+use mpi::{Rank, Tag};
+
+const DATA: Tag = 1;
+const DONE: Tag = 2;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let next_process = world.process_at_rank((rank + 1) % size);
+    let previous_process = world.process_at_rank((rank - 1 + size) % size);
+
+    mpi::request::scope(|scope| {
+        // Sent first, but only received after the data message
+        let done = next_process.tagged(DONE).immediate_send(scope, &true);
+        next_process.tagged(DATA).send(&rank);
+
+        let (value, status) = previous_process.tagged(DATA).receive::<Rank>();
+        assert_eq!(value, previous_process.rank());
+        assert_eq!(status.tag(), DATA);
+
+        let (finished, status) = world.any_process().tagged(DONE).receive::<bool>();
+        assert!(finished);
+        assert_eq!(status.tag(), DONE);
+        assert_eq!(status.source_rank(), previous_process.rank());
+
+        done.wait();
+    });
+
+    // The tags of a tagged destination and source are also used by the combined operations
+    let (value, status): (Rank, _) = p2p::send_receive(
+        &rank,
+        &next_process.tagged(DATA),
+        &world.any_process().tagged(DATA),
+    );
+    assert_eq!(value, previous_process.rank());
+    assert_eq!(status.tag(), DATA);
+
+    // Any tag is matched unless a tag is attached
+    next_process.tagged(DONE).send(&rank);
+    let (_, status) = previous_process.receive::<Rank>();
+    assert_eq!(status.tag(), DONE);
+}
@@ -0,0 +1,95 @@
+#![deny(warnings)]
+
+use std::thread;
+
+use mpi::collective::SystemOperation;
+use mpi::datatype::UserDatatype;
+use mpi::request::StaticScope;
+use mpi::topology::ThreadSafe;
+use mpi::traits::*;
+use mpi::{Rank, Tag, Threading};
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
+    let Some(world) = universe.thread_safe_world() else {
+        assert_ne!(threading, Threading::Multiple);
+        return;
+    };
+    let size = world.size();
+    let rank = world.rank();
+    let next_rank = (rank + 1) % size;
+    let previous_rank = (rank - 1 + size) % size;
+
+    // Every thread exchanges its own message with the neighbors, told apart by their tags.
+    let threads: Tag = 4;
+    let received: Vec<(Tag, Rank)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|tag| {
+                let world = &world;
+                scope.spawn(move || {
+                    let next_process = world.process_at_rank(next_rank);
+                    let previous_process = world.process_at_rank(previous_rank);
+                    let (value, _): (Rank, _) = mpi::point_to_point::send_receive_with_tags(
+                        &(rank * threads + tag),
+                        &next_process,
+                        tag,
+                        &previous_process,
+                        tag,
+                    );
+                    (tag, value)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for (tag, value) in received {
+        assert_eq!(value, previous_rank * threads + tag);
+    }
+
+    // Handles can also be moved to other threads.
+    let clone = world.clone();
+    let size_on_thread = thread::spawn(move || clone.size()).join().unwrap();
+    assert_eq!(size_on_thread, size);
+
+    // Other communicators are wrapped for sharing, e.g. a duplicate of the world communicator.
+    let Ok(duplicate) = ThreadSafe::new(world.duplicate()) else {
+        unreachable!("MPI has been initialized with `Threading::Multiple`");
+    };
+    let sum: Rank = thread::scope(|scope| {
+        let duplicate = &duplicate;
+        scope
+            .spawn(move || {
+                let mut sum = 0;
+                duplicate.all_reduce_into(&rank, &mut sum, SystemOperation::sum());
+                sum
+            })
+            .join()
+            .unwrap()
+    });
+    assert_eq!(sum, size * (size - 1) / 2);
+
+    // User datatypes are duplicated and freed by the threads they are cloned and dropped on, so
+    // they are wrapped for sharing as well.
+    let pair = UserDatatype::contiguous(2, &Rank::equivalent_datatype());
+    let Ok(pair) = ThreadSafe::new(pair) else {
+        unreachable!("MPI has been initialized with `Threading::Multiple`");
+    };
+    let pair_size = thread::spawn(move || pair.clone().size()).join().unwrap();
+    assert_eq!(pair_size, 2 * Rank::equivalent_datatype().size());
+
+    // Requests of the static scope can be completed on another thread than the one that started
+    // them.
+    let message: &'static Rank = Box::leak(Box::new(rank));
+    let request = world
+        .process_at_rank(next_rank)
+        .immediate_send(StaticScope, message);
+    let Ok(request) = ThreadSafe::new(request) else {
+        unreachable!("MPI has been initialized with `Threading::Multiple`");
+    };
+    let (received, _) = world.process_at_rank(previous_rank).receive::<Rank>();
+    thread::spawn(move || request.into_inner().wait())
+        .join()
+        .unwrap();
+    assert_eq!(received, previous_rank);
+}
@@ -1,5 +1,6 @@
 #![deny(warnings)]
 
+use mpi::environment::Stopwatch;
 use mpi::traits::*;
 
 fn main() {
@@ -15,4 +16,10 @@ fn main() {
         "the clock has a resoltion of {} seconds",
         mpi::time_resolution()
     );
+
+    let stopwatch = Stopwatch::start_synchronized(&world);
+    let local = stopwatch.elapsed();
+    let total = stopwatch.elapsed_synchronized(&world);
+    assert!(local <= total);
+    println!("measured region took: {:?}", total);
 }
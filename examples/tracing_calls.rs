@@ -0,0 +1,78 @@
+#![deny(warnings)]
+
+use std::sync::{Arc, Mutex};
+
+use mpi::traits::*;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+/// A layer that records the operation and the size of every MPI call
+#[derive(Clone, Default)]
+struct Calls(Arc<Mutex<Vec<(String, Option<i64>)>>>);
+
+#[derive(Default)]
+struct CallVisitor {
+    operation: String,
+    bytes: Option<i64>,
+}
+
+impl Visit for CallVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "operation" {
+            self.operation = value.to_owned();
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if field.name() == "bytes" {
+            self.bytes = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl<S: Subscriber> Layer<S> for Calls {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().target() == "mpi" {
+            let mut visitor = CallVisitor::default();
+            attrs.record(&mut visitor);
+            self.0
+                .lock()
+                .unwrap()
+                .push((visitor.operation, visitor.bytes));
+        }
+    }
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+
+    let calls = Calls::default();
+    let subscriber = tracing_subscriber::registry().with(calls.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        let mut values = [0u64; 4];
+        if rank == 0 {
+            values = [1, 2, 3, 4];
+        }
+        world.process_at_rank(0).broadcast_into(&mut values[..]);
+        assert_eq!(values, [1, 2, 3, 4]);
+        world.barrier();
+    });
+
+    // Without a subscriber, calls are not recorded.
+    world.barrier();
+
+    let calls = calls.0.lock().unwrap();
+    assert_eq!(
+        *calls,
+        [
+            ("MPI_Bcast".to_owned(), Some(32)),
+            ("MPI_Barrier".to_owned(), None),
+        ]
+    );
+}
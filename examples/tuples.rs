@@ -0,0 +1,34 @@
+#![deny(warnings)]
+
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let root_process = world.process_at_rank(0);
+
+    let mut pairs = if world.rank() == 0 {
+        vec![(1.5f64, 1i32), (2.5, 2), (3.5, 3)]
+    } else {
+        vec![(0.0, 0); 3]
+    };
+    root_process.broadcast_into(&mut pairs[..]);
+    assert_eq!(pairs, [(1.5, 1), (2.5, 2), (3.5, 3)]);
+
+    // Rust may reorder the fields of tuples, nested tuples work as well.
+    let mut nested = if world.rank() == 0 {
+        (true, (7u8, -7i64), 42u16)
+    } else {
+        (false, (0, 0), 0)
+    };
+    root_process.broadcast_into(&mut nested);
+    assert_eq!(nested, (true, (7, -7), 42));
+
+    let rank = world.rank();
+    let mut all = vec![(0i32, 0.0f32); world.size() as usize];
+    world.all_gather_into(&(rank, rank as f32 / 2.0), &mut all[..]);
+    for (i, &(r, half)) in all.iter().enumerate() {
+        assert_eq!(r, i as i32);
+        assert_eq!(half, i as f32 / 2.0);
+    }
+}
@@ -0,0 +1,34 @@
+#![deny(warnings)]
+
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let comm = world.duplicate();
+    // Methods that ignore error codes are only called while no process has failed.
+    unsafe { comm.set_errors_return() };
+
+    // The extension may not be available in this build of the MPI library.
+    let flag = match comm.agree(!0) {
+        Ok(flag) => flag,
+        Err(_) => return,
+    };
+    assert_eq!(flag, !0);
+
+    // Without failures the agreement is a bitwise and over all processes.
+    let flag = comm.agree(!(1 << comm.rank())).unwrap();
+    assert_eq!(flag, !((1 << comm.size()) - 1));
+
+    comm.acknowledge_failures().unwrap();
+    assert_eq!(comm.acknowledged_failures().unwrap().size(), 0);
+
+    // Revoke the communicator and continue on a shrunk one, as after a failure.
+    comm.barrier();
+    comm.revoke().unwrap();
+    let shrunk = comm.shrink().unwrap();
+    unsafe { shrunk.set_errors_return() };
+    assert_eq!(shrunk.size(), world.size());
+    assert_eq!(shrunk.rank(), world.rank());
+    shrunk.barrier();
+}
@@ -24,8 +24,8 @@ fn main() {
     let t = UserDatatype::vector(2, 2, 3, &Rank::equivalent_datatype());
     let status;
     {
-        let v1 = unsafe { View::with_count_and_datatype(&b1[..], 1, &t) };
-        let mut v2 = unsafe { MutView::with_count_and_datatype(&mut b2[..], 1, &t) };
+        let v1 = View::new(&b1[..], 1, &t);
+        let mut v2 = MutView::new(&mut b2[..], 1, &t);
         status = p2p::send_receive_into(&v1, &next_process, &mut v2, &previous_process);
     }
 
@@ -35,6 +35,9 @@ fn main() {
     );
     world.barrier();
 
+    assert_eq!(status.count(&t), 1);
+    assert_eq!(status.elements(&t), 4);
+
     let b3 = (1..)
         .map(|x| if x % 3 == 0 { -1 } else { previous_rank * x })
         .take(6)
@@ -1,6 +1,7 @@
 #![deny(warnings)]
 #![allow(clippy::float_cmp)]
 
+use mpi::topology::Rank;
 use mpi::traits::*;
 use std::{thread, time};
 
@@ -37,4 +38,45 @@ fn main() {
             println!("Process {} received data", world.rank());
         }
     });
+    let mut received = vec![0.0f32; world.size() as usize];
+    mpi::request::scope(|scope| {
+        let mut requests = Vec::new();
+        for (i, r) in received.iter_mut().enumerate() {
+            requests.push(
+                world
+                    .process_at_rank(i as Rank)
+                    .immediate_receive_into(scope, r),
+            );
+        }
+        for i in 0..world.size() {
+            requests.push(world.process_at_rank(i).immediate_send(scope, &x));
+        }
+        let total = requests.len();
+
+        let completed = mpi::request::test_some(&mut requests);
+        assert!(completed.windows(2).all(|w| w[0].0 < w[1].0));
+        let statuses = mpi::request::wait_all(&mut requests);
+        assert!(requests.is_empty());
+        assert_eq!(completed.len() + statuses.len(), total);
+    });
+    assert!(received.iter().all(|&r| r == x));
+
+    // Senders that do not need statuses can ignore them.
+    let mut received = vec![0.0f32; world.size() as usize];
+    mpi::request::scope(|scope| {
+        let mut requests = Vec::new();
+        for (i, r) in received.iter_mut().enumerate() {
+            requests.push(
+                world
+                    .process_at_rank(i as Rank)
+                    .immediate_receive_into(scope, r),
+            );
+        }
+        for i in 0..world.size() {
+            requests.push(world.process_at_rank(i).immediate_send(scope, &x));
+        }
+        mpi::request::wait_all_without_status(&mut requests);
+        assert!(requests.is_empty());
+    });
+    assert!(received.iter().all(|&r| r == x));
 }
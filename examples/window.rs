@@ -0,0 +1,49 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::traits::*;
+use mpi::window::Window;
+use mpi::Rank;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    // Every process writes its rank into its slot of the windows of all processes.
+    let mut window = Window::<Rank>::allocate(&world, size as usize);
+    assert_eq!(size as usize, window.len());
+    // The memory is initialized with the default value before any process can access it, and no
+    // RMA operation can target it before the first fence.
+    assert!(unsafe { window.local() }.iter().all(|&slot| slot == 0));
+    window.fence_epoch(|epoch| {
+        for target in 0..size {
+            epoch.put(&rank, target, rank as mpi::Address);
+        }
+    });
+    // The fence has completed all puts and no process accesses this window again.
+    assert_eq!((0..size).collect::<Vec<_>>(), unsafe { window.local() });
+
+    // Every process reads the memory of its right neighbor and adds its rank to the memory of
+    // process 0. The window borrows `memory`, so it is registered with a scope that outlives it.
+    let mut memory = vec![rank; 2];
+    mpi::request::scope(|scope| {
+        let mut window = Window::create(&world, scope, &mut memory[..]);
+        let right = (rank + 1) % size;
+        let mut neighbor = [0 as Rank; 2];
+        window.fence_epoch(|epoch| {
+            epoch.get(&mut neighbor[..], right, 0);
+        });
+        assert_eq!([right, right], neighbor);
+
+        let one = [1 as Rank, rank];
+        window.fence_epoch(|epoch| {
+            epoch.accumulate(&one[..], 0, 0, SystemOperation::sum());
+        });
+        if rank == 0 {
+            // The fence has completed all accumulates.
+            assert_eq!([size, (0..size).sum()], unsafe { window.local() });
+        }
+    });
+}
@@ -0,0 +1,48 @@
+#![deny(warnings)]
+
+use mpi::collective::SystemOperation;
+use mpi::traits::*;
+use mpi::window::{LockType, Window};
+use mpi::Rank;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    // A distributed counter and a flag on process 0
+    let mut window = Window::<Rank>::allocate(&world, if rank == 0 { 2 } else { 0 });
+
+    // Every process draws a ticket from the counter.
+    let one: Rank = 1;
+    let mut ticket: Rank = -1;
+    window.lock_epoch(LockType::Shared, 0, |epoch| {
+        epoch.fetch_and_op(&one, &mut ticket, 0, 0, SystemOperation::sum());
+    });
+    assert!((0..size).contains(&ticket));
+
+    // Only one process succeeds in setting the flag from 0 to its rank + 1.
+    let new = rank + 1;
+    let compare: Rank = 0;
+    let mut previous: Rank = -1;
+    window.lock_epoch(LockType::Shared, 0, |epoch| {
+        epoch.compare_and_swap(&new, &compare, &mut previous, 0, 1);
+    });
+    let won = previous == 0;
+
+    world.barrier();
+
+    let nothing = [0 as Rank; 2];
+    let mut state = [0 as Rank; 2];
+    window.lock_epoch(LockType::Shared, 0, |epoch| {
+        epoch.get_accumulate(&nothing[..], &mut state[..], 0, 0, SystemOperation::no_op());
+    });
+    assert_eq!(size, state[0]);
+    assert_eq!(won, state[1] == new);
+
+    let mut tickets = vec![0 as Rank; size as usize];
+    world.all_gather_into(&ticket, &mut tickets[..]);
+    tickets.sort_unstable();
+    assert_eq!((0..size).collect::<Vec<_>>(), tickets);
+}
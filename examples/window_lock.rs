@@ -0,0 +1,53 @@
+#![deny(warnings)]
+use std::panic::{self, AssertUnwindSafe};
+
+use mpi::collective::SystemOperation;
+use mpi::traits::*;
+use mpi::window::{LockType, Window};
+use mpi::{Address, Rank};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let mut window =
+        Window::<Rank>::allocate(&world, if rank == 0 { 1 + size as usize } else { 0 });
+
+    // Every process increments the counter on process 0 and writes its rank into its slot on
+    // process 0 without any other process taking part.
+    let one: Rank = 1;
+    window.lock_epoch(LockType::Exclusive, 0, |epoch| {
+        epoch.accumulate(&one, 0, 0, SystemOperation::sum());
+    });
+    window.lock_all_epoch(|epoch| {
+        epoch.put(&rank, 0, 1 + rank as Address);
+        epoch.flush(0);
+    });
+
+    world.barrier();
+
+    let mut memory = vec![0 as Rank; 1 + size as usize];
+    window.lock_epoch(LockType::Shared, 0, |epoch| {
+        epoch.get(&mut memory[..], 0, 0);
+    });
+    assert_eq!(size, memory[0]);
+    assert_eq!((0..size).collect::<Vec<_>>(), &memory[1..]);
+
+    // A panic inside of an epoch unlocks the window before the buffers of the epoch are dropped,
+    // so it can be locked again afterwards.
+    let panicked = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut counter: Rank = 0;
+        window.lock_epoch(LockType::Exclusive, 0, |epoch| {
+            epoch.get(&mut counter, 0, 0);
+            panic!("the epoch is closed while unwinding");
+        })
+    }));
+    assert!(panicked.is_err());
+    let mut counter: Rank = 0;
+    window.lock_epoch(LockType::Shared, 0, |epoch| {
+        epoch.get(&mut counter, 0, 0);
+    });
+    assert_eq!(size, counter);
+}
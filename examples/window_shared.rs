@@ -0,0 +1,36 @@
+#![deny(warnings)]
+
+use mpi::traits::*;
+use mpi::window::Window;
+use mpi::Rank;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    // The processes on the same node as the calling process
+    let node = world.split_shared(world.rank());
+    let size = node.size();
+    let rank = node.rank();
+
+    // The barriers separate the direct accesses of the processes to each other's segments.
+    let mut window = Window::<Rank>::allocate_shared(&node, 2);
+    assert_eq!([0, 0], unsafe { window.local() });
+
+    unsafe { window.local_mut() }.copy_from_slice(&[rank, 10 * rank]);
+    node.barrier();
+
+    // The segments of all processes on the node can be read directly.
+    for peer in 0..size {
+        assert_eq!([peer, 10 * peer], unsafe { window.shared_segment(peer) });
+    }
+    node.barrier();
+
+    // Write into the segment of the right neighbor on the node.
+    let right = (rank + 1) % size;
+    unsafe { window.shared_segment_mut(right)[1] = rank };
+    node.barrier();
+
+    let left = (rank + size - 1) % size;
+    assert_eq!([rank, left], unsafe { window.local() });
+}
@@ -0,0 +1,24 @@
+#![deny(warnings)]
+//! Running a closure inside an MPI universe
+use mpi::collective::SystemOperation;
+use mpi::traits::*;
+
+fn main() {
+    let sum = mpi::with_universe(|universe| {
+        // Also abort if a thread other than the main thread panics
+        universe.install_panic_hook();
+
+        let world = universe.world();
+        let mut sum = 0;
+        world.all_reduce_into(&world.rank(), &mut sum, SystemOperation::sum());
+        // A panic in here aborts all processes instead of leaving them waiting in the reduction
+        assert_eq!(sum, world.size() * (world.size() - 1) / 2);
+        sum
+    })
+    .unwrap();
+    assert!(sum >= 0);
+
+    // MPI has been finalized when the closure returned
+    assert!(mpi::environment::is_finalized());
+    assert!(mpi::with_universe(|_| ()).is_none());
+}
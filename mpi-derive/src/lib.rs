@@ -6,11 +6,19 @@ type TokenStream2 = proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Fields, Type};
 
+/// Derives `Equivalence` for structs and makes fieldless enums sendable.
+///
+/// Structs are described by a structured datatype built from the `Equivalence` of their fields.
+/// Fieldless enums need an integer `#[repr]`, e.g. `#[repr(i32)]`, and are sent as the datatype of
+/// that integer type. A received discriminant that does not belong to any variant is not a valid
+/// enum, so like `char` they are only a `Buffer` and not `Equivalence`, which would make them
+/// receive buffers as well. They are received into their representation and checked with the
+/// `TryFrom` conversion that is derived alongside.
 #[proc_macro_derive(Equivalence)]
 pub fn create_user_datatype(input: TokenStream1) -> TokenStream1 {
     let ast: syn::DeriveInput = syn::parse(input).expect("Couldn't parse struct");
     let result = match ast.data {
-        syn::Data::Enum(_) => panic!("#[derive(Equivalence)] is not compatible with enums"),
+        syn::Data::Enum(ref e) => equivalence_for_enum(&ast, e),
         syn::Data::Union(_) => panic!("#[derive(Equivalence)] is not compatible with unions"),
         syn::Data::Struct(ref s) => equivalence_for_struct(&ast, &s.fields),
     };
@@ -90,15 +98,17 @@ fn equivalence_for_struct(ast: &syn::DeriveInput, fields: &Fields) -> TokenStrea
                 use ::mpi::internal::once_cell::sync::Lazy;
                 use ::std::convert::TryInto;
 
-                static DATATYPE: Lazy<::mpi::datatype::UserDatatype> = Lazy::new(|| {
+                static DATATYPE: Lazy<::mpi::datatype::internal::StaticDatatype> = Lazy::new(|| {
                     ::mpi::datatype::internal::check_derive_equivalence_universe_state(#ident_str);
 
-                    ::mpi::datatype::UserDatatype::structured::<
-                        ::mpi::datatype::UncommittedDatatypeRef,
-                    >(
-                        &[#(#field_blocklengths as ::mpi::Count),*],
-                        &[#(::mpi::internal::memoffset::offset_of!(#ident, #field_names) as ::mpi::Address),*],
-                        &[#(::mpi::datatype::UncommittedDatatypeRef::from(#field_datatypes)),*],
+                    ::mpi::datatype::internal::StaticDatatype::new(
+                        ::mpi::datatype::UserDatatype::structured::<
+                            ::mpi::datatype::UncommittedDatatypeRef,
+                        >(
+                            &[#(#field_blocklengths as ::mpi::Count),*],
+                            &[#(::mpi::internal::memoffset::offset_of!(#ident, #field_names) as ::mpi::Address),*],
+                            &[#(::mpi::datatype::UncommittedDatatypeRef::from(#field_datatypes)),*],
+                        ),
                     )
                 });
 
@@ -107,3 +117,82 @@ fn equivalence_for_struct(ast: &syn::DeriveInput, fields: &Fields) -> TokenStrea
         }
     }
 }
+
+fn enum_repr(ast: &syn::DeriveInput) -> Option<syn::Ident> {
+    const INT_REPRS: [&str; 10] = [
+        "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+    ];
+
+    let mut repr = None;
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if INT_REPRS.contains(&ident.to_string().as_str()) {
+                    repr = Some(ident.clone());
+                }
+            }
+            Ok(())
+        })
+        .expect("Couldn't parse #[repr] attribute");
+    }
+    repr
+}
+
+fn equivalence_for_enum(ast: &syn::DeriveInput, data: &syn::DataEnum) -> TokenStream2 {
+    let ident = &ast.ident;
+
+    if data
+        .variants
+        .iter()
+        .any(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        panic!("#[derive(Equivalence)] is only compatible with fieldless enums");
+    }
+
+    let repr = enum_repr(ast).unwrap_or_else(|| {
+        panic!(
+            "#[derive(Equivalence)] requires enums to have an integer #[repr], e.g. #[repr(i32)]"
+        )
+    });
+
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+    let variants_again = variants.clone();
+
+    // Receiving a discriminant that does not correspond to any variant directly into the enum
+    // would be undefined behavior, so the enum is only a send `Buffer` and not `Equivalence`,
+    // which would make it a `BufferMut` as well.
+    quote! {
+        unsafe impl ::mpi::datatype::AsDatatype for #ident {
+            type Out = <#repr as ::mpi::datatype::Equivalence>::Out;
+            fn as_datatype(&self) -> Self::Out {
+                <#repr as ::mpi::datatype::Equivalence>::equivalent_datatype()
+            }
+        }
+
+        unsafe impl ::mpi::datatype::Collection for #ident {
+            fn count(&self) -> ::mpi::Count {
+                1
+            }
+        }
+
+        unsafe impl ::mpi::datatype::Pointer for #ident {
+            fn pointer(&self) -> *const ::std::os::raw::c_void {
+                self as *const Self as *const ::std::os::raw::c_void
+            }
+        }
+
+        unsafe impl ::mpi::datatype::Buffer for #ident {}
+
+        impl ::std::convert::TryFrom<#repr> for #ident {
+            type Error = #repr;
+            #[allow(non_upper_case_globals)]
+            fn try_from(value: #repr) -> ::std::result::Result<Self, Self::Error> {
+                #(const #variants: #repr = #ident::#variants as #repr;)*
+                match value {
+                    #(#variants_again => ::std::result::Result::Ok(#ident::#variants_again),)*
+                    _ => ::std::result::Result::Err(value),
+                }
+            }
+        }
+    }
+}
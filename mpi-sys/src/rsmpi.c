@@ -1,5 +1,11 @@
 #include "rsmpi.h"
 
+// Open MPI declares its extensions, including user level failure mitigation, in a separate
+// header.
+#if defined(OPEN_MPI) && OPEN_MPI
+#include <mpi-ext.h>
+#endif
+
 const MPI_Datatype RSMPI_C_BOOL = MPI_C_BOOL;
 
 const MPI_Datatype RSMPI_FLOAT = MPI_FLOAT;
@@ -18,6 +24,11 @@ const MPI_Datatype RSMPI_UINT64_T = MPI_UINT64_T;
 const MPI_Datatype RSMPI_FLOAT_COMPLEX = MPI_C_FLOAT_COMPLEX;
 const MPI_Datatype RSMPI_DOUBLE_COMPLEX = MPI_C_DOUBLE_COMPLEX;
 
+const MPI_Datatype RSMPI_FLOAT_INT = MPI_FLOAT_INT;
+const MPI_Datatype RSMPI_DOUBLE_INT = MPI_DOUBLE_INT;
+const MPI_Datatype RSMPI_SHORT_INT = MPI_SHORT_INT;
+const MPI_Datatype RSMPI_2INT = MPI_2INT;
+
 const MPI_Datatype RSMPI_DATATYPE_NULL = MPI_DATATYPE_NULL;
 
 const MPI_Comm RSMPI_COMM_WORLD = MPI_COMM_WORLD;
@@ -25,6 +36,13 @@ const MPI_Comm RSMPI_COMM_NULL = MPI_COMM_NULL;
 const MPI_Comm RSMPI_COMM_SELF = MPI_COMM_SELF;
 
 const int RSMPI_COMM_TYPE_SHARED = MPI_COMM_TYPE_SHARED;
+// Hardware guided splits were introduced in MPI 4.0, splitting by an undefined type returns
+// MPI_COMM_NULL on older implementations.
+#if MPI_VERSION >= 4
+const int RSMPI_COMM_TYPE_HW_GUIDED = MPI_COMM_TYPE_HW_GUIDED;
+#else
+const int RSMPI_COMM_TYPE_HW_GUIDED = MPI_UNDEFINED;
+#endif
 
 const MPI_Group RSMPI_GROUP_EMPTY = MPI_GROUP_EMPTY;
 const MPI_Group RSMPI_GROUP_NULL = MPI_GROUP_NULL;
@@ -69,6 +87,10 @@ const MPI_Op RSMPI_LOR = MPI_LOR;
 const MPI_Op RSMPI_BOR = MPI_BOR;
 const MPI_Op RSMPI_LXOR = MPI_LXOR;
 const MPI_Op RSMPI_BXOR = MPI_BXOR;
+const MPI_Op RSMPI_REPLACE = MPI_REPLACE;
+const MPI_Op RSMPI_NO_OP = MPI_NO_OP;
+const MPI_Op RSMPI_MINLOC = MPI_MINLOC;
+const MPI_Op RSMPI_MAXLOC = MPI_MAXLOC;
 
 const MPI_Errhandler RSMPI_ERRORS_ARE_FATAL = MPI_ERRORS_ARE_FATAL;
 const MPI_Errhandler RSMPI_ERRORS_RETURN = MPI_ERRORS_RETURN;
@@ -79,6 +101,32 @@ const MPI_Info RSMPI_INFO_NULL = MPI_INFO_NULL;
 
 const MPI_Win RSMPI_WIN_NULL = MPI_WIN_NULL;
 
+const int RSMPI_ORDER_C = MPI_ORDER_C;
+const int RSMPI_ORDER_FORTRAN = MPI_ORDER_FORTRAN;
+
+const int RSMPI_DISTRIBUTE_BLOCK = MPI_DISTRIBUTE_BLOCK;
+const int RSMPI_DISTRIBUTE_CYCLIC = MPI_DISTRIBUTE_CYCLIC;
+const int RSMPI_DISTRIBUTE_NONE = MPI_DISTRIBUTE_NONE;
+const int RSMPI_DISTRIBUTE_DFLT_DARG = MPI_DISTRIBUTE_DFLT_DARG;
+
+const int RSMPI_COMBINER_NAMED = MPI_COMBINER_NAMED;
+const int RSMPI_COMBINER_DUP = MPI_COMBINER_DUP;
+const int RSMPI_COMBINER_CONTIGUOUS = MPI_COMBINER_CONTIGUOUS;
+const int RSMPI_COMBINER_VECTOR = MPI_COMBINER_VECTOR;
+const int RSMPI_COMBINER_HVECTOR = MPI_COMBINER_HVECTOR;
+const int RSMPI_COMBINER_INDEXED = MPI_COMBINER_INDEXED;
+const int RSMPI_COMBINER_HINDEXED = MPI_COMBINER_HINDEXED;
+const int RSMPI_COMBINER_INDEXED_BLOCK = MPI_COMBINER_INDEXED_BLOCK;
+const int RSMPI_COMBINER_HINDEXED_BLOCK = MPI_COMBINER_HINDEXED_BLOCK;
+const int RSMPI_COMBINER_STRUCT = MPI_COMBINER_STRUCT;
+const int RSMPI_COMBINER_SUBARRAY = MPI_COMBINER_SUBARRAY;
+const int RSMPI_COMBINER_DARRAY = MPI_COMBINER_DARRAY;
+const int RSMPI_COMBINER_RESIZED = MPI_COMBINER_RESIZED;
+
+const int RSMPI_ROOT = MPI_ROOT;
+
+void* const RSMPI_IN_PLACE = MPI_IN_PLACE;
+
 int* RSMPI_UNWEIGHTED() {
     return MPI_UNWEIGHTED;
 }
@@ -90,6 +138,126 @@ double RSMPI_Wtick() {
   return MPI_Wtick();
 }
 
+#define RSMPI_errhandler_def(kind, handle) \
+  static RSMPI_ ## kind ## _errhandler_function *rsmpi_ ## kind ## _errhandler_callback = NULL; \
+  \
+  static void rsmpi_ ## kind ## _errhandler(handle *h, int *code, ...) { \
+    rsmpi_ ## kind ## _errhandler_callback(h, code); \
+  } \
+  \
+  int RSMPI_ ## kind ## _create_errhandler(RSMPI_ ## kind ## _errhandler_function *callback, MPI_Errhandler *errhandler) { \
+    rsmpi_ ## kind ## _errhandler_callback = callback; \
+    return MPI_ ## kind ## _create_errhandler(rsmpi_ ## kind ## _errhandler, errhandler); \
+  }
+
+RSMPI_errhandler_def(Comm, MPI_Comm)
+RSMPI_errhandler_def(File, MPI_File)
+RSMPI_errhandler_def(Win, MPI_Win)
+
+// Functions introduced in MPI 4.0 report an unsupported operation on older implementations.
+#if MPI_VERSION >= 4
+#define RSMPI_mpi4_def(name, params, args) \
+  int RSMPI_ ## name params { \
+    return MPI_ ## name args; \
+  }
+#else
+#define RSMPI_mpi4_def(name, params, args) \
+  int RSMPI_ ## name params { \
+    return MPI_ERR_UNSUPPORTED_OPERATION; \
+  }
+#endif
+
+RSMPI_mpi4_def(Psend_init,
+               (const void *buf, int partitions, MPI_Count count, MPI_Datatype datatype, int dest,
+                int tag, MPI_Comm comm, MPI_Info info, MPI_Request *request),
+               (buf, partitions, count, datatype, dest, tag, comm, info, request))
+RSMPI_mpi4_def(Precv_init,
+               (void *buf, int partitions, MPI_Count count, MPI_Datatype datatype, int source,
+                int tag, MPI_Comm comm, MPI_Info info, MPI_Request *request),
+               (buf, partitions, count, datatype, source, tag, comm, info, request))
+RSMPI_mpi4_def(Pready, (int partition, MPI_Request request), (partition, request))
+RSMPI_mpi4_def(Pready_range, (int partition_low, int partition_high, MPI_Request request),
+               (partition_low, partition_high, request))
+RSMPI_mpi4_def(Parrived, (MPI_Request request, int partition, int *flag),
+               (request, partition, flag))
+
+RSMPI_mpi4_def(Barrier_init, (MPI_Comm comm, MPI_Info info, MPI_Request *request),
+               (comm, info, request))
+RSMPI_mpi4_def(Bcast_init,
+               (void *buffer, int count, MPI_Datatype datatype, int root, MPI_Comm comm,
+                MPI_Info info, MPI_Request *request),
+               (buffer, count, datatype, root, comm, info, request))
+RSMPI_mpi4_def(Allgather_init,
+               (const void *sendbuf, int sendcount, MPI_Datatype sendtype, void *recvbuf,
+                int recvcount, MPI_Datatype recvtype, MPI_Comm comm, MPI_Info info,
+                MPI_Request *request),
+               (sendbuf, sendcount, sendtype, recvbuf, recvcount, recvtype, comm, info, request))
+RSMPI_mpi4_def(Allreduce_init,
+               (const void *sendbuf, void *recvbuf, int count, MPI_Datatype datatype, MPI_Op op,
+                MPI_Comm comm, MPI_Info info, MPI_Request *request),
+               (sendbuf, recvbuf, count, datatype, op, comm, info, request))
+RSMPI_mpi4_def(Reduce_init,
+               (const void *sendbuf, void *recvbuf, int count, MPI_Datatype datatype, MPI_Op op,
+                int root, MPI_Comm comm, MPI_Info info, MPI_Request *request),
+               (sendbuf, recvbuf, count, datatype, op, root, comm, info, request))
+
+RSMPI_mpi4_def(Session_init,
+               (MPI_Info info, MPI_Errhandler errhandler, RSMPI_Session *session),
+               (info, errhandler, session))
+RSMPI_mpi4_def(Session_finalize, (RSMPI_Session *session), (session))
+RSMPI_mpi4_def(Session_get_num_psets,
+               (RSMPI_Session session, MPI_Info info, int *npset_names),
+               (session, info, npset_names))
+RSMPI_mpi4_def(Session_get_nth_pset,
+               (RSMPI_Session session, MPI_Info info, int n, int *pset_len, char *pset_name),
+               (session, info, n, pset_len, pset_name))
+RSMPI_mpi4_def(Group_from_session_pset,
+               (RSMPI_Session session, const char *pset_name, MPI_Group *newgroup),
+               (session, pset_name, newgroup))
+RSMPI_mpi4_def(Comm_create_from_group,
+               (MPI_Group group, const char *stringtag, MPI_Info info, MPI_Errhandler errhandler,
+                MPI_Comm *newcomm),
+               (group, stringtag, info, errhandler, newcomm))
+
+// User level failure mitigation is an extension that is only available in some builds of some
+// implementations. Without it the functions report an unsupported operation and the error classes
+// are MPI_SUCCESS, which is never the class of an error.
+#if defined(MPIX_ERR_PROC_FAILED)
+#define RSMPI_ulfm_def(name, params, args) \
+  int RSMPI_ ## name params { \
+    return MPIX_ ## name args; \
+  }
+
+const int RSMPI_ERR_PROC_FAILED = MPIX_ERR_PROC_FAILED;
+const int RSMPI_ERR_REVOKED = MPIX_ERR_REVOKED;
+#else
+#define RSMPI_ulfm_def(name, params, args) \
+  int RSMPI_ ## name params { \
+    return MPI_ERR_UNSUPPORTED_OPERATION; \
+  }
+
+const int RSMPI_ERR_PROC_FAILED = MPI_SUCCESS;
+const int RSMPI_ERR_REVOKED = MPI_SUCCESS;
+#endif
+
+RSMPI_ulfm_def(Comm_revoke, (MPI_Comm comm), (comm))
+RSMPI_ulfm_def(Comm_shrink, (MPI_Comm comm, MPI_Comm *newcomm), (comm, newcomm))
+RSMPI_ulfm_def(Comm_agree, (MPI_Comm comm, int *flag), (comm, flag))
+RSMPI_ulfm_def(Comm_failure_ack, (MPI_Comm comm), (comm))
+RSMPI_ulfm_def(Comm_failure_get_acked, (MPI_Comm comm, MPI_Group *failedgrp), (comm, failedgrp))
+
+// CUDA-aware builds of Open MPI declare MPIX_Query_cuda_support() in mpi-ext.h, MPICH provides
+// it since version 4.0. Other libraries are assumed not to accept device memory.
+int RSMPI_Query_cuda_support(void) {
+#if defined(MPIX_CUDA_AWARE_SUPPORT) && MPIX_CUDA_AWARE_SUPPORT
+  return MPIX_Query_cuda_support();
+#elif defined(MPICH_NUMVERSION) && MPICH_NUMVERSION >= 40000000
+  return MPIX_Query_cuda_support();
+#else
+  return 0;
+#endif
+}
+
 #define RSMPI_c2f_def_base(type, ctype, argname) \
   MPI_Fint RS ## type ## _c2f(ctype     argname) { \
     return type ## _c2f(argname); \
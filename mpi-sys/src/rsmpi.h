@@ -24,6 +24,11 @@ extern const MPI_Datatype RSMPI_UINT64_T;
 extern const MPI_Datatype RSMPI_FLOAT_COMPLEX;
 extern const MPI_Datatype RSMPI_DOUBLE_COMPLEX;
 
+extern const MPI_Datatype RSMPI_FLOAT_INT;
+extern const MPI_Datatype RSMPI_DOUBLE_INT;
+extern const MPI_Datatype RSMPI_SHORT_INT;
+extern const MPI_Datatype RSMPI_2INT;
+
 extern const MPI_Datatype RSMPI_DATATYPE_NULL;
 
 extern const MPI_Comm RSMPI_COMM_WORLD;
@@ -31,6 +36,7 @@ extern const MPI_Comm RSMPI_COMM_NULL;
 extern const MPI_Comm RSMPI_COMM_SELF;
 
 extern const int RSMPI_COMM_TYPE_SHARED;
+extern const int RSMPI_COMM_TYPE_HW_GUIDED;
 
 extern const MPI_Group RSMPI_GROUP_EMPTY;
 extern const MPI_Group RSMPI_GROUP_NULL;
@@ -75,6 +81,10 @@ extern const MPI_Op RSMPI_LOR;
 extern const MPI_Op RSMPI_BOR;
 extern const MPI_Op RSMPI_LXOR;
 extern const MPI_Op RSMPI_BXOR;
+extern const MPI_Op RSMPI_REPLACE;
+extern const MPI_Op RSMPI_NO_OP;
+extern const MPI_Op RSMPI_MINLOC;
+extern const MPI_Op RSMPI_MAXLOC;
 
 extern const MPI_Errhandler RSMPI_ERRORS_ARE_FATAL;
 extern const MPI_Errhandler RSMPI_ERRORS_RETURN;
@@ -85,11 +95,97 @@ extern const MPI_Info RSMPI_INFO_NULL;
 
 extern const MPI_Win RSMPI_WIN_NULL;
 
+extern const int RSMPI_ORDER_C;
+extern const int RSMPI_ORDER_FORTRAN;
+
+extern const int RSMPI_DISTRIBUTE_BLOCK;
+extern const int RSMPI_DISTRIBUTE_CYCLIC;
+extern const int RSMPI_DISTRIBUTE_NONE;
+extern const int RSMPI_DISTRIBUTE_DFLT_DARG;
+
+extern const int RSMPI_COMBINER_NAMED;
+extern const int RSMPI_COMBINER_DUP;
+extern const int RSMPI_COMBINER_CONTIGUOUS;
+extern const int RSMPI_COMBINER_VECTOR;
+extern const int RSMPI_COMBINER_HVECTOR;
+extern const int RSMPI_COMBINER_INDEXED;
+extern const int RSMPI_COMBINER_HINDEXED;
+extern const int RSMPI_COMBINER_INDEXED_BLOCK;
+extern const int RSMPI_COMBINER_HINDEXED_BLOCK;
+extern const int RSMPI_COMBINER_STRUCT;
+extern const int RSMPI_COMBINER_SUBARRAY;
+extern const int RSMPI_COMBINER_DARRAY;
+extern const int RSMPI_COMBINER_RESIZED;
+
+extern const int RSMPI_ROOT;
+
+extern void* const RSMPI_IN_PLACE;
+
 int* RSMPI_UNWEIGHTED();
 
 double RSMPI_Wtime();
 double RSMPI_Wtick();
 
+// Error handler functions are variadic, which cannot be defined in stable Rust. These forward
+// MPI errors to a non-variadic callback instead.
+typedef void RSMPI_Comm_errhandler_function(MPI_Comm *, int *);
+typedef void RSMPI_File_errhandler_function(MPI_File *, int *);
+typedef void RSMPI_Win_errhandler_function(MPI_Win *, int *);
+
+int RSMPI_Comm_create_errhandler(RSMPI_Comm_errhandler_function *callback, MPI_Errhandler *errhandler);
+int RSMPI_File_create_errhandler(RSMPI_File_errhandler_function *callback, MPI_Errhandler *errhandler);
+int RSMPI_Win_create_errhandler(RSMPI_Win_errhandler_function *callback, MPI_Errhandler *errhandler);
+
+// Sessions were introduced in MPI 4.0, older implementations get a placeholder handle type.
+#if MPI_VERSION >= 4
+typedef MPI_Session RSMPI_Session;
+#else
+typedef int RSMPI_Session;
+#endif
+
+// Functions introduced in MPI 4.0, these return MPI_ERR_UNSUPPORTED_OPERATION on older
+// implementations.
+int RSMPI_Psend_init(const void *buf, int partitions, MPI_Count count, MPI_Datatype datatype,
+                     int dest, int tag, MPI_Comm comm, MPI_Info info, MPI_Request *request);
+int RSMPI_Precv_init(void *buf, int partitions, MPI_Count count, MPI_Datatype datatype,
+                     int source, int tag, MPI_Comm comm, MPI_Info info, MPI_Request *request);
+int RSMPI_Pready(int partition, MPI_Request request);
+int RSMPI_Pready_range(int partition_low, int partition_high, MPI_Request request);
+int RSMPI_Parrived(MPI_Request request, int partition, int *flag);
+int RSMPI_Barrier_init(MPI_Comm comm, MPI_Info info, MPI_Request *request);
+int RSMPI_Bcast_init(void *buffer, int count, MPI_Datatype datatype, int root, MPI_Comm comm,
+                     MPI_Info info, MPI_Request *request);
+int RSMPI_Allgather_init(const void *sendbuf, int sendcount, MPI_Datatype sendtype, void *recvbuf,
+                         int recvcount, MPI_Datatype recvtype, MPI_Comm comm, MPI_Info info,
+                         MPI_Request *request);
+int RSMPI_Allreduce_init(const void *sendbuf, void *recvbuf, int count, MPI_Datatype datatype,
+                         MPI_Op op, MPI_Comm comm, MPI_Info info, MPI_Request *request);
+int RSMPI_Reduce_init(const void *sendbuf, void *recvbuf, int count, MPI_Datatype datatype,
+                      MPI_Op op, int root, MPI_Comm comm, MPI_Info info, MPI_Request *request);
+int RSMPI_Session_init(MPI_Info info, MPI_Errhandler errhandler, RSMPI_Session *session);
+int RSMPI_Session_finalize(RSMPI_Session *session);
+int RSMPI_Session_get_num_psets(RSMPI_Session session, MPI_Info info, int *npset_names);
+int RSMPI_Session_get_nth_pset(RSMPI_Session session, MPI_Info info, int n, int *pset_len,
+                               char *pset_name);
+int RSMPI_Group_from_session_pset(RSMPI_Session session, const char *pset_name,
+                                  MPI_Group *newgroup);
+int RSMPI_Comm_create_from_group(MPI_Group group, const char *stringtag, MPI_Info info,
+                                 MPI_Errhandler errhandler, MPI_Comm *newcomm);
+
+// User level failure mitigation (MPIX_), these return MPI_ERR_UNSUPPORTED_OPERATION if the
+// implementation does not provide it.
+extern const int RSMPI_ERR_PROC_FAILED;
+extern const int RSMPI_ERR_REVOKED;
+
+int RSMPI_Comm_revoke(MPI_Comm comm);
+int RSMPI_Comm_shrink(MPI_Comm comm, MPI_Comm *newcomm);
+int RSMPI_Comm_agree(MPI_Comm comm, int *flag);
+int RSMPI_Comm_failure_ack(MPI_Comm comm);
+int RSMPI_Comm_failure_get_acked(MPI_Comm comm, MPI_Group *failedgrp);
+
+// Whether the library accepts CUDA device memory in communication calls, 0 if it cannot tell.
+int RSMPI_Query_cuda_support(void);
+
 // MPICH uses macros for c2f - explicitly define them.
 #define RSMPI_c2f_decl_base(type, ctype, argname) \
   MPI_Fint RS ## type ## _c2f(ctype     argname); \
@@ -1,19 +1,26 @@
-//! Attribute caching on communicators
+//! Attribute caching on communicators, datatypes, and windows
 
 use std::{any::TypeId, collections::HashMap, ffi::c_void, os::raw::c_int, ptr, sync::RwLock};
 
 use once_cell::sync::Lazy;
 
+use crate::instrument::{self, Call};
 use crate::{ffi, traits::AsRaw};
 
 /// Topology traits
 pub mod traits {
-    pub use super::CommAttribute;
+    pub use super::{CommAttribute, DatatypeAttribute, WindowAttribute};
 }
 
 pub(crate) static COMM_ATTRS: Lazy<RwLock<HashMap<TypeId, AttributeKey>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+pub(crate) static DATATYPE_ATTRS: Lazy<RwLock<HashMap<TypeId, AttributeKey>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub(crate) static WINDOW_ATTRS: Lazy<RwLock<HashMap<TypeId, AttributeKey>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 /// Attributes are user data that can be owned by communicators and accessed by
 /// users. They are useful when libraries pass communicators to a different
 /// library and get it back in a callback.
@@ -101,6 +108,7 @@ where
             }
         }
         let mut key: i32 = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Comm_create_keyval"));
         unsafe {
             ffi::MPI_Comm_create_keyval(
                 Some(Self::comm_copy_attr_fn),
@@ -116,6 +124,165 @@ where
     }
 }
 
+/// Attributes cached on datatypes, see `CommAttribute`.
+///
+/// # Examples
+///
+/// See `examples/attributes_datatype_window.rs`
+///
+/// # Standard section(s)
+///
+/// 7.7.4
+pub trait DatatypeAttribute
+where
+    Self: 'static + Sized + Clone,
+{
+    /// Whether the attribute is cloned to datatypes created by `MPI_Type_dup()`, e.g. through
+    /// `UserDatatype::clone()`. The default does not propagate attributes.
+    const CLONE_ON_DUP: bool = false;
+
+    /// Callback invoked by `MPI_Type_free()` and `MPI_Type_delete_attr()` to delete an
+    /// attribute.
+    ///
+    /// # Safety
+    ///
+    /// This default implementation goes with the boxing in `UserDatatype::set_attr()`.
+    unsafe extern "C" fn type_delete_attr_fn(
+        _datatype: ffi::MPI_Datatype,
+        _key: c_int,
+        val: *mut c_void,
+        _extra_state: *mut c_void,
+    ) -> c_int {
+        let _to_drop = Box::from_raw(val as *mut Self);
+        ffi::MPI_SUCCESS as i32
+    }
+
+    /// Callback invoked by `MPI_Type_dup()` to (optionally) clone the attribute to the new
+    /// datatype, as determined by `Self::CLONE_ON_DUP`.
+    ///
+    /// # Safety
+    ///
+    /// This default implementation must only be used with boxed attributes, as in
+    /// `UserDatatype::set_attr()`.
+    unsafe extern "C" fn type_copy_attr_fn(
+        _old_datatype: ffi::MPI_Datatype,
+        _key: c_int,
+        _extra_state: *mut c_void,
+        val_in: *mut c_void,
+        val_out: *mut c_void,
+        flag: *mut c_int,
+    ) -> c_int {
+        if Self::CLONE_ON_DUP {
+            let b_out = Box::new((*(val_in as *const Self)).clone());
+            *(val_out as *mut *mut Self) = Box::into_raw(b_out);
+            *flag = 1;
+        } else {
+            *flag = 0;
+        }
+        ffi::MPI_SUCCESS as i32
+    }
+
+    /// Get the attribute key for this attribute. User keys are provisioned by
+    /// `MPI_Type_create_keyval()` and freed prior to `MPI_Finalize()`.
+    fn get_key() -> AttributeKey {
+        let id = TypeId::of::<Self>();
+        {
+            let datatype_attrs = DATATYPE_ATTRS
+                .read()
+                .expect("DATATYPE_ATTRS RwLock poisoned");
+            if let Some(key) = datatype_attrs.get(&id) {
+                return key.clone();
+            }
+        }
+        let mut key: i32 = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_keyval"));
+        unsafe {
+            ffi::MPI_Type_create_keyval(
+                Some(Self::type_copy_attr_fn),
+                Some(Self::type_delete_attr_fn),
+                &mut key,
+                ptr::null_mut(),
+            );
+        }
+        let key = AttributeKey(key);
+        let mut datatype_attrs = DATATYPE_ATTRS
+            .write()
+            .expect("DATATYPE_ATTRS RwLock poisoned");
+        datatype_attrs.insert(id, key.clone());
+        key
+    }
+}
+
+/// Attributes cached on windows, see `CommAttribute`.
+///
+/// Windows cannot be duplicated, so there is no equivalent to `CommAttribute::CLONE_ON_DUP`.
+///
+/// # Examples
+///
+/// See `examples/attributes_datatype_window.rs`
+///
+/// # Standard section(s)
+///
+/// 7.7.3
+pub trait WindowAttribute
+where
+    Self: 'static + Sized,
+{
+    /// Callback invoked by `MPI_Win_free()` and `MPI_Win_delete_attr()` to delete an attribute.
+    ///
+    /// # Safety
+    ///
+    /// This default implementation goes with the boxing in `Window::set_attr()`.
+    unsafe extern "C" fn win_delete_attr_fn(
+        _window: ffi::MPI_Win,
+        _key: c_int,
+        val: *mut c_void,
+        _extra_state: *mut c_void,
+    ) -> c_int {
+        let _to_drop = Box::from_raw(val as *mut Self);
+        ffi::MPI_SUCCESS as i32
+    }
+
+    /// Get the attribute key for this attribute. User keys are provisioned by
+    /// `MPI_Win_create_keyval()` and freed prior to `MPI_Finalize()`.
+    fn get_key() -> AttributeKey {
+        let id = TypeId::of::<Self>();
+        {
+            let window_attrs = WINDOW_ATTRS.read().expect("WINDOW_ATTRS RwLock poisoned");
+            if let Some(key) = window_attrs.get(&id) {
+                return key.clone();
+            }
+        }
+        let mut key: i32 = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_create_keyval"));
+        unsafe {
+            ffi::MPI_Win_create_keyval(
+                Some(win_copy_attr_fn),
+                Some(Self::win_delete_attr_fn),
+                &mut key,
+                ptr::null_mut(),
+            );
+        }
+        let key = AttributeKey(key);
+        let mut window_attrs = WINDOW_ATTRS.write().expect("WINDOW_ATTRS RwLock poisoned");
+        window_attrs.insert(id, key.clone());
+        key
+    }
+}
+
+// Windows are never copied, but some implementations reject a null copy callback.
+unsafe extern "C" fn win_copy_attr_fn(
+    _old_window: ffi::MPI_Win,
+    _key: c_int,
+    _extra_state: *mut c_void,
+    _val_in: *mut c_void,
+    _val_out: *mut c_void,
+    flag: *mut c_int,
+) -> c_int {
+    *flag = 0;
+    ffi::MPI_SUCCESS as i32
+}
+
 /// Attribute keys are used internally to access attributes. They are obtained
 /// with the associated function `CommAttribute::get_key()`.
 ///
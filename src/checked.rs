@@ -0,0 +1,417 @@
+//! Runtime checks of the datatypes of point to point messages, enabled by the `checked` feature
+//!
+//! Every checked send is preceded by a small header message with the same tag which describes
+//! the type signature of the message, i.e. the sequence of predefined datatypes it consists of.
+//! Checked receives first receive the header, then the message from the process and with the tag
+//! of the header, and panic if the signature of the message is not a prefix of the signature of
+//! the receive buffer.
+//!
+//! Only the blocking point to point operations are checked. The header is sent in the mode of the
+//! message, so buffered mode sends take up room for a header of two `u64`s plus `BSEND_OVERHEAD`
+//! in the attached buffer. Blocking ready mode sends are done in standard mode while the checks
+//! are enabled, since the matching receive only takes the header first. Immediate, persistent and
+//! partitioned operations, probes and matched receives neither send nor expect a header, so mixing
+//! them with checked operations would match headers with messages. They panic while the checks
+//! are enabled instead. Messages that rsmpi exchanges internally either use the checked
+//! operations on both sides or private communicators.
+//!
+//! The checks are only done if the `RSMPI_CHECKED` environment variable is set to a value other
+//! than `0` when the first message is exchanged, so that builds with all features enabled behave
+//! like unchecked ones. All processes have to agree on the setting.
+//!
+//! Signatures are kept run-length encoded, so large buffers of a single predefined datatype are
+//! cheap to describe. Datatypes whose signature cannot be determined, e.g. distributed arrays,
+//! are not checked.
+
+use std::env;
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+use std::os::raw::c_char;
+
+use conv::ConvUtil;
+use once_cell::sync::Lazy;
+
+use crate::datatype::traits::*;
+use crate::datatype::{ConstituentDatatype, Contents};
+use crate::ffi;
+use crate::ffi::MPI_Comm;
+use crate::instrument::{self, Call};
+use crate::topology::Rank;
+use crate::{with_uninitialized, Count, Tag};
+
+/// Longest signature that is built by repeating a datatype, longer ones are not checked
+const MAX_RUNS: usize = 1 << 16;
+
+/// The header of a datatype that is not checked
+const UNCHECKED: [u64; 2] = [u64::MAX, 0];
+
+/// Whether messages are checked, see the module documentation
+static ENABLED: Lazy<bool> =
+    Lazy::new(|| env::var_os("RSMPI_CHECKED").is_some_and(|value| value != "0"));
+
+/// Whether messages are checked, see the module documentation
+pub(crate) fn enabled() -> bool {
+    *ENABLED
+}
+
+/// Panics if messages are checked, for the operations that neither send nor expect a header
+pub(crate) fn unsupported(operation: &str) {
+    assert!(
+        !*ENABLED,
+        "`{}()` cannot be used while point to point messages are checked, only blocking operations \
+         are supported with RSMPI_CHECKED set.",
+        operation
+    );
+}
+
+/// A run-length encoded sequence of predefined datatypes, identified by their names
+#[derive(Default, Clone, PartialEq, Eq)]
+struct Signature(Vec<(u64, u64)>);
+
+impl Signature {
+    fn push(&mut self, id: u64, count: u64) {
+        if count == 0 {
+            return;
+        }
+        match self.0.last_mut() {
+            Some((last, n)) if *last == id => *n += count,
+            _ => self.0.push((id, count)),
+        }
+    }
+
+    fn append(&mut self, other: &Signature, repeat: u64) -> Option<()> {
+        match &other.0[..] {
+            [] => {}
+            [(id, count)] => self.push(*id, count.checked_mul(repeat)?),
+            runs => {
+                if self.0.len() + runs.len() * repeat.value_as::<usize>().ok()? > MAX_RUNS {
+                    return None;
+                }
+                for _ in 0..repeat {
+                    for &(id, count) in runs {
+                        self.push(id, count);
+                    }
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// The number of predefined datatypes in the signature
+    fn len(&self) -> u64 {
+        self.0.iter().map(|&(_, count)| count).sum()
+    }
+
+    /// FNV-1a hash of the first `len` predefined datatypes, `None` if the signature is shorter
+    fn hash_prefix(&self, len: u64) -> Option<u64> {
+        let mut hash = Hash::new();
+        let mut remaining = len;
+        for &(id, count) in &self.0 {
+            if remaining == 0 {
+                break;
+            }
+            let count = count.min(remaining);
+            hash.write(id);
+            hash.write(count);
+            remaining -= count;
+        }
+        if remaining == 0 {
+            Some(hash.0)
+        } else {
+            None
+        }
+    }
+}
+
+struct Hash(u64);
+
+impl Hash {
+    fn new() -> Self {
+        Hash(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn name_id<D: UncommittedDatatype + ?Sized>(datatype: &D) -> u64 {
+    type BufType = [c_char; ffi::MPI_MAX_OBJECT_NAME as usize];
+    let mut hash = Hash::new();
+    unsafe {
+        let mut buf = MaybeUninit::<BufType>::zeroed();
+        with_uninitialized(|len| {
+            ffi::MPI_Type_get_name(datatype.as_raw(), &mut (*buf.as_mut_ptr())[0], len)
+        });
+        for &byte in CStr::from_ptr(buf.assume_init().as_ptr()).to_bytes() {
+            hash.write(u64::from(byte));
+        }
+    }
+    hash.0
+}
+
+fn product(counts: &[Count]) -> Option<u64> {
+    counts.iter().try_fold(1u64, |product, &count| {
+        product.checked_mul(count.value_as().ok()?)
+    })
+}
+
+fn sum(counts: &[Count]) -> Option<u64> {
+    counts
+        .iter()
+        .try_fold(0u64, |sum, &count| sum.checked_add(count.value_as().ok()?))
+}
+
+fn signature<D: UncommittedDatatype + ?Sized>(datatype: &D) -> Option<Signature> {
+    let repeated = |oldtype: &ConstituentDatatype, repeat: u64| {
+        let mut signature = Signature::default();
+        signature.append(&self::signature(oldtype)?, repeat)?;
+        Some(signature)
+    };
+    match datatype.get_contents() {
+        Contents::Named => {
+            let mut signature = Signature::default();
+            signature.push(name_id(datatype), 1);
+            Some(signature)
+        }
+        Contents::Dup { oldtype } | Contents::Resized { oldtype, .. } => self::signature(&oldtype),
+        Contents::Contiguous { count, oldtype } => repeated(&oldtype, count.value_as().ok()?),
+        Contents::Vector {
+            count,
+            blocklength,
+            oldtype,
+            ..
+        }
+        | Contents::HeterogeneousVector {
+            count,
+            blocklength,
+            oldtype,
+            ..
+        } => repeated(&oldtype, product(&[count, blocklength])?),
+        Contents::Indexed {
+            blocklengths,
+            oldtype,
+            ..
+        }
+        | Contents::HeterogeneousIndexed {
+            blocklengths,
+            oldtype,
+            ..
+        } => repeated(&oldtype, sum(&blocklengths)?),
+        Contents::IndexedBlock {
+            blocklength,
+            displacements,
+            oldtype,
+        } => repeated(
+            &oldtype,
+            product(&[blocklength, displacements.len().value_as().ok()?])?,
+        ),
+        Contents::HeterogeneousIndexedBlock {
+            blocklength,
+            displacements,
+            oldtype,
+        } => repeated(
+            &oldtype,
+            product(&[blocklength, displacements.len().value_as().ok()?])?,
+        ),
+        Contents::Structured {
+            blocklengths,
+            types,
+            ..
+        } => {
+            let mut signature = Signature::default();
+            for (&blocklength, datatype) in blocklengths.iter().zip(&types) {
+                signature.append(&self::signature(datatype)?, blocklength.value_as().ok()?)?;
+            }
+            Some(signature)
+        }
+        Contents::Subarray {
+            subsizes, oldtype, ..
+        } => repeated(&oldtype, product(&subsizes)?),
+        Contents::DistributedArray { .. } | Contents::Other { .. } => None,
+    }
+}
+
+/// The signature of `count` instances of `datatype`
+fn buffer_signature<D: UncommittedDatatype + ?Sized>(
+    datatype: &D,
+    count: Count,
+) -> Option<Signature> {
+    let mut buffer = Signature::default();
+    buffer.append(&signature(datatype)?, count.value_as().ok()?)?;
+    Some(buffer)
+}
+
+/// The header describing a message of `count` instances of `datatype`
+fn header<D: UncommittedDatatype + ?Sized>(datatype: &D, count: Count) -> [u64; 2] {
+    buffer_signature(datatype, count)
+        .and_then(|signature| {
+            let len = signature.len();
+            Some([len, signature.hash_prefix(len)?])
+        })
+        .unwrap_or(UNCHECKED)
+}
+
+/// A header received by `receive_header()`
+pub(crate) struct Header {
+    /// The rank of the process that sent the header and the message
+    pub(crate) source: Rank,
+    /// The tag of the header and the message
+    pub(crate) tag: Tag,
+    signature: [u64; 2],
+}
+
+impl Header {
+    /// The message is received as if there was no header.
+    fn unchecked(source: Rank, tag: Tag) -> Self {
+        Header {
+            source,
+            tag,
+            signature: UNCHECKED,
+        }
+    }
+
+    /// Panics if a message of this header does not fit `count` instances of `datatype`.
+    pub(crate) fn check<D: UncommittedDatatype + ?Sized>(&self, datatype: &D, count: Count) {
+        if self.signature == UNCHECKED {
+            return;
+        }
+        let [len, hash] = self.signature;
+        if let Some(buffer) = buffer_signature(datatype, count) {
+            assert!(
+                buffer.hash_prefix(len) == Some(hash),
+                "The datatype of the message from rank {} with tag {} does not match the receive \
+                 buffer.",
+                self.source,
+                self.tag
+            );
+        }
+    }
+}
+
+/// The communication mode of a checked send, which is also used for its header
+#[derive(Copy, Clone)]
+pub(crate) enum Mode {
+    Standard,
+    Buffered,
+    Synchronous,
+}
+
+/// Send the header of a message of `count` instances of `datatype` to `destination` in `mode`.
+pub(crate) fn send_header<D: UncommittedDatatype + ?Sized>(
+    mode: Mode,
+    datatype: &D,
+    count: Count,
+    destination: Rank,
+    tag: Tag,
+    comm: MPI_Comm,
+) {
+    if !*ENABLED {
+        return;
+    }
+    let header = header(datatype, count);
+    let send = match mode {
+        Mode::Standard => ffi::MPI_Send,
+        Mode::Buffered => ffi::MPI_Bsend,
+        Mode::Synchronous => ffi::MPI_Ssend,
+    };
+    let operation = match mode {
+        Mode::Standard => "MPI_Send",
+        Mode::Buffered => "MPI_Bsend",
+        Mode::Synchronous => "MPI_Ssend",
+    };
+    let _call = instrument::begin(|| {
+        Call::new(operation, comm)
+            .peer(destination)
+            .typed(2, &u64::equivalent_datatype())
+    });
+    unsafe {
+        send(
+            header.as_ptr() as _,
+            2,
+            u64::equivalent_datatype().as_raw(),
+            destination,
+            tag,
+            comm,
+        );
+    }
+}
+
+/// Receive the header of the next message from `source` with tag `tag`, which may be wildcards.
+pub(crate) fn receive_header(source: Rank, tag: Tag, comm: MPI_Comm) -> Header {
+    if !*ENABLED {
+        return Header::unchecked(source, tag);
+    }
+    let mut signature = UNCHECKED;
+    let _call = instrument::begin(|| {
+        Call::new("MPI_Recv", comm)
+            .peer(source)
+            .typed(2, &u64::equivalent_datatype())
+    });
+    let (_, status) = unsafe {
+        with_uninitialized(|status| {
+            ffi::MPI_Recv(
+                signature.as_mut_ptr() as _,
+                2,
+                u64::equivalent_datatype().as_raw(),
+                source,
+                tag,
+                comm,
+                status,
+            )
+        })
+    };
+    Header {
+        source: status.MPI_SOURCE,
+        tag: status.MPI_TAG,
+        signature,
+    }
+}
+
+/// Exchange the headers of the messages of a combined send and receive operation.
+pub(crate) fn exchange_headers<D: UncommittedDatatype + ?Sized>(
+    datatype: &D,
+    count: Count,
+    destination: Rank,
+    sendtag: Tag,
+    source: Rank,
+    receivetag: Tag,
+    comm: MPI_Comm,
+) -> Header {
+    if !*ENABLED {
+        return Header::unchecked(source, receivetag);
+    }
+    let header = header(datatype, count);
+    let mut signature = UNCHECKED;
+    let _call = instrument::begin(|| {
+        Call::new("MPI_Sendrecv", comm)
+            .peer(destination)
+            .typed(2, &u64::equivalent_datatype())
+    });
+    let (_, status) = unsafe {
+        with_uninitialized(|status| {
+            ffi::MPI_Sendrecv(
+                header.as_ptr() as _,
+                2,
+                u64::equivalent_datatype().as_raw(),
+                destination,
+                sendtag,
+                signature.as_mut_ptr() as _,
+                2,
+                u64::equivalent_datatype().as_raw(),
+                source,
+                receivetag,
+                comm,
+                status,
+            )
+        })
+    };
+    Header {
+        source: status.MPI_SOURCE,
+        tag: status.MPI_TAG,
+        signature,
+    }
+}
@@ -1,40 +1,43 @@
 //! Collective communication
 //!
 //! Developing...
-//!
-//! # Unfinished features
-//!
-//! - **5.8**: All-to-all, `MPI_Alltoallw()`
-//! - **5.10**: Reduce-scatter, `MPI_Reduce_scatter()`
-//! - **5.12**: Nonblocking collective operations,
-//! `MPI_Ialltoallw()`, `MPI_Ireduce_scatter()`
 
 use std::ffi::{CString, NulError};
-#[cfg(feature = "user-operations")]
+use std::io::{self, Write};
+use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::{c_char, c_int, c_void};
 use std::process::Command;
-use std::{fmt, ptr};
+use std::string::FromUtf8Error;
+use std::{fmt, ptr, slice};
 
 use conv::ConvUtil;
 #[cfg(feature = "user-operations")]
 use libffi::middle::{Cif, Closure, Type};
 
 use crate::ffi::MPI_Op;
-use crate::{ffi, MpiError};
+use crate::{ffi, Address, Count, ErrorCode, MpiError};
 
 use crate::datatype::traits::*;
+use crate::datatype::UserDatatype;
 #[cfg(feature = "user-operations")]
 use crate::datatype::{DatatypeRef, DynBuffer, DynBufferMut};
+use crate::exact_sum;
+use crate::instrument::{self, Call};
+use crate::point_to_point::{Destination, MatchedReceiveVec, Source};
 use crate::raw::traits::*;
-use crate::request::{Request, Scope, StaticScope};
-use crate::topology::{traits::*, InterCommunicator};
-use crate::topology::{Process, Rank};
+use crate::request::{PersistentRequest, Request, Scope, StaticScope};
+use crate::topology::sealed::AsHandle;
+use crate::topology::{private_duplicate, Process, Rank};
+use crate::topology::{
+    traits::*, CartesianCommunicator, DistributedGraphCommunicator, GraphCommunicator,
+    InterCommunicator,
+};
 use crate::with_uninitialized;
 
 /// Collective communication traits
 pub mod traits {
-    pub use super::{CommunicatorCollectives, Operation, Root};
+    pub use super::{CommunicatorCollectives, NeighborhoodCollectives, Operation, Root};
 }
 
 /// Collective communication patterns defined on `Communicator`s
@@ -54,9 +57,23 @@ pub trait CommunicatorCollectives: Communicator {
     ///
     /// 5.3
     fn barrier(&self) {
-        unsafe {
-            ffi::MPI_Barrier(self.as_raw());
-        }
+        let _ = self.try_barrier();
+    }
+
+    /// Like `barrier()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.3
+    fn try_barrier(&self) -> Result<(), ErrorCode> {
+        let _call = instrument::begin(|| Call::new("MPI_Barrier", self.as_communicator().as_raw()));
+        ErrorCode::check(unsafe { ffi::MPI_Barrier(self.as_raw()) })
     }
 
     /// Gather contents of buffers on all participating processes.
@@ -78,15 +95,77 @@ pub trait CommunicatorCollectives: Communicator {
         S: Buffer,
         R: BufferMut,
     {
-        unsafe {
+        let _ = self.try_all_gather_into(sendbuf, recvbuf);
+    }
+
+    /// Like `all_gather_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.7
+    fn try_all_gather_into<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        let recvcount = count_per_process(self, recvbuf);
+        assert_matching_size(self, sendbuf, sendbuf.count(), recvbuf, recvcount);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allgather", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Allgather(
                 sendbuf.pointer(),
                 sendbuf.count(),
                 sendbuf.as_datatype().as_raw(),
                 recvbuf.pointer_mut(),
-                recvbuf.count() / self.target_size(),
+                recvcount,
                 recvbuf.as_datatype().as_raw(),
                 self.as_raw(),
+            )
+        })
+    }
+
+    /// Gather contents of buffers on all participating processes in place.
+    ///
+    /// Every process contributes the part of `buf` at its rank, i.e. `buf` is partitioned into
+    /// `size` equally sized parts, and receives the parts of all other processes into `buf`.
+    ///
+    /// This must only be used on intra-communicators.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.7 (See: `MPI_IN_PLACE`)
+    fn all_gather_in_place<Buf: ?Sized>(&self, buf: &mut Buf)
+    where
+        Buf: BufferMut,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allgather", self.as_communicator().as_raw()).buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Allgather(
+                ffi::RSMPI_IN_PLACE,
+                0,
+                buf.as_datatype().as_raw(),
+                buf.pointer_mut(),
+                count_per_process(self, buf),
+                buf.as_datatype().as_raw(),
+                self.as_raw(),
             );
         }
     }
@@ -111,6 +190,9 @@ pub trait CommunicatorCollectives: Communicator {
         S: Buffer,
         R: PartitionedBufferMut,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allgatherv", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             ffi::MPI_Allgatherv(
                 sendbuf.pointer(),
@@ -141,16 +223,78 @@ pub trait CommunicatorCollectives: Communicator {
         S: Buffer,
         R: BufferMut,
     {
-        let c_size = self.target_size();
-        unsafe {
+        let _ = self.try_all_to_all_into(sendbuf, recvbuf);
+    }
+
+    /// Like `all_to_all_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.8
+    fn try_all_to_all_into<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        let sendcount = count_per_process(self, sendbuf);
+        let recvcount = count_per_process(self, recvbuf);
+        assert_matching_size(self, sendbuf, sendcount, recvbuf, recvcount);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Alltoall", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Alltoall(
                 sendbuf.pointer(),
-                sendbuf.count() / c_size,
+                sendcount,
                 sendbuf.as_datatype().as_raw(),
                 recvbuf.pointer_mut(),
-                recvbuf.count() / c_size,
+                recvcount,
                 recvbuf.as_datatype().as_raw(),
                 self.as_raw(),
+            )
+        })
+    }
+
+    /// Distribute the parts of `buf` from all processes to `buf` on all processes in place.
+    ///
+    /// `buf` is partitioned into `size` equally sized parts, the part at rank `i` is sent to
+    /// process `i` and replaced by the part received from process `i`.
+    ///
+    /// This must only be used on intra-communicators.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.8 (See: `MPI_IN_PLACE`)
+    fn all_to_all_in_place<Buf: ?Sized>(&self, buf: &mut Buf)
+    where
+        Buf: BufferMut,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Alltoall", self.as_communicator().as_raw()).buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Alltoall(
+                ffi::RSMPI_IN_PLACE,
+                0,
+                buf.as_datatype().as_raw(),
+                buf.pointer_mut(),
+                count_per_process(self, buf),
+                buf.as_datatype().as_raw(),
+                self.as_raw(),
             );
         }
     }
@@ -160,6 +304,10 @@ pub trait CommunicatorCollectives: Communicator {
     /// The count of elements to send and receive to and from each process can vary and is specified
     /// using `Partitioned`.
     ///
+    /// # Examples
+    ///
+    /// See `examples/all_to_all_varcount.rs`
+    ///
     /// # Standard section(s)
     ///
     /// 5.8
@@ -168,6 +316,9 @@ pub trait CommunicatorCollectives: Communicator {
         S: PartitionedBuffer,
         R: PartitionedBufferMut,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Alltoallv", self.as_communicator().as_raw()).partitioned(sendbuf)
+        });
         unsafe {
             ffi::MPI_Alltoallv(
                 sendbuf.pointer(),
@@ -183,6 +334,42 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Distribute the send buffers from all processes to the receive buffers on all processes.
+    ///
+    /// The count of elements, the displacement in bytes and the datatype of the data to send and
+    /// receive to and from each process can vary and is specified using
+    /// `HeterogeneousPartitioned`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/all_to_all_varcount.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.8
+    fn all_to_all_heterogeneous_into<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    where
+        S: HeterogeneousPartitionedBuffer,
+        R: HeterogeneousPartitionedBufferMut,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Alltoallw", self.as_communicator().as_raw()).heterogeneous(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Alltoallw(
+                sendbuf.pointer(),
+                sendbuf.counts().as_ptr(),
+                sendbuf.byte_displs().as_ptr(),
+                sendbuf.datatypes().as_ptr(),
+                recvbuf.pointer_mut(),
+                recvbuf.counts().as_ptr(),
+                recvbuf.byte_displs().as_ptr(),
+                recvbuf.datatypes().as_ptr(),
+                self.as_raw(),
+            );
+        }
+    }
+
     /// Performs a global reduction under the operation `op` of the input data in `sendbuf` and
     /// stores the result in `recvbuf` on all processes.
     ///
@@ -199,7 +386,35 @@ pub trait CommunicatorCollectives: Communicator {
         R: BufferMut,
         O: Operation,
     {
-        unsafe {
+        let _ = self.try_all_reduce_into(sendbuf, recvbuf, op);
+    }
+
+    /// Like `all_reduce_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.6
+    fn try_all_reduce_into<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allreduce", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Allreduce(
                 sendbuf.pointer(),
                 recvbuf.pointer_mut(),
@@ -207,10 +422,72 @@ pub trait CommunicatorCollectives: Communicator {
                 sendbuf.as_datatype().as_raw(),
                 op.as_raw(),
                 self.as_raw(),
+            )
+        })
+    }
+
+    /// Performs a global reduction on all processes of the data in `buf` under operation `op` and
+    /// stores the result in `buf`.
+    ///
+    /// This avoids a separate receive buffer and must only be used on intra-communicators.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.6 (See: `MPI_IN_PLACE`)
+    fn all_reduce_in_place<Buf: ?Sized, O>(&self, buf: &mut Buf, op: O)
+    where
+        Buf: BufferMut,
+        O: Operation,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allreduce", self.as_communicator().as_raw()).buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Allreduce(
+                ffi::RSMPI_IN_PLACE,
+                buf.pointer_mut(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+                op.as_raw(),
+                self.as_raw(),
             );
         }
     }
 
+    /// Performs a bitwise reproducible global sum of the input data in `sendbuf` and stores the
+    /// result in `recvbuf` on all processes.
+    ///
+    /// Unlike `all_reduce_into()` with `SystemOperation::sum()`, the contributions are summed
+    /// exactly and the exact sum is rounded once. The result only depends on the values that are
+    /// contributed, not on the reduction algorithm chosen by the MPI library, the order of the
+    /// processes or their number, so it is bitwise identical from run to run, also when the same
+    /// values are contributed by a different number of processes.
+    ///
+    /// Every element is exchanged as an exact accumulator of 71 `i64`s, so the traffic is that of
+    /// an `all_reduce_into()` of 71 times the data in `sendbuf`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reproducible_reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.6
+    fn reproducible_all_sum_into<T>(&self, sendbuf: &[T], recvbuf: &mut [T])
+    where
+        T: ExactSum,
+    {
+        assert_eq!(sendbuf.len(), recvbuf.len());
+        let accumulators = exact_sum_accumulators(sendbuf);
+        let mut sums = vec![0; accumulators.len()];
+        self.all_reduce_into(&accumulators[..], &mut sums[..], SystemOperation::sum());
+        round_exact_sums(&mut sums, recvbuf);
+    }
+
     /// Performs an element-wise global reduction under the operation `op` of the input data in
     /// `sendbuf` and scatters the result into equal sized blocks in the receive buffers on all
     /// processes.
@@ -233,6 +510,9 @@ pub trait CommunicatorCollectives: Communicator {
         O: Operation,
     {
         assert_eq!(recvbuf.count() * self.target_size(), sendbuf.count());
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce_scatter_block", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             ffi::MPI_Reduce_scatter_block(
                 sendbuf.pointer(),
@@ -245,27 +525,37 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
-    /// Performs a global inclusive prefix reduction of the data in `sendbuf` into `recvbuf` under
-    /// operation `op`.
+    /// Performs an element-wise global reduction under the operation `op` of the input data in
+    /// `sendbuf` and scatters the result into blocks of varying size in the receive buffers on all
+    /// processes.
+    ///
+    /// The `Partitioned` send buffer describes how many elements of the result end up on each
+    /// process. The partitions have to be contiguous and in rank order, i.e. the displacement of
+    /// each partition is the sum of the counts of all preceding partitions. The receive buffer on
+    /// each process has to hold exactly the count of elements of its partition.
     ///
     /// # Examples
     ///
-    /// See `examples/scan.rs`
+    /// See `examples/reduce.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 5.11.1
-    fn scan_into<S: ?Sized, R: ?Sized, O>(&self, sendbuf: &S, recvbuf: &mut R, op: O)
+    /// 5.10.2
+    fn reduce_scatter_into<S: ?Sized, R: ?Sized, O>(&self, sendbuf: &S, recvbuf: &mut R, op: O)
     where
-        S: Buffer,
+        S: PartitionedBuffer,
         R: BufferMut,
         O: Operation,
     {
+        check_reduce_scatter_partition(self, sendbuf, recvbuf);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce_scatter", self.as_communicator().as_raw()).partitioned(sendbuf)
+        });
         unsafe {
-            ffi::MPI_Scan(
+            ffi::MPI_Reduce_scatter(
                 sendbuf.pointer(),
                 recvbuf.pointer_mut(),
-                sendbuf.count(),
+                sendbuf.counts().as_ptr(),
                 sendbuf.as_datatype().as_raw(),
                 op.as_raw(),
                 self.as_raw(),
@@ -273,7 +563,92 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
-    /// Performs a global exclusive prefix reduction of the data in `sendbuf` into `recvbuf` under
+    /// Performs an element-wise global reduction under the operation `op` of the partitions of
+    /// `sendbuf` and scatters the result so that each process receives the reduction of its
+    /// partition.
+    ///
+    /// Unlike `reduce_scatter_into()`, the partitions may be placed anywhere in the send buffer,
+    /// only their counts have to agree between all processes. If they are contiguous and in rank
+    /// order, this is a plain `MPI_Reduce_scatter` that allocates nothing, otherwise the partitions
+    /// are first copied into a temporary buffer in rank order. The receive buffer on each process
+    /// has to hold exactly the count of elements of its partition.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.10.2
+    fn segmented_reduce_into<S: ?Sized, R: ?Sized, O>(&self, sendbuf: &S, recvbuf: &mut R, op: O)
+    where
+        S: PartitionedBuffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        if partitions_in_rank_order(sendbuf) {
+            self.reduce_scatter_into(sendbuf, recvbuf, op);
+            return;
+        }
+
+        let counts = sendbuf.counts();
+        assert_eq!(
+            counts.len(),
+            self.target_size()
+                .value_as::<usize>()
+                .expect("Communicator size is negative.")
+        );
+        let rank: usize = self.rank().value_as().expect("Rank is negative.");
+        assert_eq!(recvbuf.count(), counts[rank]);
+
+        let datatype = sendbuf.as_datatype();
+        let extent: usize = datatype
+            .extent()
+            .value_as()
+            .expect("Datatype extent is negative.");
+        let lower_bound: isize = datatype
+            .lower_bound()
+            .value_as()
+            .expect("Datatype lower bound exceeds the range of an isize.");
+        let elements = |count: Count| -> usize {
+            count
+                .value_as::<usize>()
+                .expect("Partition count or displacement is negative.")
+                * extent
+        };
+
+        let total: usize = counts.iter().map(|&count| elements(count)).sum();
+        let mut packed: Vec<mem::MaybeUninit<u8>> = Vec::with_capacity(total);
+        for (&count, &displ) in counts.iter().zip(sendbuf.displs()) {
+            let len = elements(count);
+            unsafe {
+                let start = sendbuf
+                    .pointer()
+                    .cast::<u8>()
+                    .offset(lower_bound)
+                    .add(elements(displ));
+                ptr::copy_nonoverlapping(start.cast(), packed.as_mut_ptr().add(packed.len()), len);
+                packed.set_len(packed.len() + len);
+            }
+        }
+
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce_scatter", self.as_communicator().as_raw()).partitioned(sendbuf)
+        });
+        unsafe {
+            // The packed partitions start at the lower bound of the datatype, like in `sendbuf`.
+            ffi::MPI_Reduce_scatter(
+                packed.as_ptr().wrapping_offset(-lower_bound) as _,
+                recvbuf.pointer_mut(),
+                counts.as_ptr(),
+                datatype.as_raw(),
+                op.as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Performs a global inclusive prefix reduction of the data in `sendbuf` into `recvbuf` under
     /// operation `op`.
     ///
     /// # Examples
@@ -282,15 +657,18 @@ pub trait CommunicatorCollectives: Communicator {
     ///
     /// # Standard section(s)
     ///
-    /// 5.11.2
-    fn exclusive_scan_into<S: ?Sized, R: ?Sized, O>(&self, sendbuf: &S, recvbuf: &mut R, op: O)
+    /// 5.11.1
+    fn scan_into<S: ?Sized, R: ?Sized, O>(&self, sendbuf: &S, recvbuf: &mut R, op: O)
     where
         S: Buffer,
         R: BufferMut,
         O: Operation,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scan", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
-            ffi::MPI_Exscan(
+            ffi::MPI_Scan(
                 sendbuf.pointer(),
                 recvbuf.pointer_mut(),
                 sendbuf.count(),
@@ -301,38 +679,137 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
-    /// Non-blocking barrier synchronization among all processes in a `Communicator`
+    /// Performs a global inclusive prefix reduction of the data in `buf` under operation `op` and
+    /// stores the result in `buf`.
     ///
-    /// Calling processes (or threads within the calling processes) enter the barrier. Completion
-    /// methods on the associated request object will block until all processes have entered.
+    /// This avoids a separate receive buffer and must only be used on intra-communicators.
     ///
     /// # Examples
     ///
-    /// See `examples/immediate_barrier.rs`
+    /// See `examples/in_place.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 5.12.1
-    fn immediate_barrier(&self) -> Request<'static, ()> {
+    /// 5.11.1 (See: `MPI_IN_PLACE`)
+    fn scan_in_place<Buf: ?Sized, O>(&self, buf: &mut Buf, op: O)
+    where
+        Buf: BufferMut,
+        O: Operation,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scan", self.as_communicator().as_raw()).buffer(buf)
+        });
         unsafe {
-            Request::from_raw(
-                with_uninitialized(|request| ffi::MPI_Ibarrier(self.as_raw(), request)).1,
-                &(),
-                StaticScope,
-            )
+            ffi::MPI_Scan(
+                ffi::RSMPI_IN_PLACE,
+                buf.pointer_mut(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+                op.as_raw(),
+                self.as_raw(),
+            );
         }
     }
 
-    /// Initiate non-blocking gather of the contents of all `sendbuf`s into all `rcevbuf`s on all
-    /// processes in the communicator.
+    /// Performs a global exclusive prefix reduction of the data in `sendbuf` into `recvbuf` under
+    /// operation `op`.
     ///
     /// # Examples
     ///
-    /// See `examples/immediate_all_gather.rs`
+    /// See `examples/scan.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 5.12.5
+    /// 5.11.2
+    fn exclusive_scan_into<S: ?Sized, R: ?Sized, O>(&self, sendbuf: &S, recvbuf: &mut R, op: O)
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Exscan", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Exscan(
+                sendbuf.pointer(),
+                recvbuf.pointer_mut(),
+                sendbuf.count(),
+                sendbuf.as_datatype().as_raw(),
+                op.as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Performs a global exclusive prefix reduction of the data in `buf` under operation `op` and
+    /// stores the result in `buf`.
+    ///
+    /// `buf` is left unchanged on the process of rank 0.
+    ///
+    /// This avoids a separate receive buffer and must only be used on intra-communicators.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.11.2 (See: `MPI_IN_PLACE`)
+    fn exclusive_scan_in_place<Buf: ?Sized, O>(&self, buf: &mut Buf, op: O)
+    where
+        Buf: BufferMut,
+        O: Operation,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Exscan", self.as_communicator().as_raw()).buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Exscan(
+                ffi::RSMPI_IN_PLACE,
+                buf.pointer_mut(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+                op.as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Non-blocking barrier synchronization among all processes in a `Communicator`
+    ///
+    /// Calling processes (or threads within the calling processes) enter the barrier. Completion
+    /// methods on the associated request object will block until all processes have entered.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate_barrier.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.12.1
+    fn immediate_barrier(&self) -> Request<'static, ()> {
+        let _call =
+            instrument::begin(|| Call::new("MPI_Ibarrier", self.as_communicator().as_raw()));
+        unsafe {
+            Request::from_raw(
+                with_uninitialized(|request| ffi::MPI_Ibarrier(self.as_raw(), request)).1,
+                &(),
+                StaticScope,
+            )
+        }
+    }
+
+    /// Initiate non-blocking gather of the contents of all `sendbuf`s into all `rcevbuf`s on all
+    /// processes in the communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate_all_gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.12.5
     fn immediate_all_gather_into<'a, S: ?Sized, R: ?Sized, Sc>(
         &self,
         scope: Sc,
@@ -344,8 +821,12 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let recvcount = count_per_process(self, recvbuf);
+        assert_matching_size(self, sendbuf, sendbuf.count(), recvbuf, recvcount);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iallgather", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
-            let recvcount = recvbuf.count() / self.target_size();
             Request::from_raw(
                 with_uninitialized(|request| {
                     ffi::MPI_Iallgather(
@@ -387,6 +868,9 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + PartitionedBufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iallgatherv", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -429,16 +913,21 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        let c_size = self.target_size();
+        let sendcount = count_per_process(self, sendbuf);
+        let recvcount = count_per_process(self, recvbuf);
+        assert_matching_size(self, sendbuf, sendcount, recvbuf, recvcount);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ialltoall", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
                     ffi::MPI_Ialltoall(
                         sendbuf.pointer(),
-                        sendbuf.count() / c_size,
+                        sendcount,
                         sendbuf.as_datatype().as_raw(),
                         recvbuf.pointer_mut(),
-                        recvbuf.count() / c_size,
+                        recvcount,
                         recvbuf.as_datatype().as_raw(),
                         self.as_raw(),
                         request,
@@ -467,6 +956,9 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + PartitionedBufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ialltoallv", self.as_communicator().as_raw()).partitioned(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -490,6 +982,57 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Initiate non-blocking distribution of the send buffers from all processes to the receive
+    /// buffers on all processes.
+    ///
+    /// The count of elements, the displacement in bytes and the datatype of the data to send and
+    /// receive to and from each process can vary and is specified using
+    /// `HeterogeneousPartitioned`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate_all_to_all.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.12.6
+    fn immediate_all_to_all_heterogeneous_into<'a, S: ?Sized, R: ?Sized, Sc>(
+        &self,
+        scope: Sc,
+        sendbuf: &'a S,
+        recvbuf: &'a mut R,
+    ) -> Request<'a, R, Sc>
+    where
+        S: 'a + HeterogeneousPartitionedBuffer,
+        R: 'a + HeterogeneousPartitionedBufferMut,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ialltoallw", self.as_communicator().as_raw()).heterogeneous(sendbuf)
+        });
+        unsafe {
+            Request::from_raw(
+                with_uninitialized(|request| {
+                    ffi::MPI_Ialltoallw(
+                        sendbuf.pointer(),
+                        sendbuf.counts().as_ptr(),
+                        sendbuf.byte_displs().as_ptr(),
+                        sendbuf.datatypes().as_ptr(),
+                        recvbuf.pointer_mut(),
+                        recvbuf.counts().as_ptr(),
+                        recvbuf.byte_displs().as_ptr(),
+                        recvbuf.datatypes().as_ptr(),
+                        self.as_raw(),
+                        request,
+                    )
+                })
+                .1,
+                recvbuf,
+                scope,
+            )
+        }
+    }
+
     /// Initiates a non-blocking global reduction under the operation `op` of the input data in
     /// `sendbuf` and stores the result in `recvbuf` on all processes.
     ///
@@ -513,6 +1056,9 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iallreduce", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -558,6 +1104,9 @@ pub trait CommunicatorCollectives: Communicator {
         Sc: Scope<'a>,
     {
         assert_eq!(recvbuf.count() * self.target_size(), sendbuf.count());
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ireduce_scatter_block", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -578,6 +1127,56 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Initiates a non-blocking element-wise global reduction under the operation `op` of the
+    /// input data in `sendbuf` and scatters the result into blocks of varying size in the receive
+    /// buffers on all processes.
+    ///
+    /// See `reduce_scatter_into()` for the requirements on the partitioning.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate_reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.12.9
+    fn immediate_reduce_scatter_into<'a, S: ?Sized, R: ?Sized, O, Sc>(
+        &self,
+        scope: Sc,
+        sendbuf: &'a S,
+        recvbuf: &'a mut R,
+        op: O,
+    ) -> Request<'a, R, Sc>
+    where
+        S: 'a + PartitionedBuffer,
+        R: 'a + BufferMut,
+        O: 'a + Operation,
+        Sc: Scope<'a>,
+    {
+        check_reduce_scatter_partition(self, sendbuf, recvbuf);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ireduce_scatter", self.as_communicator().as_raw()).partitioned(sendbuf)
+        });
+        unsafe {
+            Request::from_raw(
+                with_uninitialized(|request| {
+                    ffi::MPI_Ireduce_scatter(
+                        sendbuf.pointer(),
+                        recvbuf.pointer_mut(),
+                        sendbuf.counts().as_ptr(),
+                        sendbuf.as_datatype().as_raw(),
+                        op.as_raw(),
+                        self.as_raw(),
+                        request,
+                    )
+                })
+                .1,
+                recvbuf,
+                scope,
+            )
+        }
+    }
+
     /// Initiates a non-blocking global inclusive prefix reduction of the data in `sendbuf` into
     /// `recvbuf` under operation `op`.
     ///
@@ -601,6 +1200,9 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iscan", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -644,6 +1246,9 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iexscan", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -663,104 +1268,648 @@ pub trait CommunicatorCollectives: Communicator {
             )
         }
     }
-}
-
-impl<C: Communicator + ?Sized> CommunicatorCollectives for C {}
-
-/// Something that can take the role of 'root' in a collective operation.
-///
-/// Many collective operations define a 'root' process that takes a special role in the
-/// communication. These collective operations are implemented as default methods of this trait.
-pub trait Root: AsCommunicator {
-    /// Rank of the root process
-    fn root_rank(&self) -> Rank;
 
-    /// Broadcast of the contents of a buffer
+    /// Create a persistent request for a barrier across all processes in the communicator.
     ///
-    /// After the call completes, the `Buffer` on all processes in the `Communicator` of the `Root`
-    /// `&self` will contain what it contains on the `Root`.
+    /// Every run of the request started with `PersistentRequest::start()` synchronizes the
+    /// processes like `immediate_barrier()`.
+    ///
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
     ///
     /// # Examples
     ///
-    /// See `examples/broadcast.rs`
+    /// See `examples/persistent_collectives.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 5.4
-    fn broadcast_into<Buf: ?Sized>(&self, buffer: &mut Buf)
+    /// 6.13.1
+    fn barrier_init(&self) -> Result<PersistentRequest<'static, ()>, ErrorCode> {
+        let _call =
+            instrument::begin(|| Call::new("MPI_Barrier_init", self.as_communicator().as_raw()));
+        let (code, request) = unsafe {
+            with_uninitialized(|request| {
+                ffi::RSMPI_Barrier_init(self.as_raw(), ffi::RSMPI_INFO_NULL, request)
+            })
+        };
+        ErrorCode::check(code)?;
+        // Leaking a zero-sized value does not allocate.
+        Ok(unsafe { PersistentRequest::from_raw(request, Box::leak(Box::new(())), StaticScope) })
+    }
+
+    /// Create a persistent request for gathering the contents of `sendbuf` into `recvbuf` on all
+    /// processes in the communicator.
+    ///
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.13.5
+    fn all_gather_init<'a, S: ?Sized, R: ?Sized, Sc>(
+        &self,
+        scope: Sc,
+        sendbuf: &'a S,
+        recvbuf: &'a mut R,
+    ) -> Result<PersistentRequest<'a, R, Sc>, ErrorCode>
     where
-        Buf: BufferMut,
+        S: 'a + Buffer,
+        R: 'a + BufferMut,
+        Sc: Scope<'a>,
     {
+        let recvcount = count_per_process(self, recvbuf);
+        assert_matching_size(self, sendbuf, sendbuf.count(), recvbuf, recvcount);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allgather_init", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
-            ffi::MPI_Bcast(
-                buffer.pointer_mut(),
-                buffer.count(),
-                buffer.as_datatype().as_raw(),
-                self.root_rank(),
-                self.as_communicator().as_raw(),
-            );
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Allgather_init(
+                    sendbuf.pointer(),
+                    sendbuf.count(),
+                    sendbuf.as_datatype().as_raw(),
+                    recvbuf.pointer_mut(),
+                    recvcount,
+                    recvbuf.as_datatype().as_raw(),
+                    self.as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PersistentRequest::from_raw(request, recvbuf, scope))
         }
     }
 
-    /// Gather contents of buffers on `Root`.
-    ///
-    /// After the call completes, the contents of the `Buffer`s on all ranks will be
-    /// concatenated into the `Buffer` on `Root`.
-    ///
-    /// All send `Buffer`s must have the same count of elements.
-    ///
-    /// This function must be called on all non-root processes.
+    /// Create a persistent request for reducing the contents of `sendbuf` under `op` into
+    /// `recvbuf` on all processes in the communicator.
     ///
-    /// # Examples
+    /// `sendbuf` stays borrowed for the lifetime of the request, use `all_reduce_in_place_init()`
+    /// to update the contribution between runs.
     ///
-    /// See `examples/gather.rs`
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
     ///
     /// # Standard section(s)
     ///
-    /// 5.5
-    fn gather_into<S: ?Sized>(&self, sendbuf: &S)
+    /// 6.13.8
+    fn all_reduce_init<'a, S: ?Sized, R: ?Sized, O, Sc>(
+        &self,
+        scope: Sc,
+        sendbuf: &'a S,
+        recvbuf: &'a mut R,
+        op: O,
+    ) -> Result<PersistentRequest<'a, R, Sc>, ErrorCode>
     where
-        S: Buffer,
+        S: 'a + Buffer,
+        R: 'a + BufferMut,
+        O: 'a + Operation,
+        Sc: Scope<'a>,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allreduce_init", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
         unsafe {
-            ffi::MPI_Gather(
-                sendbuf.pointer(),
-                sendbuf.count(),
-                sendbuf.as_datatype().as_raw(),
-                ptr::null_mut(),
-                0,
-                u8::equivalent_datatype().as_raw(),
-                self.root_rank(),
-                self.as_communicator().as_raw(),
-            );
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Allreduce_init(
+                    sendbuf.pointer(),
+                    recvbuf.pointer_mut(),
+                    sendbuf.count(),
+                    sendbuf.as_datatype().as_raw(),
+                    op.as_raw(),
+                    self.as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PersistentRequest::from_raw(request, recvbuf, scope))
         }
     }
 
-    /// Gather contents of buffers on `Root`.
-    ///
-    /// After the call completes, the contents of the `Buffer`s on all ranks will be
-    /// concatenated into the `Buffer` on `Root`.
+    /// Create a persistent request for reducing the contents of `buf` under `op` in place on all
+    /// processes in the communicator.
     ///
-    /// All send `Buffer`s must have the same count of elements.
+    /// Between runs the contribution of the process is written to `PersistentRequest::data_mut()`,
+    /// after completion `PersistentRequest::data()` holds the result.
     ///
-    /// This function must be called on the root process.
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
     ///
     /// # Examples
     ///
-    /// See `examples/gather.rs`
+    /// See `examples/persistent_collectives.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 5.5
-    fn gather_into_root<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    /// 6.13.8 (See: `MPI_IN_PLACE`)
+    fn all_reduce_in_place_init<'a, Buf: ?Sized, O, Sc>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+        op: O,
+    ) -> Result<PersistentRequest<'a, Buf, Sc>, ErrorCode>
     where
-        S: Buffer,
-        R: BufferMut,
-    {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
-        unsafe {
-            let recvcount = recvbuf.count() / self.as_communicator().target_size();
+        Buf: 'a + BufferMut,
+        O: 'a + Operation,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Allreduce_init", self.as_communicator().as_raw()).buffer(buf)
+        });
+        unsafe {
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Allreduce_init(
+                    ffi::RSMPI_IN_PLACE,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    op.as_raw(),
+                    self.as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PersistentRequest::from_raw(request, buf, scope))
+        }
+    }
+}
+
+impl<C: Communicator + ?Sized> CommunicatorCollectives for C {}
+
+/// Collective communication patterns along the edges of a process topology
+///
+/// Every process only exchanges data with its neighbors in the topology as opposed to all
+/// processes of the communicator. For a `CartesianCommunicator` the neighbors are ordered by
+/// dimension, first the neighbor in negative and then the neighbor in positive direction. Sources
+/// and destinations that are `MPI_PROC_NULL` still occupy their part of the buffers.
+///
+/// # Standard section(s)
+///
+/// 7.6
+pub trait NeighborhoodCollectives: Communicator {
+    /// The number of processes this process receives data from (in-degree) and sends data to
+    /// (out-degree) in the topology.
+    fn neighbor_degrees(&self) -> (Count, Count);
+
+    /// Gather contents of buffers on all neighbors.
+    ///
+    /// After the call completes, the contents of the send `Buffer`s on all source neighbors will
+    /// be concatenated into the receive `Buffer`, which has to hold `sendbuf.count()` elements per
+    /// source neighbor.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/neighbor_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.6.1
+    fn neighbor_all_gather_into<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        let (indegree, _) = self.neighbor_degrees();
+        let recvcount = count_per_neighbor(indegree, recvbuf);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Neighbor_allgather", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Neighbor_allgather(
+                sendbuf.pointer(),
+                sendbuf.count(),
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.pointer_mut(),
+                recvcount,
+                recvbuf.as_datatype().as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Gather contents of buffers on all neighbors.
+    ///
+    /// The send `Buffer`s may contain different counts of elements on different processes. The
+    /// distribution of elements in the receive `Buffer` is specified via `Partitioned` with one
+    /// partition per source neighbor.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/neighbor_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.6.1
+    fn neighbor_all_gather_varcount_into<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    where
+        S: Buffer,
+        R: PartitionedBufferMut,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Neighbor_allgatherv", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Neighbor_allgatherv(
+                sendbuf.pointer(),
+                sendbuf.count(),
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.pointer_mut(),
+                recvbuf.counts().as_ptr(),
+                recvbuf.displs().as_ptr(),
+                recvbuf.as_datatype().as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Distribute the send `Buffer` to all destination neighbors and receive from all source
+    /// neighbors.
+    ///
+    /// Each process sends and receives the same count of elements to and from each neighbor.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/neighbor_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.6.2
+    fn neighbor_all_to_all_into<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        let (indegree, outdegree) = self.neighbor_degrees();
+        let sendcount = count_per_neighbor(outdegree, sendbuf);
+        let recvcount = count_per_neighbor(indegree, recvbuf);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Neighbor_alltoall", self.as_communicator().as_raw()).buffer(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Neighbor_alltoall(
+                sendbuf.pointer(),
+                sendcount,
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.pointer_mut(),
+                recvcount,
+                recvbuf.as_datatype().as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Distribute the send `Buffer` to all destination neighbors and receive from all source
+    /// neighbors.
+    ///
+    /// The count of elements to send and receive to and from each neighbor can vary and is
+    /// specified using `Partitioned`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/neighbor_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.6.2
+    fn neighbor_all_to_all_varcount_into<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    where
+        S: PartitionedBuffer,
+        R: PartitionedBufferMut,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Neighbor_alltoallv", self.as_communicator().as_raw())
+                .partitioned(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Neighbor_alltoallv(
+                sendbuf.pointer(),
+                sendbuf.counts().as_ptr(),
+                sendbuf.displs().as_ptr(),
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.pointer_mut(),
+                recvbuf.counts().as_ptr(),
+                recvbuf.displs().as_ptr(),
+                recvbuf.as_datatype().as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Distribute the send buffer to all destination neighbors and receive from all source
+    /// neighbors.
+    ///
+    /// The count of elements, the displacement in bytes and the datatype of the data to send and
+    /// receive to and from each neighbor can vary and is specified using
+    /// `HeterogeneousPartitioned`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.6.2
+    fn neighbor_all_to_all_heterogeneous_into<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) where
+        S: HeterogeneousPartitionedBuffer,
+        R: HeterogeneousPartitionedBufferMut,
+    {
+        // Unlike `MPI_Alltoallw()` the neighborhood variant takes address-sized displacements.
+        let send_displs: Vec<Address> = sendbuf
+            .byte_displs()
+            .iter()
+            .map(|&displ| displ as Address)
+            .collect();
+        let recv_displs: Vec<Address> = recvbuf
+            .byte_displs()
+            .iter()
+            .map(|&displ| displ as Address)
+            .collect();
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Neighbor_alltoallw", self.as_communicator().as_raw())
+                .heterogeneous(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Neighbor_alltoallw(
+                sendbuf.pointer(),
+                sendbuf.counts().as_ptr(),
+                send_displs.as_ptr(),
+                sendbuf.datatypes().as_ptr(),
+                recvbuf.pointer_mut(),
+                recvbuf.counts().as_ptr(),
+                recv_displs.as_ptr(),
+                recvbuf.datatypes().as_ptr(),
+                self.as_raw(),
+            );
+        }
+    }
+}
+
+impl NeighborhoodCollectives for CartesianCommunicator {
+    fn neighbor_degrees(&self) -> (Count, Count) {
+        let degree = 2 * self.num_dimensions();
+        (degree, degree)
+    }
+}
+
+impl NeighborhoodCollectives for GraphCommunicator {
+    fn neighbor_degrees(&self) -> (Count, Count) {
+        let degree = self.neighbors_count(self.rank());
+        (degree, degree)
+    }
+}
+
+impl NeighborhoodCollectives for DistributedGraphCommunicator {
+    fn neighbor_degrees(&self) -> (Count, Count) {
+        let (indegree, outdegree, _) = self.neighbors_count();
+        (indegree, outdegree)
+    }
+}
+
+/// Something that can take the role of 'root' in a collective operation.
+///
+/// Many collective operations define a 'root' process that takes a special role in the
+/// communication. These collective operations are implemented as default methods of this trait.
+pub trait Root: AsCommunicator {
+    /// Rank of the root process
+    fn root_rank(&self) -> Rank;
+
+    /// Broadcast of the contents of a buffer
+    ///
+    /// After the call completes, the `Buffer` on all processes in the `Communicator` of the `Root`
+    /// `&self` will contain what it contains on the `Root`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/broadcast.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    fn broadcast_into<Buf: ?Sized>(&self, buffer: &mut Buf)
+    where
+        Buf: BufferMut,
+    {
+        let _ = self.try_broadcast_into(buffer);
+    }
+
+    /// Like `broadcast_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    fn try_broadcast_into<Buf: ?Sized>(&self, buffer: &mut Buf) -> Result<(), ErrorCode>
+    where
+        Buf: BufferMut,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Bcast", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buffer)
+        });
+        ErrorCode::check(unsafe {
+            ffi::MPI_Bcast(
+                buffer.pointer_mut(),
+                buffer.count(),
+                buffer.as_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
+            )
+        })
+    }
+
+    /// Broadcast of a `Vec` or `String` whose length is only known on the `Root`
+    ///
+    /// The length of `buffer` is broadcast first, then its contents. On all other processes
+    /// `buffer` is resized to match before the contents are received, so its prior contents are
+    /// discarded.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/broadcast.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    fn broadcast_owned<Buf: ?Sized>(&self, buffer: &mut Buf)
+    where
+        Buf: BroadcastOwned,
+    {
+        buffer.broadcast_owned_from(self);
+    }
+
+    /// Broadcast a `String` from `Root`, replacing `string` on the receiving processes.
+    ///
+    /// Unlike `broadcast_owned()` the received bytes are checked to be valid UTF-8. If they are
+    /// not, the error is returned and `string` is left empty.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/broadcast.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    fn broadcast_string(&self, string: &mut String) -> Result<(), FromUtf8Error> {
+        let mut bytes = mem::take(string).into_bytes();
+        bytes.broadcast_owned_from(self);
+        *string = String::from_utf8(bytes)?;
+        Ok(())
+    }
+
+    /// Derive a seed for a random number generator on every process from the `seed` of `Root`.
+    ///
+    /// The seed of `Root` is broadcast and mixed with the rank of the calling process, so the
+    /// processes get distinct seeds and a run can be reproduced by passing the same seed on
+    /// `Root`. The seeds are consecutive outputs of the SplitMix64 generator seeded with `seed`.
+    /// `seed` is ignored on all other processes.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/seed.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    fn seed_per_rank(&self, seed: u64) -> u64 {
+        const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut seed = seed;
+        self.broadcast_into(&mut seed);
+        let rank = self.as_communicator().rank() as u64;
+        let mut z = seed.wrapping_add(rank.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Gather contents of buffers on `Root`.
+    ///
+    /// After the call completes, the contents of the `Buffer`s on all ranks will be
+    /// concatenated into the `Buffer` on `Root`.
+    ///
+    /// All send `Buffer`s must have the same count of elements.
+    ///
+    /// This function must be called on all non-root processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather_into<S: ?Sized>(&self, sendbuf: &S)
+    where
+        S: Buffer,
+    {
+        let _ = self.try_gather_into(sendbuf);
+    }
+
+    /// Like `gather_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn try_gather_into<S: ?Sized>(&self, sendbuf: &S) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+    {
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gather", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
+            ffi::MPI_Gather(
+                sendbuf.pointer(),
+                sendbuf.count(),
+                sendbuf.as_datatype().as_raw(),
+                ptr::null_mut(),
+                0,
+                u8::equivalent_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
+            )
+        })
+    }
+
+    /// Gather contents of buffers on `Root`.
+    ///
+    /// After the call completes, the contents of the `Buffer`s on all ranks will be
+    /// concatenated into the `Buffer` on `Root`.
+    ///
+    /// All send `Buffer`s must have the same count of elements.
+    ///
+    /// This function must be called on the root process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather_into_root<S: ?Sized, R: ?Sized>(&self, sendbuf: &S, recvbuf: &mut R)
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        let _ = self.try_gather_into_root(sendbuf, recvbuf);
+    }
+
+    /// Like `gather_into_root()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn try_gather_into_root<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        assert_is_root(self);
+        let recvcount = count_per_process(self.as_communicator(), recvbuf);
+        assert_matching_size(
+            self.as_communicator(),
+            sendbuf,
+            sendbuf.count(),
+            recvbuf,
+            recvcount,
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gather", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Gather(
                 sendbuf.pointer(),
                 sendbuf.count(),
@@ -770,8 +1919,216 @@ pub trait Root: AsCommunicator {
                 recvbuf.as_datatype().as_raw(),
                 self.root_rank(),
                 self.as_communicator().as_raw(),
+            )
+        })
+    }
+
+    /// Gather contents of buffers on `Root` in place.
+    ///
+    /// The contribution of `Root` is expected at its rank in `buf`, which is partitioned into
+    /// `size` equally sized parts. The other processes call `gather_into()`.
+    ///
+    /// This function must be called on the root process of an intra-communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5 (See: `MPI_IN_PLACE`)
+    fn gather_in_place_root<Buf: ?Sized>(&self, buf: &mut Buf)
+    where
+        Buf: BufferMut,
+    {
+        assert_is_root(self);
+        let recvcount = count_per_process(self.as_communicator(), buf);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gather", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Gather(
+                ffi::RSMPI_IN_PLACE,
+                0,
+                buf.as_datatype().as_raw(),
+                buf.pointer_mut(),
+                recvcount,
+                buf.as_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
+            );
+        }
+    }
+
+    /// Gather single values on `Root` into a newly allocated `Vec`.
+    ///
+    /// Returns the values of all processes in rank order on `Root` and `None` elsewhere.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather<T>(&self, sendbuf: &T) -> Option<Vec<T>>
+    where
+        T: Equivalence,
+    {
+        self.gather_vec(slice::from_ref(sendbuf))
+    }
+
+    /// Gather slices on `Root` into a newly allocated `Vec`.
+    ///
+    /// Returns the concatenation of the slices of all processes in rank order on `Root` and
+    /// `None` elsewhere. All slices must have the same length. On an inter-communicator the length
+    /// of `sendbuf` on `Root` is the number of elements received from each process.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather_vec<T>(&self, sendbuf: &[T]) -> Option<Vec<T>>
+    where
+        T: Equivalence,
+    {
+        if !is_root(self) {
+            self.gather_into(sendbuf);
+            return None;
+        }
+        let len = sendbuf.len()
+            * self
+                .as_communicator()
+                .target_size()
+                .value_as::<usize>()
+                .expect("Communicator size cannot be expressed as a usize.");
+        let mut recvbuf = Vec::with_capacity(len);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gather", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Gather(
+                sendbuf.pointer(),
+                sendbuf.count(),
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.as_mut_ptr() as *mut c_void,
+                sendbuf.count(),
+                T::equivalent_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
+            );
+            // All `len` elements have been written by the gather.
+            recvbuf.set_len(len);
+        }
+        Some(recvbuf)
+    }
+
+    /// Gather slices of different lengths on `Root` into a newly allocated `Vec` per process.
+    ///
+    /// The lengths are gathered first, followed by the contents via `MPI_Gatherv()`. Returns the
+    /// slices of all processes in rank order on `Root` and `None` elsewhere. Strings can be
+    /// gathered with `gather_strings()`.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather_varcount_vec<T>(&self, sendbuf: &[T]) -> Option<Vec<Vec<T>>>
+    where
+        T: Equivalence,
+    {
+        let count: Count = sendbuf
+            .len()
+            .value_as()
+            .expect("Length of slice cannot be expressed as an MPI Count.");
+        let Some(counts) = self.gather(&count) else {
+            self.gather_varcount_into(sendbuf);
+            return None;
+        };
+        let displs: Vec<Count> = counts
+            .iter()
+            .scan(0, |offset, &count| {
+                let displ = *offset;
+                *offset += count;
+                Some(displ)
+            })
+            .collect();
+        let len: usize = counts
+            .iter()
+            .map(|&count| {
+                count
+                    .value_as::<usize>()
+                    .expect("Received count cannot be expressed as a usize.")
+            })
+            .sum();
+        let mut recvbuf = Vec::<T>::with_capacity(len);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gatherv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        unsafe {
+            ffi::MPI_Gatherv(
+                sendbuf.pointer(),
+                sendbuf.count(),
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.as_mut_ptr() as *mut c_void,
+                counts.as_ptr(),
+                displs.as_ptr(),
+                T::equivalent_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
             );
+            // All `len` elements have been written by the gather.
+            recvbuf.set_len(len);
         }
+        let mut rest = recvbuf.into_iter();
+        Some(
+            counts
+                .iter()
+                .map(|&count| rest.by_ref().take(count as usize).collect())
+                .collect(),
+        )
+    }
+
+    /// Gather strings of different lengths on `Root`.
+    ///
+    /// The strings are gathered as bytes with `gather_varcount_vec()`. Returns the strings of all
+    /// processes in rank order on `Root` and `None` elsewhere.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather_strings(&self, string: &str) -> Option<Vec<String>> {
+        self.gather_varcount_vec(string.as_bytes()).map(|all| {
+            all.into_iter()
+                .map(|bytes| {
+                    String::from_utf8(bytes).expect("Every process sends a valid UTF-8 string.")
+                })
+                .collect()
+        })
     }
 
     /// Gather contents of buffers on `Root`.
@@ -795,7 +2152,12 @@ pub trait Root: AsCommunicator {
     where
         S: Buffer,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gatherv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             ffi::MPI_Gatherv(
                 sendbuf.pointer(),
@@ -833,7 +2195,12 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         R: PartitionedBufferMut,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Gatherv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             ffi::MPI_Gatherv(
                 sendbuf.pointer(),
@@ -849,6 +2216,71 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Stream the contents of byte buffers of varying length to a writer on `Root`.
+    ///
+    /// This is the non-root counterpart of `gather_into_writer_root()`. The contents of `sendbuf`
+    /// are sent to `Root` in a single point-to-point message on a private duplicate of the
+    /// communicator, so that it cannot be matched by, or match, any message of the user.
+    ///
+    /// This function must be called on all non-root processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather_into_writer.rs`
+    fn gather_into_writer(&self, sendbuf: &[u8]) {
+        assert_is_not_root(self);
+        let comm = private_duplicate(self.as_communicator());
+        if self.root_rank() != unsafe { ffi::RSMPI_PROC_NULL } {
+            comm.process_at_rank(self.root_rank()).send(sendbuf);
+        }
+    }
+
+    /// Stream the contents of byte buffers of varying length to a writer on `Root`.
+    ///
+    /// The contributions of all processes, including `sendbuf` on `Root`, are written to `writer`
+    /// in rank order. Unlike `gather_varcount_into_root()` the counts do not have to be known in
+    /// advance and the concatenated result is never materialized on `Root`: each contribution is
+    /// received into a buffer of exactly the right size and written out before the next one is
+    /// received.
+    ///
+    /// If writing fails, the remaining contributions are still received (and discarded) so that
+    /// the other processes can complete, and the first error is returned.
+    ///
+    /// On an inter-communicator, the contributions of the processes of the remote group are
+    /// written and `sendbuf` is ignored.
+    ///
+    /// The contributions are exchanged on a private duplicate of the communicator, which is
+    /// created collectively by the first call and cached on the communicator until it is freed.
+    ///
+    /// This function must be called on the root process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/gather_into_writer.rs`
+    fn gather_into_writer_root<W: ?Sized>(&self, sendbuf: &[u8], writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        assert_is_root(self);
+        let comm = private_duplicate(self.as_communicator());
+        let mut result = Ok(());
+        // The ranks of an inter-communicator refer to the remote group, which never contains the
+        // root.
+        for rank in 0..comm.target_size() {
+            if rank == self.root_rank() {
+                if result.is_ok() {
+                    result = writer.write_all(sendbuf);
+                }
+            } else {
+                let (chunk, _) = comm.process_at_rank(rank).receive_vec::<u8>();
+                if result.is_ok() {
+                    result = writer.write_all(&chunk);
+                }
+            }
+        }
+        result
+    }
+
     /// Scatter contents of a buffer on the root process to all processes.
     ///
     /// After the call completes each participating process will have received a part of the send
@@ -869,8 +2301,31 @@ pub trait Root: AsCommunicator {
     where
         R: BufferMut,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
-        unsafe {
+        let _ = self.try_scatter_into(recvbuf);
+    }
+
+    /// Like `scatter_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.6
+    fn try_scatter_into<R: ?Sized>(&self, recvbuf: &mut R) -> Result<(), ErrorCode>
+    where
+        R: BufferMut,
+    {
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scatter", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(recvbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Scatter(
                 ptr::null(),
                 0,
@@ -880,8 +2335,8 @@ pub trait Root: AsCommunicator {
                 recvbuf.as_datatype().as_raw(),
                 self.root_rank(),
                 self.as_communicator().as_raw(),
-            );
-        }
+            )
+        })
     }
 
     /// Scatter contents of a buffer on the root process to all processes.
@@ -905,15 +2360,166 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         R: BufferMut,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
-        let sendcount = sendbuf.count() / self.as_communicator().target_size();
+        let _ = self.try_scatter_into_root(sendbuf, recvbuf);
+    }
+
+    /// Like `scatter_into_root()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.6
+    fn try_scatter_into_root<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        assert_is_root(self);
+        let sendcount = count_per_process(self.as_communicator(), sendbuf);
+        assert_matching_size(
+            self.as_communicator(),
+            sendbuf,
+            sendcount,
+            recvbuf,
+            recvbuf.count(),
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scatter", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
+            ffi::MPI_Scatter(
+                sendbuf.pointer(),
+                sendcount,
+                sendbuf.as_datatype().as_raw(),
+                recvbuf.pointer_mut(),
+                recvbuf.count(),
+                recvbuf.as_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
+            )
+        })
+    }
+
+    /// Scatter contents of a buffer on `Root` to all processes in place.
+    ///
+    /// The part of `buf` at the rank of `Root` is kept in place, `buf` is partitioned into `size`
+    /// equally sized parts. The other processes call `scatter_into()`.
+    ///
+    /// This function must be called on the root process of an intra-communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.6 (See: `MPI_IN_PLACE`)
+    fn scatter_in_place_root<Buf: ?Sized>(&self, buf: &Buf)
+    where
+        Buf: Buffer,
+    {
+        assert_is_root(self);
+        let sendcount = count_per_process(self.as_communicator(), buf);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scatter", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Scatter(
+                buf.pointer(),
+                sendcount,
+                buf.as_datatype().as_raw(),
+                ffi::RSMPI_IN_PLACE,
+                0,
+                buf.as_datatype().as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
+            );
+        }
+    }
+
+    /// Scatter blocks of consecutive columns of a row-major matrix on `Root` to all processes.
+    ///
+    /// `matrix` has `ncols` columns and is only significant on `Root`, the other processes may
+    /// pass an empty slice. Every process receives `ncols / size` consecutive columns in rank
+    /// order, stored row-major in `recvbuf`, which determines the number of rows. The columns are
+    /// described by a vector datatype resized to the width of a block, so the matrix is not
+    /// copied.
+    ///
+    /// This function must be called on all processes of an intra-communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/scatter.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.6
+    fn scatter_columns_into<T>(&self, matrix: &[T], ncols: Count, recvbuf: &mut [T])
+    where
+        T: Equivalence,
+    {
+        let size = self.as_communicator().size();
+        assert!(
+            ncols > 0 && ncols % size == 0,
+            "The number of columns ({}) is not a multiple of the number of processes ({}).",
+            ncols,
+            size
+        );
+        let width = ncols / size;
+        let recvcount = recvbuf.count();
+        assert_eq!(
+            recvcount % width,
+            0,
+            "The receive buffer does not hold whole rows of {} columns.",
+            width
+        );
+        let rows = recvcount / width;
+
+        if !is_root(self) {
+            self.scatter_into(recvbuf);
+            return;
+        }
+
+        assert_eq!(
+            matrix.count(),
+            rows * ncols,
+            "The matrix does not have {} rows of {} columns.",
+            rows,
+            ncols
+        );
+        let block = UserDatatype::vector(rows, width, ncols, &T::equivalent_datatype());
+        let block = UserDatatype::resized(
+            &block,
+            0,
+            (width as usize * mem::size_of::<T>())
+                .value_as()
+                .expect("Width of a block exceeds the range of an Address."),
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scatter", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(recvbuf)
+        });
         unsafe {
             ffi::MPI_Scatter(
-                sendbuf.pointer(),
-                sendcount,
-                sendbuf.as_datatype().as_raw(),
+                matrix.pointer(),
+                1,
+                block.as_raw(),
                 recvbuf.pointer_mut(),
-                recvbuf.count(),
+                recvcount,
                 recvbuf.as_datatype().as_raw(),
                 self.root_rank(),
                 self.as_communicator().as_raw(),
@@ -942,7 +2548,12 @@ pub trait Root: AsCommunicator {
     where
         R: BufferMut,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scatterv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(recvbuf)
+        });
         unsafe {
             ffi::MPI_Scatterv(
                 ptr::null(),
@@ -980,7 +2591,12 @@ pub trait Root: AsCommunicator {
         S: PartitionedBuffer,
         R: BufferMut,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Scatterv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .partitioned(sendbuf)
+        });
         unsafe {
             ffi::MPI_Scatterv(
                 sendbuf.pointer(),
@@ -1013,8 +2629,32 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         O: Operation,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
-        unsafe {
+        let _ = self.try_reduce_into(sendbuf, op);
+    }
+
+    /// Like `reduce_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.1
+    fn try_reduce_into<S: ?Sized, O>(&self, sendbuf: &S, op: O) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        O: Operation,
+    {
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Reduce(
                 sendbuf.pointer(),
                 ptr::null_mut(),
@@ -1023,8 +2663,8 @@ pub trait Root: AsCommunicator {
                 op.as_raw(),
                 self.root_rank(),
                 self.as_communicator().as_raw(),
-            );
-        }
+            )
+        })
     }
 
     /// Performs a global reduction under the operation `op` of the input data in `sendbuf` and
@@ -1045,8 +2685,38 @@ pub trait Root: AsCommunicator {
         R: BufferMut,
         O: Operation,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
-        unsafe {
+        let _ = self.try_reduce_into_root(sendbuf, recvbuf, op);
+    }
+
+    /// Like `reduce_into_root()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.1
+    fn try_reduce_into_root<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), ErrorCode>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        ErrorCode::check(unsafe {
             ffi::MPI_Reduce(
                 sendbuf.pointer(),
                 recvbuf.pointer_mut(),
@@ -1055,10 +2725,103 @@ pub trait Root: AsCommunicator {
                 op.as_raw(),
                 self.root_rank(),
                 self.as_communicator().as_raw(),
+            )
+        })
+    }
+
+    /// Performs a global reduction under the operation `op` of the data in `buf` on all processes
+    /// and stores the result in `buf` on the `Root` process.
+    ///
+    /// The other processes call `reduce_into()`.
+    ///
+    /// This function must be called on the root process of an intra-communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/in_place.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.1 (See: `MPI_IN_PLACE`)
+    fn reduce_in_place_root<Buf: ?Sized, O>(&self, buf: &mut Buf, op: O)
+    where
+        Buf: BufferMut,
+        O: Operation,
+    {
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            ffi::MPI_Reduce(
+                ffi::RSMPI_IN_PLACE,
+                buf.pointer_mut(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+                op.as_raw(),
+                self.root_rank(),
+                self.as_communicator().as_raw(),
             );
         }
     }
 
+    /// Contributes the input data in `sendbuf` to a bitwise reproducible global sum on the `Root`
+    /// process.
+    ///
+    /// See `reproducible_sum_into_root()` for how the result is computed.
+    ///
+    /// This function must be called on all non-root processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reproducible_reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.1
+    fn reproducible_sum_into<T>(&self, sendbuf: &[T])
+    where
+        T: ExactSum,
+    {
+        assert_is_not_root(self);
+        self.reduce_into(&exact_sum_accumulators(sendbuf)[..], SystemOperation::sum());
+    }
+
+    /// Performs a bitwise reproducible global sum of the input data in `sendbuf` and stores the
+    /// result on the `Root` process.
+    ///
+    /// Unlike `reduce_into_root()` with `SystemOperation::sum()`, the contributions are summed
+    /// exactly and the exact sum is rounded once. The result only depends on the values that are
+    /// contributed, not on the reduction algorithm chosen by the MPI library, the order of the
+    /// processes or their number, so it is bitwise identical from run to run, also when the same
+    /// values are contributed by a different number of processes.
+    ///
+    /// Every element is exchanged as an exact accumulator of 71 `i64`s, so the traffic is that of
+    /// a `reduce_into_root()` of 71 times the data in `sendbuf`.
+    ///
+    /// This function must be called on the root process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reproducible_reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.1
+    fn reproducible_sum_into_root<T>(&self, sendbuf: &[T], recvbuf: &mut [T])
+    where
+        T: ExactSum,
+    {
+        assert_is_root(self);
+        assert_eq!(sendbuf.len(), recvbuf.len());
+        let accumulators = exact_sum_accumulators(sendbuf);
+        let mut sums = vec![0; accumulators.len()];
+        self.reduce_into_root(&accumulators[..], &mut sums[..], SystemOperation::sum());
+        round_exact_sums(&mut sums, recvbuf);
+    }
+
     /// Initiate broadcast of a value from the `Root` process to all other processes.
     ///
     /// # Examples
@@ -1077,6 +2840,11 @@ pub trait Root: AsCommunicator {
         Buf: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ibcast", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1096,6 +2864,52 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Create a persistent request for broadcasting the contents of `buf` from `Root` `&self`.
+    ///
+    /// Between runs the `Root` writes the message to `PersistentRequest::data_mut()`, after
+    /// completion `PersistentRequest::data()` holds it on all processes.
+    ///
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/persistent_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.13.2
+    fn broadcast_init<'a, Buf: ?Sized, Sc>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+    ) -> Result<PersistentRequest<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Bcast_init", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Bcast_init(
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    self.root_rank(),
+                    self.as_communicator().as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PersistentRequest::from_raw(request, buf, scope))
+        }
+    }
+
     /// Initiate non-blocking gather of the contents of all `sendbuf`s on `Root` `&self`.
     ///
     /// This function must be called on all non-root processes.
@@ -1116,7 +2930,12 @@ pub trait Root: AsCommunicator {
         S: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Igather", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1161,9 +2980,21 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let recvcount = count_per_process(self.as_communicator(), recvbuf);
+        assert_matching_size(
+            self.as_communicator(),
+            sendbuf,
+            sendbuf.count(),
+            recvbuf,
+            recvcount,
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Igather", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
-            let recvcount = recvbuf.count() / self.as_communicator().target_size();
             Request::from_raw(
                 with_uninitialized(|request| {
                     ffi::MPI_Igather(
@@ -1205,7 +3036,12 @@ pub trait Root: AsCommunicator {
         S: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Igatherv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1251,7 +3087,12 @@ pub trait Root: AsCommunicator {
         R: 'a + PartitionedBufferMut,
         Sc: Scope<'a>,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Igatherv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1295,7 +3136,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iscatter", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(recvbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1340,9 +3186,21 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let sendcount = count_per_process(self.as_communicator(), sendbuf);
+        assert_matching_size(
+            self.as_communicator(),
+            sendbuf,
+            sendcount,
+            recvbuf,
+            recvbuf.count(),
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iscatter", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
-            let sendcount = sendbuf.count() / self.as_communicator().target_size();
             Request::from_raw(
                 with_uninitialized(|request| {
                     ffi::MPI_Iscatter(
@@ -1384,7 +3242,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iscatterv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(recvbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1430,7 +3293,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iscatterv", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .partitioned(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1477,7 +3345,12 @@ pub trait Root: AsCommunicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
-        assert_ne!(self.as_communicator().rank(), self.root_rank());
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ireduce", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1524,7 +3397,12 @@ pub trait Root: AsCommunicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
-        assert_eq!(self.as_communicator().rank(), self.root_rank());
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ireduce", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1546,6 +3424,114 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Create a persistent request for reducing the contents of `sendbuf` under `op` on the
+    /// `Root`.
+    ///
+    /// The buffer is mutably borrowed so that the contribution can be updated between runs, see
+    /// `PersistentRequest::data_mut()`.
+    ///
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
+    ///
+    /// This function must be called on all non-root processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/persistent_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.13.7
+    fn reduce_init<'a, Sc, S: ?Sized, O>(
+        &self,
+        scope: Sc,
+        sendbuf: &'a mut S,
+        op: O,
+    ) -> Result<PersistentRequest<'a, S, Sc>, ErrorCode>
+    where
+        S: 'a + Buffer,
+        O: 'a + Operation,
+        Sc: Scope<'a>,
+    {
+        assert_is_not_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce_init", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
+        unsafe {
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Reduce_init(
+                    sendbuf.pointer(),
+                    ptr::null_mut(),
+                    sendbuf.count(),
+                    sendbuf.as_datatype().as_raw(),
+                    op.as_raw(),
+                    self.root_rank(),
+                    self.as_communicator().as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PersistentRequest::from_raw(request, sendbuf, scope))
+        }
+    }
+
+    /// Create a persistent request for reducing the contents of `buf` under `op` in place on the
+    /// `Root`.
+    ///
+    /// Between runs the contribution of the `Root` is written to `PersistentRequest::data_mut()`,
+    /// after completion `PersistentRequest::data()` holds the result.
+    ///
+    /// Fails if the MPI library does not support persistent collective operations, which were
+    /// introduced in MPI 4.0.
+    ///
+    /// This function must be called on the root process of an intra-communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/persistent_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.13.7 (See: `MPI_IN_PLACE`)
+    fn reduce_in_place_init_root<'a, Sc, Buf: ?Sized, O>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+        op: O,
+    ) -> Result<PersistentRequest<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + BufferMut,
+        O: 'a + Operation,
+        Sc: Scope<'a>,
+    {
+        assert_is_root(self);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Reduce_init", self.as_communicator().as_raw())
+                .peer(self.root_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Reduce_init(
+                    ffi::RSMPI_IN_PLACE,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    op.as_raw(),
+                    self.root_rank(),
+                    self.as_communicator().as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PersistentRequest::from_raw(request, buf, scope))
+        }
+    }
+
     /// Spawns child processes
     ///
     /// # Standard sections
@@ -1589,6 +3575,9 @@ pub trait Root: AsCommunicator {
             vec![0; maxprocs.value_as().expect("maxprocs should be positive")];
 
         unsafe {
+            let _call = instrument::begin(|| {
+                Call::new("MPI_Comm_spawn", self.as_communicator().as_raw()).peer(self.root_rank())
+            });
             ffi::MPI_Comm_spawn(
                 prog.as_ptr(),
                 argv.as_mut_ptr(),
@@ -1657,6 +3646,10 @@ pub trait Root: AsCommunicator {
         let mut errcodes = vec![0; usize::try_from(sum_maxprocs).unwrap()];
 
         unsafe {
+            let _call = instrument::begin(|| {
+                Call::new("MPI_Comm_spawn_multiple", self.as_communicator().as_raw())
+                    .peer(self.root_rank())
+            });
             ffi::MPI_Comm_spawn_multiple(
                 progs.len().value_as().unwrap(),
                 progp.as_mut_ptr(),
@@ -1690,6 +3683,254 @@ impl<'a> Root for Process<'a> {
     }
 }
 
+/// Owned buffers that can be resized to the length of a broadcast message
+///
+/// See `Root::broadcast_owned()`.
+pub trait BroadcastOwned {
+    /// Broadcast the length and contents of `self` from `root`, resizing `self` on the receiving
+    /// processes.
+    fn broadcast_owned_from<R: Root + ?Sized>(&mut self, root: &R);
+}
+
+impl<T: Equivalence> BroadcastOwned for Vec<T> {
+    fn broadcast_owned_from<R: Root + ?Sized>(&mut self, root: &R) {
+        let mut len = self.len();
+        root.broadcast_into(&mut len);
+        if receives_from_root(root) {
+            self.clear();
+            self.reserve_exact(len);
+        }
+        let count: Count = len
+            .value_as()
+            .expect("Length of Vec cannot be expressed as an MPI Count.");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Bcast", root.as_communicator().as_raw())
+                .peer(root.root_rank())
+                .typed(count, &T::equivalent_datatype())
+        });
+        unsafe {
+            ffi::MPI_Bcast(
+                self.as_mut_ptr() as *mut c_void,
+                count,
+                T::equivalent_datatype().as_raw(),
+                root.root_rank(),
+                root.as_communicator().as_raw(),
+            );
+            // The first `len` elements have been written by the broadcast.
+            self.set_len(len);
+        }
+    }
+}
+
+impl BroadcastOwned for String {
+    fn broadcast_owned_from<R: Root + ?Sized>(&mut self, root: &R) {
+        // The received bytes are the contents of the `String` on `Root`, so they are valid UTF-8.
+        unsafe { self.as_mut_vec() }.broadcast_owned_from(root);
+    }
+}
+
+/// Checks whether the calling process receives the data of a rooted one-to-all operation.
+///
+/// On an inter-communicator only the processes in the remote group receive the data, they see the
+/// actual rank of the root.
+fn receives_from_root<R: Root + ?Sized>(root: &R) -> bool {
+    if root.as_communicator().as_handle().is_inter_comm() {
+        root.root_rank() >= 0
+    } else {
+        root.as_communicator().rank() != root.root_rank()
+    }
+}
+
+/// Checks whether the calling process is the root of a rooted collective operation.
+fn is_root<R: Root + ?Sized>(root: &R) -> bool {
+    root.root_rank() == unsafe { ffi::RSMPI_ROOT }
+        || (!root.as_communicator().as_handle().is_inter_comm()
+            && root.as_communicator().rank() == root.root_rank())
+}
+
+/// Checks that the calling process is the root of a rooted collective operation.
+///
+/// On an inter-communicator the root is identified by `MPI_ROOT`.
+fn assert_is_root<R: Root + ?Sized>(root: &R) {
+    if root.root_rank() != unsafe { ffi::RSMPI_ROOT } {
+        assert_eq!(root.as_communicator().rank(), root.root_rank());
+    }
+}
+
+/// Checks that the calling process is not the root of a rooted collective operation.
+///
+/// On an inter-communicator the rank of the root refers to the remote group, so it cannot be
+/// compared to the rank of the calling process.
+fn assert_is_not_root<R: Root + ?Sized>(root: &R) {
+    assert_ne!(root.root_rank(), unsafe { ffi::RSMPI_ROOT });
+    if !root.as_communicator().as_handle().is_inter_comm() {
+        assert_ne!(root.as_communicator().rank(), root.root_rank());
+    }
+}
+
+/// Divides the count of `buf` into equally sized parts, one for each process of `comm`.
+///
+/// Panics if the count is not a multiple of the number of processes, MPI would silently leave the
+/// remaining elements out of the operation.
+fn count_per_process<C, B>(comm: &C, buf: &B) -> Count
+where
+    C: Communicator + ?Sized,
+    B: Collection + ?Sized,
+{
+    let size = comm.target_size();
+    let count = buf.count();
+    assert!(
+        count % size == 0,
+        "Buffer count ({}) is not a multiple of the number of processes ({}).",
+        count,
+        size
+    );
+    count / size
+}
+
+/// Divides the count of `buf` into equally sized parts, one for each of `degree` neighbors.
+///
+/// Panics if the count is not a multiple of the number of neighbors, MPI would silently leave the
+/// remaining elements out of the operation. Without neighbors, the buffer is not accessed at all.
+fn count_per_neighbor<B>(degree: Count, buf: &B) -> Count
+where
+    B: Collection + ?Sized,
+{
+    if degree == 0 {
+        return 0;
+    }
+    let count = buf.count();
+    assert!(
+        count % degree == 0,
+        "Buffer count ({}) is not a multiple of the number of neighbors ({}).",
+        count,
+        degree
+    );
+    count / degree
+}
+
+/// Checks that the part a process sends to itself and the part it receives from itself have the
+/// same size in bytes, as required for matching type signatures.
+///
+/// On an inter-communicator the parts are exchanged with the other group, so they are not
+/// checked.
+fn assert_matching_size<C, S, R>(
+    comm: &C,
+    sendbuf: &S,
+    sendcount: Count,
+    recvbuf: &R,
+    recvcount: Count,
+) where
+    C: Communicator + ?Sized,
+    S: AsDatatype + ?Sized,
+    R: AsDatatype + ?Sized,
+{
+    if comm.as_handle().is_inter_comm() {
+        return;
+    }
+    let sent = Address::from(sendcount) * Address::from(sendbuf.as_datatype().size());
+    let received = Address::from(recvcount) * Address::from(recvbuf.as_datatype().size());
+    assert_eq!(
+        sent, received,
+        "The data sent to each process ({} bytes) and received from each process ({} bytes) \
+         differ in size.",
+        sent, received
+    );
+}
+
+/// Whether the partitions of `buf` are contiguous and in rank order, i.e. the displacement of each
+/// partition is the sum of the counts of all preceding partitions.
+fn partitions_in_rank_order<B>(buf: &B) -> bool
+where
+    B: Partitioned + ?Sized,
+{
+    buf.counts()
+        .iter()
+        .scan(0, |displ, &count| {
+            let expected = *displ;
+            *displ += count;
+            Some(expected)
+        })
+        .eq(buf.displs().iter().copied())
+}
+
+/// Checks that the partitions of `sendbuf` are contiguous, in rank order and that `recvbuf` holds
+/// the partition of the calling process, as required by `MPI_Reduce_scatter()`.
+fn check_reduce_scatter_partition<C, S, R>(comm: &C, sendbuf: &S, recvbuf: &R)
+where
+    C: Communicator + ?Sized,
+    S: PartitionedBuffer + ?Sized,
+    R: BufferMut + ?Sized,
+{
+    let counts = sendbuf.counts();
+    assert_eq!(
+        counts.len(),
+        comm.target_size()
+            .value_as::<usize>()
+            .expect("Communicator size is negative.")
+    );
+    assert!(
+        partitions_in_rank_order(sendbuf),
+        "reduce_scatter_into() requires contiguous partitions in rank order"
+    );
+    let rank: usize = comm.rank().value_as().expect("Rank is negative.");
+    assert_eq!(recvbuf.count(), counts[rank]);
+}
+
+/// Floating point numbers that the reproducible sums, e.g.
+/// `CommunicatorCollectives::reproducible_all_sum_into()`, add up exactly
+///
+/// The values are accumulated as `f64`s, which have to represent them exactly, and the exact sum
+/// is rounded to an `f64` before it is converted back.
+pub trait ExactSum: Copy {
+    /// The value as an `f64`
+    fn to_f64(self) -> f64;
+    /// The value closest to `x`
+    fn from_f64(x: f64) -> Self;
+}
+
+impl ExactSum for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+}
+
+impl ExactSum for f32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+}
+
+/// One exact accumulator of `exact_sum::WORDS` words per element of `values`, holding that element
+fn exact_sum_accumulators<T: ExactSum>(values: &[T]) -> Vec<i64> {
+    let mut accumulators = vec![0; values.len() * exact_sum::WORDS];
+    for (&value, words) in values
+        .iter()
+        .zip(accumulators.chunks_exact_mut(exact_sum::WORDS))
+    {
+        exact_sum::accumulate(words, value.to_f64());
+    }
+    accumulators
+}
+
+/// Rounds the sums in the accumulators `sums` into the elements of `recvbuf`.
+fn round_exact_sums<T: ExactSum>(sums: &mut [i64], recvbuf: &mut [T]) {
+    for (sum, words) in recvbuf
+        .iter_mut()
+        .zip(sums.chunks_exact_mut(exact_sum::WORDS))
+    {
+        *sum = T::from_f64(exact_sum::round(words));
+    }
+}
+
 /// An operation to be used in a reduction or scan type operation, e.g. `MPI_SUM`
 pub trait Operation: AsRaw<Raw = MPI_Op> {
     /// Returns whether the operation is commutative.
@@ -1698,6 +3939,7 @@ pub trait Operation: AsRaw<Raw = MPI_Op> {
     ///
     /// 5.9.7
     fn is_commutative(&self) -> bool {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Op_commutative"));
         unsafe {
             let mut commute = 0;
             ffi::MPI_Op_commutative(self.as_raw(), &mut commute);
@@ -1719,6 +3961,12 @@ impl<'a, T: 'a + Operation> Operation for &'a T {}
 #[derive(Copy, Clone)]
 pub struct SystemOperation(MPI_Op);
 
+// SAFETY: Predefined operations are constants of the MPI library that are never freed. Like
+// datatypes, they are only read by the operations they are passed to, which are started through
+// communicators that only move between threads as `ThreadSafe` under `Threading::Multiple`.
+unsafe impl Send for SystemOperation {}
+unsafe impl Sync for SystemOperation {}
+
 macro_rules! system_operation_constructors {
     ($($ctor:ident => $val:path),*) => (
         $(pub fn $ctor() -> SystemOperation {
@@ -1739,7 +3987,11 @@ impl SystemOperation {
         logical_or => ffi::RSMPI_LOR,
         bitwise_or => ffi::RSMPI_BOR,
         logical_xor => ffi::RSMPI_LXOR,
-        bitwise_xor => ffi::RSMPI_BXOR
+        bitwise_xor => ffi::RSMPI_BXOR,
+        replace => ffi::RSMPI_REPLACE,
+        no_op => ffi::RSMPI_NO_OP,
+        min_loc => ffi::RSMPI_MINLOC,
+        max_loc => ffi::RSMPI_MAXLOC
     }
 }
 
@@ -1791,6 +4043,7 @@ impl<'a> fmt::Debug for UserOperation<'a> {
 #[cfg(feature = "user-operations")]
 impl<'a> Drop for UserOperation<'a> {
     fn drop(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Op_free"));
         unsafe {
             ffi::MPI_Op_free(&mut self.op);
         }
@@ -1906,6 +4159,7 @@ impl<'a> UserOperation<'a> {
         let op;
         anchor._ffi_closure = Some(unsafe {
             let ffi_closure = Closure::new(cif, trampoline, &anchor.rust_closure);
+            let _call = instrument::begin(|| Call::without_comm("MPI_Op_create"));
             op = with_uninitialized(|op| {
                 ffi::MPI_Op_create(Some(*ffi_closure.instantiate_code_ptr()), commute as _, op)
             })
@@ -1943,6 +4197,8 @@ impl<'a> UserOperation<'a> {
 /// available.
 pub struct UnsafeUserOperation {
     op: MPI_Op,
+    // Stays on the thread that created it, since it is freed with `MPI_Op_free()` when dropped.
+    phantom: PhantomData<*const ()>,
 }
 
 impl fmt::Debug for UnsafeUserOperation {
@@ -1955,6 +4211,7 @@ impl fmt::Debug for UnsafeUserOperation {
 
 impl Drop for UnsafeUserOperation {
     fn drop(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Op_free"));
         unsafe {
             ffi::MPI_Op_free(&mut self.op);
         }
@@ -2028,8 +4285,10 @@ impl UnsafeUserOperation {
     /// The construction of an `UnsafeUserOperation` asserts that `function` is safe to be called
     /// in all reductions that this `UnsafeUserOperation` is used in.
     pub unsafe fn new(commute: bool, function: UnsafeUserFunction) -> Self {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Op_create"));
         UnsafeUserOperation {
             op: with_uninitialized(|op| ffi::MPI_Op_create(Some(function), commute as _, op)).1,
+            phantom: PhantomData,
         }
     }
 }
@@ -2050,6 +4309,7 @@ where
     R: BufferMut,
     O: Operation,
 {
+    let _call = instrument::begin(|| Call::without_comm("MPI_Reduce_local").buffer(inbuf));
     unsafe {
         ffi::MPI_Reduce_local(
             inbuf.pointer(),
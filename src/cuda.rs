@@ -0,0 +1,150 @@
+//! Communication of CUDA device memory
+//!
+//! CUDA-aware MPI libraries accept pointers to device memory in communication calls and move the
+//! data without staging it through host memory first. Whether the library in use does so is only
+//! known at runtime, see `is_cuda_aware()`.
+//!
+//! Device memory is described by the `DeviceBuffer` and `DeviceBufferMut` traits, which only
+//! require a device pointer and a length. This crate does not depend on any CUDA bindings and
+//! implements the traits for none of their types, so allocations of crates like `cust` or
+//! `cudarc` have to be wrapped in a type of the application that implements them. `Device` and
+//! `DeviceMut` turn them into a `Buffer` and `BufferMut` that can be passed to all operations of
+//! this crate.
+//!
+//! This module is available with the `cuda` feature.
+
+use std::os::raw::c_void;
+
+use conv::ConvUtil;
+
+use crate::datatype::traits::*;
+use crate::{ffi, Count};
+
+/// Whether the MPI library accepts CUDA device memory, queried with `MPIX_Query_cuda_support()`
+///
+/// Returns `false` if the library does not provide the query. The answer is only known after
+/// MPI has been initialized.
+///
+/// # Examples
+/// See `examples/cuda.rs`
+pub fn is_cuda_aware() -> bool {
+    unsafe { ffi::RSMPI_Query_cuda_support() != 0 }
+}
+
+/// An allocation of CUDA device memory holding `len()` elements of type `Item`
+///
+/// # Safety
+///
+/// `device_ptr()` must point to device memory holding `len()` initialized elements, which stays
+/// valid as long as `self` is borrowed.
+pub unsafe trait DeviceBuffer {
+    /// The type of the elements
+    type Item: Equivalence;
+    /// The device pointer to the first element
+    fn device_ptr(&self) -> *const Self::Item;
+    /// The number of elements
+    fn len(&self) -> usize;
+    /// Whether the allocation holds no elements
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A mutable allocation of CUDA device memory
+///
+/// # Safety
+///
+/// In addition to the requirements of `DeviceBuffer`, `device_ptr_mut()` must point to the same
+/// memory as `device_ptr()`, which may be written as long as `self` is borrowed mutably.
+pub unsafe trait DeviceBufferMut: DeviceBuffer {
+    /// The mutable device pointer to the first element
+    fn device_ptr_mut(&mut self) -> *mut Self::Item;
+}
+
+/// A `Buffer` of device memory
+///
+/// # Examples
+/// See `examples/cuda.rs`
+pub struct Device<'b, B: ?Sized>(&'b B);
+
+impl<'b, B: DeviceBuffer + ?Sized> Device<'b, B> {
+    /// Use `buffer` in communication calls.
+    ///
+    /// Panics if the MPI library is not CUDA-aware, it would treat the device pointer as a host
+    /// pointer.
+    pub fn new(buffer: &'b B) -> Self {
+        assert!(
+            is_cuda_aware(),
+            "The MPI library does not accept CUDA device memory."
+        );
+        Device(buffer)
+    }
+}
+
+unsafe impl<'b, B: DeviceBuffer + ?Sized> Pointer for Device<'b, B> {
+    fn pointer(&self) -> *const c_void {
+        self.0.device_ptr() as _
+    }
+}
+
+unsafe impl<'b, B: DeviceBuffer + ?Sized> Collection for Device<'b, B> {
+    fn count(&self) -> Count {
+        self.0
+            .len()
+            .value_as()
+            .expect("Length of device buffer cannot be expressed as an MPI Count.")
+    }
+}
+
+unsafe impl<'b, B: DeviceBuffer + ?Sized> AsDatatype for Device<'b, B> {
+    type Out = <B::Item as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        B::Item::equivalent_datatype()
+    }
+}
+
+unsafe impl<'b, B: DeviceBuffer + ?Sized> Buffer for Device<'b, B> {}
+
+/// A `BufferMut` of device memory
+///
+/// # Examples
+/// See `examples/cuda.rs`
+pub struct DeviceMut<'b, B: ?Sized>(&'b mut B);
+
+impl<'b, B: DeviceBufferMut + ?Sized> DeviceMut<'b, B> {
+    /// Use `buffer` in communication calls.
+    ///
+    /// Panics if the MPI library is not CUDA-aware, it would treat the device pointer as a host
+    /// pointer.
+    pub fn new(buffer: &'b mut B) -> Self {
+        assert!(
+            is_cuda_aware(),
+            "The MPI library does not accept CUDA device memory."
+        );
+        DeviceMut(buffer)
+    }
+}
+
+unsafe impl<'b, B: DeviceBufferMut + ?Sized> PointerMut for DeviceMut<'b, B> {
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.0.device_ptr_mut() as _
+    }
+}
+
+unsafe impl<'b, B: DeviceBufferMut + ?Sized> Collection for DeviceMut<'b, B> {
+    fn count(&self) -> Count {
+        self.0
+            .len()
+            .value_as()
+            .expect("Length of device buffer cannot be expressed as an MPI Count.")
+    }
+}
+
+unsafe impl<'b, B: DeviceBufferMut + ?Sized> AsDatatype for DeviceMut<'b, B> {
+    type Out = <B::Item as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        B::Item::equivalent_datatype()
+    }
+}
+
+unsafe impl<'b, B: DeviceBufferMut + ?Sized> BufferMut for DeviceMut<'b, B> {}
@@ -44,7 +44,8 @@
 //! A `Buffer` describes a specific piece of data in memory that MPI should operate on. In addition
 //! to specifying the datatype of the data. It knows the address in memory where the data begins
 //! and how many instances of the datatype are contained in the data. The `Buffer` trait is
-//! implemented for slices that contain types implementing `Equivalence`.
+//! implemented for slices that contain types implementing `Equivalence`. Empty slices are valid
+//! buffers, and `Empty` is a buffer without an element type for messages that carry no data.
 //!
 //! In order to use arbitrary datatypes to describe the contents of a slice, the `View` type is
 //! provided. However, since it can be used to instruct the underlying MPI implementation to
@@ -52,40 +53,44 @@
 //!
 //! # Unfinished features
 //!
-//! - **4.1.3**: Subarray datatype constructors, `MPI_Type_create_subarray()`,
-//! - **4.1.4**: Distributed array datatype constructors, `MPI_Type_create_darray()`
-//! - **4.1.5**: Address and size functions, `MPI_Get_address()`, `MPI_Aint_add()`,
-//! `MPI_Aint_diff()`, `MPI_Type_size()`, `MPI_Type_size_x()`
-//! - **4.1.7**: Extent and bounds of datatypes: `MPI_Type_get_extent()`,
-//! `MPI_Type_get_extent_x()`, `MPI_Type_create_resized()`
-//! - **4.1.8**: True extent of datatypes, `MPI_Type_get_true_extent()`,
-//! `MPI_Type_get_true_extent_x()`
-//! - **4.1.11**: `MPI_Get_elements()`, `MPI_Get_elements_x()`
-//! - **4.1.13**: Decoding a datatype, `MPI_Type_get_envelope()`, `MPI_Type_get_contents()`
-//! - **4.3**: Canonical pack and unpack, `MPI_Pack_external()`, `MPI_Unpack_external()`,
-//! `MPI_Pack_external_size()`
+//! - **4.1.5**: Address and size functions, `MPI_Aint_add()`, `MPI_Aint_diff()`,
+//! `MPI_Type_size_x()`
+//! - **4.1.7**: Extent and bounds of datatypes: `MPI_Type_get_extent_x()`
+//! - **4.1.8**: True extent of datatypes, `MPI_Type_get_true_extent_x()`
+//! - **4.1.11**: `MPI_Get_elements_x()`
 
+use std::any::TypeId;
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::marker::PhantomData;
-use std::os::raw::c_void;
-use std::{mem, slice};
+use std::mem::MaybeUninit;
+use std::os::raw::{c_char, c_int, c_void};
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+use std::{mem, ptr, slice};
 
 use conv::ConvUtil;
+use once_cell::sync::Lazy;
 
 use super::{Address, Count};
 
+use crate::attribute::DatatypeAttribute;
 use crate::ffi;
 use crate::ffi::MPI_Datatype;
+use crate::instrument::{self, Call};
 
 use crate::raw::traits::*;
 
-use crate::with_uninitialized;
+use crate::topology::ThreadSafe;
+use crate::{with_uninitialized, with_uninitialized2};
 
 /// Datatype traits
 pub mod traits {
     pub use super::{
-        AsDatatype, Buffer, BufferMut, Collection, Datatype, Equivalence, Partitioned,
-        PartitionedBuffer, PartitionedBufferMut, Pointer, PointerMut, UncommittedDatatype,
+        AnyBitPattern, AsDatatype, Buffer, BufferMut, Collection, Datatype, Equivalence,
+        HeterogeneousPartitioned, HeterogeneousPartitionedBuffer,
+        HeterogeneousPartitionedBufferMut, Partitioned, PartitionedBuffer, PartitionedBufferMut,
+        Pointer, PointerMut, UncommittedDatatype,
     };
 }
 
@@ -177,6 +182,17 @@ pub unsafe trait Equivalence {
     fn equivalent_datatype() -> Self::Out;
 }
 
+/// An `Equivalence` for which every bit pattern of its size is a valid value
+///
+/// Receive buffers whose elements are overwritten with arbitrary bytes, e.g. by the datatype of
+/// a `MutView`, must consist of such types. Numbers are, but `bool` is not.
+///
+/// # Safety
+///
+/// Every bit pattern of `mem::size_of::<Self>()` bytes, apart from padding, has to be a valid
+/// value of the type.
+pub unsafe trait AnyBitPattern: Equivalence {}
+
 macro_rules! equivalent_system_datatype {
     ($rstype:path, $mpitype:path) => {
         unsafe impl Equivalence for $rstype {
@@ -213,13 +229,340 @@ equivalent_system_datatype!(usize, ffi::RSMPI_UINT64_T);
 #[cfg(target_pointer_width = "64")]
 equivalent_system_datatype!(isize, ffi::RSMPI_INT64_T);
 
+unsafe impl AnyBitPattern for f32 {}
+unsafe impl AnyBitPattern for f64 {}
+unsafe impl AnyBitPattern for i8 {}
+unsafe impl AnyBitPattern for i16 {}
+unsafe impl AnyBitPattern for i32 {}
+unsafe impl AnyBitPattern for i64 {}
+unsafe impl AnyBitPattern for isize {}
+unsafe impl AnyBitPattern for u8 {}
+unsafe impl AnyBitPattern for u16 {}
+unsafe impl AnyBitPattern for u32 {}
+unsafe impl AnyBitPattern for u64 {}
+unsafe impl AnyBitPattern for usize {}
+
+/// A value paired with an index, e.g. the rank of the process that contributed it
+///
+/// Reductions of `ValueIndex` under `SystemOperation::min_loc()` and `SystemOperation::max_loc()`
+/// find the minimum or maximum value together with its index, where ties are resolved by the
+/// smallest index. The layout matches the predefined pair types like `MPI_DOUBLE_INT`.
+///
+/// # Examples
+///
+/// See `examples/min_max_loc.rs`
+///
+/// # Standard section(s)
+///
+/// 5.9.4
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct ValueIndex<T>(pub T, pub c_int);
+
+equivalent_system_datatype!(ValueIndex<f32>, ffi::RSMPI_FLOAT_INT);
+equivalent_system_datatype!(ValueIndex<f64>, ffi::RSMPI_DOUBLE_INT);
+equivalent_system_datatype!(ValueIndex<i16>, ffi::RSMPI_SHORT_INT);
+equivalent_system_datatype!(ValueIndex<i32>, ffi::RSMPI_2INT);
+
+unsafe impl<T> AnyBitPattern for ValueIndex<T> where ValueIndex<T>: Equivalence {}
+
+/// Committed datatypes created by `cached_datatype()`, e.g. for generic types like tuples, which
+/// cannot have a `static` of their own
+static CACHED_DATATYPES: Lazy<RwLock<HashMap<TypeId, internal::StaticDatatype>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The datatype cached for the key type `K`, which is created by `create` on first use.
+///
+/// Committing a datatype on every call, e.g. when sending a struct in a loop, can be expensive.
+/// The cached datatype is shared by all threads and lives until MPI is finalized, `K` can be any
+/// type that identifies the layout, like the type whose `Equivalence` implementation calls this
+/// function or a marker type.
+///
+/// # Examples
+/// See `examples/cached_datatype.rs`
+pub fn cached_datatype<K: 'static>(create: impl FnOnce() -> UserDatatype) -> DatatypeRef<'static> {
+    let id = TypeId::of::<K>();
+    if let Some(datatype) = CACHED_DATATYPES
+        .read()
+        .expect("CACHED_DATATYPES RwLock poisoned")
+        .get(&id)
+    {
+        return unsafe { DatatypeRef::from_raw(datatype.as_raw()) };
+    }
+    // The lock is not held while creating the datatype, which may recursively require datatypes
+    // of nested tuples.
+    let datatype = create();
+    let mut cached_datatypes = CACHED_DATATYPES
+        .write()
+        .expect("CACHED_DATATYPES RwLock poisoned");
+    let datatype = cached_datatypes
+        .entry(id)
+        .or_insert(internal::StaticDatatype::new(datatype));
+    // Datatypes are only removed when MPI is finalized, so the handle stays valid.
+    unsafe { DatatypeRef::from_raw(datatype.as_raw()) }
+}
+
+/// Free the datatypes created by `cached_datatype()` before MPI is finalized.
+pub(crate) fn free_cached_datatypes() {
+    CACHED_DATATYPES
+        .write()
+        .expect("CACHED_DATATYPES RwLock poisoned")
+        .clear();
+}
+
+macro_rules! equivalent_tuple_datatype {
+    ($($field:tt: $ty:ident),*) => {
+        unsafe impl<$($ty),*> Equivalence for ($($ty,)*)
+        where
+            $($ty: Equivalence + 'static,)*
+        {
+            type Out = DatatypeRef<'static>;
+            fn equivalent_datatype() -> Self::Out {
+                cached_datatype::<Self>(|| {
+                    let tuple = MaybeUninit::<Self>::uninit();
+                    let base = tuple.as_ptr();
+                    let displacements = [$(
+                        unsafe {
+                            ptr::addr_of!((*base).$field)
+                                .cast::<u8>()
+                                .offset_from(base.cast())
+                        }
+                        .value_as::<Address>()
+                        .expect("tuple field offset does not fit into an Address"),
+                    )*];
+                    let datatypes = ($(<$ty as Equivalence>::equivalent_datatype(),)*);
+                    let types = [$(
+                        unsafe { UncommittedDatatypeRef::from_raw(datatypes.$field.as_raw()) },
+                    )*];
+                    // Rust may reorder and pad the fields of tuples, the extent must match the size
+                    // for consecutive tuples in memory.
+                    UserDatatype::resized(
+                        &UncommittedUserDatatype::structured(
+                            &[$({ let _ = $field; 1 }),*],
+                            &displacements,
+                            &types,
+                        ),
+                        0,
+                        mem::size_of::<Self>()
+                            .value_as()
+                            .expect("tuple size does not fit into an Address"),
+                    )
+                })
+            }
+        }
+
+        unsafe impl<$($ty),*> AnyBitPattern for ($($ty,)*)
+        where
+            $($ty: AnyBitPattern + 'static,)*
+        {
+        }
+    };
+}
+
+equivalent_tuple_datatype!(0: A, 1: B);
+equivalent_tuple_datatype!(0: A, 1: B, 2: C);
+equivalent_tuple_datatype!(0: A, 1: B, 2: C, 3: D);
+equivalent_tuple_datatype!(0: A, 1: B, 2: C, 3: D, 4: E);
+equivalent_tuple_datatype!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+
 #[cfg(feature = "complex")]
 /// Implement direct equivalence for complex types
 pub mod complex_datatype {
-    use super::{ffi, DatatypeRef, Equivalence, FromRaw, SystemDatatype};
+    use super::{ffi, AnyBitPattern, DatatypeRef, Equivalence, FromRaw, SystemDatatype};
     use num_complex::{Complex32, Complex64};
     equivalent_system_datatype!(Complex32, ffi::RSMPI_FLOAT_COMPLEX);
     equivalent_system_datatype!(Complex64, ffi::RSMPI_DOUBLE_COMPLEX);
+    unsafe impl AnyBitPattern for Complex32 {}
+    unsafe impl AnyBitPattern for Complex64 {}
+}
+
+#[cfg(feature = "ndarray")]
+/// Buffers of `ndarray` arrays and views
+///
+/// Arrays in standard layout are described as `len()` elements of their element type. All other
+/// arrays, e.g. views of columns or strided slices, are described as a single element of a derived
+/// datatype that follows their strides, so they are communicated without copying them into a
+/// contiguous buffer first.
+pub mod ndarray_buffer {
+    use std::mem;
+    use std::os::raw::c_void;
+
+    use conv::ConvUtil;
+    use ndarray::{ArrayBase, Data, DataMut, Dimension};
+
+    use super::{
+        AsDatatype, Buffer, BufferMut, Collection, Equivalence, Pointer, PointerMut,
+        UncommittedUserDatatype, UserDatatype,
+    };
+    use crate::{Address, Count};
+
+    unsafe impl<T, S, D> AsDatatype for ArrayBase<S, D>
+    where
+        T: Equivalence,
+        S: Data<Elem = T>,
+        D: Dimension,
+    {
+        type Out = UserDatatype;
+        fn as_datatype(&self) -> Self::Out {
+            let element = T::equivalent_datatype();
+            if self.is_standard_layout() {
+                return UserDatatype::contiguous(1, &element);
+            }
+            let size: Address = mem::size_of::<T>()
+                .value_as()
+                .expect("Size of element cannot be expressed as an MPI Address.");
+            let mut datatype = UncommittedUserDatatype::contiguous(1, &element);
+            for (&len, &stride) in self.shape().iter().zip(self.strides()).rev() {
+                let stride: Address = stride
+                    .value_as()
+                    .expect("Stride of array cannot be expressed as an MPI Address.");
+                datatype = UncommittedUserDatatype::heterogeneous_vector(
+                    len.value_as()
+                        .expect("Length of array cannot be expressed as an MPI Count."),
+                    1,
+                    stride * size,
+                    &datatype,
+                );
+            }
+            datatype.commit()
+        }
+    }
+
+    unsafe impl<T, S, D> Collection for ArrayBase<S, D>
+    where
+        T: Equivalence,
+        S: Data<Elem = T>,
+        D: Dimension,
+    {
+        fn count(&self) -> Count {
+            if self.is_standard_layout() {
+                self.len()
+                    .value_as()
+                    .expect("Length of array cannot be expressed as an MPI Count.")
+            } else {
+                1
+            }
+        }
+    }
+
+    unsafe impl<T, S, D> Pointer for ArrayBase<S, D>
+    where
+        T: Equivalence,
+        S: Data<Elem = T>,
+        D: Dimension,
+    {
+        fn pointer(&self) -> *const c_void {
+            self.as_ptr() as _
+        }
+    }
+
+    unsafe impl<T, S, D> PointerMut for ArrayBase<S, D>
+    where
+        T: Equivalence,
+        S: DataMut<Elem = T>,
+        D: Dimension,
+    {
+        fn pointer_mut(&mut self) -> *mut c_void {
+            self.as_mut_ptr() as _
+        }
+    }
+
+    unsafe impl<T, S, D> Buffer for ArrayBase<S, D>
+    where
+        T: Equivalence,
+        S: Data<Elem = T>,
+        D: Dimension,
+    {
+    }
+
+    unsafe impl<T, S, D> BufferMut for ArrayBase<S, D>
+    where
+        T: Equivalence,
+        S: DataMut<Elem = T>,
+        D: Dimension,
+    {
+    }
+}
+
+/// The storage order of a multidimensional array
+///
+/// # Standard section(s)
+///
+/// 4.1.3
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Order {
+    /// Row-major order, the last dimension varies fastest, as in C and Rust.
+    C,
+    /// Column-major order, the first dimension varies fastest, as in Fortran.
+    Fortran,
+}
+
+impl Order {
+    /// The raw value understood by the MPI C API
+    fn as_raw(self) -> c_int {
+        match self {
+            Order::C => unsafe { ffi::RSMPI_ORDER_C },
+            Order::Fortran => unsafe { ffi::RSMPI_ORDER_FORTRAN },
+        }
+    }
+
+    fn from_raw(order: c_int) -> Self {
+        if order == unsafe { ffi::RSMPI_ORDER_C } {
+            Order::C
+        } else {
+            Order::Fortran
+        }
+    }
+}
+
+/// The distribution of one dimension of a distributed array onto a dimension of a process grid
+///
+/// # Standard section(s)
+///
+/// 4.1.4
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Distribution {
+    /// Contiguous blocks of the given size, by default the size of the dimension divided by the
+    /// number of processes along it, rounded up.
+    Block(Option<Count>),
+    /// Blocks of the given size are dealt out to the processes in a round-robin fashion, by default
+    /// blocks of a single element.
+    Cyclic(Option<Count>),
+    /// The dimension is not distributed.
+    None,
+}
+
+impl Distribution {
+    /// The raw distribution and distribution argument understood by the MPI C API
+    fn as_raw(self) -> (c_int, c_int) {
+        let dflt_darg = unsafe { ffi::RSMPI_DISTRIBUTE_DFLT_DARG };
+        match self {
+            Distribution::Block(darg) => (
+                unsafe { ffi::RSMPI_DISTRIBUTE_BLOCK },
+                darg.unwrap_or(dflt_darg),
+            ),
+            Distribution::Cyclic(darg) => (
+                unsafe { ffi::RSMPI_DISTRIBUTE_CYCLIC },
+                darg.unwrap_or(dflt_darg),
+            ),
+            Distribution::None => (unsafe { ffi::RSMPI_DISTRIBUTE_NONE }, dflt_darg),
+        }
+    }
+
+    fn from_raw(distrib: c_int, darg: c_int) -> Self {
+        let darg = if darg == unsafe { ffi::RSMPI_DISTRIBUTE_DFLT_DARG } {
+            None
+        } else {
+            Some(darg)
+        };
+        if distrib == unsafe { ffi::RSMPI_DISTRIBUTE_BLOCK } {
+            Distribution::Block(darg)
+        } else if distrib == unsafe { ffi::RSMPI_DISTRIBUTE_CYCLIC } {
+            Distribution::Cyclic(darg)
+        } else {
+            Distribution::None
+        }
+    }
 }
 
 /// A user defined MPI datatype
@@ -364,19 +707,126 @@ impl UserDatatype {
         UncommittedUserDatatype::structured(blocklengths, displacements, types).commit()
     }
 
+    /// Construct a new datatype with the same type map as `oldtype` but with the lower bound
+    /// `lb` and the extent `extent` in bytes.
+    ///
+    /// This is used to control the spacing between consecutive elements of a datatype, e.g. to
+    /// scatter the columns of a matrix stored in row-major order.
+    ///
+    /// # Examples
+    /// See `examples/resized.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.7
+    pub fn resized<D>(oldtype: &D, lb: Address, extent: Address) -> UserDatatype
+    where
+        D: UncommittedDatatype,
+    {
+        UncommittedUserDatatype::resized(oldtype, lb, extent).commit()
+    }
+
+    /// Construct a new datatype describing the subarray of size `subsizes` starting at `starts`
+    /// of an n-dimensional array of size `sizes` with elements of `oldtype` stored in `order`.
+    ///
+    /// # Examples
+    /// See `examples/subarray.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.3
+    pub fn subarray<D>(
+        sizes: &[Count],
+        subsizes: &[Count],
+        starts: &[Count],
+        order: Order,
+        oldtype: &D,
+    ) -> UserDatatype
+    where
+        D: UncommittedDatatype,
+    {
+        UncommittedUserDatatype::subarray(sizes, subsizes, starts, order, oldtype).commit()
+    }
+
+    /// Construct a new datatype describing the part of process `rank` of an n-dimensional array
+    /// of size `gsizes` with elements of `oldtype` stored in `order`, which is distributed onto a
+    /// grid of `size` processes with dimensions `psizes` according to `distribs`.
+    ///
+    /// # Examples
+    /// See `examples/darray.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.4
+    #[allow(clippy::too_many_arguments)]
+    pub fn distributed_array<D>(
+        size: Count,
+        rank: Count,
+        gsizes: &[Count],
+        distribs: &[Distribution],
+        psizes: &[Count],
+        order: Order,
+        oldtype: &D,
+    ) -> UserDatatype
+    where
+        D: UncommittedDatatype,
+    {
+        UncommittedUserDatatype::distributed_array(
+            size, rank, gsizes, distribs, psizes, order, oldtype,
+        )
+        .commit()
+    }
+
     /// Creates a DatatypeRef from this datatype object.
     pub fn as_ref(&self) -> DatatypeRef<'_> {
         unsafe { DatatypeRef::from_raw(self.as_raw()) }
     }
+
+    /// Get the `DatatypeAttribute` cached on this datatype, or `None` if not set.
+    ///
+    /// # Examples
+    /// See `examples/attributes_datatype_window.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.4 (MPI_Type_get_attr)
+    pub fn get_attr<A: DatatypeAttribute>(&self) -> Option<&A> {
+        unsafe { get_type_attr(self.0) }
+    }
+
+    /// Cache a `DatatypeAttribute` on this datatype, replacing a previous value.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.4 (MPI_Type_set_attr)
+    pub fn set_attr<A: DatatypeAttribute>(&mut self, val: A) {
+        unsafe { set_type_attr(self.0, val) }
+    }
+
+    /// Delete the `DatatypeAttribute` cached on this datatype, if any.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.4 (MPI_Type_delete_attr)
+    pub fn delete_attr<A: DatatypeAttribute>(&mut self) {
+        unsafe { delete_type_attr::<A>(self.0) }
+    }
 }
 
-// TODO and NOTE: These impls are not 100% implemented, but reflect the larger reality that the
-// current API does not strongly enforce the threading requirements of MPI's `MPI_Init_thread`
-// function. In order to enforce uniformity across platforms, these traits are explicitly applied.
-// Due to the nature of MPI implementations, without this declaration some implementations of
-// MPI would otherwise be Send + Sync by default, and others would be !Sync by default.
-unsafe impl Send for UserDatatype {}
-unsafe impl Sync for UserDatatype {}
+// SAFETY: References to datatypes neither duplicate nor free the datatype, they are only read by
+// the operations they are passed to. Those are started through communicators and requests, which
+// only move between threads as `ThreadSafe` under `Threading::Multiple`. User datatypes are
+// duplicated when cloned and freed when dropped, so they stay on the thread that created them
+// unless they are wrapped in `ThreadSafe`.
+unsafe impl<'a> Send for DatatypeRef<'a> {}
+unsafe impl<'a> Sync for DatatypeRef<'a> {}
+unsafe impl<'a> Send for UncommittedDatatypeRef<'a> {}
+unsafe impl<'a> Sync for UncommittedDatatypeRef<'a> {}
+
+// Only constructed under `Threading::Multiple`, where any thread may duplicate and free the
+// datatype.
+unsafe impl Send for ThreadSafe<UserDatatype> {}
+unsafe impl Sync for ThreadSafe<UserDatatype> {}
 
 impl Clone for UserDatatype {
     fn clone(&self) -> Self {
@@ -386,6 +836,7 @@ impl Clone for UserDatatype {
 
 impl Drop for UserDatatype {
     fn drop(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_free"));
         unsafe {
             ffi::MPI_Type_free(&mut self.0);
         }
@@ -434,7 +885,7 @@ impl<'a> From<&'a UserDatatype> for UncommittedDatatypeRef<'a> {
 ///
 /// # Standard section(s)
 /// 4.1.9
-pub struct UncommittedUserDatatype(MPI_Datatype);
+pub struct UncommittedUserDatatype(MPI_Datatype, PhantomData<*const ()>);
 
 impl UncommittedUserDatatype {
     /// Constructs a new datatype by concatenating `count` repetitions of `oldtype`
@@ -449,12 +900,14 @@ impl UncommittedUserDatatype {
     where
         D: UncommittedDatatype,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_contiguous"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
                     ffi::MPI_Type_contiguous(count, oldtype.as_raw(), newtype)
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -472,12 +925,14 @@ impl UncommittedUserDatatype {
     where
         D: UncommittedDatatype,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_vector"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
                     ffi::MPI_Type_vector(count, blocklength, stride, oldtype.as_raw(), newtype)
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -496,6 +951,7 @@ impl UncommittedUserDatatype {
     where
         D: UncommittedDatatype,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_hvector"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
@@ -508,6 +964,7 @@ impl UncommittedUserDatatype {
                     )
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -529,6 +986,7 @@ impl UncommittedUserDatatype {
             "'blocklengths' and 'displacements' must be the same length"
         );
 
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_indexed"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
@@ -541,6 +999,7 @@ impl UncommittedUserDatatype {
                     )
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -565,6 +1024,7 @@ impl UncommittedUserDatatype {
             displacements.len(),
             "'blocklengths' and 'displacements' must be the same length"
         );
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_hindexed"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
@@ -577,6 +1037,7 @@ impl UncommittedUserDatatype {
                     )
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -590,6 +1051,7 @@ impl UncommittedUserDatatype {
     where
         D: UncommittedDatatype,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_indexed_block"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
@@ -602,6 +1064,7 @@ impl UncommittedUserDatatype {
                     )
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -620,6 +1083,7 @@ impl UncommittedUserDatatype {
     where
         D: UncommittedDatatype,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_hindexed_block"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
@@ -632,6 +1096,7 @@ impl UncommittedUserDatatype {
                     )
                 })
                 .1,
+                PhantomData,
             )
         }
     }
@@ -659,6 +1124,7 @@ impl UncommittedUserDatatype {
             "'displacements', 'blocklengths', and 'types' must be the same length"
         );
 
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_struct"));
         unsafe {
             UncommittedUserDatatype(
                 with_uninitialized(|newtype| {
@@ -671,6 +1137,160 @@ impl UncommittedUserDatatype {
                     )
                 })
                 .1,
+                PhantomData,
+            )
+        }
+    }
+
+    /// Construct a new datatype with the same type map as `oldtype` but with the lower bound
+    /// `lb` and the extent `extent` in bytes.
+    ///
+    /// This is used to control the spacing between consecutive elements of a datatype, e.g. to
+    /// scatter the columns of a matrix stored in row-major order.
+    ///
+    /// # Examples
+    /// See `examples/resized.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.7
+    pub fn resized<D>(oldtype: &D, lb: Address, extent: Address) -> Self
+    where
+        D: UncommittedDatatype,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_resized"));
+        unsafe {
+            UncommittedUserDatatype(
+                with_uninitialized(|newtype| {
+                    ffi::MPI_Type_create_resized(oldtype.as_raw(), lb, extent, newtype)
+                })
+                .1,
+                PhantomData,
+            )
+        }
+    }
+
+    /// Construct a new datatype describing the subarray of size `subsizes` starting at `starts`
+    /// of an n-dimensional array of size `sizes` with elements of `oldtype` stored in `order`.
+    ///
+    /// # Examples
+    /// See `examples/subarray.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.3
+    pub fn subarray<D>(
+        sizes: &[Count],
+        subsizes: &[Count],
+        starts: &[Count],
+        order: Order,
+        oldtype: &D,
+    ) -> Self
+    where
+        D: UncommittedDatatype,
+    {
+        assert_eq!(
+            sizes.len(),
+            subsizes.len(),
+            "'sizes', 'subsizes', and 'starts' must be the same length"
+        );
+        assert_eq!(
+            sizes.len(),
+            starts.len(),
+            "'sizes', 'subsizes', and 'starts' must be the same length"
+        );
+        assert!(
+            sizes
+                .iter()
+                .zip(subsizes)
+                .zip(starts)
+                .all(|((&size, &subsize), &start)| 0 <= start
+                    && 0 <= subsize
+                    && start + subsize <= size),
+            "The subarray must lie within the array"
+        );
+
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_subarray"));
+        unsafe {
+            UncommittedUserDatatype(
+                with_uninitialized(|newtype| {
+                    ffi::MPI_Type_create_subarray(
+                        sizes.count(),
+                        sizes.as_ptr(),
+                        subsizes.as_ptr(),
+                        starts.as_ptr(),
+                        order.as_raw(),
+                        oldtype.as_raw(),
+                        newtype,
+                    )
+                })
+                .1,
+                PhantomData,
+            )
+        }
+    }
+
+    /// Construct a new datatype describing the part of process `rank` of an n-dimensional array
+    /// of size `gsizes` with elements of `oldtype` stored in `order`, which is distributed onto a
+    /// grid of `size` processes with dimensions `psizes` according to `distribs`.
+    ///
+    /// # Examples
+    /// See `examples/darray.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.4
+    #[allow(clippy::too_many_arguments)]
+    pub fn distributed_array<D>(
+        size: Count,
+        rank: Count,
+        gsizes: &[Count],
+        distribs: &[Distribution],
+        psizes: &[Count],
+        order: Order,
+        oldtype: &D,
+    ) -> Self
+    where
+        D: UncommittedDatatype,
+    {
+        assert_eq!(
+            gsizes.len(),
+            distribs.len(),
+            "'gsizes', 'distribs', and 'psizes' must be the same length"
+        );
+        assert_eq!(
+            gsizes.len(),
+            psizes.len(),
+            "'gsizes', 'distribs', and 'psizes' must be the same length"
+        );
+        assert_eq!(
+            psizes.iter().product::<Count>(),
+            size,
+            "The process grid 'psizes' must contain 'size' processes"
+        );
+
+        let (distribs, dargs): (Vec<c_int>, Vec<c_int>) =
+            distribs.iter().map(|d| d.as_raw()).unzip();
+
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_create_darray"));
+        unsafe {
+            UncommittedUserDatatype(
+                with_uninitialized(|newtype| {
+                    ffi::MPI_Type_create_darray(
+                        size,
+                        rank,
+                        gsizes.count(),
+                        gsizes.as_ptr(),
+                        distribs.as_ptr(),
+                        dargs.as_ptr(),
+                        psizes.as_ptr(),
+                        order.as_raw(),
+                        oldtype.as_raw(),
+                        newtype,
+                    )
+                })
+                .1,
+                PhantomData,
             )
         }
     }
@@ -681,6 +1301,7 @@ impl UncommittedUserDatatype {
     /// 4.1.9
     pub fn commit(mut self) -> UserDatatype {
         let handle = self.0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_commit"));
         unsafe {
             ffi::MPI_Type_commit(&mut self.0);
         }
@@ -692,6 +1313,38 @@ impl UncommittedUserDatatype {
     pub fn as_ref(&self) -> UncommittedDatatypeRef<'_> {
         unsafe { UncommittedDatatypeRef::from_raw(self.as_raw()) }
     }
+
+    /// Get the `DatatypeAttribute` cached on this datatype, or `None` if not set.
+    ///
+    /// # Examples
+    /// See `examples/attributes_datatype_window.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.4 (MPI_Type_get_attr)
+    pub fn get_attr<A: DatatypeAttribute>(&self) -> Option<&A> {
+        unsafe { get_type_attr(self.0) }
+    }
+
+    /// Cache a `DatatypeAttribute` on this datatype, replacing a previous value.
+    ///
+    /// Attributes are kept when the datatype is committed.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.4 (MPI_Type_set_attr)
+    pub fn set_attr<A: DatatypeAttribute>(&mut self, val: A) {
+        unsafe { set_type_attr(self.0, val) }
+    }
+
+    /// Delete the `DatatypeAttribute` cached on this datatype, if any.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.4 (MPI_Type_delete_attr)
+    pub fn delete_attr<A: DatatypeAttribute>(&mut self) {
+        unsafe { delete_type_attr::<A>(self.0) }
+    }
 }
 
 impl Clone for UncommittedUserDatatype {
@@ -702,6 +1355,7 @@ impl Clone for UncommittedUserDatatype {
 
 impl Drop for UncommittedUserDatatype {
     fn drop(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_free"));
         unsafe {
             ffi::MPI_Type_free(&mut self.0);
         }
@@ -719,7 +1373,7 @@ unsafe impl AsRaw for UncommittedUserDatatype {
 impl FromRaw for UncommittedUserDatatype {
     unsafe fn from_raw(handle: MPI_Datatype) -> Self {
         assert_ne!(handle, ffi::RSMPI_DATATYPE_NULL);
-        UncommittedUserDatatype(handle)
+        UncommittedUserDatatype(handle, PhantomData)
     }
 }
 
@@ -735,12 +1389,49 @@ impl<'a> From<&'a UncommittedUserDatatype> for UncommittedDatatypeRef<'a> {
     }
 }
 
+unsafe fn get_type_attr<'a, A: DatatypeAttribute>(datatype: MPI_Datatype) -> Option<&'a A> {
+    let key = A::get_key();
+    let mut ptr: MaybeUninit<*mut A> = MaybeUninit::uninit();
+    let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_attr"));
+    let (_, flag) = with_uninitialized(|flag| {
+        ffi::MPI_Type_get_attr(
+            datatype,
+            key.as_raw(),
+            ptr.as_mut_ptr() as *mut c_void,
+            flag,
+        )
+    });
+    if flag == 0 {
+        None
+    } else {
+        // The attribute was boxed by `set_type_attr()`
+        ptr.assume_init().as_ref()
+    }
+}
+
+unsafe fn set_type_attr<A: DatatypeAttribute>(datatype: MPI_Datatype, val: A) {
+    let key = A::get_key();
+    let val = Box::new(val);
+    let _call = instrument::begin(|| Call::without_comm("MPI_Type_set_attr"));
+    ffi::MPI_Type_set_attr(datatype, key.as_raw(), Box::into_raw(val) as *mut c_void);
+}
+
+unsafe fn delete_type_attr<A: DatatypeAttribute>(datatype: MPI_Datatype) {
+    let key = A::get_key();
+    if get_type_attr::<A>(datatype).is_some() {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_delete_attr"));
+        ffi::MPI_Type_delete_attr(datatype, key.as_raw());
+    }
+}
+
 /// A Datatype describes the layout of messages in memory.
 ///
 /// `Datatype` always represents a committed datatype that can be immediately used for sending and
 /// receiving messages. `UncommittedDatatype` is used for datatypes that are possibly uncommitted.
 pub trait Datatype: UncommittedDatatype {}
 impl<'a, D> Datatype for &'a D where D: 'a + Datatype {}
+impl<D> Datatype for Rc<D> where D: Datatype + ?Sized {}
+impl<D> Datatype for Arc<D> where D: Datatype + ?Sized {}
 
 /// An UncommittedDatatype is a partial description of the layout of messages in memory which may
 /// not yet have been committed to an implementation-defined message format.
@@ -755,21 +1446,544 @@ pub trait UncommittedDatatype: AsRaw<Raw = MPI_Datatype> {
     /// # Standard section(s)
     /// 4.1.10
     fn dup(&self) -> Self::DuplicatedDatatype {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_dup"));
         unsafe {
             Self::DuplicatedDatatype::from_raw(
                 with_uninitialized(|newtype| ffi::MPI_Type_dup(self.as_raw(), newtype)).1,
             )
         }
     }
-}
-impl<'a, D> UncommittedDatatype for &'a D
-where
-    D: 'a + UncommittedDatatype,
-{
-    type DuplicatedDatatype = <D as UncommittedDatatype>::DuplicatedDatatype;
-}
 
-/// Something that has an associated datatype
+    /// The total size in bytes of the data described by the datatype, excluding any gaps.
+    ///
+    /// # Examples
+    /// See `examples/extent.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.5
+    fn size(&self) -> Count {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_size"));
+        unsafe { with_uninitialized(|size| ffi::MPI_Type_size(self.as_raw(), size)).1 }
+    }
+
+    /// The extent of the datatype in bytes, i.e. the distance between the starts of consecutive
+    /// elements of this datatype in a buffer.
+    ///
+    /// # Examples
+    /// See `examples/extent.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.7
+    fn extent(&self) -> Address {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_extent"));
+        unsafe {
+            with_uninitialized2(|lb, extent| ffi::MPI_Type_get_extent(self.as_raw(), lb, extent)).2
+        }
+    }
+
+    /// The lower bound of the datatype in bytes relative to the start of a buffer.
+    ///
+    /// # Examples
+    /// See `examples/extent.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.7
+    fn lower_bound(&self) -> Address {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_extent"));
+        unsafe {
+            with_uninitialized2(|lb, extent| ffi::MPI_Type_get_extent(self.as_raw(), lb, extent)).1
+        }
+    }
+
+    /// The extent in bytes of the memory actually accessed by the datatype, ignoring any
+    /// artificial bounds.
+    ///
+    /// # Examples
+    /// See `examples/extent.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.8
+    fn true_extent(&self) -> Address {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_true_extent"));
+        unsafe {
+            with_uninitialized2(|lb, extent| {
+                ffi::MPI_Type_get_true_extent(self.as_raw(), lb, extent)
+            })
+            .2
+        }
+    }
+
+    /// The lower bound in bytes of the memory actually accessed by the datatype, ignoring any
+    /// artificial bounds.
+    ///
+    /// # Examples
+    /// See `examples/extent.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.8
+    fn true_lower_bound(&self) -> Address {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_true_extent"));
+        unsafe {
+            with_uninitialized2(|lb, extent| {
+                ffi::MPI_Type_get_true_extent(self.as_raw(), lb, extent)
+            })
+            .1
+        }
+    }
+
+    /// The combiner used to construct the datatype and the number of arguments passed to it.
+    ///
+    /// # Examples
+    /// See `examples/datatype_contents.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.13
+    fn get_envelope(&self) -> Envelope {
+        let mut num_integers: Count = 0;
+        let mut num_addresses: Count = 0;
+        let mut num_datatypes: Count = 0;
+        let mut combiner: c_int = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_envelope"));
+        unsafe {
+            ffi::MPI_Type_get_envelope(
+                self.as_raw(),
+                &mut num_integers,
+                &mut num_addresses,
+                &mut num_datatypes,
+                &mut combiner,
+            );
+        }
+        Envelope {
+            combiner: Combiner::from_raw(combiner),
+            num_integers,
+            num_addresses,
+            num_datatypes,
+        }
+    }
+
+    /// Decodes the arguments that were passed to the constructor of the datatype.
+    ///
+    /// # Examples
+    /// See `examples/datatype_contents.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.13
+    fn get_contents(&self) -> Contents {
+        let envelope = self.get_envelope();
+        if envelope.combiner == Combiner::Named {
+            return Contents::Named;
+        }
+
+        let mut integers: Vec<c_int> = vec![0; count_of(envelope.num_integers)];
+        let mut addresses: Vec<Address> = vec![0; count_of(envelope.num_addresses)];
+        let mut datatypes: Vec<MPI_Datatype> =
+            vec![unsafe { ffi::RSMPI_DATATYPE_NULL }; count_of(envelope.num_datatypes)];
+        let _call = instrument::begin(|| Call::without_comm("MPI_Type_get_contents"));
+        unsafe {
+            ffi::MPI_Type_get_contents(
+                self.as_raw(),
+                envelope.num_integers,
+                envelope.num_addresses,
+                envelope.num_datatypes,
+                integers.as_mut_ptr(),
+                addresses.as_mut_ptr(),
+                datatypes.as_mut_ptr(),
+            );
+        }
+        let datatypes = datatypes
+            .into_iter()
+            .map(|datatype| unsafe { ConstituentDatatype::from_raw(datatype) })
+            .collect();
+
+        Contents::decode(envelope.combiner, integers, addresses, datatypes)
+    }
+}
+impl<'a, D> UncommittedDatatype for &'a D
+where
+    D: 'a + UncommittedDatatype,
+{
+    type DuplicatedDatatype = <D as UncommittedDatatype>::DuplicatedDatatype;
+}
+
+impl<D> UncommittedDatatype for Rc<D>
+where
+    D: UncommittedDatatype + ?Sized,
+{
+    type DuplicatedDatatype = <D as UncommittedDatatype>::DuplicatedDatatype;
+}
+
+impl<D> UncommittedDatatype for Arc<D>
+where
+    D: UncommittedDatatype + ?Sized,
+{
+    type DuplicatedDatatype = <D as UncommittedDatatype>::DuplicatedDatatype;
+}
+
+/// The combiner used to construct a datatype
+///
+/// # Standard section(s)
+/// 4.1.13
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Combiner {
+    /// A predefined datatype, e.g. `MPI_INT`
+    Named,
+    /// `MPI_Type_dup()`
+    Dup,
+    /// `MPI_Type_contiguous()`
+    Contiguous,
+    /// `MPI_Type_vector()`
+    Vector,
+    /// `MPI_Type_create_hvector()`
+    HeterogeneousVector,
+    /// `MPI_Type_indexed()`
+    Indexed,
+    /// `MPI_Type_create_hindexed()`
+    HeterogeneousIndexed,
+    /// `MPI_Type_create_indexed_block()`
+    IndexedBlock,
+    /// `MPI_Type_create_hindexed_block()`
+    HeterogeneousIndexedBlock,
+    /// `MPI_Type_create_struct()`
+    Structured,
+    /// `MPI_Type_create_subarray()`
+    Subarray,
+    /// `MPI_Type_create_darray()`
+    DistributedArray,
+    /// `MPI_Type_create_resized()`
+    Resized,
+    /// Any other combiner, e.g. `MPI_COMBINER_F90_REAL`, given as its raw value
+    Other(c_int),
+}
+
+impl Combiner {
+    fn from_raw(combiner: c_int) -> Self {
+        let combiners = unsafe {
+            [
+                (ffi::RSMPI_COMBINER_NAMED, Combiner::Named),
+                (ffi::RSMPI_COMBINER_DUP, Combiner::Dup),
+                (ffi::RSMPI_COMBINER_CONTIGUOUS, Combiner::Contiguous),
+                (ffi::RSMPI_COMBINER_VECTOR, Combiner::Vector),
+                (ffi::RSMPI_COMBINER_HVECTOR, Combiner::HeterogeneousVector),
+                (ffi::RSMPI_COMBINER_INDEXED, Combiner::Indexed),
+                (ffi::RSMPI_COMBINER_HINDEXED, Combiner::HeterogeneousIndexed),
+                (ffi::RSMPI_COMBINER_INDEXED_BLOCK, Combiner::IndexedBlock),
+                (
+                    ffi::RSMPI_COMBINER_HINDEXED_BLOCK,
+                    Combiner::HeterogeneousIndexedBlock,
+                ),
+                (ffi::RSMPI_COMBINER_STRUCT, Combiner::Structured),
+                (ffi::RSMPI_COMBINER_SUBARRAY, Combiner::Subarray),
+                (ffi::RSMPI_COMBINER_DARRAY, Combiner::DistributedArray),
+                (ffi::RSMPI_COMBINER_RESIZED, Combiner::Resized),
+            ]
+        };
+        combiners
+            .iter()
+            .find(|&&(raw, _)| raw == combiner)
+            .map_or(Combiner::Other(combiner), |&(_, combiner)| combiner)
+    }
+}
+
+/// The combiner used to construct a datatype and the number of arguments passed to it
+///
+/// # Standard section(s)
+/// 4.1.13
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Envelope {
+    /// The combiner used to construct the datatype
+    pub combiner: Combiner,
+    /// The number of integer arguments passed to the combiner
+    pub num_integers: Count,
+    /// The number of address arguments passed to the combiner
+    pub num_addresses: Count,
+    /// The number of datatype arguments passed to the combiner
+    pub num_datatypes: Count,
+}
+
+/// A datatype that was used in the construction of another datatype
+///
+/// Predefined datatypes are returned as references, derived datatypes are returned as new datatype
+/// objects which are freed when dropped.
+pub enum ConstituentDatatype {
+    /// A predefined datatype
+    Named(SystemDatatype),
+    /// A derived datatype
+    Derived(UncommittedUserDatatype),
+}
+
+impl ConstituentDatatype {
+    /// # Safety
+    ///
+    /// `datatype` must be a datatype handle returned from `MPI_Type_get_contents()`.
+    unsafe fn from_raw(datatype: MPI_Datatype) -> Self {
+        let datatype_ref = DatatypeRef::from_raw(datatype);
+        if datatype_ref.get_envelope().combiner == Combiner::Named {
+            ConstituentDatatype::Named(datatype_ref)
+        } else {
+            ConstituentDatatype::Derived(UncommittedUserDatatype::from_raw(datatype))
+        }
+    }
+}
+
+unsafe impl AsRaw for ConstituentDatatype {
+    type Raw = MPI_Datatype;
+    fn as_raw(&self) -> Self::Raw {
+        match self {
+            ConstituentDatatype::Named(datatype) => datatype.as_raw(),
+            ConstituentDatatype::Derived(datatype) => datatype.as_raw(),
+        }
+    }
+}
+
+impl UncommittedDatatype for ConstituentDatatype {
+    type DuplicatedDatatype = UncommittedUserDatatype;
+}
+
+/// The arguments that were passed to the constructor of a datatype
+///
+/// The variants mirror the datatype constructors of `UserDatatype`.
+///
+/// # Standard section(s)
+/// 4.1.13
+pub enum Contents {
+    /// A predefined datatype which was not constructed from other datatypes
+    Named,
+    /// A duplicate of `oldtype`, see `UncommittedDatatype::dup()`
+    Dup {
+        /// The duplicated datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::contiguous()`
+    Contiguous {
+        /// The number of repetitions of `oldtype`
+        count: Count,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::vector()`
+    Vector {
+        /// The number of blocks
+        count: Count,
+        /// The number of elements per block
+        blocklength: Count,
+        /// The distance between the starts of consecutive blocks in elements
+        stride: Count,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::heterogeneous_vector()`
+    HeterogeneousVector {
+        /// The number of blocks
+        count: Count,
+        /// The number of elements per block
+        blocklength: Count,
+        /// The distance between the starts of consecutive blocks in bytes
+        stride: Address,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::indexed()`
+    Indexed {
+        /// The number of elements of each block
+        blocklengths: Vec<Count>,
+        /// The displacement of each block in elements
+        displacements: Vec<Count>,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::heterogeneous_indexed()`
+    HeterogeneousIndexed {
+        /// The number of elements of each block
+        blocklengths: Vec<Count>,
+        /// The displacement of each block in bytes
+        displacements: Vec<Address>,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::indexed_block()`
+    IndexedBlock {
+        /// The number of elements per block
+        blocklength: Count,
+        /// The displacement of each block in elements
+        displacements: Vec<Count>,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::heterogeneous_indexed_block()`
+    HeterogeneousIndexedBlock {
+        /// The number of elements per block
+        blocklength: Count,
+        /// The displacement of each block in bytes
+        displacements: Vec<Address>,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::structured()`
+    Structured {
+        /// The number of elements of each block
+        blocklengths: Vec<Count>,
+        /// The displacement of each block in bytes
+        displacements: Vec<Address>,
+        /// The element datatype of each block
+        types: Vec<ConstituentDatatype>,
+    },
+    /// See `UserDatatype::subarray()`
+    Subarray {
+        /// The size of the full array
+        sizes: Vec<Count>,
+        /// The size of the subarray
+        subsizes: Vec<Count>,
+        /// The start of the subarray
+        starts: Vec<Count>,
+        /// The storage order of the array
+        order: Order,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::distributed_array()`
+    DistributedArray {
+        /// The number of processes in the process grid
+        size: Count,
+        /// The rank of the process owning the part described by the datatype
+        rank: Count,
+        /// The size of the global array
+        gsizes: Vec<Count>,
+        /// The distribution of each dimension
+        distribs: Vec<Distribution>,
+        /// The size of the process grid
+        psizes: Vec<Count>,
+        /// The storage order of the array
+        order: Order,
+        /// The element datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// See `UserDatatype::resized()`
+    Resized {
+        /// The new lower bound in bytes
+        lb: Address,
+        /// The new extent in bytes
+        extent: Address,
+        /// The resized datatype
+        oldtype: ConstituentDatatype,
+    },
+    /// Any other combiner with its raw arguments in the order defined by the MPI standard
+    Other {
+        /// The raw combiner
+        combiner: c_int,
+        /// The integer arguments
+        integers: Vec<c_int>,
+        /// The address arguments
+        addresses: Vec<Address>,
+        /// The datatype arguments
+        datatypes: Vec<ConstituentDatatype>,
+    },
+}
+
+impl Contents {
+    fn decode(
+        combiner: Combiner,
+        integers: Vec<c_int>,
+        addresses: Vec<Address>,
+        mut datatypes: Vec<ConstituentDatatype>,
+    ) -> Self {
+        let i = &integers[..];
+        match combiner {
+            Combiner::Named => Contents::Named,
+            Combiner::Dup => Contents::Dup {
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::Contiguous => Contents::Contiguous {
+                count: i[0],
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::Vector => Contents::Vector {
+                count: i[0],
+                blocklength: i[1],
+                stride: i[2],
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::HeterogeneousVector => Contents::HeterogeneousVector {
+                count: i[0],
+                blocklength: i[1],
+                stride: addresses[0],
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::Indexed => {
+                let n = count_of(i[0]);
+                Contents::Indexed {
+                    blocklengths: i[1..=n].to_vec(),
+                    displacements: i[n + 1..2 * n + 1].to_vec(),
+                    oldtype: datatypes.remove(0),
+                }
+            }
+            Combiner::HeterogeneousIndexed => Contents::HeterogeneousIndexed {
+                blocklengths: i[1..].to_vec(),
+                displacements: addresses,
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::IndexedBlock => Contents::IndexedBlock {
+                blocklength: i[1],
+                displacements: i[2..].to_vec(),
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::HeterogeneousIndexedBlock => Contents::HeterogeneousIndexedBlock {
+                blocklength: i[1],
+                displacements: addresses,
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::Structured => Contents::Structured {
+                blocklengths: i[1..].to_vec(),
+                displacements: addresses,
+                types: datatypes,
+            },
+            Combiner::Subarray => {
+                let n = count_of(i[0]);
+                Contents::Subarray {
+                    sizes: i[1..=n].to_vec(),
+                    subsizes: i[n + 1..2 * n + 1].to_vec(),
+                    starts: i[2 * n + 1..3 * n + 1].to_vec(),
+                    order: Order::from_raw(i[3 * n + 1]),
+                    oldtype: datatypes.remove(0),
+                }
+            }
+            Combiner::DistributedArray => {
+                let n = count_of(i[2]);
+                Contents::DistributedArray {
+                    size: i[0],
+                    rank: i[1],
+                    gsizes: i[3..n + 3].to_vec(),
+                    distribs: i[n + 3..2 * n + 3]
+                        .iter()
+                        .zip(&i[2 * n + 3..3 * n + 3])
+                        .map(|(&distrib, &darg)| Distribution::from_raw(distrib, darg))
+                        .collect(),
+                    psizes: i[3 * n + 3..4 * n + 3].to_vec(),
+                    order: Order::from_raw(i[4 * n + 3]),
+                    oldtype: datatypes.remove(0),
+                }
+            }
+            Combiner::Resized => Contents::Resized {
+                lb: addresses[0],
+                extent: addresses[1],
+                oldtype: datatypes.remove(0),
+            },
+            Combiner::Other(combiner) => Contents::Other {
+                combiner,
+                integers,
+                addresses,
+                datatypes,
+            },
+        }
+    }
+}
+
+fn count_of(count: c_int) -> usize {
+    count
+        .value_as()
+        .expect("Datatype contents contain a negative count.")
+}
+
+/// Something that has an associated datatype
 pub unsafe trait AsDatatype {
     /// The type of the associated MPI datatype (e.g. `SystemDatatype` or `UserDatatype`)
     type Out: Datatype;
@@ -807,6 +2021,16 @@ where
     }
 }
 
+unsafe impl<T> AsDatatype for Box<[T]>
+where
+    T: Equivalence,
+{
+    type Out = <T as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        <T as Equivalence>::equivalent_datatype()
+    }
+}
+
 unsafe impl<T, const D: usize> AsDatatype for [T; D]
 where
     T: Equivalence,
@@ -819,6 +2043,38 @@ where
 
 #[doc(hidden)]
 pub mod internal {
+    use super::{DatatypeRef, UserDatatype};
+    use crate::raw::traits::*;
+
+    /// A committed datatype kept in a `static` by `#[derive(Equivalence)]` or
+    /// `cached_datatype()`
+    pub struct StaticDatatype(UserDatatype);
+
+    impl StaticDatatype {
+        /// Keep `datatype` for the rest of the program.
+        pub fn new(datatype: UserDatatype) -> Self {
+            StaticDatatype(datatype)
+        }
+
+        /// The kept datatype
+        pub fn as_ref(&self) -> DatatypeRef<'_> {
+            self.0.as_ref()
+        }
+    }
+
+    // SAFETY: The datatype is never duplicated. It is only read through the references handed
+    // out, see `DatatypeRef`, and never freed when kept in a `static`, or freed by
+    // `free_cached_datatypes()` on the thread that finalizes MPI.
+    unsafe impl Send for StaticDatatype {}
+    unsafe impl Sync for StaticDatatype {}
+
+    unsafe impl AsRaw for StaticDatatype {
+        type Raw = crate::ffi::MPI_Datatype;
+        fn as_raw(&self) -> Self::Raw {
+            self.0.as_raw()
+        }
+    }
+
     #[cfg(feature = "derive")]
     pub fn check_derive_equivalence_universe_state(type_name: &str) {
         use crate::environment::UNIVERSE_STATE;
@@ -909,6 +2165,17 @@ where
     }
 }
 
+unsafe impl<T> Collection for Box<[T]>
+where
+    T: Equivalence,
+{
+    fn count(&self) -> Count {
+        self.len()
+            .value_as()
+            .expect("Length of slice cannot be expressed as an MPI Count.")
+    }
+}
+
 unsafe impl<T, const D: usize> Collection for [T; D]
 where
     T: Equivalence,
@@ -954,6 +2221,15 @@ where
     }
 }
 
+unsafe impl<T> Pointer for Box<[T]>
+where
+    T: Equivalence,
+{
+    fn pointer(&self) -> *const c_void {
+        self.as_ptr() as _
+    }
+}
+
 unsafe impl<T, const D: usize> Pointer for [T; D]
 where
     T: Equivalence,
@@ -997,6 +2273,15 @@ where
     }
 }
 
+unsafe impl<T> PointerMut for Box<[T]>
+where
+    T: Equivalence,
+{
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.as_mut_ptr() as _
+    }
+}
+
 unsafe impl<T, const D: usize> PointerMut for [T; D]
 where
     T: Equivalence,
@@ -1012,6 +2297,7 @@ pub unsafe trait Buffer: Pointer + Collection + AsDatatype {}
 unsafe impl<T> Buffer for T where T: Equivalence {}
 unsafe impl<T> Buffer for [T] where T: Equivalence {}
 unsafe impl<T> Buffer for Vec<T> where T: Equivalence {}
+unsafe impl<T> Buffer for Box<[T]> where T: Equivalence {}
 unsafe impl<T, const D: usize> Buffer for [T; D] where T: Equivalence {}
 
 /// A mutable buffer is a region in memory that starts at `pointer_mut()` and contains `count()`
@@ -1020,8 +2306,99 @@ pub unsafe trait BufferMut: PointerMut + Collection + AsDatatype {}
 unsafe impl<T> BufferMut for T where T: Equivalence {}
 unsafe impl<T> BufferMut for [T] where T: Equivalence {}
 unsafe impl<T> BufferMut for Vec<T> where T: Equivalence {}
+unsafe impl<T> BufferMut for Box<[T]> where T: Equivalence {}
 unsafe impl<T, const D: usize> BufferMut for [T; D] where T: Equivalence {}
 
+// Every `char` is a valid `u32`, but not vice versa, so `char`s are sent as `u32`s but are not
+// `Equivalence`. They have to be received into `u32` buffers and converted with
+// `char::from_u32()`, as `Source::receive_char()` does.
+unsafe impl AsDatatype for char {
+    type Out = SystemDatatype;
+    fn as_datatype(&self) -> Self::Out {
+        u32::equivalent_datatype()
+    }
+}
+
+unsafe impl AsDatatype for [char] {
+    type Out = SystemDatatype;
+    fn as_datatype(&self) -> Self::Out {
+        u32::equivalent_datatype()
+    }
+}
+
+unsafe impl Collection for char {
+    fn count(&self) -> Count {
+        1
+    }
+}
+
+unsafe impl Collection for [char] {
+    fn count(&self) -> Count {
+        self.len()
+            .value_as()
+            .expect("Length of slice cannot be expressed as an MPI Count.")
+    }
+}
+
+unsafe impl Pointer for char {
+    fn pointer(&self) -> *const c_void {
+        let p: *const char = self;
+        p as *const c_void
+    }
+}
+
+unsafe impl Pointer for [char] {
+    fn pointer(&self) -> *const c_void {
+        self.as_ptr() as _
+    }
+}
+
+unsafe impl Buffer for char {}
+unsafe impl Buffer for [char] {}
+
+/// A buffer that contains nothing, e.g. for messages that only signal an event
+///
+/// Slices of length zero are valid buffers as well, `Empty` spares choosing an element type for
+/// them. Its pointer refers to a valid static location, `count()` is zero and the datatype is
+/// `MPI_UINT8_T`.
+///
+/// # Examples
+/// See `examples/empty.rs`
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Empty;
+
+/// Storage that the pointer of `Empty` refers to, it is never read or written.
+static EMPTY_STORAGE: u8 = 0;
+
+unsafe impl Collection for Empty {
+    fn count(&self) -> Count {
+        0
+    }
+}
+
+unsafe impl Pointer for Empty {
+    fn pointer(&self) -> *const c_void {
+        ptr::addr_of!(EMPTY_STORAGE) as _
+    }
+}
+
+unsafe impl PointerMut for Empty {
+    fn pointer_mut(&mut self) -> *mut c_void {
+        // MPI does not access the memory of buffers with a count of zero.
+        ptr::addr_of!(EMPTY_STORAGE) as *mut c_void
+    }
+}
+
+unsafe impl AsDatatype for Empty {
+    type Out = <u8 as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        u8::equivalent_datatype()
+    }
+}
+
+unsafe impl Buffer for Empty {}
+unsafe impl BufferMut for Empty {}
+
 /// An immutable dynamically-typed buffer.
 ///
 /// The buffer has a definite length and MPI datatype, but it is not yet known which Rust type it
@@ -1249,8 +2626,9 @@ impl<'a> DynBufferMut<'a> {
 /// # Safety
 ///
 /// Views can be used to instruct the underlying MPI library to rummage around at arbitrary
-/// locations in memory. This might be controlled later on using datatype bounds an slice lengths
-/// but for now, all View constructors are marked `unsafe`.
+/// locations in memory. Views of slices can be created safely with `View::new()`, which checks
+/// the memory accessed by the datatype against the bounds of the slice, all other constructors
+/// are marked `unsafe`.
 pub struct View<'d, 'b, D, B: ?Sized>
 where
     D: 'd + Datatype,
@@ -1287,6 +2665,30 @@ where
     }
 }
 
+impl<'d, 'b, D, T> View<'d, 'b, D, [T]>
+where
+    D: 'd + Datatype,
+    T: 'b + Equivalence,
+{
+    /// Return a view of the slice `buffer` containing `count` instances of MPI datatype
+    /// `datatype`.
+    ///
+    /// # Examples
+    /// See `examples/contiguous.rs`, `examples/vector.rs`
+    ///
+    /// # Panics
+    /// If the `count` instances of `datatype`, spaced by its extent, access memory outside of
+    /// `buffer`, as determined by the true extent of `datatype`.
+    pub fn new(buffer: &'b [T], count: Count, datatype: &'d D) -> View<'d, 'b, D, [T]> {
+        assert_view_in_bounds(datatype, count, mem::size_of_val(buffer));
+        View {
+            datatype,
+            count,
+            buffer,
+        }
+    }
+}
+
 unsafe impl<'d, 'b, D, B: ?Sized> AsDatatype for View<'d, 'b, D, B>
 where
     D: 'd + Datatype,
@@ -1330,8 +2732,9 @@ where
 /// # Safety
 ///
 /// Views can be used to instruct the underlying MPI library to rummage around at arbitrary
-/// locations in memory. This might be controlled later on using datatype bounds an slice lengths
-/// but for now, all View constructors are marked `unsafe`.
+/// locations in memory. Views of slices of `AnyBitPattern` elements can be created safely with
+/// `MutView::new()`, which checks the memory accessed by the datatype against the bounds of the
+/// slice, all other constructors are marked `unsafe`.
 pub struct MutView<'d, 'b, D, B: ?Sized>
 where
     D: 'd + Datatype,
@@ -1368,18 +2771,106 @@ where
     }
 }
 
-unsafe impl<'d, 'b, D, B: ?Sized> AsDatatype for MutView<'d, 'b, D, B>
+impl<'d, 'b, D, T> MutView<'d, 'b, D, [T]>
 where
     D: 'd + Datatype,
-    B: 'b + PointerMut,
+    T: 'b + AnyBitPattern,
 {
-    type Out = &'d D;
-    fn as_datatype(&self) -> Self::Out {
-        self.datatype
+    /// Return a view of the slice `buffer` containing `count` instances of MPI datatype
+    /// `datatype`.
+    ///
+    /// The datatype may write arbitrary bytes into the elements of `buffer`, so they have to be
+    /// `AnyBitPattern`.
+    ///
+    /// # Examples
+    /// See `examples/contiguous.rs`, `examples/vector.rs`
+    ///
+    /// # Panics
+    /// If the `count` instances of `datatype`, spaced by its extent, access memory outside of
+    /// `buffer`, as determined by the true extent of `datatype`.
+    pub fn new(buffer: &'b mut [T], count: Count, datatype: &'d D) -> MutView<'d, 'b, D, [T]> {
+        assert_view_in_bounds(datatype, count, mem::size_of_val(buffer));
+        MutView {
+            datatype,
+            count,
+            buffer,
+        }
     }
 }
 
-unsafe impl<'d, 'b, D, B: ?Sized> Collection for MutView<'d, 'b, D, B>
+/// The range of bytes accessed by `count` instances of `datatype`, relative to the start of the
+/// buffer, or `None` if `count` is zero
+///
+/// Computed in i128 so that large counts and extents cannot overflow.
+fn accessed_bytes<D>(datatype: &D, count: Count) -> Option<(i128, i128)>
+where
+    D: Datatype + ?Sized,
+{
+    if count == 0 {
+        return None;
+    }
+    let last = i128::from(count - 1) * datatype.extent() as i128;
+    let true_lower_bound = datatype.true_lower_bound() as i128;
+    let start = true_lower_bound + last.min(0);
+    let end = true_lower_bound + datatype.true_extent() as i128 + last.max(0);
+    Some((start, end))
+}
+
+/// Checks that `count` instances of `datatype` located `displ` bytes from the start of a buffer
+/// only access its first `len` bytes.
+fn assert_partition_in_bounds<D>(datatype: &D, count: Count, displ: Count, len: usize)
+where
+    D: Datatype + ?Sized,
+{
+    assert!(count >= 0, "Partition count must not be negative.");
+    let Some((start, end)) = accessed_bytes(datatype, count) else {
+        return;
+    };
+    let (start, end) = (start + i128::from(displ), end + i128::from(displ));
+    assert!(
+        start >= 0 && end <= len as i128,
+        "Partition of {} instances of the datatype at byte {} accesses bytes {} to {}, outside of \
+         the buffer of {} bytes.",
+        count,
+        displ,
+        start,
+        end,
+        len
+    );
+}
+
+/// Checks that `count` instances of `datatype` only access the first `len` bytes of a buffer.
+fn assert_view_in_bounds<D>(datatype: &D, count: Count, len: usize)
+where
+    D: Datatype + ?Sized,
+{
+    assert!(count >= 0, "View count must not be negative.");
+    let Some((start, end)) = accessed_bytes(datatype, count) else {
+        return;
+    };
+    assert!(
+        start >= 0 && end <= len as i128,
+        "View of {} instances of the datatype accesses bytes {} to {}, outside of the buffer of {} \
+         bytes.",
+        count,
+        start,
+        end,
+        len
+    );
+}
+
+unsafe impl<'d, 'b, D, B: ?Sized> AsDatatype for MutView<'d, 'b, D, B>
+where
+    D: 'd + Datatype,
+    B: 'b + PointerMut,
+{
+    type Out = &'d D;
+    fn as_datatype(&self) -> Self::Out {
+        self.datatype
+    }
+}
+
+unsafe impl<'d, 'b, D, B: ?Sized> Collection for MutView<'d, 'b, D, B>
 where
     D: 'd + Datatype,
     B: 'b + PointerMut,
@@ -1406,6 +2897,140 @@ where
 {
 }
 
+/// Number of elements described by each block of a `LargeSlice`
+const LARGE_CHUNK: usize = 1 << 30;
+
+/// Describe `len` consecutive elements of type `T` with a single instance of a datatype.
+///
+/// The elements are split into blocks of `LARGE_CHUNK` elements followed by the remainder, so
+/// `len` does not have to fit into a `Count`.
+fn large_contiguous<T: Equivalence>(len: usize) -> UserDatatype {
+    let element = T::equivalent_datatype();
+    let chunk = UncommittedUserDatatype::contiguous(
+        LARGE_CHUNK
+            .value_as()
+            .expect("Chunk length cannot be expressed as an MPI Count."),
+        &element,
+    );
+    let chunks = len / LARGE_CHUNK;
+    let types = unsafe {
+        [
+            UncommittedDatatypeRef::from_raw(chunk.as_raw()),
+            UncommittedDatatypeRef::from_raw(element.as_raw()),
+        ]
+    };
+    UserDatatype::structured(
+        &[
+            chunks
+                .value_as()
+                .expect("Number of chunks cannot be expressed as an MPI Count."),
+            (len % LARGE_CHUNK)
+                .value_as()
+                .expect("Remainder cannot be expressed as an MPI Count."),
+        ],
+        &[
+            0,
+            (chunks * LARGE_CHUNK * mem::size_of::<T>())
+                .value_as()
+                .expect("Offset of remainder cannot be expressed as an MPI Address."),
+        ],
+        &types,
+    )
+}
+
+/// A slice that may contain more elements than fit into a `Count`
+///
+/// Regular slices report their length as the `Count` of elements, which limits them to
+/// `Count::MAX` elements. A `LargeSlice` instead describes the whole slice with a single instance
+/// of a derived datatype, so it can be used with all operations taking a `Buffer`, even if the
+/// MPI library does not provide the large count (`_c`) functions of MPI-4.
+///
+/// The count of elements received into a `LargeSliceMut` cannot be queried from the `Status` if it
+/// does not fit into a `Count` either.
+///
+/// # Examples
+/// See `examples/large_count.rs`
+///
+/// # Standard section(s)
+///
+/// 4.1.2
+pub struct LargeSlice<'b, T>(&'b [T]);
+
+impl<'b, T: Equivalence> LargeSlice<'b, T> {
+    /// Describe `buffer` as a single element of a derived datatype.
+    pub fn new(buffer: &'b [T]) -> Self {
+        LargeSlice(buffer)
+    }
+}
+
+unsafe impl<'b, T: Equivalence> AsDatatype for LargeSlice<'b, T> {
+    type Out = UserDatatype;
+    fn as_datatype(&self) -> Self::Out {
+        large_contiguous::<T>(self.0.len())
+    }
+}
+
+unsafe impl<'b, T: Equivalence> Collection for LargeSlice<'b, T> {
+    fn count(&self) -> Count {
+        1
+    }
+}
+
+unsafe impl<'b, T: Equivalence> Pointer for LargeSlice<'b, T> {
+    fn pointer(&self) -> *const c_void {
+        self.0.as_ptr() as _
+    }
+}
+
+unsafe impl<'b, T: Equivalence> Buffer for LargeSlice<'b, T> {}
+
+/// A mutable slice that may contain more elements than fit into a `Count`
+///
+/// See `LargeSlice`.
+///
+/// # Examples
+/// See `examples/large_count.rs`
+///
+/// # Standard section(s)
+///
+/// 4.1.2
+pub struct LargeSliceMut<'b, T>(&'b mut [T]);
+
+impl<'b, T: Equivalence> LargeSliceMut<'b, T> {
+    /// Describe `buffer` as a single element of a derived datatype.
+    pub fn new(buffer: &'b mut [T]) -> Self {
+        LargeSliceMut(buffer)
+    }
+}
+
+unsafe impl<'b, T: Equivalence> AsDatatype for LargeSliceMut<'b, T> {
+    type Out = UserDatatype;
+    fn as_datatype(&self) -> Self::Out {
+        large_contiguous::<T>(self.0.len())
+    }
+}
+
+unsafe impl<'b, T: Equivalence> Collection for LargeSliceMut<'b, T> {
+    fn count(&self) -> Count {
+        1
+    }
+}
+
+unsafe impl<'b, T: Equivalence> Pointer for LargeSliceMut<'b, T> {
+    fn pointer(&self) -> *const c_void {
+        self.0.as_ptr() as _
+    }
+}
+
+unsafe impl<'b, T: Equivalence> PointerMut for LargeSliceMut<'b, T> {
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.0.as_mut_ptr() as _
+    }
+}
+
+unsafe impl<'b, T: Equivalence> Buffer for LargeSliceMut<'b, T> {}
+unsafe impl<'b, T: Equivalence> BufferMut for LargeSliceMut<'b, T> {}
+
 /// Describes how a `Buffer` is partitioned by specifying the count of elements and displacement
 /// from the start of the buffer for each partition.
 pub trait Partitioned {
@@ -1563,6 +3188,244 @@ where
 {
 }
 
+/// Describes how a buffer is partitioned into parts of potentially different datatypes by
+/// specifying the count of elements, the displacement in bytes from the start of the buffer and
+/// the datatype of each partition.
+pub trait HeterogeneousPartitioned {
+    /// The count of elements in each partition.
+    fn counts(&self) -> &[Count];
+    /// The displacement in bytes from the start of the buffer for each partition.
+    fn byte_displs(&self) -> &[Count];
+    /// The datatype of the elements in each partition.
+    fn datatypes(&self) -> &[MPI_Datatype];
+}
+
+/// A buffer that is `HeterogeneousPartitioned`
+pub trait HeterogeneousPartitionedBuffer: HeterogeneousPartitioned + Pointer {}
+
+/// A mutable buffer that is `HeterogeneousPartitioned`
+pub trait HeterogeneousPartitionedBufferMut: HeterogeneousPartitioned + PointerMut {}
+
+/// Adds a partitioning with a datatype per partition to an existing `Pointer` so that it becomes
+/// `HeterogeneousPartitioned`
+pub struct HeterogeneousPartition<'b, 'd, B: 'b + ?Sized, C, D> {
+    buf: &'b B,
+    counts: C,
+    displs: D,
+    datatypes: Vec<MPI_Datatype>,
+    phantom: PhantomData<DatatypeRef<'d>>,
+}
+
+impl<'b, 'd, B: ?Sized, C, D> HeterogeneousPartition<'b, 'd, B, C, D>
+where
+    B: 'b + Pointer,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+    /// Partition `buf` using `counts`, `displs` in bytes and `datatypes` without checking the
+    /// partitions against the bounds of `buf`
+    ///
+    /// # Safety
+    ///
+    /// For every partition `i`, `counts[i]` elements of `datatypes[i]` located `displs[i]` bytes
+    /// from the start of `buf` must lie within `buf`.
+    pub unsafe fn new_unchecked<T>(buf: &'b B, counts: C, displs: D, datatypes: &'d [T]) -> Self
+    where
+        T: Datatype,
+    {
+        assert_eq!(counts.borrow().len(), displs.borrow().len());
+        assert_eq!(counts.borrow().len(), datatypes.len());
+
+        HeterogeneousPartition {
+            buf,
+            counts,
+            displs,
+            datatypes: datatypes.iter().map(|d| d.as_raw()).collect(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'b, 'd, T, C, D> HeterogeneousPartition<'b, 'd, [T], C, D>
+where
+    T: 'b + Equivalence,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+    /// Partition the slice `buf` using `counts`, `displs` in bytes and `datatypes`
+    ///
+    /// # Examples
+    /// See `examples/all_to_all_varcount.rs`
+    ///
+    /// # Panics
+    /// If `counts`, `displs` and `datatypes` differ in length or if a partition accesses memory
+    /// outside of `buf`, as determined by the true extent of its datatype.
+    pub fn new<U>(buf: &'b [T], counts: C, displs: D, datatypes: &'d [U]) -> Self
+    where
+        U: Datatype,
+    {
+        assert_eq!(counts.borrow().len(), displs.borrow().len());
+        assert_eq!(counts.borrow().len(), datatypes.len());
+        let len = mem::size_of_val(buf);
+        for ((&count, &displ), datatype) in counts
+            .borrow()
+            .iter()
+            .zip(displs.borrow().iter())
+            .zip(datatypes)
+        {
+            assert_partition_in_bounds(datatype, count, displ, len);
+        }
+        unsafe { Self::new_unchecked(buf, counts, displs, datatypes) }
+    }
+}
+
+unsafe impl<'b, 'd, B: ?Sized, C, D> Pointer for HeterogeneousPartition<'b, 'd, B, C, D>
+where
+    B: 'b + Pointer,
+{
+    fn pointer(&self) -> *const c_void {
+        self.buf.pointer()
+    }
+}
+
+impl<'b, 'd, B: ?Sized, C, D> HeterogeneousPartitioned for HeterogeneousPartition<'b, 'd, B, C, D>
+where
+    B: 'b,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+    fn counts(&self) -> &[Count] {
+        self.counts.borrow()
+    }
+    fn byte_displs(&self) -> &[Count] {
+        self.displs.borrow()
+    }
+    fn datatypes(&self) -> &[MPI_Datatype] {
+        &self.datatypes
+    }
+}
+
+impl<'b, 'd, B: ?Sized, C, D> HeterogeneousPartitionedBuffer
+    for HeterogeneousPartition<'b, 'd, B, C, D>
+where
+    B: 'b + Pointer,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+}
+
+/// Adds a partitioning with a datatype per partition to an existing `PointerMut` so that it
+/// becomes `HeterogeneousPartitioned`
+pub struct HeterogeneousPartitionMut<'b, 'd, B: 'b + ?Sized, C, D> {
+    buf: &'b mut B,
+    counts: C,
+    displs: D,
+    datatypes: Vec<MPI_Datatype>,
+    phantom: PhantomData<DatatypeRef<'d>>,
+}
+
+impl<'b, 'd, B: ?Sized, C, D> HeterogeneousPartitionMut<'b, 'd, B, C, D>
+where
+    B: 'b + PointerMut,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+    /// Partition `buf` using `counts`, `displs` in bytes and `datatypes` without checking the
+    /// partitions against the bounds of `buf`
+    ///
+    /// # Safety
+    ///
+    /// For every partition `i`, `counts[i]` elements of `datatypes[i]` located `displs[i]` bytes
+    /// from the start of `buf` must lie within `buf`.
+    pub unsafe fn new_unchecked<T>(buf: &'b mut B, counts: C, displs: D, datatypes: &'d [T]) -> Self
+    where
+        T: Datatype,
+    {
+        assert_eq!(counts.borrow().len(), displs.borrow().len());
+        assert_eq!(counts.borrow().len(), datatypes.len());
+
+        HeterogeneousPartitionMut {
+            buf,
+            counts,
+            displs,
+            datatypes: datatypes.iter().map(|d| d.as_raw()).collect(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'b, 'd, T, C, D> HeterogeneousPartitionMut<'b, 'd, [T], C, D>
+where
+    T: 'b + AnyBitPattern,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+    /// Partition the slice `buf` using `counts`, `displs` in bytes and `datatypes`
+    ///
+    /// The datatypes may write arbitrary bytes into the elements of `buf`, so they have to be
+    /// `AnyBitPattern`.
+    ///
+    /// # Examples
+    /// See `examples/all_to_all_varcount.rs`
+    ///
+    /// # Panics
+    /// If `counts`, `displs` and `datatypes` differ in length or if a partition accesses memory
+    /// outside of `buf`, as determined by the true extent of its datatype.
+    pub fn new<U>(buf: &'b mut [T], counts: C, displs: D, datatypes: &'d [U]) -> Self
+    where
+        U: Datatype,
+    {
+        assert_eq!(counts.borrow().len(), displs.borrow().len());
+        assert_eq!(counts.borrow().len(), datatypes.len());
+        let len = mem::size_of_val(buf);
+        for ((&count, &displ), datatype) in counts
+            .borrow()
+            .iter()
+            .zip(displs.borrow().iter())
+            .zip(datatypes)
+        {
+            assert_partition_in_bounds(datatype, count, displ, len);
+        }
+        unsafe { Self::new_unchecked(buf, counts, displs, datatypes) }
+    }
+}
+
+unsafe impl<'b, 'd, B: ?Sized, C, D> PointerMut for HeterogeneousPartitionMut<'b, 'd, B, C, D>
+where
+    B: 'b + PointerMut,
+{
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.buf.pointer_mut()
+    }
+}
+
+impl<'b, 'd, B: ?Sized, C, D> HeterogeneousPartitioned
+    for HeterogeneousPartitionMut<'b, 'd, B, C, D>
+where
+    B: 'b,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+    fn counts(&self) -> &[Count] {
+        self.counts.borrow()
+    }
+    fn byte_displs(&self) -> &[Count] {
+        self.displs.borrow()
+    }
+    fn datatypes(&self) -> &[MPI_Datatype] {
+        &self.datatypes
+    }
+}
+
+impl<'b, 'd, B: ?Sized, C, D> HeterogeneousPartitionedBufferMut
+    for HeterogeneousPartitionMut<'b, 'd, B, C, D>
+where
+    B: 'b + PointerMut,
+    C: Borrow<[Count]>,
+    D: Borrow<[Count]>,
+{
+}
+
 /// Returns the address of the argument in a format suitable for use with datatype constructors
 ///
 /// # Examples
@@ -1573,5 +3436,140 @@ where
 /// 4.1.5
 pub fn address_of<T>(x: &T) -> Address {
     let x: *const T = x;
+    let _call = instrument::begin(|| Call::without_comm("MPI_Get_address"));
     unsafe { with_uninitialized(|address| ffi::MPI_Get_address(x as *const c_void, address)).1 }
 }
+
+/// The portable data representation used for canonical packing
+const EXTERNAL32: &[u8] = b"external32\0";
+
+/// Gets the buffer size required to pack `incount` elements of type `datatype` in the portable
+/// `external32` representation.
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Pack_external_size
+pub fn pack_external_size<Dt>(incount: Count, datatype: &Dt) -> Address
+where
+    Dt: Datatype,
+{
+    let _call =
+        instrument::begin(|| Call::without_comm("MPI_Pack_external_size").typed(incount, datatype));
+    unsafe {
+        with_uninitialized(|size| {
+            ffi::MPI_Pack_external_size(
+                EXTERNAL32.as_ptr() as *const c_char,
+                incount,
+                datatype.as_raw(),
+                size,
+            )
+        })
+        .1
+    }
+}
+
+/// Packs `inbuf` into a byte array in the portable `external32` representation which can be
+/// unpacked by any MPI implementation. Often paired with `unpack_external_into` to convert back
+/// into a specific datatype.
+///
+/// # Examples
+/// See `examples/pack_external.rs`
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Pack_external
+pub fn pack_external<Buf>(inbuf: &Buf) -> Vec<u8>
+where
+    Buf: ?Sized + Buffer,
+{
+    let mut outbuf = vec![
+        0;
+        pack_external_size(inbuf.count(), &inbuf.as_datatype())
+            .value_as::<usize>()
+            .expect("MPI_Pack_external_size returned a negative buffer size!")
+    ];
+
+    let position = pack_external_into(inbuf, &mut outbuf[..], 0);
+
+    outbuf.resize(
+        position
+            .value_as()
+            .expect("MPI_Pack_external returned a negative position!"),
+        0,
+    );
+
+    outbuf
+}
+
+/// Packs `inbuf` into `outbuf` at `position` in the portable `external32` representation and
+/// returns the position following the packed data.
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Pack_external
+pub fn pack_external_into<Buf>(inbuf: &Buf, outbuf: &mut [u8], position: Address) -> Address
+where
+    Buf: ?Sized + Buffer,
+{
+    let inbuf_dt = inbuf.as_datatype();
+
+    let mut position = position;
+    let _call = instrument::begin(|| Call::without_comm("MPI_Pack_external").buffer(inbuf));
+    unsafe {
+        ffi::MPI_Pack_external(
+            EXTERNAL32.as_ptr() as *const c_char,
+            inbuf.pointer(),
+            inbuf.count(),
+            inbuf_dt.as_raw(),
+            outbuf.as_mut_ptr() as *mut _,
+            outbuf
+                .len()
+                .value_as()
+                .expect("Length of packing buffer cannot be expressed as an MPI address."),
+            &mut position,
+        );
+    }
+    position
+}
+
+/// Unpacks a byte array in the portable `external32` representation from `pack_external` or
+/// `pack_external_into` at `position` into a buffer of a specific datatype and returns the
+/// position following the unpacked data.
+///
+/// # Examples
+/// See `examples/pack_external.rs`
+///
+/// # Safety
+///
+/// The bytes of `inbuf` starting at `position` must describe valid values of the datatype of
+/// `outbuf`.
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Unpack_external
+pub unsafe fn unpack_external_into<Buf>(
+    inbuf: &[u8],
+    outbuf: &mut Buf,
+    position: Address,
+) -> Address
+where
+    Buf: ?Sized + BufferMut,
+{
+    let outbuf_dt = outbuf.as_datatype();
+
+    let mut position = position;
+    let _call = instrument::begin(|| Call::without_comm("MPI_Unpack_external").buffer(outbuf));
+    ffi::MPI_Unpack_external(
+        EXTERNAL32.as_ptr() as *const c_char,
+        inbuf.as_ptr() as *const _,
+        inbuf
+            .len()
+            .value_as()
+            .expect("Length of packed buffer cannot be expressed as an MPI address."),
+        &mut position,
+        outbuf.pointer_mut(),
+        outbuf.count(),
+        outbuf_dt.as_raw(),
+    );
+    position
+}
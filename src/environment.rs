@@ -5,29 +5,39 @@
 //! # Unfinished features
 //!
 //! - **8.1.2**: `MPI_TAG_UB`, ...
-//! - **8.2**: Memory allocation
-//! - **8.3, 8.4, and 8.5**: Error handling
+//! - **8.5**: User-defined error classes and codes
 
 use std::{
+    alloc::Layout,
     cmp::Ordering,
+    mem,
+    ops::{Deref, DerefMut},
     os::raw::{c_char, c_double, c_int, c_void},
-    ptr,
+    panic::{self, AssertUnwindSafe},
+    ptr::{self, NonNull},
+    slice,
     string::FromUtf8Error,
-    sync::RwLock,
+    sync::{Arc, Mutex, RwLock, Weak},
     thread::{self, ThreadId},
+    time::Duration,
 };
 
 use conv::ConvUtil;
 use once_cell::sync::Lazy;
 
-use crate::{attribute::AppNum, ffi};
+use crate::datatype::traits::*;
+use crate::instrument::{self, Call};
+use crate::{attribute::AppNum, ffi, Address, Count};
 use crate::{attribute::UniverseSize, traits::FromRaw};
+use crate::{
+    collective::CommunicatorCollectives, with_uninitialized, with_uninitialized2, ErrorCode,
+};
+
 use crate::{
     topology::traits::AnyCommunicator,
-    topology::{Communicator, InterCommunicator, SimpleCommunicator},
+    topology::{Communicator, InterCommunicator, SimpleCommunicator, ThreadSafeWorld},
     traits::AsRaw,
 };
-use crate::{with_uninitialized, with_uninitialized2};
 
 /// Internal data structure used to uphold certain MPI invariants.
 /// State is currently only used with the derive feature.
@@ -41,7 +51,7 @@ pub(crate) static UNIVERSE_STATE: Lazy<RwLock<Option<UniverseState>>> =
 
 /// Global context
 pub struct Universe {
-    buffer: Option<Vec<u8>>,
+    buffer: Option<BufferAttachment>,
 }
 
 impl Universe {
@@ -55,6 +65,26 @@ impl Universe {
         SimpleCommunicator::world()
     }
 
+    /// The 'self communicator'
+    ///
+    /// Contains only the current process, e.g. for files and windows that are local to it.
+    ///
+    /// # Examples
+    /// See `examples/self_comm.rs`
+    pub fn self_comm(&self) -> SimpleCommunicator {
+        SimpleCommunicator::self_comm()
+    }
+
+    /// The 'world communicator' for sharing between threads
+    ///
+    /// Returns `None` unless MPI has been initialized with `Threading::Multiple`.
+    ///
+    /// # Examples
+    /// See `examples/thread_safe_world.rs`
+    pub fn thread_safe_world(&self) -> Option<ThreadSafeWorld> {
+        ThreadSafeWorld::new()
+    }
+
     /// Total number of "slots" that can reasonably be filled in the environment
     ///
     /// This can be larger or smaller than the world (e.g., when
@@ -82,48 +112,52 @@ impl Universe {
         self.world().get_attr::<AppNum>().map(isize::from)
     }
 
+    /// Abort the job with `MPI_Abort()` when any thread of this process panics.
+    ///
+    /// The previous panic hook, which prints the panic message by default, runs first. Without
+    /// the hook a panicking process leaves the other processes waiting for it at the next
+    /// collective operation. Panics after MPI has been finalized are left to the previous hook.
+    ///
+    /// # Examples
+    /// See `examples/with_universe.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.7
+    pub fn install_panic_hook(&self) {
+        self.install_panic_hook_with_code(PANIC_ABORT_CODE);
+    }
+
+    /// Like `install_panic_hook()`, but aborts with `errorcode`.
+    pub fn install_panic_hook_with_code(&self, errorcode: c_int) {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous(info);
+            if !is_finalized() {
+                SimpleCommunicator::world().abort(errorcode);
+            }
+        }));
+    }
+
     /// The size in bytes of the buffer used for buffered communication.
     pub fn buffer_size(&self) -> usize {
-        self.buffer.as_ref().map_or(0, Vec::len)
+        self.buffer.as_ref().map_or(0, BufferAttachment::size)
     }
 
     /// Set the size in bytes of the buffer used for buffered communication.
+    ///
+    /// See `BufferAttachment` for attaching a buffer independently of the `Universe`.
     pub fn set_buffer_size(&mut self, size: usize) {
         self.detach_buffer();
 
         if size > 0 {
-            let mut buffer = vec![0; size];
-            unsafe {
-                ffi::MPI_Buffer_attach(
-                    buffer.as_mut_ptr() as _,
-                    buffer
-                        .len()
-                        .value_as()
-                        .expect("Buffer length exceeds the range of a C int."),
-                );
-            }
-            self.buffer = Some(buffer);
+            self.buffer = Some(BufferAttachment::new(size).expect("MPI_Buffer_attach() failed."));
         }
     }
 
     /// Detach the buffer used for buffered communication.
     pub fn detach_buffer(&mut self) {
-        if let Some(buffer) = self.buffer.take() {
-            let mut addr: *const c_void = ptr::null();
-            let addr_ptr: *mut *const c_void = &mut addr;
-            let mut size: c_int = 0;
-            unsafe {
-                ffi::MPI_Buffer_detach(addr_ptr as *mut c_void, &mut size);
-                assert_eq!(addr, buffer.as_ptr() as _);
-            }
-            assert_eq!(
-                size,
-                buffer
-                    .len()
-                    .value_as()
-                    .expect("Buffer length exceeds the range of a C int.")
-            );
-        }
+        self.buffer = None;
     }
 
     /// Disconnect parent
@@ -144,40 +178,286 @@ impl Universe {
     ///
     /// 11.8.2
     pub fn disconnect_parent(&mut self) {
+        self.free_parent();
+    }
+
+    fn free_parent(&self) {
         if let Some(parent) = self.world().parent() {
             // Make it look like a user communicator so it can be dropped
             let _p = unsafe { InterCommunicator::from_raw(parent.as_raw()) };
         }
     }
 
-    fn free_attribute_keys(&mut self) {
+    fn free_attribute_keys(&self) {
         let mut comm_attrs = crate::attribute::COMM_ATTRS.write().unwrap();
         for (_, v) in comm_attrs.drain() {
             let mut k = v.as_raw();
+            let _call = instrument::begin(|| Call::without_comm("MPI_Comm_free_keyval"));
             unsafe { ffi::MPI_Comm_free_keyval(&mut k) };
         }
+        let mut datatype_attrs = crate::attribute::DATATYPE_ATTRS.write().unwrap();
+        for (_, v) in datatype_attrs.drain() {
+            let mut k = v.as_raw();
+            let _call = instrument::begin(|| Call::without_comm("MPI_Type_free_keyval"));
+            unsafe { ffi::MPI_Type_free_keyval(&mut k) };
+        }
+        let mut window_attrs = crate::attribute::WINDOW_ATTRS.write().unwrap();
+        for (_, v) in window_attrs.drain() {
+            let mut k = v.as_raw();
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_free_keyval"));
+            unsafe { ffi::MPI_Win_free_keyval(&mut k) };
+        }
     }
-}
 
-impl Drop for Universe {
-    fn drop(&mut self) {
-        // This can only ever be called once since it's only possible to initialize a single
-        // Universe per application run.
-        //
+    /// Finalizes MPI, which can only happen once per application run.
+    fn finalize(&self) {
+        #[cfg(feature = "async")]
+        crate::request::stop_progress();
+
         // NOTE: The write lock is taken to prevent racing with `#[derive(Equivalence)]`
         let mut _universe_state = UNIVERSE_STATE
             .write()
             .expect("rsmpi internal error: UNIVERSE_STATE lock poisoned");
 
-        self.detach_buffer();
-        self.disconnect_parent();
+        self.free_parent();
         self.free_attribute_keys();
+        crate::datatype::free_cached_datatypes();
         unsafe {
             ffi::MPI_Finalize();
         }
     }
 }
 
+impl Drop for Universe {
+    fn drop(&mut self) {
+        // This can only ever be called once since it's only possible to initialize a single
+        // Universe per application run.
+        self.detach_buffer();
+        self.finalize();
+    }
+}
+
+/// The number of bytes every message sent in buffered mode takes up in the attached buffer in
+/// addition to its packed size, `MPI_BSEND_OVERHEAD`
+///
+/// # Standard section(s)
+///
+/// 3.6.1
+pub const BSEND_OVERHEAD: usize = ffi::MPI_BSEND_OVERHEAD as usize;
+
+/// A buffer attached for sends in buffered mode, which is detached when dropped
+///
+/// The buffer has to hold all messages that are in transit at the same time, each of them takes
+/// up its `Communicator::pack_size()` plus `BSEND_OVERHEAD` bytes. Detaching the buffer blocks
+/// until all messages in it have been transmitted. Only one buffer can be attached at a time,
+/// this includes the buffer set via `Universe::set_buffer_size()`. A buffer that is dropped after
+/// MPI has been finalized is only freed, as finalizing detaches it.
+///
+/// # Examples
+/// See `examples/buffered.rs`
+///
+/// # Standard section(s)
+///
+/// 3.6.1
+pub struct BufferAttachment {
+    buffer: Vec<u8>,
+}
+
+impl BufferAttachment {
+    /// Attach a newly allocated buffer of `size` bytes.
+    pub fn new(size: usize) -> Result<Self, ErrorCode> {
+        let mut buffer = vec![0; size];
+        let _call = instrument::begin(|| Call::without_comm("MPI_Buffer_attach"));
+        ErrorCode::check(unsafe {
+            ffi::MPI_Buffer_attach(
+                buffer.as_mut_ptr() as _,
+                buffer
+                    .len()
+                    .value_as()
+                    .expect("Buffer length exceeds the range of a C int."),
+            )
+        })?;
+        Ok(BufferAttachment { buffer })
+    }
+
+    /// The size of the buffer in bytes
+    pub fn size(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl Drop for BufferAttachment {
+    fn drop(&mut self) {
+        // `MPI_Finalize()` has detached the buffer already.
+        if is_finalized() {
+            return;
+        }
+        let mut addr: *const c_void = ptr::null();
+        let addr_ptr: *mut *const c_void = &mut addr;
+        let mut size: c_int = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Buffer_detach"));
+        unsafe {
+            ffi::MPI_Buffer_detach(addr_ptr as *mut c_void, &mut size);
+        }
+        assert_eq!(addr, self.buffer.as_ptr() as _);
+        assert_eq!(
+            size,
+            self.buffer
+                .len()
+                .value_as()
+                .expect("Buffer length exceeds the range of a C int.")
+        );
+    }
+}
+
+/// An array of `T` in memory allocated with `MPI_Alloc_mem()`, which is freed when dropped
+///
+/// Some interconnects register this memory with the network, which speeds up sends from it and
+/// one-sided operations on windows created over it. Otherwise it behaves like a `Box<[T]>` and
+/// can be used as a `Buffer` or `BufferMut`. An array that is dropped after MPI has been
+/// finalized is not freed.
+///
+/// # Examples
+/// See `examples/alloc_mem.rs`
+///
+/// # Standard section(s)
+///
+/// 8.2
+pub struct MpiAllocated<T> {
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<T> MpiAllocated<T> {
+    /// Allocate an array of `len` copies of `value`.
+    pub fn from_elem(value: T, len: usize) -> Result<Self, ErrorCode>
+    where
+        T: Clone,
+    {
+        let mut allocation = MpiAllocated::allocate(len)?;
+        for _ in 0..len {
+            allocation.push(value.clone());
+        }
+        Ok(allocation)
+    }
+
+    /// Allocate an array of clones of the elements of `values`.
+    pub fn from_slice(values: &[T]) -> Result<Self, ErrorCode>
+    where
+        T: Clone,
+    {
+        let mut allocation = MpiAllocated::allocate(values.len())?;
+        for value in values {
+            allocation.push(value.clone());
+        }
+        Ok(allocation)
+    }
+
+    /// Allocate memory for `capacity` elements, with no element initialized yet.
+    fn allocate(capacity: usize) -> Result<Self, ErrorCode> {
+        let layout = Layout::array::<T>(capacity).expect("Allocation size exceeds isize::MAX.");
+        if layout.size() == 0 {
+            return Ok(MpiAllocated {
+                ptr: NonNull::dangling(),
+                len: 0,
+                capacity,
+            });
+        }
+
+        let size: Address = layout
+            .size()
+            .value_as()
+            .expect("Allocation size exceeds the range of MPI_Aint.");
+        let mut base: *mut c_void = ptr::null_mut();
+        let base_ptr: *mut *mut c_void = &mut base;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Alloc_mem"));
+        ErrorCode::check(unsafe {
+            ffi::MPI_Alloc_mem(size, ffi::RSMPI_INFO_NULL, base_ptr as *mut c_void)
+        })?;
+        // Freed again if the alignment check fails
+        let allocation = MpiAllocated {
+            ptr: NonNull::new(base as *mut T).expect("MPI_Alloc_mem() returned a null pointer."),
+            len: 0,
+            capacity,
+        };
+        assert_eq!(
+            base as usize % layout.align(),
+            0,
+            "MPI_Alloc_mem() returned memory that is not aligned for the element type."
+        );
+        Ok(allocation)
+    }
+
+    /// Initialize the element after the last one.
+    fn push(&mut self, value: T) {
+        assert!(self.len < self.capacity);
+        unsafe { self.ptr.as_ptr().add(self.len).write(value) };
+        self.len += 1;
+    }
+}
+
+impl<T> Deref for MpiAllocated<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for MpiAllocated<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for MpiAllocated<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(&mut **self as *mut [T]);
+            // `MPI_Free_mem()` cannot be called anymore once MPI has been finalized.
+            if mem::size_of::<T>() != 0 && self.capacity != 0 && !is_finalized() {
+                let _call = instrument::begin(|| Call::without_comm("MPI_Free_mem"));
+                ffi::MPI_Free_mem(self.ptr.as_ptr() as *mut c_void);
+            }
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for MpiAllocated<T> {}
+unsafe impl<T: Sync> Sync for MpiAllocated<T> {}
+
+unsafe impl<T: Equivalence> AsDatatype for MpiAllocated<T> {
+    type Out = <T as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        T::equivalent_datatype()
+    }
+}
+
+unsafe impl<T: Equivalence> Collection for MpiAllocated<T> {
+    fn count(&self) -> Count {
+        (**self).count()
+    }
+}
+
+unsafe impl<T: Equivalence> Pointer for MpiAllocated<T> {
+    fn pointer(&self) -> *const c_void {
+        (**self).pointer()
+    }
+}
+
+unsafe impl<T: Equivalence> PointerMut for MpiAllocated<T> {
+    fn pointer_mut(&mut self) -> *mut c_void {
+        (**self).pointer_mut()
+    }
+}
+
+unsafe impl<T: Equivalence> Buffer for MpiAllocated<T> {}
+unsafe impl<T: Equivalence> BufferMut for MpiAllocated<T> {}
+
+/// The universe shared by `initialize_shared()`, which is finalized when the last handle to it is
+/// dropped
+static SHARED_UNIVERSE: Lazy<Mutex<Weak<Universe>>> = Lazy::new(|| Mutex::new(Weak::new()));
+
 /// Describes the various levels of multithreading that can be supported by an MPI library.
 ///
 /// # Examples
@@ -241,14 +521,24 @@ impl From<c_int> for Threading {
 }
 
 /// Whether the MPI library has been initialized
-pub(crate) fn is_initialized() -> bool {
+///
+/// Can be called without initializing MPI.
+///
+/// # Standard section(s)
+///
+/// 8.7 (MPI_Initialized)
+pub fn is_initialized() -> bool {
     unsafe { with_uninitialized(|initialized| ffi::MPI_Initialized(initialized)).1 != 0 }
 }
 
-/// Whether the MPI library has been initialized
-/// NOTE: Used by "derive" feature
-#[allow(unused)]
-pub(crate) fn is_finalized() -> bool {
+/// Whether the MPI library has been finalized
+///
+/// Can be called without initializing MPI.
+///
+/// # Standard section(s)
+///
+/// 8.7 (MPI_Finalized)
+pub fn is_finalized() -> bool {
     unsafe { with_uninitialized(|finalized| ffi::MPI_Finalized(finalized)).1 != 0 }
 }
 
@@ -317,6 +607,90 @@ pub fn initialize_with_threading(threading: Threading) -> Option<(Universe, Thre
     Some((Universe { buffer: None }, provided.into()))
 }
 
+/// Initialize MPI on the first call and return a handle to the shared `Universe` on all
+/// subsequent calls.
+///
+/// Unlike `initialize()`, this can be called any number of times, e.g. by every test of a test
+/// harness or by several libraries that do not know about each other. The shared universe lives
+/// as long as any of the returned handles, MPI is finalized when the last one is dropped. MPI
+/// cannot be initialized again afterwards, so callers that come and go should keep a handle
+/// alive, e.g. in the `main()` function of the program, until all of them are done. That handle
+/// should also be the last one, as MPI is finalized on the thread that drops it, which should be
+/// the thread that initialized MPI.
+///
+/// As the callers may run on different threads, like the tests run by `cargo test`, MPI is
+/// initialized requesting `Threading::Multiple`. If `threading_support()` reports a lower level,
+/// the callers have to make sure that MPI is not called from several threads at the same time,
+/// e.g. by running the tests with `cargo test -- --test-threads=1`.
+///
+/// Returns `None` if MPI has been initialized by other means than this function, e.g. via
+/// `initialize()`, or if the shared universe has already been finalized.
+///
+/// # Examples
+/// See `examples/initialize_shared.rs`
+///
+/// # Standard section(s)
+///
+/// 8.7
+pub fn initialize_shared() -> Option<Arc<Universe>> {
+    let mut shared = SHARED_UNIVERSE
+        .lock()
+        .expect("SHARED_UNIVERSE Mutex poisoned");
+    if let Some(universe) = shared.upgrade() {
+        return Some(universe);
+    }
+    let (universe, _) = initialize_with_threading(Threading::Multiple)?;
+    let universe = Arc::new(universe);
+    *shared = Arc::downgrade(&universe);
+    Some(universe)
+}
+
+/// Error code passed to `MPI_Abort()` by `with_universe()` and `Universe::install_panic_hook()`
+/// after a panic, the exit code of a Rust program that panics
+pub const PANIC_ABORT_CODE: c_int = 101;
+
+/// Initialize MPI, run `f` and finalize MPI afterwards.
+///
+/// If `f` panics, the panic is caught and the job is aborted with `MPI_Abort()` and the error
+/// code `PANIC_ABORT_CODE`. Otherwise the other processes would wait forever for the crashed
+/// process at the next collective operation or at `MPI_Finalize()`. Panics on other threads are
+/// not caught, see `Universe::install_panic_hook()` for those.
+///
+/// Returns `None` without calling `f` if MPI has already been initialized.
+///
+/// # Examples
+/// See `examples/with_universe.rs`
+///
+/// # Standard section(s)
+///
+/// 8.7
+pub fn with_universe<F, R>(f: F) -> Option<R>
+where
+    F: FnOnce(&Universe) -> R,
+{
+    with_universe_abort_code(PANIC_ABORT_CODE, f)
+}
+
+/// Like `with_universe()`, but aborts with `errorcode` if `f` panics.
+///
+/// # Examples
+/// See `examples/with_universe.rs`
+///
+/// # Standard section(s)
+///
+/// 8.7
+pub fn with_universe_abort_code<F, R>(errorcode: c_int, f: F) -> Option<R>
+where
+    F: FnOnce(&Universe) -> R,
+{
+    let universe = initialize()?;
+    // The universe is not used after a panic, the job is aborted instead.
+    match panic::catch_unwind(AssertUnwindSafe(|| f(&universe))) {
+        Ok(result) => Some(result),
+        Err(_) => universe.world().abort(errorcode),
+    }
+}
+
 /// Level of multithreading supported by this MPI universe
 ///
 /// See the `Threading` enum.
@@ -324,6 +698,7 @@ pub fn initialize_with_threading(threading: Threading) -> Option<(Universe, Thre
 /// # Examples
 /// See `examples/init_with_threading.rs`
 pub fn threading_support() -> Threading {
+    let _call = instrument::begin(|| Call::without_comm("MPI_Query_thread"));
     unsafe {
         with_uninitialized(|threading| ffi::MPI_Query_thread(threading))
             .1
@@ -331,6 +706,21 @@ pub fn threading_support() -> Threading {
     }
 }
 
+/// Whether the calling thread is the thread that initialized MPI
+///
+/// Under `Threading::Funneled` only this thread may call MPI functions.
+///
+/// # Examples
+/// See `examples/init_with_threading.rs`
+///
+/// # Standard section(s)
+///
+/// 12.4.3 (MPI_Is_thread_main)
+pub fn is_thread_main() -> bool {
+    let _call = instrument::begin(|| Call::without_comm("MPI_Is_thread_main"));
+    unsafe { with_uninitialized(|flag| ffi::MPI_Is_thread_main(flag)).1 != 0 }
+}
+
 /// Identifies the version of the MPI standard implemented by the library.
 ///
 /// Returns a tuple of `(version, subversion)`, e.g. `(3, 0)`.
@@ -390,6 +780,7 @@ pub fn processor_name() -> Result<String, FromUtf8Error> {
     let mut buf = vec![0u8; bufsize];
     let mut len: c_int = 0;
 
+    let _call = instrument::begin(|| Call::without_comm("MPI_Get_processor_name"));
     unsafe {
         ffi::MPI_Get_processor_name(buf.as_mut_ptr() as *mut c_char, &mut len);
     }
@@ -414,3 +805,45 @@ pub fn time() -> c_double {
 pub fn time_resolution() -> c_double {
     unsafe { ffi::RSMPI_Wtick() }
 }
+
+/// Measures the time elapsed since it was started using the timer of `time()`
+///
+/// # Examples
+/// See `examples/time.rs`
+///
+/// # Standard section(s)
+///
+/// 8.6
+#[derive(Copy, Clone, Debug)]
+pub struct Stopwatch {
+    start: c_double,
+}
+
+impl Stopwatch {
+    /// Start measuring on the calling process.
+    pub fn start() -> Stopwatch {
+        Stopwatch { start: time() }
+    }
+
+    /// Start measuring once all processes of `comm` have arrived.
+    ///
+    /// This is a collective operation.
+    pub fn start_synchronized<C: Communicator + ?Sized>(comm: &C) -> Stopwatch {
+        comm.barrier();
+        Stopwatch::start()
+    }
+
+    /// The time elapsed since the stopwatch was started
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64((time() - self.start).max(0.0))
+    }
+
+    /// The time elapsed since the stopwatch was started until all processes of `comm` have
+    /// arrived.
+    ///
+    /// This is a collective operation.
+    pub fn elapsed_synchronized<C: Communicator + ?Sized>(&self, comm: &C) -> Duration {
+        comm.barrier();
+        self.elapsed()
+    }
+}
@@ -0,0 +1,169 @@
+//! Error handlers that forward MPI errors to Rust closures
+//!
+//! Communicators and windows keep their handler in an attribute, so that it is freed together with
+//! the object and, for communicators, duplicated with it. Files cannot carry attributes, so their
+//! handlers are kept in a registry keyed by the raw handle instead, which is cleared when the file
+//! is closed.
+
+use std::{
+    collections::HashMap,
+    ffi::c_void,
+    mem::MaybeUninit,
+    os::raw::c_int,
+    panic::{self, AssertUnwindSafe},
+    process,
+    sync::{Arc, RwLock},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::attribute::{CommAttribute, WindowAttribute};
+use crate::ffi;
+use crate::ffi::{MPI_Comm, MPI_Errhandler, MPI_File, MPI_Win};
+use crate::raw::traits::*;
+use crate::{with_uninitialized, ErrorCode};
+
+type Handler = Arc<dyn Fn(ErrorCode) + Send + Sync>;
+
+/// The handler attached to a communicator
+#[derive(Clone)]
+struct CommErrorHandler(Handler);
+
+impl CommAttribute for CommErrorHandler {
+    // MPI_Comm_dup() also propagates the error handler
+    const CLONE_ON_DUP: bool = true;
+}
+
+/// The handler attached to a window
+struct WindowErrorHandler(Handler);
+
+impl WindowAttribute for WindowErrorHandler {}
+
+static FILE_HANDLERS: Lazy<RwLock<HashMap<usize, Handler>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// Raw handles are integers in some implementations and pointers in others, both can be used as
+// a key.
+fn file_key(file: MPI_File) -> usize {
+    file as usize
+}
+
+fn call(handler: &Handler, code: c_int) {
+    if let Err(error) = ErrorCode::check(code) {
+        // Unwinding into MPI is undefined behavior.
+        if panic::catch_unwind(AssertUnwindSafe(|| handler(error))).is_err() {
+            process::abort();
+        }
+    }
+}
+
+unsafe extern "C" fn comm_error_handler(comm: *mut MPI_Comm, code: *mut c_int) {
+    let key = CommErrorHandler::get_key();
+    let mut handler: MaybeUninit<*mut CommErrorHandler> = MaybeUninit::uninit();
+    let (_, flag) = with_uninitialized(|flag| {
+        ffi::MPI_Comm_get_attr(
+            *comm,
+            key.as_raw(),
+            handler.as_mut_ptr() as *mut c_void,
+            flag,
+        )
+    });
+    if flag != 0 {
+        if let Some(handler) = handler.assume_init().as_ref() {
+            call(&handler.0, *code);
+        }
+    }
+}
+
+unsafe extern "C" fn file_error_handler(file: *mut MPI_File, code: *mut c_int) {
+    let handler = FILE_HANDLERS
+        .read()
+        .expect("FILE_HANDLERS RwLock poisoned")
+        .get(&file_key(*file))
+        .cloned();
+    if let Some(handler) = handler {
+        call(&handler, *code);
+    }
+}
+
+unsafe extern "C" fn window_error_handler(window: *mut MPI_Win, code: *mut c_int) {
+    let key = WindowErrorHandler::get_key();
+    let mut handler: MaybeUninit<*mut WindowErrorHandler> = MaybeUninit::uninit();
+    let (_, flag) = with_uninitialized(|flag| {
+        ffi::MPI_Win_get_attr(
+            *window,
+            key.as_raw(),
+            handler.as_mut_ptr() as *mut c_void,
+            flag,
+        )
+    });
+    if flag != 0 {
+        if let Some(handler) = handler.assume_init().as_ref() {
+            call(&handler.0, *code);
+        }
+    }
+}
+
+/// Make errors on `comm` call `handler`.
+///
+/// # Safety
+///
+/// `comm` must be a valid communicator.
+pub(crate) unsafe fn set_comm_error_handler(comm: MPI_Comm, handler: Handler) {
+    let key = CommErrorHandler::get_key();
+    let handler = Box::new(CommErrorHandler(handler));
+    ffi::MPI_Comm_set_attr(comm, key.as_raw(), Box::into_raw(handler) as *mut c_void);
+
+    let mut errhandler: MPI_Errhandler = with_uninitialized(|errhandler| {
+        ffi::RSMPI_Comm_create_errhandler(Some(comm_error_handler), errhandler)
+    })
+    .1;
+    ffi::MPI_Comm_set_errhandler(comm, errhandler);
+    ffi::MPI_Errhandler_free(&mut errhandler);
+}
+
+/// Make errors on `file` call `handler`.
+///
+/// # Safety
+///
+/// `file` must be a valid file handle, `remove_file_error_handler()` must be called before it is
+/// closed.
+pub(crate) unsafe fn set_file_error_handler(file: MPI_File, handler: Handler) {
+    FILE_HANDLERS
+        .write()
+        .expect("FILE_HANDLERS RwLock poisoned")
+        .insert(file_key(file), handler);
+
+    let mut errhandler: MPI_Errhandler = with_uninitialized(|errhandler| {
+        ffi::RSMPI_File_create_errhandler(Some(file_error_handler), errhandler)
+    })
+    .1;
+    ffi::MPI_File_set_errhandler(file, errhandler);
+    ffi::MPI_Errhandler_free(&mut errhandler);
+}
+
+/// Forget the handler of `file`, if any.
+pub(crate) fn remove_file_error_handler(file: MPI_File) {
+    FILE_HANDLERS
+        .write()
+        .expect("FILE_HANDLERS RwLock poisoned")
+        .remove(&file_key(file));
+}
+
+/// Make errors on `window` call `handler`.
+///
+/// # Safety
+///
+/// `window` must be a valid window.
+pub(crate) unsafe fn set_window_error_handler(window: MPI_Win, handler: Handler) {
+    let key = WindowErrorHandler::get_key();
+    let handler = Box::new(WindowErrorHandler(handler));
+    ffi::MPI_Win_set_attr(window, key.as_raw(), Box::into_raw(handler) as *mut c_void);
+
+    let mut errhandler: MPI_Errhandler = with_uninitialized(|errhandler| {
+        ffi::RSMPI_Win_create_errhandler(Some(window_error_handler), errhandler)
+    })
+    .1;
+    ffi::MPI_Win_set_errhandler(window, errhandler);
+    ffi::MPI_Errhandler_free(&mut errhandler);
+}
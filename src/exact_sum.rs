@@ -0,0 +1,137 @@
+//! Exact sums of floating point numbers for the reproducible reductions
+//!
+//! Every `f64` is an integer multiple of `2^-1074`, the smallest subnormal number. A value is
+//! accumulated exactly by adding its significand, at the position of its exponent, to a fixed
+//! point number of `LIMBS` limbs of 32 bits each. The limbs are kept in `i64`s, so the limbs of
+//! the accumulators of up to `2^31` processes can be added with `MPI_SUM` without overflowing.
+//! Integer addition is associative, so the reduction algorithm of the MPI library does not
+//! matter, and the exact sum is only rounded once at the end. The result thus depends only on the
+//! values that are summed, not on their order or their distribution over processes.
+//!
+//! Infinities and NaNs are counted separately and turn the result into an infinity or NaN like
+//! floating point addition does.
+
+/// Number of bits of every limb, the remaining bits of an `i64` hold carries and the sign
+const LIMB_BITS: u32 = 32;
+
+/// Number of limbs, enough for the 2046 exponents and 53 bits of significand of finite `f64`s
+/// plus 31 bits for the carries of summing `2^31` of them
+const LIMBS: usize = 68;
+
+/// Number of `i64`s that describe the sum of one element: the limbs followed by the counts of
+/// positive infinities, negative infinities and NaNs
+pub(crate) const WORDS: usize = LIMBS + 3;
+
+/// Add `x` to the accumulator `words`, which consists of `WORDS` words.
+pub(crate) fn accumulate(words: &mut [i64], x: f64) {
+    debug_assert_eq!(words.len(), WORDS);
+    if x.is_nan() {
+        words[LIMBS + 2] += 1;
+        return;
+    }
+    if x.is_infinite() {
+        words[if x > 0.0 { LIMBS } else { LIMBS + 1 }] += 1;
+        return;
+    }
+
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as u32;
+    let fraction = bits & ((1 << 52) - 1);
+    // The value is `significand * 2^(position - 1074)`.
+    let (significand, position) = if exponent == 0 {
+        (fraction, 0)
+    } else {
+        (fraction | (1 << 52), exponent - 1)
+    };
+
+    let limb = (position / LIMB_BITS) as usize;
+    let shifted = u128::from(significand) << (position % LIMB_BITS);
+    for i in 0..3 {
+        let part = ((shifted >> (i * LIMB_BITS)) & u128::from(u32::MAX)) as i64;
+        if x.is_sign_negative() {
+            words[limb + i as usize] -= part;
+        } else {
+            words[limb + i as usize] += part;
+        }
+    }
+}
+
+/// Round the sum accumulated in `words` to the nearest `f64`.
+pub(crate) fn round(words: &mut [i64]) -> f64 {
+    debug_assert_eq!(words.len(), WORDS);
+    let (positive_infinities, negative_infinities, nans) =
+        (words[LIMBS], words[LIMBS + 1], words[LIMBS + 2]);
+    if nans > 0 || (positive_infinities > 0 && negative_infinities > 0) {
+        return f64::NAN;
+    }
+    if positive_infinities > 0 {
+        return f64::INFINITY;
+    }
+    if negative_infinities > 0 {
+        return f64::NEG_INFINITY;
+    }
+
+    let limbs = &mut words[..LIMBS];
+    normalize(limbs);
+    // After normalizing, only the top limb carries the sign.
+    let negative = limbs[LIMBS - 1] < 0;
+    if negative {
+        limbs.iter_mut().for_each(|limb| *limb = -*limb);
+        normalize(limbs);
+    }
+
+    let magnitude = match limbs.iter().rposition(|&limb| limb != 0) {
+        None => 0.0,
+        // Less than 64 bits, i.e. subnormal or small normal numbers, which are converted exactly
+        // or rounded once by the conversion.
+        Some(top) if top < 2 => {
+            let value = (limbs[1] as u64) << LIMB_BITS | limbs[0] as u64;
+            value as f64 * f64::from_bits(1)
+        }
+        Some(top) => {
+            // The top three limbs hold at least 65 bits, so a bit for the remaining limbs below
+            // them is far below the 53 bits that are kept and only breaks ties when rounding.
+            let significand = (limbs[top] as u128) << (2 * LIMB_BITS)
+                | (limbs[top - 1] as u128) << LIMB_BITS
+                | limbs[top - 2] as u128
+                | u128::from(limbs[..top - 2].iter().any(|&limb| limb != 0));
+            scale(
+                significand as f64,
+                (LIMB_BITS as i32) * (top as i32 - 2) - 1074,
+            )
+        }
+    };
+    if negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Propagate the carries so that every limb but the top one is in `0..2^LIMB_BITS`.
+fn normalize(limbs: &mut [i64]) {
+    for i in 0..limbs.len() - 1 {
+        let carry = limbs[i] >> LIMB_BITS;
+        limbs[i] -= carry << LIMB_BITS;
+        limbs[i + 1] += carry;
+    }
+}
+
+/// Compute `x * 2^exponent` in steps that are exact as long as the result is a normal number.
+fn scale(mut x: f64, mut exponent: i32) -> f64 {
+    const STEP: i32 = 1000;
+    while exponent > STEP {
+        x *= power_of_two(STEP);
+        exponent -= STEP;
+    }
+    while exponent < -STEP {
+        x *= power_of_two(-STEP);
+        exponent += STEP;
+    }
+    x * power_of_two(exponent)
+}
+
+/// `2^exponent` for exponents of normal numbers
+fn power_of_two(exponent: i32) -> f64 {
+    f64::from_bits(((exponent + 1023) as u64) << 52)
+}
@@ -0,0 +1,68 @@
+//! Overlapping MPI communication with computations on a Rayon thread pool
+//!
+//! With `Threading::Funneled`, the threads of a process may run in parallel as long as only the
+//! main thread calls MPI. A `FunneledGuard` is created on the main thread and cannot be sent to
+//! other threads, so closures that receive it run where MPI calls are allowed, while the work
+//! handed to [Rayon][rayon] only runs on its worker threads.
+//!
+//! [rayon]: https://crates.io/crates/rayon
+//!
+//! This module is available with the `rayon` feature.
+
+use std::marker::PhantomData;
+
+use crate::environment::{self, Universe};
+use crate::Threading;
+
+/// Proof that the current thread is the one that may call MPI while Rayon threads compute
+///
+/// # Examples
+/// See `examples/rayon_funneled.rs`
+pub struct FunneledGuard<'u> {
+    // Not `Send` or `Sync`, the guard stays on the thread that created it.
+    phantom: PhantomData<(&'u Universe, *const ())>,
+}
+
+impl<'u> FunneledGuard<'u> {
+    /// Create a guard on the main thread of `universe`.
+    ///
+    /// Panics if the current thread is not the main thread, i.e. the thread that initialized MPI.
+    /// In debug builds, it also panics if MPI has been initialized with `Threading::Single`,
+    /// which does not allow any other threads.
+    pub fn new(_universe: &'u Universe) -> Self {
+        debug_assert_ne!(
+            environment::threading_support(),
+            Threading::Single,
+            "Rayon threads require MPI to be initialized with at least Threading::Funneled."
+        );
+        assert!(
+            environment::is_thread_main(),
+            "A FunneledGuard must be created on the main thread."
+        );
+        FunneledGuard {
+            phantom: PhantomData,
+        }
+    }
+
+    /// Run `communicate` on the current thread while `compute` runs on the Rayon thread pool and
+    /// return both results.
+    ///
+    /// `communicate` may call MPI, `compute` must not. The current thread does not take part in
+    /// the computation, so the MPI calls are never delayed by Rayon tasks and vice versa.
+    pub fn join<C, P, RC, RP>(&self, communicate: C, compute: P) -> (RC, RP)
+    where
+        C: FnOnce(&Self) -> RC,
+        P: FnOnce() -> RP + Send,
+        RP: Send,
+    {
+        let mut computed = None;
+        let communicated = rayon::in_place_scope(|scope| {
+            scope.spawn(|_| computed = Some(compute()));
+            communicate(self)
+        });
+        (
+            communicated,
+            computed.expect("the computation has completed at the end of the scope"),
+        )
+    }
+}
@@ -0,0 +1,186 @@
+//! Callbacks around the MPI calls of this crate
+//!
+//! Performance tools traditionally intercept MPI calls through the profiling interface (PMPI) by
+//! linking a wrapper library in front of the MPI library. Tools written in Rust can instead
+//! implement `Hook` and `register()` it. Its methods are then called before and after every MPI
+//! call that this crate makes, with a `CallInfo` describing the operation.
+//!
+//! The only calls without hooks are those that can be made while MPI is not initialized or that
+//! initialize or finalize it, i.e. `MPI_Init_thread()`, `MPI_Finalize()`, `MPI_Session_init()`,
+//! `MPI_Session_finalize()`, `MPI_Initialized()`, `MPI_Finalized()`, `MPI_Get_version()` and
+//! `MPI_Get_library_version()`, the timers `MPI_Wtime()` and `MPI_Wtick()`, the polling of the
+//! progress thread of the `async` feature and the calls that only serve the bookkeeping of this
+//! crate, i.e. for error classes and strings, error handlers installed from Rust, info objects,
+//! datatype names, the status of completed operations and `MPIX_Query_cuda_support()`.
+//!
+//! Hooks are called on the thread that makes the MPI call. MPI calls made from within a hook do
+//! not invoke the hooks again, so hooks can e.g. reduce their statistics over a communicator.
+//! While no hook is registered, the cost of a call is checking a flag.
+//!
+//! # Examples
+//! See `examples/hooks.rs`
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::ffi::MPI_Fint;
+use crate::topology::Rank;
+use crate::Count;
+
+type Hooks = Arc<[Arc<dyn Hook>]>;
+
+static HOOKS: Lazy<RwLock<Hooks>> = Lazy::new(|| RwLock::new(Vec::new().into()));
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The description of an MPI call passed to a `Hook`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CallInfo {
+    pub(crate) operation: &'static str,
+    pub(crate) comm: Option<MPI_Fint>,
+    pub(crate) peer: Option<Rank>,
+    pub(crate) count: Option<Count>,
+    pub(crate) bytes: Option<u64>,
+}
+
+impl CallInfo {
+    /// The name of the MPI function, e.g. `"MPI_Send"`
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// The communicator of the call, identified by its Fortran handle (`MPI_Comm_c2f()`)
+    ///
+    /// Completion, one-sided and file operations do not have a communicator. The handle of a
+    /// communicator can be reused once it has been freed.
+    pub fn comm_id(&self) -> Option<MPI_Fint> {
+        self.comm
+    }
+
+    /// The rank of the process on the other end of a point to point or one-sided operation, or
+    /// of the root of a rooted collective operation
+    pub fn peer(&self) -> Option<Rank> {
+        self.peer
+    }
+
+    /// The number of elements sent or, for receives, received into
+    pub fn count(&self) -> Option<Count> {
+        self.count
+    }
+
+    /// The size in bytes of the elements sent or, for receives, received into
+    ///
+    /// For receives, this is the size of the receive buffer rather than that of the message. It is
+    /// `None` for calls without a buffer and sizes that MPI cannot express.
+    pub fn bytes(&self) -> Option<u64> {
+        self.bytes
+    }
+}
+
+/// Callbacks invoked before and after MPI calls
+///
+/// Both methods do nothing by default, so a hook only implements the ones it needs.
+pub trait Hook: Send + Sync {
+    /// Called before the MPI call described by `call`
+    fn before(&self, call: &CallInfo) {
+        let _ = call;
+    }
+
+    /// Called after the MPI call described by `call` returned, which took `elapsed`
+    ///
+    /// For nonblocking operations this is the time it took to start the operation, its
+    /// completion is reported by the calls that wait for or test its request.
+    fn after(&self, call: &CallInfo, elapsed: Duration) {
+        let _ = (call, elapsed);
+    }
+}
+
+/// Register `hook` to be called around all subsequent MPI calls on all threads
+///
+/// Hooks are called in the order in which they have been registered.
+///
+/// # Examples
+/// See `examples/hooks.rs`
+pub fn register(hook: Arc<dyn Hook>) {
+    let mut hooks = HOOKS.write().unwrap();
+    *hooks = hooks.iter().cloned().chain(Some(hook)).collect();
+    REGISTERED.store(true, Ordering::Release);
+}
+
+/// Unregister `hook`, which has been registered with `register()` before
+///
+/// Returns whether `hook` was registered. Calls that are already in progress still call the
+/// `after()` method of the hook.
+pub fn unregister<H: Hook + ?Sized>(hook: &Arc<H>) -> bool {
+    let hook = Arc::as_ptr(hook) as *const ();
+    let mut hooks = HOOKS.write().unwrap();
+    let remaining: Hooks = hooks
+        .iter()
+        .filter(|registered| Arc::as_ptr(registered) as *const () != hook)
+        .cloned()
+        .collect();
+    let found = remaining.len() < hooks.len();
+    REGISTERED.store(!remaining.is_empty(), Ordering::Release);
+    *hooks = remaining;
+    found
+}
+
+/// Whether a call made now has to be described for the hooks
+#[inline]
+pub(crate) fn enabled() -> bool {
+    REGISTERED.load(Ordering::Acquire) && !IN_HOOK.with(Cell::get)
+}
+
+/// The hooks around a call in progress, calling `after()` when dropped
+pub(crate) struct Active {
+    hooks: Hooks,
+    call: CallInfo,
+    start: Instant,
+}
+
+/// Call `before()` of all registered hooks for `call`
+pub(crate) fn begin(call: CallInfo) -> Option<Active> {
+    let hooks = HOOKS.read().unwrap().clone();
+    if hooks.is_empty() {
+        return None;
+    }
+    in_hook(|| hooks.iter().for_each(|hook| hook.before(&call)));
+    Some(Active {
+        hooks,
+        call,
+        start: Instant::now(),
+    })
+}
+
+impl Drop for Active {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        in_hook(|| {
+            self.hooks
+                .iter()
+                .for_each(|hook| hook.after(&self.call, elapsed))
+        });
+    }
+}
+
+/// Run `f` with the hooks disabled on this thread
+fn in_hook(f: impl FnOnce()) {
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IN_HOOK.with(|in_hook| in_hook.set(false));
+        }
+    }
+
+    IN_HOOK.with(|in_hook| in_hook.set(true));
+    let _reset = Reset;
+    f();
+}
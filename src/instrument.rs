@@ -0,0 +1,209 @@
+//! Instrumentation of the MPI calls made by this crate
+//!
+//! Every MPI call, except for those listed in the documentation of the `hooks` module, creates a
+//! `Call` describing it and keeps the `Guard` returned by `begin()` alive while it calls MPI. The
+//! guard calls the registered `hooks` before and after the call. With the `tracing` feature, it
+//! also enters a `TRACE` level span with target `mpi` and the fields `operation`, `comm`, `peer`,
+//! `count`, `datatype` and `bytes`, and emits an event with the `elapsed` time of the call when it
+//! is dropped. The description is only created if a hook is registered or such a span is enabled,
+//! so the instrumentation costs next to nothing otherwise.
+
+use conv::ConvUtil;
+
+use crate::datatype::traits::*;
+use crate::ffi::{self, MPI_Comm, MPI_Datatype};
+use crate::hooks::{self, CallInfo};
+use crate::topology::Rank;
+use crate::{with_uninitialized, Count};
+
+/// The description of an MPI call
+pub(crate) struct Call {
+    operation: &'static str,
+    comm: Option<MPI_Comm>,
+    peer: Option<Rank>,
+    count: Option<Count>,
+    datatype: Option<MPI_Datatype>,
+    // Only known up front for buffers of several datatypes, otherwise computed from the above
+    bytes: Option<u64>,
+}
+
+impl Call {
+    /// A call of the MPI function `operation` on `comm`
+    pub(crate) fn new(operation: &'static str, comm: MPI_Comm) -> Self {
+        Call {
+            operation,
+            comm: Some(comm),
+            peer: None,
+            count: None,
+            datatype: None,
+            bytes: None,
+        }
+    }
+
+    /// A call of the MPI function `operation` that does not involve a communicator, like
+    /// completing requests or one-sided and file operations
+    pub(crate) fn without_comm(operation: &'static str) -> Self {
+        Call {
+            operation,
+            comm: None,
+            peer: None,
+            count: None,
+            datatype: None,
+            bytes: None,
+        }
+    }
+
+    /// The rank of the process on the other end of a point to point or one-sided operation, or
+    /// of the root of a rooted collective operation
+    pub(crate) fn peer(self, peer: Rank) -> Self {
+        Call {
+            peer: Some(peer),
+            ..self
+        }
+    }
+
+    /// The buffer sent from or, for receives, received into
+    pub(crate) fn buffer<B: AsDatatype + Collection + ?Sized>(self, buffer: &B) -> Self {
+        self.typed(buffer.count(), &buffer.as_datatype())
+    }
+
+    /// All partitions of a buffer sent from
+    pub(crate) fn partitioned<B: Partitioned + AsDatatype + ?Sized>(self, buffer: &B) -> Self {
+        self.typed(buffer.counts().iter().sum(), &buffer.as_datatype())
+    }
+
+    /// All partitions of a buffer sent from, whose partitions differ in their datatypes
+    pub(crate) fn heterogeneous<B: HeterogeneousPartitioned + ?Sized>(self, buffer: &B) -> Self {
+        let bytes = buffer.counts().iter().zip(buffer.datatypes()).try_fold(
+            0u64,
+            |bytes, (&count, &datatype)| {
+                let size =
+                    unsafe { with_uninitialized(|size| ffi::MPI_Type_size_x(datatype, size)).1 };
+                let size: u64 = size.value_as().ok()?;
+                bytes.checked_add(size.checked_mul(count.value_as().ok()?)?)
+            },
+        );
+        Call {
+            count: Some(buffer.counts().iter().sum()),
+            bytes,
+            ..self
+        }
+    }
+
+    /// `count` elements of `datatype`
+    pub(crate) fn typed<D: Datatype + ?Sized>(self, count: Count, datatype: &D) -> Self {
+        Call {
+            count: Some(count),
+            datatype: Some(datatype.as_raw()),
+            ..self
+        }
+    }
+
+    /// The description of the call passed to the hooks
+    fn info(&self) -> CallInfo {
+        let computed = self.count.zip(self.datatype).and_then(|(count, datatype)| {
+            // The datatypes of large buffers, e.g. `LargeSlice`, are too large for
+            // `MPI_Type_size()`. A size that does not even fit an `MPI_Count` is `MPI_UNDEFINED`,
+            // which fails the conversion.
+            let size = unsafe { with_uninitialized(|size| ffi::MPI_Type_size_x(datatype, size)).1 };
+            let size: u64 = size.value_as().ok()?;
+            size.checked_mul(count.value_as().ok()?)
+        });
+        let bytes = self.bytes.or(computed);
+        CallInfo {
+            operation: self.operation,
+            comm: self.comm.map(|comm| unsafe { ffi::RSMPI_Comm_c2f(comm) }),
+            peer: self.peer,
+            count: self.count,
+            bytes,
+        }
+    }
+}
+
+/// Keeps the instrumentation of a call active until it is dropped
+pub(crate) struct Guard {
+    // Dropped before the span, so the hooks run inside of it
+    _hooks: Option<hooks::Active>,
+    #[cfg(feature = "tracing")]
+    span: Option<(tracing::span::EnteredSpan, std::time::Instant)>,
+}
+
+/// Start instrumenting the call described by `call`, which is only evaluated if needed.
+#[inline]
+pub(crate) fn begin(call: impl FnOnce() -> Call) -> Guard {
+    #[cfg(feature = "tracing")]
+    let traced = tracing::enabled!(target: "mpi", tracing::Level::TRACE);
+    #[cfg(not(feature = "tracing"))]
+    let traced = false;
+    let hooked = hooks::enabled();
+    if !traced && !hooked {
+        return Guard {
+            #[cfg(feature = "tracing")]
+            span: None,
+            _hooks: None,
+        };
+    }
+
+    let call = call();
+    let info = call.info();
+    Guard {
+        #[cfg(feature = "tracing")]
+        span: traced.then(|| {
+            (
+                trace::span(&call, &info).entered(),
+                std::time::Instant::now(),
+            )
+        }),
+        _hooks: if hooked { hooks::begin(info) } else { None },
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Guard {
+    fn drop(&mut self) {
+        if let Some((_span, start)) = &self.span {
+            tracing::trace!(target: "mpi", elapsed = ?start.elapsed());
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod trace {
+    use std::ffi::CStr;
+    use std::mem::MaybeUninit;
+    use std::os::raw::c_char;
+
+    use super::Call;
+    use crate::ffi::{self, MPI_Datatype};
+    use crate::hooks::CallInfo;
+    use crate::with_uninitialized;
+
+    /// The span of `call`, which is described by `info`
+    pub(super) fn span(call: &Call, info: &CallInfo) -> tracing::Span {
+        let datatype = call.datatype.map(name);
+        tracing::trace_span!(
+            target: "mpi",
+            "mpi",
+            operation = info.operation,
+            comm = info.comm,
+            peer = info.peer,
+            count = info.count,
+            datatype = datatype.as_deref(),
+            bytes = info.bytes,
+        )
+    }
+
+    /// The name of a datatype, which is empty for most user-defined datatypes
+    fn name(datatype: MPI_Datatype) -> String {
+        type BufType = [c_char; ffi::MPI_MAX_OBJECT_NAME as usize];
+        unsafe {
+            let mut buf = MaybeUninit::<BufType>::zeroed();
+            with_uninitialized(|len| {
+                ffi::MPI_Type_get_name(datatype, &mut (*buf.as_mut_ptr())[0], len)
+            });
+            CStr::from_ptr(buf.assume_init().as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+}
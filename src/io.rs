@@ -0,0 +1,927 @@
+//! Parallel file I/O
+//!
+//! A `File` is opened collectively by all processes of a communicator. Processes can then access
+//! the file independently (`read()`, `write_at()`, ...) or collectively (`read_all()`,
+//! `write_at_all()`, ...), the latter allowing the MPI library to combine the accesses of all
+//! processes into fewer and larger requests to the file system.
+//!
+//! Unlike communicators, files return errors instead of aborting the program by default, so all
+//! operations on a `File` return a `Result`.
+//!
+//! # Unfinished features
+//!
+//! - **13.2.5**: `MPI_File_preallocate()`
+//! - **13.2.7**: `MPI_File_get_group()`, `MPI_File_get_amode()`
+//! - **13.2.8**: File info, `MPI_File_set_info()`, `MPI_File_get_info()`
+//! - **13.3**: `MPI_File_get_view()`
+//! - **13.4.2**, **13.4.3**: Nonblocking collective data access, `MPI_File_iread_all()`, ...
+//! - **13.4.4**: Shared file pointers
+//! - **13.5**: File interoperability
+//! - **13.6.1**: Atomicity, `MPI_File_set_atomicity()`, `MPI_File_get_atomicity()`
+
+use std::ffi::CString;
+use std::io::SeekFrom;
+use std::mem;
+use std::ops::BitOr;
+use std::os::raw::{c_char, c_int};
+use std::path::Path;
+use std::sync::Arc;
+
+use conv::ConvUtil;
+
+use crate::datatype::traits::*;
+use crate::error_handler;
+use crate::ffi;
+use crate::ffi::MPI_File;
+use crate::instrument::{self, Call};
+use crate::point_to_point::Status;
+use crate::raw::traits::*;
+use crate::request::{Request, Scope, StaticScope};
+use crate::topology::Communicator;
+use crate::{with_uninitialized_checked, ErrorCode, MpiError};
+
+/// Offsets and sizes of files
+pub type Offset = ffi::MPI_Offset;
+
+const NATIVE: &[u8] = b"native\0";
+
+/// The mode in which a `File` is opened
+///
+/// Modes can be combined via `|`, e.g. `AccessMode::CREATE | AccessMode::WRITE_ONLY`. Exactly one
+/// of `READ_ONLY`, `READ_WRITE` and `WRITE_ONLY` has to be given.
+///
+/// # Standard section(s)
+///
+/// 13.2.1
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AccessMode(c_int);
+
+impl AccessMode {
+    /// Open the file for reading only
+    pub const READ_ONLY: AccessMode = AccessMode(ffi::MPI_MODE_RDONLY as c_int);
+    /// Open the file for reading and writing
+    pub const READ_WRITE: AccessMode = AccessMode(ffi::MPI_MODE_RDWR as c_int);
+    /// Open the file for writing only
+    pub const WRITE_ONLY: AccessMode = AccessMode(ffi::MPI_MODE_WRONLY as c_int);
+    /// Create the file if it does not exist
+    pub const CREATE: AccessMode = AccessMode(ffi::MPI_MODE_CREATE as c_int);
+    /// Fail if the file already exists when creating it
+    pub const EXCLUSIVE: AccessMode = AccessMode(ffi::MPI_MODE_EXCL as c_int);
+    /// Delete the file when it is closed
+    pub const DELETE_ON_CLOSE: AccessMode = AccessMode(ffi::MPI_MODE_DELETE_ON_CLOSE as c_int);
+    /// The file is not opened concurrently elsewhere
+    pub const UNIQUE_OPEN: AccessMode = AccessMode(ffi::MPI_MODE_UNIQUE_OPEN as c_int);
+    /// The file is only accessed sequentially
+    pub const SEQUENTIAL: AccessMode = AccessMode(ffi::MPI_MODE_SEQUENTIAL as c_int);
+    /// Set the initial position of all file pointers to the end of the file
+    pub const APPEND: AccessMode = AccessMode(ffi::MPI_MODE_APPEND as c_int);
+}
+
+impl BitOr for AccessMode {
+    type Output = AccessMode;
+    fn bitor(self, rhs: AccessMode) -> AccessMode {
+        AccessMode(self.0 | rhs.0)
+    }
+}
+
+/// A file opened collectively by the processes of a communicator
+///
+/// Offsets passed to the explicit offset operations (`read_at()`, `write_at()`, ...) and the
+/// positions of the individual file pointers are measured in bytes, or in elements of the
+/// elementary datatype once a view has been set via `set_view()`.
+///
+/// The file is closed when the `File` is dropped, which like `File::close()` is a collective
+/// operation.
+///
+/// # Examples
+///
+/// See `examples/file.rs`
+///
+/// # Standard section(s)
+///
+/// 13
+#[derive(Debug)]
+pub struct File(MPI_File);
+
+/// Convert `path` to the string passed to MPI.
+///
+/// On Unix, paths are arbitrary bytes and passed unchanged. Elsewhere, they have to be valid
+/// Unicode, so that they are not silently replaced by a different path.
+fn path_to_c_string(path: &Path) -> Result<CString, MpiError> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes()
+    };
+    #[cfg(not(unix))]
+    let bytes = path
+        .to_str()
+        .ok_or_else(|| MpiError::NonUnicodePath(path.to_owned()))?
+        .as_bytes();
+    Ok(CString::new(bytes)?)
+}
+
+impl File {
+    /// Collectively open the file at `path` on all processes of `comm`.
+    ///
+    /// All processes have to pass the same `path` and `mode`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.2.1 (MPI_File_open)
+    pub fn open<C, P>(comm: &C, path: P, mode: AccessMode) -> Result<File, MpiError>
+    where
+        C: Communicator + ?Sized,
+        P: AsRef<Path>,
+    {
+        let path = path_to_c_string(path.as_ref())?;
+        let _call = instrument::begin(|| Call::new("MPI_File_open", comm.as_raw()));
+        let file = unsafe {
+            with_uninitialized_checked(|file| {
+                ffi::MPI_File_open(
+                    comm.as_raw(),
+                    path.as_ptr(),
+                    mode.0,
+                    ffi::RSMPI_INFO_NULL,
+                    file,
+                )
+            })
+        }?;
+        Ok(File(file))
+    }
+
+    /// Delete the file at `path`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.2.3 (MPI_File_delete)
+    pub fn delete<P: AsRef<Path>>(path: P) -> Result<(), MpiError> {
+        let path = path_to_c_string(path.as_ref())?;
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_delete"));
+        ErrorCode::check(unsafe { ffi::MPI_File_delete(path.as_ptr(), ffi::RSMPI_INFO_NULL) })?;
+        Ok(())
+    }
+
+    /// Collectively close the file.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.2.2 (MPI_File_close)
+    pub fn close(mut self) -> Result<(), ErrorCode> {
+        error_handler::remove_file_error_handler(self.0);
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_close"));
+        ErrorCode::check(unsafe { ffi::MPI_File_close(&mut self.0) })
+    }
+
+    /// Call `handler` with the error code when an MPI function called on this file fails.
+    ///
+    /// Once `handler` returns, the MPI function returns the error code, which is then passed on by
+    /// the methods of `File`. A panic inside of `handler` aborts the program.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.3.3 (MPI_File_create_errhandler, MPI_File_set_errhandler)
+    pub fn set_error_handler<F>(&self, handler: F)
+    where
+        F: Fn(ErrorCode) + Send + Sync + 'static,
+    {
+        unsafe {
+            error_handler::set_file_error_handler(self.0, Arc::new(handler));
+        }
+    }
+
+    /// The size of the file in bytes
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.2.6 (MPI_File_get_size)
+    pub fn size(&self) -> Result<Offset, ErrorCode> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_get_size"));
+        unsafe { with_uninitialized_checked(|size| ffi::MPI_File_get_size(self.0, size)) }
+    }
+
+    /// Collectively truncate or expand the file to `size` bytes.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.2.4 (MPI_File_set_size)
+    pub fn set_size(&self, size: Offset) -> Result<(), ErrorCode> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_set_size"));
+        ErrorCode::check(unsafe { ffi::MPI_File_set_size(self.0, size) })
+    }
+
+    /// Collectively transfer all data written to the file by the calling process to the storage
+    /// device.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.6.1 (MPI_File_sync)
+    pub fn sync(&self) -> Result<(), ErrorCode> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_sync"));
+        ErrorCode::check(unsafe { ffi::MPI_File_sync(self.0) })
+    }
+
+    /// Collectively change the view of the calling process onto the file.
+    ///
+    /// The view starts `disp` bytes into the file and consists of repetitions of `filetype`, only
+    /// the parts of the file covered by `filetype` are accessible. Offsets and file pointers are
+    /// measured in elements of `etype` afterwards and reset to zero. The data is stored in the
+    /// `"native"` representation.
+    ///
+    /// All processes have to pass the same `etype`, but `disp` and `filetype` can differ, e.g. to
+    /// let every process access its own block of a global array.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file_view.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.3 (MPI_File_set_view)
+    pub fn set_view<E, F>(&self, disp: Offset, etype: E, filetype: F) -> Result<(), ErrorCode>
+    where
+        E: Datatype,
+        F: Datatype,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_set_view"));
+        ErrorCode::check(unsafe {
+            ffi::MPI_File_set_view(
+                self.0,
+                disp,
+                etype.as_raw(),
+                filetype.as_raw(),
+                NATIVE.as_ptr() as *const c_char,
+                ffi::RSMPI_INFO_NULL,
+            )
+        })
+    }
+
+    /// Move the individual file pointer of the calling process.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_seek)
+    pub fn seek(&self, pos: SeekFrom) -> Result<(), ErrorCode> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (
+                offset
+                    .value_as()
+                    .expect("Offset exceeds the range of MPI_Offset."),
+                ffi::MPI_SEEK_SET,
+            ),
+            SeekFrom::Current(offset) => (
+                offset
+                    .value_as()
+                    .expect("Offset exceeds the range of MPI_Offset."),
+                ffi::MPI_SEEK_CUR,
+            ),
+            SeekFrom::End(offset) => (
+                offset
+                    .value_as()
+                    .expect("Offset exceeds the range of MPI_Offset."),
+                ffi::MPI_SEEK_END,
+            ),
+        };
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_seek"));
+        ErrorCode::check(unsafe { ffi::MPI_File_seek(self.0, offset, whence as c_int) })
+    }
+
+    /// The position of the individual file pointer of the calling process
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_get_position)
+    pub fn position(&self) -> Result<Offset, ErrorCode> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_get_position"));
+        unsafe { with_uninitialized_checked(|offset| ffi::MPI_File_get_position(self.0, offset)) }
+    }
+
+    /// Read from the file at the individual file pointer into `buf` and advance the pointer.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_read)
+    pub fn read<B: ?Sized>(&self, buf: &mut B) -> Result<Status, ErrorCode>
+    where
+        B: BufferMut,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_read").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_read(
+                    self.0,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Collectively read from the file at the individual file pointers into `buf` and advance the
+    /// pointers.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_read_all)
+    pub fn read_all<B: ?Sized>(&self, buf: &mut B) -> Result<Status, ErrorCode>
+    where
+        B: BufferMut,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_read_all").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_read_all(
+                    self.0,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Write the contents of `buf` to the file at the individual file pointer and advance the
+    /// pointer.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_write)
+    pub fn write<B: ?Sized>(&self, buf: &B) -> Result<Status, ErrorCode>
+    where
+        B: Buffer,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_write").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_write(
+                    self.0,
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Collectively write the contents of `buf` to the file at the individual file pointers and
+    /// advance the pointers.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_write_all)
+    pub fn write_all<B: ?Sized>(&self, buf: &B) -> Result<Status, ErrorCode>
+    where
+        B: Buffer,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_write_all").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_write_all(
+                    self.0,
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Read from the file at `offset` into `buf`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.2 (MPI_File_read_at)
+    pub fn read_at<B: ?Sized>(&self, offset: Offset, buf: &mut B) -> Result<Status, ErrorCode>
+    where
+        B: BufferMut,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_read_at").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_read_at(
+                    self.0,
+                    offset,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Collectively read from the file at `offset` into `buf`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.2 (MPI_File_read_at_all)
+    pub fn read_at_all<B: ?Sized>(&self, offset: Offset, buf: &mut B) -> Result<Status, ErrorCode>
+    where
+        B: BufferMut,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_read_at_all").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_read_at_all(
+                    self.0,
+                    offset,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Write the contents of `buf` to the file at `offset`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.2 (MPI_File_write_at)
+    pub fn write_at<B: ?Sized>(&self, offset: Offset, buf: &B) -> Result<Status, ErrorCode>
+    where
+        B: Buffer,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_write_at").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_write_at(
+                    self.0,
+                    offset,
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Collectively write the contents of `buf` to the file at `offset`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.2 (MPI_File_write_at_all)
+    pub fn write_at_all<B: ?Sized>(&self, offset: Offset, buf: &B) -> Result<Status, ErrorCode>
+    where
+        B: Buffer,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_write_at_all").buffer(buf));
+        file_status(unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_File_write_at_all(
+                    self.0,
+                    offset,
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    status,
+                )
+            })
+        })
+    }
+
+    /// Initiate an immediate (non-blocking) read from the file at the individual file pointer into
+    /// `buf` and advance the pointer.
+    ///
+    /// The operation completes when the returned `Request` completes, which borrows the file so
+    /// that it cannot be closed before.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file_immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_iread)
+    pub fn immediate_read<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        buf: &'a mut Buf,
+    ) -> Result<Request<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_iread").buffer(buf));
+        let request = unsafe {
+            with_uninitialized_checked(|request| {
+                ffi::MPI_File_iread(
+                    self.0,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    request,
+                )
+            })
+        }?;
+        Ok(unsafe { Request::from_raw(request, buf, scope) })
+    }
+
+    /// Initiate an immediate (non-blocking) write of the contents of `buf` to the file at the
+    /// individual file pointer and advance the pointer.
+    ///
+    /// The operation completes when the returned `Request` completes, which borrows the file so
+    /// that it cannot be closed before.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.3 (MPI_File_iwrite)
+    pub fn immediate_write<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        buf: &'a Buf,
+    ) -> Result<Request<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_iwrite").buffer(buf));
+        let request = unsafe {
+            with_uninitialized_checked(|request| {
+                ffi::MPI_File_iwrite(
+                    self.0,
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    request,
+                )
+            })
+        }?;
+        Ok(unsafe { Request::from_raw(request, buf, scope) })
+    }
+
+    /// Initiate an immediate (non-blocking) read from the file at `offset` into `buf`.
+    ///
+    /// The operation completes when the returned `Request` completes, which borrows the file so
+    /// that it cannot be closed before.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.2 (MPI_File_iread_at)
+    pub fn immediate_read_at<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        offset: Offset,
+        buf: &'a mut Buf,
+    ) -> Result<Request<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_iread_at").buffer(buf));
+        let request = unsafe {
+            with_uninitialized_checked(|request| {
+                ffi::MPI_File_iread_at(
+                    self.0,
+                    offset,
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    request,
+                )
+            })
+        }?;
+        Ok(unsafe { Request::from_raw(request, buf, scope) })
+    }
+
+    /// Initiate an immediate (non-blocking) write of the contents of `buf` to the file at `offset`.
+    ///
+    /// The operation completes when the returned `Request` completes, which borrows the file so
+    /// that it cannot be closed before.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file_immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.2 (MPI_File_iwrite_at)
+    pub fn immediate_write_at<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        offset: Offset,
+        buf: &'a Buf,
+    ) -> Result<Request<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_iwrite_at").buffer(buf));
+        let request = unsafe {
+            with_uninitialized_checked(|request| {
+                ffi::MPI_File_iwrite_at(
+                    self.0,
+                    offset,
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    request,
+                )
+            })
+        }?;
+        Ok(unsafe { Request::from_raw(request, buf, scope) })
+    }
+
+    /// Begin a split collective read from the file at the individual file pointers into `buf`.
+    ///
+    /// The operation is completed via `SplitRead::end()`, the buffer must not be accessed in
+    /// between.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file_immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.5 (MPI_File_read_all_begin)
+    pub fn read_all_begin<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        buf: &'a mut Buf,
+    ) -> Result<SplitRead<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_File_read_all_begin").buffer(buf));
+        ErrorCode::check(unsafe {
+            ffi::MPI_File_read_all_begin(
+                self.0,
+                buf.pointer_mut(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+            )
+        })?;
+        scope.register();
+        Ok(SplitRead {
+            file: self,
+            at: false,
+            buf,
+            scope,
+        })
+    }
+
+    /// Begin a split collective write of the contents of `buf` to the file at the individual file
+    /// pointers.
+    ///
+    /// The operation is completed via `SplitWrite::end()`, the buffer must not be accessed in
+    /// between.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.5 (MPI_File_write_all_begin)
+    pub fn write_all_begin<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        buf: &'a Buf,
+    ) -> Result<SplitWrite<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        let _call =
+            instrument::begin(|| Call::without_comm("MPI_File_write_all_begin").buffer(buf));
+        ErrorCode::check(unsafe {
+            ffi::MPI_File_write_all_begin(
+                self.0,
+                buf.pointer(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+            )
+        })?;
+        scope.register();
+        Ok(SplitWrite {
+            file: self,
+            at: false,
+            buf,
+            scope,
+        })
+    }
+
+    /// Begin a split collective read from the file at `offset` into `buf`.
+    ///
+    /// The operation is completed via `SplitRead::end()`, the buffer must not be accessed in
+    /// between.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.5 (MPI_File_read_at_all_begin)
+    pub fn read_at_all_begin<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        offset: Offset,
+        buf: &'a mut Buf,
+    ) -> Result<SplitRead<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        let _call =
+            instrument::begin(|| Call::without_comm("MPI_File_read_at_all_begin").buffer(buf));
+        ErrorCode::check(unsafe {
+            ffi::MPI_File_read_at_all_begin(
+                self.0,
+                offset,
+                buf.pointer_mut(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+            )
+        })?;
+        scope.register();
+        Ok(SplitRead {
+            file: self,
+            at: true,
+            buf,
+            scope,
+        })
+    }
+
+    /// Begin a split collective write of the contents of `buf` to the file at `offset`.
+    ///
+    /// The operation is completed via `SplitWrite::end()`, the buffer must not be accessed in
+    /// between.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/file_immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.5 (MPI_File_write_at_all_begin)
+    pub fn write_at_all_begin<'a, Sc, Buf: ?Sized>(
+        &'a self,
+        scope: Sc,
+        offset: Offset,
+        buf: &'a Buf,
+    ) -> Result<SplitWrite<'a, Buf, Sc>, ErrorCode>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        let _call =
+            instrument::begin(|| Call::without_comm("MPI_File_write_at_all_begin").buffer(buf));
+        ErrorCode::check(unsafe {
+            ffi::MPI_File_write_at_all_begin(
+                self.0,
+                offset,
+                buf.pointer(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+            )
+        })?;
+        scope.register();
+        Ok(SplitWrite {
+            file: self,
+            at: true,
+            buf,
+            scope,
+        })
+    }
+}
+
+unsafe impl AsRaw for File {
+    type Raw = MPI_File;
+    fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        if self.0 != unsafe { ffi::RSMPI_FILE_NULL } {
+            error_handler::remove_file_error_handler(self.0);
+            let _call = instrument::begin(|| Call::without_comm("MPI_File_close"));
+            unsafe {
+                ffi::MPI_File_close(&mut self.0);
+            }
+        }
+    }
+}
+
+/// A split collective read started by `File::read_all_begin()` or `File::read_at_all_begin()`
+///
+/// # Panics
+///
+/// When dropped without calling `end()`.
+#[must_use]
+#[derive(Debug)]
+pub struct SplitRead<'a, Buf: ?Sized, S: Scope<'a> = StaticScope> {
+    file: &'a File,
+    at: bool,
+    buf: &'a mut Buf,
+    scope: S,
+}
+
+impl<'a, Buf: ?Sized, S: Scope<'a>> SplitRead<'a, Buf, S>
+where
+    Buf: BufferMut,
+{
+    /// Collectively complete the read.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.5 (MPI_File_read_all_end, MPI_File_read_at_all_end)
+    pub fn end(mut self) -> Result<Status, ErrorCode> {
+        let _call = instrument::begin(|| {
+            Call::without_comm(if self.at {
+                "MPI_File_read_at_all_end"
+            } else {
+                "MPI_File_read_all_end"
+            })
+        });
+        let status = unsafe {
+            with_uninitialized_checked(|status| {
+                if self.at {
+                    ffi::MPI_File_read_at_all_end(self.file.0, self.buf.pointer_mut(), status)
+                } else {
+                    ffi::MPI_File_read_all_end(self.file.0, self.buf.pointer_mut(), status)
+                }
+            })
+        };
+        unsafe {
+            self.scope.unregister();
+        }
+        mem::forget(self);
+        file_status(status)
+    }
+}
+
+impl<'a, Buf: ?Sized, S: Scope<'a>> Drop for SplitRead<'a, Buf, S> {
+    fn drop(&mut self) {
+        panic!("split collective read was dropped without being completed");
+    }
+}
+
+/// A split collective write started by `File::write_all_begin()` or
+/// `File::write_at_all_begin()`
+///
+/// # Panics
+///
+/// When dropped without calling `end()`.
+#[must_use]
+#[derive(Debug)]
+pub struct SplitWrite<'a, Buf: ?Sized, S: Scope<'a> = StaticScope> {
+    file: &'a File,
+    at: bool,
+    buf: &'a Buf,
+    scope: S,
+}
+
+impl<'a, Buf: ?Sized, S: Scope<'a>> SplitWrite<'a, Buf, S>
+where
+    Buf: Buffer,
+{
+    /// Collectively complete the write.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 13.4.5 (MPI_File_write_all_end, MPI_File_write_at_all_end)
+    pub fn end(self) -> Result<Status, ErrorCode> {
+        let _call = instrument::begin(|| {
+            Call::without_comm(if self.at {
+                "MPI_File_write_at_all_end"
+            } else {
+                "MPI_File_write_all_end"
+            })
+        });
+        let status = unsafe {
+            with_uninitialized_checked(|status| {
+                if self.at {
+                    ffi::MPI_File_write_at_all_end(self.file.0, self.buf.pointer(), status)
+                } else {
+                    ffi::MPI_File_write_all_end(self.file.0, self.buf.pointer(), status)
+                }
+            })
+        };
+        unsafe {
+            self.scope.unregister();
+        }
+        mem::forget(self);
+        file_status(status)
+    }
+}
+
+impl<'a, Buf: ?Sized, S: Scope<'a>> Drop for SplitWrite<'a, Buf, S> {
+    fn drop(&mut self) {
+        panic!("split collective write was dropped without being completed");
+    }
+}
+
+fn file_status(status: Result<ffi::MPI_Status, ErrorCode>) -> Result<Status, ErrorCode> {
+    status.map(Status::from_raw)
+}
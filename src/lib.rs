@@ -86,8 +86,8 @@
 //!
 //! - **Groups, Contexts, Communicators**:
 //!   - Group and (Intra-)Communicator management from section 6 is mostly complete.
-//!   - no Inter-Communicators
-//!   - no process topologies
+//!   - Inter-Communicators
+//!   - process topologies
 //! - **Point to point communication**:
 //!   - standard, buffered, synchronous and ready mode send in blocking and non-blocking variants
 //!   - receive in blocking and non-blocking variants
@@ -103,13 +103,16 @@
 //!   - varying counts operations
 //!   - reductions/scans
 //!   - blocking and non-blocking variants
+//! - **One-sided communication (RMA)**: windows, put, get, accumulate with fence and lock
+//! synchronization
+//! - **Parallel file I/O**: independent and collective reads and writes
 //! - **Datatypes**: Bridging between Rust types and MPI basic types as well as custom MPI datatypes
 //! which can act as views into buffers.
+//! - **MPI 4.0**, if supported by the MPI library: partitioned point-to-point communication,
+//! persistent collectives and the sessions model
 //!
 //! Not supported (yet):
 //!
-//! - One-sided communication (RMA)
-//! - MPI parallel I/O
 //! - A million small things
 //!
 //! The sub-modules contain a more detailed description of which features are and are not
@@ -123,8 +126,10 @@
 //!
 //! [MPIspec]: http://www.mpi-forum.org/docs/docs.html
 
+use std::ffi::CStr;
+use std::fmt;
 use std::mem::MaybeUninit;
-use std::os::raw::c_int;
+use std::os::raw::{c_char, c_int};
 
 /// The raw C language MPI API
 ///
@@ -138,13 +143,29 @@ pub mod ffi {
 }
 
 pub mod attribute;
+#[cfg(feature = "checked")]
+mod checked;
 pub mod collective;
+#[cfg(feature = "cuda")]
+pub mod cuda;
 pub mod datatype;
 pub mod environment;
+mod error_handler;
+mod exact_sum;
+#[cfg(feature = "rayon")]
+pub mod funneled;
+pub mod hooks;
+mod instrument;
+pub mod io;
 pub mod point_to_point;
 pub mod raw;
 pub mod request;
+pub mod session;
+pub mod shared_memory;
 pub mod topology;
+#[cfg(feature = "ulfm")]
+pub mod ulfm;
+pub mod window;
 
 /// Re-exports all traits.
 pub mod traits {
@@ -154,6 +175,9 @@ pub mod traits {
     pub use crate::point_to_point::traits::*;
     pub use crate::raw::traits::*;
     pub use crate::topology::traits::*;
+    #[cfg(feature = "ulfm")]
+    pub use crate::ulfm::traits::*;
+    pub use crate::window::traits::*;
 
     // Re-export derives
     #[cfg(feature = "derive")]
@@ -171,7 +195,8 @@ pub mod internal {
 
 #[doc(inline)]
 pub use crate::environment::{
-    initialize, initialize_with_threading, time, time_resolution, Threading,
+    initialize, initialize_shared, initialize_with_threading, time, time_resolution, with_universe,
+    with_universe_abort_code, Threading,
 };
 
 use crate::ffi::MPI_Aint;
@@ -187,6 +212,9 @@ pub type Address = MPI_Aint;
 /// Reexport the Rank type
 pub use crate::topology::Rank;
 
+/// Reexport the File type
+pub use crate::io::File;
+
 /// IntArray is used to translate Rust bool values to and from the int-bool types preferred by MPI
 /// without incurring allocation in the common case.
 type IntArray = smallvec::SmallVec<[c_int; 8]>;
@@ -200,6 +228,17 @@ where
     (res, uninitialized.assume_init())
 }
 
+/// Like `with_uninitialized()`, but the output is only assumed to be initialized if `f` returns
+/// `MPI_SUCCESS`.
+unsafe fn with_uninitialized_checked<F, U>(f: F) -> Result<U, ErrorCode>
+where
+    F: FnOnce(*mut U) -> c_int,
+{
+    let mut uninitialized = MaybeUninit::uninit();
+    ErrorCode::check(f(uninitialized.as_mut_ptr()))?;
+    Ok(uninitialized.assume_init())
+}
+
 unsafe fn with_uninitialized2<F, U1, U2, R>(f: F) -> (R, U1, U2)
 where
     F: FnOnce(*mut U1, *mut U2) -> R,
@@ -216,9 +255,11 @@ where
 
 /// Errors
 ///
-/// RSMPI is currently configured with MPI_ERRORS_ARE_FATAL, but:
+/// RSMPI is configured with MPI_ERRORS_ARE_FATAL by default, but:
 ///
-/// 1. we intend to remove this restriction at some point
+/// 1. communicators can be switched to MPI_ERRORS_RETURN using
+/// `Communicator::set_errors_return()`, after which error codes of MPI functions can be
+/// turned into errors with `ErrorCode::check()`
 ///
 /// 2. we need to be able to return parse errors and it seems better to make a
 /// stable error type than to propagate raw types like ``std::ffi::NulError` in
@@ -235,4 +276,76 @@ pub enum MpiError {
     /// CString::new fails if a Rust string contains interior 0 bytes
     #[error("An interior 0 byte was found in string")]
     StringNul(#[from] std::ffi::NulError),
+    /// A path is not valid Unicode on a platform that does not pass paths as bytes
+    #[error("The path {0:?} is not valid Unicode")]
+    NonUnicodePath(std::path::PathBuf),
+    /// An MPI function returned an error code
+    #[error(transparent)]
+    Code(#[from] ErrorCode),
+    /// A message could not be serialized or deserialized
+    #[cfg(feature = "serde")]
+    #[error("Serialization failed: {0}")]
+    Serialization(#[from] bincode::Error),
+}
+
+/// An error code returned by an MPI function
+///
+/// # Examples
+///
+/// See `examples/errors_return.rs`
+///
+/// # Standard section(s)
+///
+/// 9.4
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ErrorCode(Error);
+
+impl ErrorCode {
+    /// Turns the value returned by an MPI function into a `Result`.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes, on all
+    /// other communicators errors abort the program.
+    pub fn check(code: Error) -> Result<(), ErrorCode> {
+        if code == ffi::MPI_SUCCESS as Error {
+            Ok(())
+        } else {
+            Err(ErrorCode(code))
+        }
+    }
+
+    /// The raw error code
+    pub fn raw(&self) -> Error {
+        self.0
+    }
+
+    /// The error class of the error code, an implementation independent error code
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.4, see MPI_Error_class
+    pub fn class(&self) -> ErrorCode {
+        ErrorCode(unsafe { with_uninitialized(|class| ffi::MPI_Error_class(self.0, class)).1 })
+    }
+
+    /// The implementation specific message describing the error code
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.4, see MPI_Error_string
+    pub fn message(&self) -> String {
+        let mut buf = [0 as c_char; ffi::MPI_MAX_ERROR_STRING as usize];
+        let mut len: c_int = 0;
+        unsafe {
+            ffi::MPI_Error_string(self.0, buf.as_mut_ptr(), &mut len);
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        }
+    }
 }
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl std::error::Error for ErrorCode {}
@@ -7,12 +7,16 @@
 //! # Unfinished features
 //!
 //! - **3.2.6**: `MPI_STATUS_IGNORE`
-//! - **3.6**: Buffer usage, `MPI_Buffer_attach()`, `MPI_Buffer_detach()`
-//! - **3.9**: Persistent requests, `MPI_Send_init()`, `MPI_Bsend_init()`, `MPI_Ssend_init()`,
-//! `MPI_Rsend_init()`, `MPI_Recv_init()`, `MPI_Start()`, `MPI_Startall()`
 
 use std::alloc::{self, Layout};
-use std::mem::{transmute, MaybeUninit};
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::marker::PhantomData;
+use std::mem::{self, transmute, MaybeUninit};
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
 use std::{fmt, ptr};
 
 use conv::ConvUtil;
@@ -20,16 +24,21 @@ use conv::ConvUtil;
 use super::{Count, Tag};
 
 use crate::ffi;
-use crate::ffi::{MPI_Message, MPI_Status};
+use crate::ffi::{MPI_Comm, MPI_Message, MPI_Request, MPI_Status};
 
+#[cfg(feature = "checked")]
+use crate::checked;
 use crate::datatype::traits::*;
+use crate::instrument::{self, Call};
 use crate::raw::traits::*;
-use crate::request::{Request, Scope, StaticScope};
+use crate::request::{PartitionedRequest, PersistentRequest, Request, Scope, StaticScope};
 use crate::topology::traits::*;
 use crate::topology::{AnyProcess, CommunicatorRelation, Process, Rank};
-use crate::{with_uninitialized, with_uninitialized2};
-
-// TODO: rein in _with_tag ugliness, use optional tags or make tag part of Source and Destination
+#[cfg(feature = "serde")]
+use crate::MpiError;
+use crate::{with_uninitialized, with_uninitialized2, with_uninitialized_checked, ErrorCode};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
 
 /// Point to point communication traits
 pub mod traits {
@@ -51,6 +60,12 @@ pub unsafe trait Source: AsCommunicator {
     /// `Rank` that identifies the source
     fn source_rank(&self) -> Rank;
 
+    /// `Tag` matched by the receive operations that do not take a tag, any tag unless the source
+    /// has been `tagged()`
+    fn match_tag(&self) -> Tag {
+        any_tag()
+    }
+
     /// Probe a source for incoming messages.
     ///
     /// Probe `Source` `&self` for incoming messages with a certain tag.
@@ -64,6 +79,11 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.1
     fn probe_with_tag(&self, tag: Tag) -> Status {
+        #[cfg(feature = "checked")]
+        checked::unsupported("probe");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Probe", self.as_communicator().as_raw()).peer(self.source_rank())
+        });
         unsafe {
             Status(
                 with_uninitialized(|status| {
@@ -92,7 +112,7 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.1
     fn probe(&self) -> Status {
-        self.probe_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.probe_with_tag(self.match_tag())
     }
 
     /// Probe a source for incoming messages with guaranteed reception.
@@ -107,6 +127,11 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.2
     fn matched_probe_with_tag(&self, tag: Tag) -> (Message, Status) {
+        #[cfg(feature = "checked")]
+        checked::unsupported("matched_probe");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Mprobe", self.as_communicator().as_raw()).peer(self.source_rank())
+        });
         let (_, message, status) = unsafe {
             with_uninitialized2(|message, status| {
                 ffi::MPI_Mprobe(
@@ -133,7 +158,7 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.2
     fn matched_probe(&self) -> (Message, Status) {
-        self.matched_probe_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.matched_probe_with_tag(self.match_tag())
     }
 
     /// Receive a message containing a single instance of type `Msg`.
@@ -148,24 +173,56 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
-        unsafe {
-            let (_, msg, status) = with_uninitialized2(|msg, status| {
+        self.try_receive_with_tag(tag)
+            .unwrap_or_else(|code| panic!("Receiving a message failed: {}", code))
+    }
+
+    /// Like `receive_with_tag()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4
+    fn try_receive_with_tag<Msg>(&self, tag: Tag) -> Result<(Msg, Status), ErrorCode>
+    where
+        Msg: Equivalence,
+    {
+        let source = self.source_rank();
+        #[cfg(feature = "checked")]
+        let (source, tag) = {
+            let header = checked::receive_header(source, tag, self.as_communicator().as_raw());
+            header.check(&Msg::equivalent_datatype(), 1);
+            (header.source, header.tag)
+        };
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Recv", self.as_communicator().as_raw())
+                .peer(source)
+                .typed(1, &Msg::equivalent_datatype())
+        });
+        let mut msg = MaybeUninit::<Msg>::uninit();
+        let status = unsafe {
+            with_uninitialized_checked(|status| {
                 ffi::MPI_Recv(
-                    msg as _,
+                    msg.as_mut_ptr() as _,
                     1,
                     Msg::equivalent_datatype().as_raw(),
-                    self.source_rank(),
+                    source,
                     tag,
                     self.as_communicator().as_raw(),
                     status,
                 )
-            });
-            let status = Status(status);
-            if status.count(Msg::equivalent_datatype()) == 0 {
-                panic!("Received an empty message.");
-            }
-            (msg, status)
+            })
+        }?;
+        let status = Status(status);
+        if status.count(Msg::equivalent_datatype()) == 0 {
+            panic!("Received an empty message.");
         }
+        Ok((unsafe { msg.assume_init() }, status))
     }
 
     /// Receive a message containing a single instance of type `Msg`.
@@ -190,7 +247,56 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
-        self.receive_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.receive_with_tag(self.match_tag())
+    }
+
+    /// Like `receive()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4
+    fn try_receive<Msg>(&self) -> Result<(Msg, Status), ErrorCode>
+    where
+        Msg: Equivalence,
+    {
+        self.try_receive_with_tag(self.match_tag())
+    }
+
+    /// Receive a message containing a single `char`.
+    ///
+    /// Receive a message from `Source` `&self` tagged `tag` containing a `char`, which is sent as
+    /// a `u32`. A received value that is not a Unicode scalar value is returned as the error.
+    ///
+    /// # Examples
+    /// See `examples/send_receive.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4
+    fn receive_char_with_tag(&self, tag: Tag) -> (Result<char, u32>, Status) {
+        let (value, status) = self.receive_with_tag::<u32>(tag);
+        (char::from_u32(value).ok_or(value), status)
+    }
+
+    /// Receive a message containing a single `char`.
+    ///
+    /// Receive a message from `Source` `&self` containing a `char`, which is sent as a `u32`. A
+    /// received value that is not a Unicode scalar value is returned as the error.
+    ///
+    /// # Examples
+    /// See `examples/send_receive.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4
+    fn receive_char(&self) -> (Result<char, u32>, Status) {
+        self.receive_char_with_tag(self.match_tag())
     }
 
     /// Receive a message into a `Buffer`.
@@ -204,22 +310,55 @@ pub unsafe trait Source: AsCommunicator {
     where
         Buf: BufferMut,
     {
-        unsafe {
-            Status(
-                with_uninitialized(|status| {
-                    ffi::MPI_Recv(
-                        buf.pointer_mut(),
-                        buf.count(),
-                        buf.as_datatype().as_raw(),
-                        self.source_rank(),
-                        tag,
-                        self.as_communicator().as_raw(),
-                        status,
-                    )
-                })
-                .1,
-            )
-        }
+        self.try_receive_into_with_tag(buf, tag)
+            .unwrap_or_else(|code| panic!("Receiving a message failed: {}", code))
+    }
+
+    /// Like `receive_into_with_tag()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4
+    fn try_receive_into_with_tag<Buf: ?Sized>(
+        &self,
+        buf: &mut Buf,
+        tag: Tag,
+    ) -> Result<Status, ErrorCode>
+    where
+        Buf: BufferMut,
+    {
+        let source = self.source_rank();
+        #[cfg(feature = "checked")]
+        let (source, tag) = {
+            let header = checked::receive_header(source, tag, self.as_communicator().as_raw());
+            header.check(&buf.as_datatype(), buf.count());
+            (header.source, header.tag)
+        };
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Recv", self.as_communicator().as_raw())
+                .peer(source)
+                .buffer(buf)
+        });
+        let status = unsafe {
+            with_uninitialized_checked(|status| {
+                ffi::MPI_Recv(
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    source,
+                    tag,
+                    self.as_communicator().as_raw(),
+                    status,
+                )
+            })
+        }?;
+        Ok(Status(status))
     }
 
     /// Receive a message into a `Buffer`.
@@ -233,7 +372,25 @@ pub unsafe trait Source: AsCommunicator {
     where
         Buf: BufferMut,
     {
-        self.receive_into_with_tag(buf, unsafe { ffi::RSMPI_ANY_TAG })
+        self.receive_into_with_tag(buf, self.match_tag())
+    }
+
+    /// Like `receive_into()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4
+    fn try_receive_into<Buf: ?Sized>(&self, buf: &mut Buf) -> Result<Status, ErrorCode>
+    where
+        Buf: BufferMut,
+    {
+        self.try_receive_into_with_tag(buf, self.match_tag())
     }
 
     /// Receive a message containing multiple instances of type `Msg` into a `Vec`.
@@ -248,6 +405,24 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
+        #[cfg(feature = "checked")]
+        {
+            let comm = self.as_communicator().as_raw();
+            let header = checked::receive_header(self.source_rank(), tag, comm);
+            let _call = instrument::begin(|| Call::new("MPI_Mprobe", comm).peer(header.source));
+            let (_, message, status) = unsafe {
+                with_uninitialized2(|message, status| {
+                    ffi::MPI_Mprobe(header.source, header.tag, comm, message, status)
+                })
+            };
+            let status = Status(status);
+            header.check(
+                &Msg::equivalent_datatype(),
+                status.count(Msg::equivalent_datatype()),
+            );
+            (Message(message), status).matched_receive_vec()
+        }
+        #[cfg(not(feature = "checked"))]
         self.matched_probe_with_tag(tag).matched_receive_vec()
     }
 
@@ -266,7 +441,45 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
-        self.receive_vec_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.receive_vec_with_tag(self.match_tag())
+    }
+
+    /// Receive a serialized message and deserialize it into a `Msg`.
+    ///
+    /// Receive a message from `Source` `&self` tagged `tag` that was sent with
+    /// `Destination::send_serialized_with_tag()`. The length of the message is determined by a
+    /// matched probe.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4, 3.8.2
+    #[cfg(feature = "serde")]
+    fn receive_deserialized_with_tag<Msg>(&self, tag: Tag) -> Result<(Msg, Status), MpiError>
+    where
+        Msg: DeserializeOwned,
+    {
+        let (bytes, status) = self.receive_vec_with_tag::<u8>(tag);
+        Ok((bincode::deserialize(&bytes)?, status))
+    }
+
+    /// Receive a serialized message and deserialize it into a `Msg`.
+    ///
+    /// Receive a message from `Source` `&self` that was sent with
+    /// `Destination::send_serialized()`. This allows exchanging types like `String`, `HashMap` or
+    /// enums with data without describing them with datatypes, at the cost of serializing them.
+    ///
+    /// # Examples
+    /// See `examples/serde.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.4, 3.8.2
+    #[cfg(feature = "serde")]
+    fn receive_deserialized<Msg>(&self) -> Result<(Msg, Status), MpiError>
+    where
+        Msg: DeserializeOwned,
+    {
+        self.receive_deserialized_with_tag(self.match_tag())
     }
 
     /// Initiate an immediate (non-blocking) receive operation.
@@ -286,6 +499,13 @@ pub unsafe trait Source: AsCommunicator {
         Buf: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_receive_into");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Irecv", self.as_communicator().as_raw())
+                .peer(self.source_rank())
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -325,7 +545,151 @@ pub unsafe trait Source: AsCommunicator {
         Buf: 'a + BufferMut,
         Sc: Scope<'a>,
     {
-        self.immediate_receive_into_with_tag(scope, buf, unsafe { ffi::RSMPI_ANY_TAG })
+        self.immediate_receive_into_with_tag(scope, buf, self.match_tag())
+    }
+
+    /// Create a persistent request for receiving messages matching `tag` into `buf`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.9
+    fn receive_init_with_tag<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+        tag: Tag,
+    ) -> PersistentRequest<'a, Buf, Sc>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("receive_init");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Recv_init", self.as_communicator().as_raw())
+                .peer(self.source_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let request = with_uninitialized(|request| {
+                ffi::MPI_Recv_init(
+                    buf.pointer_mut(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    self.source_rank(),
+                    tag,
+                    self.as_communicator().as_raw(),
+                    request,
+                )
+            })
+            .1;
+            PersistentRequest::from_raw(request, buf, scope)
+        }
+    }
+
+    /// Create a persistent request for receiving messages into `buf`.
+    ///
+    /// # Examples
+    /// See `examples/persistent.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.9
+    fn receive_init<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+    ) -> PersistentRequest<'a, Buf, Sc>
+    where
+        Buf: 'a + BufferMut,
+        Sc: Scope<'a>,
+    {
+        self.receive_init_with_tag(scope, buf, self.match_tag())
+    }
+
+    /// Create a partitioned request for receiving a message matching `tag` into `buf` divided
+    /// into `partitions` equally sized partitions.
+    ///
+    /// Fails if the MPI library does not support partitioned communication, which was introduced
+    /// in MPI 4.0.
+    ///
+    /// # Panics
+    ///
+    /// If `partitions` is zero or the length of `buf` is not a multiple of `partitions`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.2.1
+    fn partitioned_receive_init_with_tag<'a, Sc, T>(
+        &self,
+        scope: Sc,
+        buf: &'a mut [T],
+        partitions: usize,
+        tag: Tag,
+    ) -> Result<PartitionedRequest<'a, T, Sc>, ErrorCode>
+    where
+        T: 'a + Equivalence,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("partitioned_receive_init");
+        let count = partition_count(buf.len(), partitions);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Precv_init", self.as_communicator().as_raw())
+                .peer(self.source_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Precv_init(
+                    buf.as_mut_ptr() as _,
+                    partitions
+                        .value_as()
+                        .expect("Number of partitions cannot be expressed as a c_int."),
+                    count,
+                    T::equivalent_datatype().as_raw(),
+                    self.source_rank(),
+                    tag,
+                    self.as_communicator().as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PartitionedRequest::from_raw(
+                request, buf, partitions, false, scope,
+            ))
+        }
+    }
+
+    /// Create a partitioned request for receiving a message into `buf` divided into `partitions`
+    /// equally sized partitions.
+    ///
+    /// Partitioned receives cannot match any tag. If the source has been `tagged()`, this
+    /// receives messages with its tag, otherwise with the default tag used by
+    /// `Destination::partitioned_send_init()`.
+    ///
+    /// # Examples
+    /// See `examples/partitioned.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.2.1
+    fn partitioned_receive_init<'a, Sc, T>(
+        &self,
+        scope: Sc,
+        buf: &'a mut [T],
+        partitions: usize,
+    ) -> Result<PartitionedRequest<'a, T, Sc>, ErrorCode>
+    where
+        T: 'a + Equivalence,
+        Sc: Scope<'a>,
+    {
+        let tag = match self.match_tag() {
+            tag if tag == any_tag() => Tag::default(),
+            tag => tag,
+        };
+        self.partitioned_receive_init_with_tag(scope, buf, partitions, tag)
     }
 
     /// Initiate a non-blocking receive operation for messages matching tag `tag`.
@@ -337,6 +701,13 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_receive");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Irecv", self.as_communicator().as_raw())
+                .peer(self.source_rank())
+                .typed(1, &Msg::equivalent_datatype())
+        });
         unsafe {
             let val = alloc::alloc(Layout::new::<Msg>()) as *mut Msg;
             let (_, request) = with_uninitialized(|request| {
@@ -369,7 +740,48 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
-        self.immediate_receive_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.immediate_receive_with_tag(self.match_tag())
+    }
+
+    /// Initiate a non-blocking receive operation of a message of unknown length matching tag
+    /// `tag` into a newly allocated `Vec`.
+    ///
+    /// The message is matched with `MPI_Improbe()` whenever the returned future is tested, and
+    /// received with `MPI_Imrecv()` into a `Vec` of the right length once it has arrived.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.2, 3.8.3
+    fn immediate_receive_vec_with_tag<Msg>(&self, tag: Tag) -> ReceiveVecFuture<'_, Msg>
+    where
+        Msg: Equivalence,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_receive_vec");
+        ReceiveVecFuture {
+            state: ReceiveVecState::Probing {
+                source: self.source_rank(),
+                tag,
+                comm: self.as_communicator().as_raw(),
+            },
+            phantom: PhantomData,
+        }
+    }
+
+    /// Initiate a non-blocking receive operation of a message of unknown length into a newly
+    /// allocated `Vec`.
+    ///
+    /// # Examples
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.2, 3.8.3
+    fn immediate_receive_vec<Msg>(&self) -> ReceiveVecFuture<'_, Msg>
+    where
+        Msg: Equivalence,
+    {
+        self.immediate_receive_vec_with_tag(self.match_tag())
     }
 
     /// Asynchronously probe a source for incoming messages.
@@ -382,6 +794,11 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.1
     fn immediate_probe_with_tag(&self, tag: Tag) -> Option<Status> {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_probe");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Iprobe", self.as_communicator().as_raw()).peer(self.source_rank())
+        });
         unsafe {
             let mut status = MaybeUninit::uninit();
 
@@ -413,7 +830,7 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.1
     fn immediate_probe(&self) -> Option<Status> {
-        self.immediate_probe_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.immediate_probe_with_tag(self.match_tag())
     }
 
     /// Asynchronously probe a source for incoming messages with guaranteed reception.
@@ -427,6 +844,11 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.2
     fn immediate_matched_probe_with_tag(&self, tag: Tag) -> Option<(Message, Status)> {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_matched_probe");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Improbe", self.as_communicator().as_raw()).peer(self.source_rank())
+        });
         unsafe {
             let mut message = MaybeUninit::uninit();
             let mut status = MaybeUninit::uninit();
@@ -461,7 +883,7 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.2
     fn immediate_matched_probe(&self) -> Option<(Message, Status)> {
-        self.immediate_matched_probe_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+        self.immediate_matched_probe_with_tag(self.match_tag())
     }
 }
 
@@ -489,6 +911,12 @@ pub trait Destination: AsCommunicator {
     /// `Rank` that identifies the destination
     fn destination_rank(&self) -> Rank;
 
+    /// `Tag` of the messages sent by the operations that do not take a tag, `0` unless the
+    /// destination has been `tagged()`
+    fn message_tag(&self) -> Tag {
+        Tag::default()
+    }
+
     /// Blocking standard mode send operation
     ///
     /// Send the contents of a `Buffer` to the `Destination` `&self` and tag it.
@@ -500,21 +928,53 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
-        unsafe {
-            ffi::MPI_Send(
-                buf.pointer(),
-                buf.count(),
-                buf.as_datatype().as_raw(),
-                self.destination_rank(),
-                tag,
-                self.as_communicator().as_raw(),
-            );
-        }
+        let _ = self.try_send_with_tag(buf, tag);
     }
 
-    /// Blocking standard mode send operation
+    /// Like `send_with_tag()`, but returns the error code of a failing MPI function.
     ///
-    /// Send the contents of a `Buffer` to the `Destination` `&self`.
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    fn try_send_with_tag<Buf: ?Sized>(&self, buf: &Buf, tag: Tag) -> Result<(), ErrorCode>
+    where
+        Buf: Buffer,
+    {
+        #[cfg(feature = "checked")]
+        checked::send_header(
+            checked::Mode::Standard,
+            &buf.as_datatype(),
+            buf.count(),
+            self.destination_rank(),
+            tag,
+            self.as_communicator().as_raw(),
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Send", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        ErrorCode::check(unsafe {
+            ffi::MPI_Send(
+                buf.pointer(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+                self.destination_rank(),
+                tag,
+                self.as_communicator().as_raw(),
+            )
+        })
+    }
+
+    /// Blocking standard mode send operation
+    ///
+    /// Send the contents of a `Buffer` to the `Destination` `&self`.
     ///
     /// # Examples
     ///
@@ -537,7 +997,62 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
-        self.send_with_tag(buf, Tag::default())
+        self.send_with_tag(buf, self.message_tag())
+    }
+
+    /// Like `send()`, but returns the error code of a failing MPI function.
+    ///
+    /// Only communicators set to `Communicator::set_errors_return()` return error codes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    fn try_send<Buf: ?Sized>(&self, buf: &Buf) -> Result<(), ErrorCode>
+    where
+        Buf: Buffer,
+    {
+        self.try_send_with_tag(buf, self.message_tag())
+    }
+
+    /// Serialize `msg` and send it in blocking standard mode
+    ///
+    /// Send the serialized `msg` to the `Destination` `&self` and tag it, to be received with
+    /// `Source::receive_deserialized_with_tag()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    #[cfg(feature = "serde")]
+    fn send_serialized_with_tag<Msg: ?Sized>(&self, msg: &Msg, tag: Tag) -> Result<(), MpiError>
+    where
+        Msg: Serialize,
+    {
+        let bytes = bincode::serialize(msg)?;
+        self.send_with_tag(&bytes[..], tag);
+        Ok(())
+    }
+
+    /// Serialize `msg` and send it in blocking standard mode
+    ///
+    /// Send the serialized `msg` to the `Destination` `&self`, to be received with
+    /// `Source::receive_deserialized()`.
+    ///
+    /// # Examples
+    /// See `examples/serde.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    #[cfg(feature = "serde")]
+    fn send_serialized<Msg: ?Sized>(&self, msg: &Msg) -> Result<(), MpiError>
+    where
+        Msg: Serialize,
+    {
+        self.send_serialized_with_tag(msg, self.message_tag())
     }
 
     /// Blocking buffered mode send operation
@@ -551,6 +1066,20 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
+        #[cfg(feature = "checked")]
+        checked::send_header(
+            checked::Mode::Buffered,
+            &buf.as_datatype(),
+            buf.count(),
+            self.destination_rank(),
+            tag,
+            self.as_communicator().as_raw(),
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Bsend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Bsend(
                 buf.pointer(),
@@ -574,7 +1103,7 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
-        self.buffered_send_with_tag(buf, Tag::default())
+        self.buffered_send_with_tag(buf, self.message_tag())
     }
 
     /// Blocking synchronous mode send operation
@@ -590,6 +1119,20 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
+        #[cfg(feature = "checked")]
+        checked::send_header(
+            checked::Mode::Synchronous,
+            &buf.as_datatype(),
+            buf.count(),
+            self.destination_rank(),
+            tag,
+            self.as_communicator().as_raw(),
+        );
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ssend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Ssend(
                 buf.pointer(),
@@ -615,7 +1158,7 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
-        self.synchronous_send_with_tag(buf, Tag::default())
+        self.synchronous_send_with_tag(buf, self.message_tag())
     }
 
     /// Blocking ready mode send operation
@@ -648,6 +1191,18 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
+        // The matching checked receive only takes the header first, a standard mode send is always
+        // a valid replacement of a ready mode send.
+        #[cfg(feature = "checked")]
+        if checked::enabled() {
+            self.send_with_tag(buf, tag);
+            return;
+        }
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Rsend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Rsend(
                 buf.pointer(),
@@ -690,7 +1245,7 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
-        self.ready_send_with_tag(buf, Tag::default())
+        self.ready_send_with_tag(buf, self.message_tag())
     }
 
     /// Initiate an immediate (non-blocking) standard mode send operation.
@@ -710,6 +1265,13 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_send");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Isend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -745,7 +1307,7 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        self.immediate_send_with_tag(scope, buf, Tag::default())
+        self.immediate_send_with_tag(scope, buf, self.message_tag())
     }
 
     /// Initiate an immediate (non-blocking) buffered mode send operation.
@@ -765,6 +1327,13 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_buffered_send");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ibsend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -785,177 +1354,519 @@ pub trait Destination: AsCommunicator {
         }
     }
 
-    /// Initiate an immediate (non-blocking) buffered mode send operation.
-    ///
-    /// Initiate sending the data in `buf` in buffered mode.
+    /// Initiate an immediate (non-blocking) buffered mode send operation.
+    ///
+    /// Initiate sending the data in `buf` in buffered mode.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.2
+    fn immediate_buffered_send<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a Buf,
+    ) -> Request<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        self.immediate_buffered_send_with_tag(scope, buf, self.message_tag())
+    }
+
+    /// Initiate an immediate (non-blocking) synchronous mode send operation.
+    ///
+    /// Initiate sending the data in `buf` in synchronous mode and tag it.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.2
+    fn immediate_synchronous_send_with_tag<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a Buf,
+        tag: Tag,
+    ) -> Request<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_synchronous_send");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Issend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            Request::from_raw(
+                with_uninitialized(|request| {
+                    ffi::MPI_Issend(
+                        buf.pointer(),
+                        buf.count(),
+                        buf.as_datatype().as_raw(),
+                        self.destination_rank(),
+                        tag,
+                        self.as_communicator().as_raw(),
+                        request,
+                    )
+                })
+                .1,
+                buf,
+                scope,
+            )
+        }
+    }
+
+    /// Initiate an immediate (non-blocking) synchronous mode send operation.
+    ///
+    /// Initiate sending the data in `buf` in synchronous mode.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.2
+    fn immediate_synchronous_send<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a Buf,
+    ) -> Request<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        self.immediate_synchronous_send_with_tag(scope, buf, self.message_tag())
+    }
+
+    /// Initiate an immediate (non-blocking) ready mode send operation.
+    ///
+    /// Initiate sending the data in `buf` in ready mode and tag it.
+    ///
+    /// # Safety
+    ///
+    /// Use of this function is erroneous if the matching receive operation has
+    /// not already been started. MPI-4.1 §3.4 Communication Modes:
+    ///
+    /// > A send that uses the *ready* communication mode may be started only if
+    /// > the matching receive is already started. Otherwise, the operation is
+    /// > erroneous and its outcome is undefined. On some systems, this allows the
+    /// > removal of a hand-shake protocol that is otherwise required and results
+    /// > in improved performance.
+    ///
+    /// Moreover, in case such an error is detected, it may not be reportable.
+    /// MPI-4.1 §2.8 Error Reporting:
+    ///
+    /// > In a few cases, the error may occur after all calls that relate to the
+    /// > operation have returned, so that no error value can be used to indicate
+    /// > the nature of the error (e.g., an erroneous program on the receiver in a
+    /// > send with the *ready* mode).
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.4, 3.7.2
+    unsafe fn immediate_ready_send_with_tag<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a Buf,
+        tag: Tag,
+    ) -> Request<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("immediate_ready_send");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Irsend", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            Request::from_raw(
+                with_uninitialized(|request| {
+                    ffi::MPI_Irsend(
+                        buf.pointer(),
+                        buf.count(),
+                        buf.as_datatype().as_raw(),
+                        self.destination_rank(),
+                        tag,
+                        self.as_communicator().as_raw(),
+                        request,
+                    )
+                })
+                .1,
+                buf,
+                scope,
+            )
+        }
+    }
+
+    /// Initiate an immediate (non-blocking) ready mode send operation.
+    ///
+    /// Initiate sending the data in `buf` in ready mode.
+    ///
+    /// # Safety
+    ///
+    /// Use of this function is erroneous if the matching receive operation has
+    /// not already been started. MPI-4.1 §3.4 Communication Modes:
+    ///
+    /// > A send that uses the *ready* communication mode may be started only if
+    /// > the matching receive is already started. Otherwise, the operation is
+    /// > erroneous and its outcome is undefined. On some systems, this allows the
+    /// > removal of a hand-shake protocol that is otherwise required and results
+    /// > in improved performance.
+    ///
+    /// Moreover, in case such an error is detected, it may not be reportable.
+    /// MPI-4.1 §2.8 Error Reporting:
+    ///
+    /// > In a few cases, the error may occur after all calls that relate to the
+    /// > operation have returned, so that no error value can be used to indicate
+    /// > the nature of the error (e.g., an erroneous program on the receiver in a
+    /// > send with the *ready* mode).
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.4, 3.7.2
+    unsafe fn immediate_ready_send<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a Buf,
+    ) -> Request<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        self.immediate_ready_send_with_tag(scope, buf, self.message_tag())
+    }
+
+    /// Create a persistent request for sending the data in `buf` in standard mode and tagging it.
+    ///
+    /// The buffer is mutably borrowed so that it can be updated between two runs of the
+    /// operation, see `PersistentRequest::data_mut()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.9
+    fn send_init_with_tag<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+        tag: Tag,
+    ) -> PersistentRequest<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("send_init");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Send_init", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let request = with_uninitialized(|request| {
+                ffi::MPI_Send_init(
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    self.destination_rank(),
+                    tag,
+                    self.as_communicator().as_raw(),
+                    request,
+                )
+            })
+            .1;
+            PersistentRequest::from_raw(request, buf, scope)
+        }
+    }
+
+    /// Create a persistent request for sending the data in `buf` in standard mode.
+    ///
+    /// # Examples
+    /// See `examples/persistent.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.9
+    fn send_init<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+    ) -> PersistentRequest<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        self.send_init_with_tag(scope, buf, self.message_tag())
+    }
+
+    /// Create a partitioned request for sending `buf` divided into `partitions` equally sized
+    /// partitions and tagging it.
+    ///
+    /// Fails if the MPI library does not support partitioned communication, which was introduced
+    /// in MPI 4.0.
+    ///
+    /// # Panics
+    ///
+    /// If `partitions` is zero or the length of `buf` is not a multiple of `partitions`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.2.1
+    fn partitioned_send_init_with_tag<'a, Sc, T>(
+        &self,
+        scope: Sc,
+        buf: &'a mut [T],
+        partitions: usize,
+        tag: Tag,
+    ) -> Result<PartitionedRequest<'a, T, Sc>, ErrorCode>
+    where
+        T: 'a + Equivalence,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("partitioned_send_init");
+        let count = partition_count(buf.len(), partitions);
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Psend_init", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let (code, request) = with_uninitialized(|request| {
+                ffi::RSMPI_Psend_init(
+                    buf.as_ptr() as _,
+                    partitions
+                        .value_as()
+                        .expect("Number of partitions cannot be expressed as a c_int."),
+                    count,
+                    T::equivalent_datatype().as_raw(),
+                    self.destination_rank(),
+                    tag,
+                    self.as_communicator().as_raw(),
+                    ffi::RSMPI_INFO_NULL,
+                    request,
+                )
+            });
+            ErrorCode::check(code)?;
+            Ok(PartitionedRequest::from_raw(
+                request, buf, partitions, true, scope,
+            ))
+        }
+    }
+
+    /// Create a partitioned request for sending `buf` divided into `partitions` equally sized
+    /// partitions.
+    ///
+    /// # Examples
+    /// See `examples/partitioned.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.2.1
+    fn partitioned_send_init<'a, Sc, T>(
+        &self,
+        scope: Sc,
+        buf: &'a mut [T],
+        partitions: usize,
+    ) -> Result<PartitionedRequest<'a, T, Sc>, ErrorCode>
+    where
+        T: 'a + Equivalence,
+        Sc: Scope<'a>,
+    {
+        self.partitioned_send_init_with_tag(scope, buf, partitions, self.message_tag())
+    }
+
+    /// Create a persistent request for sending the data in `buf` in buffered mode and tagging it.
+    ///
+    /// The buffer is mutably borrowed so that it can be updated between two runs of the
+    /// operation, see `PersistentRequest::data_mut()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.9
+    fn buffered_send_init_with_tag<'a, Sc, Buf: ?Sized>(
+        &self,
+        scope: Sc,
+        buf: &'a mut Buf,
+        tag: Tag,
+    ) -> PersistentRequest<'a, Buf, Sc>
+    where
+        Buf: 'a + Buffer,
+        Sc: Scope<'a>,
+    {
+        #[cfg(feature = "checked")]
+        checked::unsupported("buffered_send_init");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Bsend_init", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        unsafe {
+            let request = with_uninitialized(|request| {
+                ffi::MPI_Bsend_init(
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    self.destination_rank(),
+                    tag,
+                    self.as_communicator().as_raw(),
+                    request,
+                )
+            })
+            .1;
+            PersistentRequest::from_raw(request, buf, scope)
+        }
+    }
+
+    /// Create a persistent request for sending the data in `buf` in buffered mode.
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.2
-    fn immediate_buffered_send<'a, Sc, Buf: ?Sized>(
+    /// 3.9
+    fn buffered_send_init<'a, Sc, Buf: ?Sized>(
         &self,
         scope: Sc,
-        buf: &'a Buf,
-    ) -> Request<'a, Buf, Sc>
+        buf: &'a mut Buf,
+    ) -> PersistentRequest<'a, Buf, Sc>
     where
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        self.immediate_buffered_send_with_tag(scope, buf, Tag::default())
+        self.buffered_send_init_with_tag(scope, buf, self.message_tag())
     }
 
-    /// Initiate an immediate (non-blocking) synchronous mode send operation.
+    /// Create a persistent request for sending the data in `buf` in synchronous mode and tagging
+    /// it.
     ///
-    /// Initiate sending the data in `buf` in synchronous mode and tag it.
+    /// The buffer is mutably borrowed so that it can be updated between two runs of the
+    /// operation, see `PersistentRequest::data_mut()`.
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.2
-    fn immediate_synchronous_send_with_tag<'a, Sc, Buf: ?Sized>(
+    /// 3.9
+    fn synchronous_send_init_with_tag<'a, Sc, Buf: ?Sized>(
         &self,
         scope: Sc,
-        buf: &'a Buf,
+        buf: &'a mut Buf,
         tag: Tag,
-    ) -> Request<'a, Buf, Sc>
+    ) -> PersistentRequest<'a, Buf, Sc>
     where
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        #[cfg(feature = "checked")]
+        checked::unsupported("synchronous_send_init");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Ssend_init", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
         unsafe {
-            Request::from_raw(
-                with_uninitialized(|request| {
-                    ffi::MPI_Issend(
-                        buf.pointer(),
-                        buf.count(),
-                        buf.as_datatype().as_raw(),
-                        self.destination_rank(),
-                        tag,
-                        self.as_communicator().as_raw(),
-                        request,
-                    )
-                })
-                .1,
-                buf,
-                scope,
-            )
+            let request = with_uninitialized(|request| {
+                ffi::MPI_Ssend_init(
+                    buf.pointer(),
+                    buf.count(),
+                    buf.as_datatype().as_raw(),
+                    self.destination_rank(),
+                    tag,
+                    self.as_communicator().as_raw(),
+                    request,
+                )
+            })
+            .1;
+            PersistentRequest::from_raw(request, buf, scope)
         }
     }
 
-    /// Initiate an immediate (non-blocking) synchronous mode send operation.
-    ///
-    /// Initiate sending the data in `buf` in synchronous mode.
+    /// Create a persistent request for sending the data in `buf` in synchronous mode.
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.2
-    fn immediate_synchronous_send<'a, Sc, Buf: ?Sized>(
+    /// 3.9
+    fn synchronous_send_init<'a, Sc, Buf: ?Sized>(
         &self,
         scope: Sc,
-        buf: &'a Buf,
-    ) -> Request<'a, Buf, Sc>
+        buf: &'a mut Buf,
+    ) -> PersistentRequest<'a, Buf, Sc>
     where
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        self.immediate_synchronous_send_with_tag(scope, buf, Tag::default())
+        self.synchronous_send_init_with_tag(scope, buf, self.message_tag())
     }
 
-    /// Initiate an immediate (non-blocking) ready mode send operation.
+    /// Create a persistent request for sending the data in `buf` in ready mode and tagging it.
     ///
-    /// Initiate sending the data in `buf` in ready mode and tag it.
+    /// The buffer is mutably borrowed so that it can be updated between two runs of the
+    /// operation, see `PersistentRequest::data_mut()`.
     ///
     /// # Safety
     ///
-    /// Use of this function is erroneous if the matching receive operation has
-    /// not already been started. MPI-4.1 §3.4 Communication Modes:
-    ///
-    /// > A send that uses the *ready* communication mode may be started only if
-    /// > the matching receive is already started. Otherwise, the operation is
-    /// > erroneous and its outcome is undefined. On some systems, this allows the
-    /// > removal of a hand-shake protocol that is otherwise required and results
-    /// > in improved performance.
-    ///
-    /// Moreover, in case such an error is detected, it may not be reportable.
-    /// MPI-4.1 §2.8 Error Reporting:
-    ///
-    /// > In a few cases, the error may occur after all calls that relate to the
-    /// > operation have returned, so that no error value can be used to indicate
-    /// > the nature of the error (e.g., an erroneous program on the receiver in a
-    /// > send with the *ready* mode).
+    /// Every run of the operation may only be started with `PersistentRequest::start()` if the
+    /// matching receive operation has already been started, see `immediate_ready_send()`.
     ///
     /// # Standard section(s)
     ///
-    /// 3.4, 3.7.2
-    unsafe fn immediate_ready_send_with_tag<'a, Sc, Buf: ?Sized>(
+    /// 3.9
+    unsafe fn ready_send_init_with_tag<'a, Sc, Buf: ?Sized>(
         &self,
         scope: Sc,
-        buf: &'a Buf,
+        buf: &'a mut Buf,
         tag: Tag,
-    ) -> Request<'a, Buf, Sc>
+    ) -> PersistentRequest<'a, Buf, Sc>
     where
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        unsafe {
-            Request::from_raw(
-                with_uninitialized(|request| {
-                    ffi::MPI_Irsend(
-                        buf.pointer(),
-                        buf.count(),
-                        buf.as_datatype().as_raw(),
-                        self.destination_rank(),
-                        tag,
-                        self.as_communicator().as_raw(),
-                        request,
-                    )
-                })
-                .1,
-                buf,
-                scope,
+        #[cfg(feature = "checked")]
+        checked::unsupported("ready_send_init");
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Rsend_init", self.as_communicator().as_raw())
+                .peer(self.destination_rank())
+                .buffer(buf)
+        });
+        let request = with_uninitialized(|request| {
+            ffi::MPI_Rsend_init(
+                buf.pointer(),
+                buf.count(),
+                buf.as_datatype().as_raw(),
+                self.destination_rank(),
+                tag,
+                self.as_communicator().as_raw(),
+                request,
             )
-        }
+        })
+        .1;
+        PersistentRequest::from_raw(request, buf, scope)
     }
 
-    /// Initiate an immediate (non-blocking) ready mode send operation.
-    ///
-    /// Initiate sending the data in `buf` in ready mode.
+    /// Create a persistent request for sending the data in `buf` in ready mode.
     ///
     /// # Safety
     ///
-    /// Use of this function is erroneous if the matching receive operation has
-    /// not already been started. MPI-4.1 §3.4 Communication Modes:
-    ///
-    /// > A send that uses the *ready* communication mode may be started only if
-    /// > the matching receive is already started. Otherwise, the operation is
-    /// > erroneous and its outcome is undefined. On some systems, this allows the
-    /// > removal of a hand-shake protocol that is otherwise required and results
-    /// > in improved performance.
-    ///
-    /// Moreover, in case such an error is detected, it may not be reportable.
-    /// MPI-4.1 §2.8 Error Reporting:
-    ///
-    /// > In a few cases, the error may occur after all calls that relate to the
-    /// > operation have returned, so that no error value can be used to indicate
-    /// > the nature of the error (e.g., an erroneous program on the receiver in a
-    /// > send with the *ready* mode).
+    /// Every run of the operation may only be started with `PersistentRequest::start()` if the
+    /// matching receive operation has already been started, see `immediate_ready_send()`.
     ///
     /// # Examples
-    ///
-    /// See `examples/immediate.rs`
+    /// See `examples/persistent.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 3.4, 3.7.2
-    unsafe fn immediate_ready_send<'a, Sc, Buf: ?Sized>(
+    /// 3.9
+    unsafe fn ready_send_init<'a, Sc, Buf: ?Sized>(
         &self,
         scope: Sc,
-        buf: &'a Buf,
-    ) -> Request<'a, Buf, Sc>
+        buf: &'a mut Buf,
+    ) -> PersistentRequest<'a, Buf, Sc>
     where
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
-        self.immediate_ready_send_with_tag(scope, buf, Tag::default())
+        self.ready_send_init_with_tag(scope, buf, self.message_tag())
     }
 }
 
@@ -965,6 +1876,91 @@ impl<'a> Destination for Process<'a> {
     }
 }
 
+impl<'a> Process<'a> {
+    /// Attach `tag` to this process
+    ///
+    /// Sending to or receiving from the returned `Tagged` process without an explicit tag uses
+    /// `tag`, e.g. `process.tagged(42).receive()` only matches messages tagged `42`.
+    ///
+    /// # Examples
+    /// See `examples/tagged.rs`
+    pub fn tagged(self, tag: Tag) -> Tagged<Self> {
+        Tagged { process: self, tag }
+    }
+}
+
+impl<'a> AnyProcess<'a> {
+    /// Attach `tag` to this source
+    ///
+    /// Receiving from the returned `Tagged` source without an explicit tag only matches messages
+    /// tagged `tag`, but still from any process.
+    ///
+    /// # Examples
+    /// See `examples/tagged.rs`
+    pub fn tagged(self, tag: Tag) -> Tagged<Self> {
+        Tagged { process: self, tag }
+    }
+}
+
+/// A `Source` or `Destination` with a tag attached, created via `Process::tagged()` or
+/// `AnyProcess::tagged()`
+///
+/// The `_with_tag` variants of the operations still use the tag they are given.
+#[derive(Copy, Clone)]
+pub struct Tagged<P> {
+    process: P,
+    tag: Tag,
+}
+
+impl<P> Tagged<P> {
+    /// The attached tag
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// The process the tag is attached to
+    pub fn process(&self) -> &P {
+        &self.process
+    }
+}
+
+impl<P: AsCommunicator> AsCommunicator for Tagged<P> {
+    type Out = P::Out;
+
+    fn as_communicator(&self) -> &Self::Out {
+        self.process.as_communicator()
+    }
+}
+
+unsafe impl<P: Source> Source for Tagged<P> {
+    fn source_rank(&self) -> Rank {
+        self.process.source_rank()
+    }
+
+    fn match_tag(&self) -> Tag {
+        self.tag
+    }
+}
+
+impl<P: Destination> Destination for Tagged<P> {
+    fn destination_rank(&self) -> Rank {
+        self.process.destination_rank()
+    }
+
+    fn message_tag(&self) -> Tag {
+        self.tag
+    }
+}
+
+/// The wildcard `Tag` that matches messages with any tag, `MPI_ANY_TAG`
+///
+/// # Standard section(s)
+///
+/// 3.2.4
+pub fn any_tag() -> Tag {
+    unsafe { ffi::RSMPI_ANY_TAG }
+}
+
 /// Describes the result of a point to point receive operation.
 ///
 /// # Standard section(s)
@@ -993,6 +1989,32 @@ impl Status {
     pub fn count<D: Datatype>(&self, d: D) -> Count {
         unsafe { with_uninitialized(|count| ffi::MPI_Get_count(&self.0, d.as_raw(), count)).1 }
     }
+
+    /// Number of basic elements of the type contained in the message
+    ///
+    /// Unlike `count()`, this also counts the basic elements of a message that only contains a
+    /// part of an instance of a derived datatype `d`.
+    ///
+    /// # Examples
+    /// See `examples/vector.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.11
+    pub fn elements<D: Datatype>(&self, d: D) -> Count {
+        unsafe {
+            with_uninitialized(|elements| ffi::MPI_Get_elements(&self.0, d.as_raw(), elements)).1
+        }
+    }
+
+    /// Whether the operation described by the status was successfully cancelled
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.4
+    pub fn is_cancelled(&self) -> bool {
+        unsafe { with_uninitialized(|flag| ffi::MPI_Test_cancelled(&self.0, flag)).1 != 0 }
+    }
 }
 
 impl fmt::Debug for Status {
@@ -1031,6 +2053,9 @@ impl Message {
     where
         Msg: Equivalence,
     {
+        let _call = instrument::begin(|| {
+            Call::without_comm("MPI_Mrecv").typed(1, &Msg::equivalent_datatype())
+        });
         unsafe {
             let (_, res, status) = with_uninitialized2(|res, status| {
                 ffi::MPI_Mrecv(
@@ -1061,6 +2086,7 @@ impl Message {
         Buf: BufferMut,
     {
         let status;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Mrecv").buffer(buf));
         unsafe {
             status = with_uninitialized(|status| {
                 ffi::MPI_Mrecv(
@@ -1093,6 +2119,7 @@ impl Message {
         Buf: BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Imrecv").buffer(buf));
         unsafe {
             let request = with_uninitialized(|request| {
                 ffi::MPI_Imrecv(
@@ -1204,6 +2231,26 @@ where
             .compare(destination.as_communicator()),
         CommunicatorRelation::Identical
     );
+    let source_rank = source.source_rank();
+    #[cfg(feature = "checked")]
+    let (source_rank, receivetag) = {
+        let header = checked::exchange_headers(
+            &msg.as_datatype(),
+            msg.count(),
+            destination.destination_rank(),
+            sendtag,
+            source_rank,
+            receivetag,
+            source.as_communicator().as_raw(),
+        );
+        header.check(&R::equivalent_datatype(), 1);
+        (header.source, header.tag)
+    };
+    let _call = instrument::begin(|| {
+        Call::new("MPI_Sendrecv", destination.as_communicator().as_raw())
+            .peer(destination.destination_rank())
+            .buffer(msg)
+    });
     unsafe {
         let (_, res, status) = with_uninitialized2(|res, status| {
             ffi::MPI_Sendrecv(
@@ -1215,7 +2262,7 @@ where
                 res as _,
                 1,
                 R::equivalent_datatype().as_raw(),
-                source.source_rank(),
+                source_rank,
                 receivetag,
                 source.as_communicator().as_raw(),
                 status,
@@ -1242,9 +2289,13 @@ where
     R: Equivalence,
     S: Source,
 {
-    send_receive_with_tags(msg, destination, Tag::default(), source, unsafe {
-        ffi::RSMPI_ANY_TAG
-    })
+    send_receive_with_tags(
+        msg,
+        destination,
+        destination.message_tag(),
+        source,
+        source.match_tag(),
+    )
 }
 
 /// Sends the contents of `msg` to `destination` tagging it `sendtag` and
@@ -1274,6 +2325,26 @@ where
             .compare(destination.as_communicator()),
         CommunicatorRelation::Identical
     );
+    let source_rank = source.source_rank();
+    #[cfg(feature = "checked")]
+    let (source_rank, receivetag) = {
+        let header = checked::exchange_headers(
+            &msg.as_datatype(),
+            msg.count(),
+            destination.destination_rank(),
+            sendtag,
+            source_rank,
+            receivetag,
+            source.as_communicator().as_raw(),
+        );
+        header.check(&buf.as_datatype(), buf.count());
+        (header.source, header.tag)
+    };
+    let _call = instrument::begin(|| {
+        Call::new("MPI_Sendrecv", destination.as_communicator().as_raw())
+            .peer(destination.destination_rank())
+            .buffer(msg)
+    });
     unsafe {
         Status(
             with_uninitialized(|status| {
@@ -1286,7 +2357,7 @@ where
                     buf.pointer_mut(),
                     buf.count(),
                     buf.as_datatype().as_raw(),
-                    source.source_rank(),
+                    source_rank,
                     receivetag,
                     source.as_communicator().as_raw(),
                     status,
@@ -1316,9 +2387,14 @@ where
     B: BufferMut,
     S: Source,
 {
-    send_receive_into_with_tags(msg, destination, Tag::default(), buf, source, unsafe {
-        ffi::RSMPI_ANY_TAG
-    })
+    send_receive_into_with_tags(
+        msg,
+        destination,
+        destination.message_tag(),
+        buf,
+        source,
+        source.match_tag(),
+    )
 }
 
 /// Sends the contents of `buf` to `destination` tagging it `sendtag` and
@@ -1346,6 +2422,29 @@ where
             .compare(destination.as_communicator()),
         CommunicatorRelation::Identical
     );
+    let source_rank = source.source_rank();
+    #[cfg(feature = "checked")]
+    let (source_rank, receivetag) = {
+        let header = checked::exchange_headers(
+            &buf.as_datatype(),
+            buf.count(),
+            destination.destination_rank(),
+            sendtag,
+            source_rank,
+            receivetag,
+            source.as_communicator().as_raw(),
+        );
+        header.check(&buf.as_datatype(), buf.count());
+        (header.source, header.tag)
+    };
+    let _call = instrument::begin(|| {
+        Call::new(
+            "MPI_Sendrecv_replace",
+            destination.as_communicator().as_raw(),
+        )
+        .peer(destination.destination_rank())
+        .buffer(buf)
+    });
     unsafe {
         Status(
             with_uninitialized(|status| {
@@ -1355,7 +2454,7 @@ where
                     buf.as_datatype().as_raw(),
                     destination.destination_rank(),
                     sendtag,
-                    source.source_rank(),
+                    source_rank,
                     receivetag,
                     source.as_communicator().as_raw(),
                     status,
@@ -1383,9 +2482,13 @@ where
     D: Destination,
     S: Source,
 {
-    send_receive_replace_into_with_tags(buf, destination, Tag::default(), source, unsafe {
-        ffi::RSMPI_ANY_TAG
-    })
+    send_receive_replace_into_with_tags(
+        buf,
+        destination,
+        destination.message_tag(),
+        source,
+        source.match_tag(),
+    )
 }
 
 /// Will contain a value of type `T` received via a non-blocking receive operation.
@@ -1427,3 +2530,180 @@ where
         }
     }
 }
+
+/// Will contain a `Vec` of values of type `T` received via a non-blocking receive operation.
+///
+/// Created by `Source::immediate_receive_vec()`, it borrows the source so that the communicator
+/// outlives the operation. If it is dropped after the message has been matched, it waits for the
+/// message to arrive and discards it.
+///
+/// With the `async` feature, this is also a `Future`, which tests for the message whenever it is
+/// polled and asks to be polled again until it has arrived.
+#[must_use]
+pub struct ReceiveVecFuture<'a, T> {
+    state: ReceiveVecState<T>,
+    phantom: PhantomData<&'a ()>,
+}
+
+enum ReceiveVecState<T> {
+    /// The message has not been matched yet.
+    Probing {
+        source: Rank,
+        tag: Tag,
+        comm: MPI_Comm,
+    },
+    /// The message is being received into the spare capacity of `buf`.
+    Receiving {
+        buf: Vec<T>,
+        count: usize,
+        request: MPI_Request,
+    },
+    Complete,
+}
+
+impl<'a, T> ReceiveVecFuture<'a, T>
+where
+    T: Equivalence,
+{
+    /// Wait for the receive operation to finish and return the received data.
+    pub fn get(mut self) -> (Vec<T>, Status) {
+        if let ReceiveVecState::Probing { source, tag, comm } = self.state {
+            let _call = instrument::begin(|| Call::new("MPI_Mprobe", comm).peer(source));
+            let (_, message, status) = unsafe {
+                with_uninitialized2(|message, status| {
+                    ffi::MPI_Mprobe(source, tag, comm, message, status)
+                })
+            };
+            self.receive(Message(message), Status(status));
+        }
+        match &mut self.state {
+            ReceiveVecState::Receiving { request, .. } => {
+                let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+                let (_, status) =
+                    unsafe { with_uninitialized(|status| ffi::MPI_Wait(request, status)) };
+                self.complete(Status(status))
+            }
+            _ => unreachable!("ReceiveVecFuture has already completed"),
+        }
+    }
+
+    /// Check whether the receive operation has finished.
+    ///
+    /// If the operation has finished, the data received is returned. Otherwise the future itself
+    /// is returned.
+    pub fn r#try(mut self) -> Result<(Vec<T>, Status), Self> {
+        match self.test() {
+            Some(result) => Ok(result),
+            None => Err(self),
+        }
+    }
+
+    fn test(&mut self) -> Option<(Vec<T>, Status)> {
+        if let ReceiveVecState::Probing { source, tag, comm } = self.state {
+            let mut message = MaybeUninit::uninit();
+            let _call = instrument::begin(|| Call::new("MPI_Improbe", comm).peer(source));
+            let (_, flag, status) = unsafe {
+                with_uninitialized2(|flag, status| {
+                    ffi::MPI_Improbe(source, tag, comm, flag, message.as_mut_ptr(), status)
+                })
+            };
+            if flag == 0 {
+                return None;
+            }
+            self.receive(unsafe { Message(message.assume_init()) }, Status(status));
+        }
+        match &mut self.state {
+            ReceiveVecState::Receiving { request, .. } => {
+                let _call = instrument::begin(|| Call::without_comm("MPI_Test"));
+                let (_, flag, status) = unsafe {
+                    with_uninitialized2(|flag, status| ffi::MPI_Test(request, flag, status))
+                };
+                if flag == 0 {
+                    None
+                } else {
+                    Some(self.complete(Status(status)))
+                }
+            }
+            _ => panic!("ReceiveVecFuture has already completed"),
+        }
+    }
+
+    /// Start receiving the matched `message` into a `Vec` of the right length.
+    fn receive(&mut self, mut message: Message, status: Status) {
+        let count = status.count(T::equivalent_datatype());
+        let len = count
+            .value_as()
+            .expect("Message element count cannot be expressed as a usize.");
+        let mut buf = Vec::with_capacity(len);
+        let _call = instrument::begin(|| {
+            Call::without_comm("MPI_Imrecv").typed(count, &T::equivalent_datatype())
+        });
+        let (_, request) = unsafe {
+            with_uninitialized(|request| {
+                ffi::MPI_Imrecv(
+                    buf.as_mut_ptr() as _,
+                    count,
+                    T::equivalent_datatype().as_raw(),
+                    message.as_raw_mut(),
+                    request,
+                )
+            })
+        };
+        self.state = ReceiveVecState::Receiving {
+            buf,
+            count: len,
+            request,
+        };
+    }
+
+    fn complete(&mut self, status: Status) -> (Vec<T>, Status) {
+        match mem::replace(&mut self.state, ReceiveVecState::Complete) {
+            ReceiveVecState::Receiving { mut buf, count, .. } => {
+                // The elements have been received into the spare capacity by MPI.
+                unsafe { buf.set_len(count) };
+                (buf, status)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a, T> Future for ReceiveVecFuture<'a, T>
+where
+    T: Equivalence,
+{
+    type Output = (Vec<T>, Status);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.test() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<'a, T> Drop for ReceiveVecFuture<'a, T> {
+    fn drop(&mut self) {
+        if let ReceiveVecState::Receiving { request, .. } = &mut self.state {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+            unsafe {
+                ffi::MPI_Wait(request, ffi::RSMPI_STATUS_IGNORE);
+            }
+        }
+    }
+}
+
+/// The count of elements per partition of a buffer of `len` elements divided into `partitions`.
+fn partition_count(len: usize, partitions: usize) -> ffi::MPI_Count {
+    assert!(
+        partitions > 0 && len % partitions == 0,
+        "buffer of length {len} cannot be divided into {partitions} partitions"
+    );
+    (len / partitions)
+        .value_as()
+        .expect("Length of partition cannot be expressed as an MPI_Count.")
+}
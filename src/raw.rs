@@ -1,5 +1,8 @@
 //! Bridge between rust types and raw values
 
+use std::rc::Rc;
+use std::sync::Arc;
+
 /// Rust C bridge traits
 pub mod traits {
     pub use super::{AsRaw, AsRawMut, FromRaw, MatchesRaw};
@@ -23,6 +26,26 @@ where
     }
 }
 
+unsafe impl<T> AsRaw for Rc<T>
+where
+    T: AsRaw + ?Sized,
+{
+    type Raw = <T as AsRaw>::Raw;
+    fn as_raw(&self) -> Self::Raw {
+        (**self).as_raw()
+    }
+}
+
+unsafe impl<T> AsRaw for Arc<T>
+where
+    T: AsRaw + ?Sized,
+{
+    type Raw = <T as AsRaw>::Raw;
+    fn as_raw(&self) -> Self::Raw {
+        (**self).as_raw()
+    }
+}
+
 /// A rust type than can provide a mutable pointer to a raw value understood by the MPI C API.
 pub unsafe trait AsRawMut: AsRaw {
     /// A mutable pointer to the raw value
@@ -22,23 +22,33 @@
 //! # Unfinished features
 //!
 //! - **3.7**: Nonblocking mode:
-//!   - Completion, `MPI_Waitall()`, `MPI_Waitsome()`,
-//!   `MPI_Testany()`, `MPI_Testall()`, `MPI_Testsome()`, `MPI_Request_get_status()`
-//! - **3.8**:
-//!   - Cancellation, `MPI_Test_cancelled()`
+//!   - Completion, `MPI_Request_get_status()`
 
 use std::cell::Cell;
 use std::fmt;
+#[cfg(feature = "async")]
+use std::future::{Future, IntoFuture};
 use std::marker::PhantomData;
 use std::mem::{self, MaybeUninit};
+use std::ops::{Deref, DerefMut};
 use std::os::raw::c_int;
+#[cfg(feature = "async")]
+use std::pin::Pin;
 use std::ptr;
+#[cfg(feature = "async")]
+use std::sync::Arc;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll};
 
 use crate::ffi;
 use crate::ffi::{MPI_Request, MPI_Status};
 
+use crate::datatype::Equivalence;
+use crate::environment::{threading_support, Threading};
+use crate::instrument::{self, Call};
 use crate::point_to_point::Status;
 use crate::raw::traits::*;
+use crate::topology::ThreadSafe;
 use crate::with_uninitialized;
 
 /// Check if the request is `MPI_REQUEST_NULL`.
@@ -68,7 +78,8 @@ pub struct Request<'a, D: ?Sized, S: Scope<'a> = StaticScope> {
     request: MPI_Request,
     data: &'a D,
     scope: S,
-    phantom: PhantomData<Cell<&'a ()>>,
+    // Requests stay on the thread that created them, see `topology::SimpleCommunicator`.
+    phantom: PhantomData<(Cell<&'a ()>, *const ())>,
 }
 
 impl<'a, D: ?Sized, S: Scope<'a>> fmt::Debug for Request<'a, D, S>
@@ -91,6 +102,10 @@ unsafe impl<'a, D: ?Sized, S: Scope<'a>> AsRaw for Request<'a, D, S> {
     }
 }
 
+// Only constructed under `Threading::Multiple`, where any thread may complete the request. The
+// thread that completes it shares the buffer with the thread that started the operation.
+unsafe impl<'a, D: ?Sized + Sync, S: Scope<'a> + Send> Send for ThreadSafe<Request<'a, D, S>> {}
+
 impl<'a, D: ?Sized, S: Scope<'a>> Drop for Request<'a, D, S> {
     fn drop(&mut self) {
         panic!("request was dropped without being completed");
@@ -107,7 +122,7 @@ impl<'a, D: ?Sized, S: Scope<'a>> Drop for Request<'a, D, S> {
 /// # Examples
 ///
 /// See `examples/wait_any.rs`
-pub fn wait_any<'a, D, S: Scope<'a>>(
+pub fn wait_any<'a, D: ?Sized, S: Scope<'a>>(
     requests: &mut Vec<Request<'a, D, S>>,
 ) -> Option<(usize, Status)> {
     let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
@@ -117,6 +132,7 @@ pub fn wait_any<'a, D, S: Scope<'a>>(
         .try_into()
         .expect("Error while casting usize to i32");
     let status;
+    let _call = instrument::begin(|| Call::without_comm("MPI_Waitany"));
     unsafe {
         status = Status::from_raw(
             with_uninitialized(|s| {
@@ -126,281 +142,1464 @@ pub fn wait_any<'a, D, S: Scope<'a>>(
             .1,
         );
     }
-    if index != mpi_sys::MPI_UNDEFINED {
-        let u_index: usize = index.try_into().expect("Error while casting i32 to usize");
-        assert!(is_null(mpi_requests[u_index]));
-        let r = requests.remove(u_index);
+    if index != mpi_sys::MPI_UNDEFINED {
+        let u_index: usize = index.try_into().expect("Error while casting i32 to usize");
+        assert!(is_null(mpi_requests[u_index]));
+        let r = requests.remove(u_index);
+        unsafe {
+            r.into_raw();
+        }
+        Some((u_index, status))
+    } else {
+        None
+    }
+}
+
+/// Wait for the completion of all requests in the vector and return their statuses in the order
+/// of the requests.
+///
+/// All requests are removed from the vector.
+///
+/// # Examples
+///
+/// See `examples/wait_any.rs`
+///
+/// # Standard section(s)
+///
+/// 3.7.5
+pub fn wait_all<'a, D: ?Sized, S: Scope<'a>>(requests: &mut Vec<Request<'a, D, S>>) -> Vec<Status> {
+    let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
+    let mut statuses: Vec<MaybeUninit<MPI_Status>> = Vec::with_capacity(mpi_requests.len());
+    let _call = instrument::begin(|| Call::without_comm("MPI_Waitall"));
+    unsafe {
+        statuses.set_len(mpi_requests.len());
+        ffi::MPI_Waitall(
+            count_of(&mpi_requests),
+            mpi_requests.as_mut_ptr(),
+            statuses.as_mut_ptr() as *mut MPI_Status,
+        );
+    }
+    for (request, r) in requests.drain(..).zip(&mpi_requests) {
+        assert!(is_null(*r)); // persistent requests are not supported
+        unsafe {
+            request.into_raw();
+        }
+    }
+    statuses
+        .into_iter()
+        .map(|status| Status::from_raw(unsafe { status.assume_init() }))
+        .collect()
+}
+
+/// Wait for the completion of all requests in the vector without returning their statuses.
+///
+/// Like `wait_all()`, but passes `MPI_STATUSES_IGNORE`, so no statuses are allocated.
+///
+/// # Examples
+///
+/// See `examples/wait_any.rs`
+///
+/// # Standard section(s)
+///
+/// 3.7.5
+pub fn wait_all_without_status<'a, D: ?Sized, S: Scope<'a>>(requests: &mut Vec<Request<'a, D, S>>) {
+    let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
+    let _call = instrument::begin(|| Call::without_comm("MPI_Waitall"));
+    unsafe {
+        ffi::MPI_Waitall(
+            count_of(&mpi_requests),
+            mpi_requests.as_mut_ptr(),
+            ffi::RSMPI_STATUSES_IGNORE,
+        );
+    }
+    for (request, r) in requests.drain(..).zip(&mpi_requests) {
+        assert!(is_null(*r)); // persistent requests are not supported
+        unsafe {
+            request.into_raw();
+        }
+    }
+}
+
+/// Test for the completion of one of the requests in the vector,
+/// returns the index of the request completed and the status of the request.
+///
+/// The completed request is removed from the vector of requests.
+///
+/// If none of the requests has completed, or no Request is active, None is returned.
+///
+/// # Examples
+///
+/// See `examples/wait_any.rs`
+///
+/// # Standard section(s)
+///
+/// 3.7.5
+pub fn test_any<'a, D: ?Sized, S: Scope<'a>>(
+    requests: &mut Vec<Request<'a, D, S>>,
+) -> Option<(usize, Status)> {
+    let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
+    let mut index: c_int = ffi::MPI_UNDEFINED;
+    let mut flag: c_int = 0;
+    let _call = instrument::begin(|| Call::without_comm("MPI_Testany"));
+    let status = unsafe {
+        Status::from_raw(
+            with_uninitialized(|s| {
+                ffi::MPI_Testany(
+                    count_of(&mpi_requests),
+                    mpi_requests.as_mut_ptr(),
+                    &mut index,
+                    &mut flag,
+                    s,
+                )
+            })
+            .1,
+        )
+    };
+    if flag != 0 && index != ffi::MPI_UNDEFINED {
+        let u_index: usize = index.try_into().expect("Error while casting i32 to usize");
+        assert!(is_null(mpi_requests[u_index]));
+        let r = requests.remove(u_index);
+        unsafe {
+            r.into_raw();
+        }
+        Some((u_index, status))
+    } else {
+        None
+    }
+}
+
+/// Wait for the completion of at least one of the requests in the vector, returns the indices of
+/// the completed requests together with their statuses.
+///
+/// The completed requests are removed from the vector of requests. The returned indices refer to
+/// the positions of the requests in the vector before the call and are in increasing order.
+///
+/// If no Request is active an empty vector is returned.
+///
+/// # Examples
+///
+/// See `examples/wait_any.rs`
+///
+/// # Standard section(s)
+///
+/// 3.7.5
+pub fn wait_some<'a, D: ?Sized, S: Scope<'a>>(
+    requests: &mut Vec<Request<'a, D, S>>,
+) -> Vec<(usize, Status)> {
+    let _call = instrument::begin(|| Call::without_comm("MPI_Waitsome"));
+    complete_some(
+        requests,
+        |count, requests, outcount, indices, statuses| unsafe {
+            ffi::MPI_Waitsome(count, requests, outcount, indices, statuses);
+        },
+    )
+}
+
+/// Test for the completion of some of the requests in the vector, returns the indices of the
+/// completed requests together with their statuses.
+///
+/// The completed requests are removed from the vector of requests. The returned indices refer to
+/// the positions of the requests in the vector before the call and are in increasing order.
+///
+/// If none of the requests has completed, or no Request is active, an empty vector is returned.
+///
+/// # Examples
+///
+/// See `examples/wait_any.rs`
+///
+/// # Standard section(s)
+///
+/// 3.7.5
+pub fn test_some<'a, D: ?Sized, S: Scope<'a>>(
+    requests: &mut Vec<Request<'a, D, S>>,
+) -> Vec<(usize, Status)> {
+    let _call = instrument::begin(|| Call::without_comm("MPI_Testsome"));
+    complete_some(
+        requests,
+        |count, requests, outcount, indices, statuses| unsafe {
+            ffi::MPI_Testsome(count, requests, outcount, indices, statuses);
+        },
+    )
+}
+
+/// Shared implementation of `wait_some()` and `test_some()`.
+fn complete_some<'a, D: ?Sized, S: Scope<'a>, F>(
+    requests: &mut Vec<Request<'a, D, S>>,
+    complete: F,
+) -> Vec<(usize, Status)>
+where
+    F: FnOnce(c_int, *mut MPI_Request, *mut c_int, *mut c_int, *mut MPI_Status),
+{
+    let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
+    let mut indices: Vec<c_int> = vec![0; mpi_requests.len()];
+    let mut statuses: Vec<MaybeUninit<MPI_Status>> = Vec::with_capacity(mpi_requests.len());
+    let mut outcount: c_int = 0;
+    unsafe {
+        statuses.set_len(mpi_requests.len());
+    }
+    complete(
+        count_of(&mpi_requests),
+        mpi_requests.as_mut_ptr(),
+        &mut outcount,
+        indices.as_mut_ptr(),
+        statuses.as_mut_ptr() as *mut MPI_Status,
+    );
+
+    if outcount == ffi::MPI_UNDEFINED {
+        return Vec::new();
+    }
+    let outcount: usize = outcount
+        .try_into()
+        .expect("Error while casting i32 to usize");
+    let mut completed: Vec<(usize, Status)> = indices[..outcount]
+        .iter()
+        .zip(&statuses[..outcount])
+        .map(|(&index, status)| {
+            let index: usize = index.try_into().expect("Error while casting i32 to usize");
+            assert!(is_null(mpi_requests[index])); // persistent requests are not supported
+            (index, Status::from_raw(unsafe { status.assume_init() }))
+        })
+        .collect();
+    completed.sort_by_key(|&(index, _)| index);
+
+    // Remove from the back so that the remaining indices stay valid.
+    for &(index, _) in completed.iter().rev() {
+        unsafe {
+            requests.remove(index).into_raw();
+        }
+    }
+    completed
+}
+
+/// The number of requests as expected by the MPI completion functions.
+fn count_of(requests: &[MPI_Request]) -> c_int {
+    requests
+        .len()
+        .try_into()
+        .expect("Error while casting usize to i32")
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> Request<'a, D, S> {
+    /// Construct a request object from the raw MPI type.
+    ///
+    /// # Requirements
+    ///
+    /// - The request is a valid, active request.  It must not be `MPI_REQUEST_NULL`.
+    /// - The request must not be persistent.
+    /// - All buffers associated with the request must outlive `'a`.
+    /// - The request must not be registered with the given scope.
+    ///
+    /// # Safety
+    /// - `request` must be a live MPI object.
+    /// - `request` must not be used after calling `from_raw`.
+    /// - Any buffers owned by `request` must live longer than `scope`.
+    pub unsafe fn from_raw(request: MPI_Request, data: &'a D, scope: S) -> Self {
+        debug_assert!(!is_null(request));
+        scope.register();
+        Self {
+            request,
+            data,
+            scope,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Unregister the request object from its scope and deconstruct it into its raw parts.
+    ///
+    /// This is unsafe because the request may outlive its associated buffers.
+    ///
+    /// # Safety
+    /// - The returned `MPI_Request` must be completed within the lifetime of the returned scope.
+    pub unsafe fn into_raw(self) -> (MPI_Request, &'a D, S) {
+        let request = ptr::read(&self.request);
+        let data = ptr::read(&self.data);
+        let scope = ptr::read(&self.scope);
+        let _ = ptr::read(&self.phantom);
+        mem::forget(self);
+        scope.unregister();
+        (request, data, scope)
+    }
+
+    /// Wait for the request to finish and unregister the request object from its scope.
+    /// If provided, the status is written to the referent of the given reference.
+    /// The referent `MPI_Status` object is never read. Also returns the data
+    /// reference.
+    fn wait_with(self, status: *mut MPI_Status) -> &'a D {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+        unsafe {
+            let (mut request, data, _) = self.into_raw();
+            ffi::MPI_Wait(&mut request, status);
+            assert!(is_null(request)); // persistent requests are not supported
+            data
+        }
+    }
+
+    /// Wait for the request to finish, unregister it, and return the data
+    /// reference.
+    pub fn wait_for_data(self) -> &'a D {
+        // TODO: Just ignores the status for now, but this info might be needed.
+        self.wait_with(unsafe { ffi::RSMPI_STATUS_IGNORE })
+    }
+
+    /// Wait for an operation to finish.
+    ///
+    /// Will block execution of the calling thread until the associated operation has finished.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3
+    pub fn wait(self) -> Status {
+        unsafe { Status::from_raw(with_uninitialized(|status| self.wait_with(status)).1) }
+    }
+
+    /// Wait for an operation to finish, but don’t bother retrieving the `Status` information.
+    ///
+    /// Will block execution of the calling thread until the associated operation has finished.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3
+    pub fn wait_without_status(self) {
+        self.wait_with(unsafe { ffi::RSMPI_STATUS_IGNORE });
+    }
+
+    /// Test whether an operation has finished.
+    ///
+    /// If the operation has finished, `Status` is returned.  Otherwise returns the unfinished
+    /// `Request`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3
+    pub fn test(self) -> Result<Status, Self> {
+        unsafe {
+            let mut status = MaybeUninit::uninit();
+            let mut request = self.as_raw();
+
+            let _call = instrument::begin(|| Call::without_comm("MPI_Test"));
+            let (_, flag) =
+                with_uninitialized(|flag| ffi::MPI_Test(&mut request, flag, status.as_mut_ptr()));
+            if flag != 0 {
+                assert!(is_null(request)); // persistent requests are not supported
+                let (_, _data, _) = self.into_raw();
+                Ok(Status::from_raw(status.assume_init()))
+            } else {
+                Err(self)
+            }
+        }
+    }
+
+    /// Test whether an operation has finished.
+    ///
+    /// If the operation has finished, a tuple (`Status`, saved_data) is returned.
+    /// Otherwise returns the unfinished `Request`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3
+    pub fn test_with_data(self) -> Result<(Status, &'a D), Self> {
+        unsafe {
+            let mut status = MaybeUninit::uninit();
+            let mut request = self.as_raw();
+
+            let _call = instrument::begin(|| Call::without_comm("MPI_Test"));
+            let (_, flag) =
+                with_uninitialized(|flag| ffi::MPI_Test(&mut request, flag, status.as_mut_ptr()));
+            if flag != 0 {
+                assert!(is_null(request)); // persistent requests are not supported
+                let (_, data, _) = self.into_raw();
+                Ok((Status::from_raw(status.assume_init()), data))
+            } else {
+                Err(self)
+            }
+        }
+    }
+
+    /// Initiate cancellation of the request.
+    ///
+    /// The MPI implementation is not guaranteed to fulfill this operation.  It may not even be
+    /// valid for certain types of requests.  In the future, the MPI forum may [deprecate
+    /// cancellation of sends][mpi26] entirely.
+    ///
+    /// [mpi26]: https://github.com/mpi-forum/mpi-issues/issues/26
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.4
+    pub fn cancel(&self) {
+        let mut request = self.as_raw();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Cancel"));
+        unsafe {
+            ffi::MPI_Cancel(&mut request);
+        }
+    }
+
+    /// Initiate cancellation of the request and wait for the operation to either be cancelled or
+    /// finish.
+    ///
+    /// Use `Status::is_cancelled()` on the returned status to find out whether the operation was
+    /// cancelled. This is the safe way of tearing down speculative receives that may never be
+    /// matched.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.4
+    pub fn cancel_and_wait(self) -> Status {
+        self.cancel();
+        self.wait()
+    }
+
+    /// Mark the request for deallocation without waiting for the operation to finish.
+    ///
+    /// The operation continues in the background and its completion can not be observed through
+    /// the request anymore.
+    ///
+    /// # Safety
+    ///
+    /// The operation may still access the buffers associated with the request after this call. The
+    /// caller has to ensure by other means, e.g. a reply from the receiving process, that the
+    /// operation has completed before the buffers are accessed again or go out of scope.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3
+    pub unsafe fn free(self) {
+        let (mut request, _, _) = self.into_raw();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Request_free"));
+        ffi::MPI_Request_free(&mut request);
+    }
+
+    /// Reduce the scope of a request.
+    pub fn shrink_scope_to<'b, S2>(self, scope: S2) -> Request<'b, D, S2>
+    where
+        'a: 'b,
+        S2: Scope<'b>,
+    {
+        unsafe {
+            let (request, data, _) = self.into_raw();
+            Request::from_raw(request, data, scope)
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D: ?Sized> IntoFuture for ThreadSafe<Request<'static, D, StaticScope>> {
+    type Output = (&'static D, Status);
+    type IntoFuture = RequestFuture<D>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let (request, data, _) = unsafe { self.into_inner().into_raw() };
+        RequestFuture {
+            watch: progress::watch(request),
+            data,
+        }
+    }
+}
+
+/// A `Request` that is being awaited
+///
+/// The request is completed by a progress thread, which tests all awaited requests with
+/// `MPI_Testsome()` and wakes the tasks awaiting them once they have completed. The thread is
+/// started when the first request is awaited, and it busy polls while requests are pending and
+/// sleeps otherwise. Only requests of universes initialized with `Threading::Multiple` can be
+/// awaited, which `ThreadSafe` proves, and the future can be sent to other threads, e.g. by the
+/// executor of a multi-threaded runtime.
+///
+/// The buffers of the request are borrowed for the `StaticScope`, so the future does not block
+/// if it is dropped before it has completed. The progress thread cancels the operation and
+/// completes it in the background instead. When the universe is finalized, the progress thread
+/// cancels all operations that are still pending, waits for them to complete and stops. Like
+/// `Request::wait_for_data()`, the future returns the buffer together with the status, so that the
+/// data received into it can be read, and `Status::is_cancelled()` tells whether the operation has
+/// been cancelled.
+///
+/// This type is available with the `async` feature.
+///
+/// # Examples
+///
+/// See `examples/async_requests.rs`
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+pub struct RequestFuture<D: ?Sized> {
+    watch: Arc<progress::Watch>,
+    data: &'static D,
+}
+
+#[cfg(feature = "async")]
+impl<D: ?Sized> fmt::Debug for RequestFuture<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RequestFuture").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D: ?Sized> Future for RequestFuture<D> {
+    type Output = (&'static D, Status);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.watch.poll(cx.waker()) {
+            Some(status) => Poll::Ready((self.data, status)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<D: ?Sized> Drop for RequestFuture<D> {
+    fn drop(&mut self) {
+        self.watch.cancel();
+    }
+}
+
+/// Cancel the requests of all pending `RequestFuture`s, wait for them and stop the progress
+/// thread, before MPI is finalized.
+#[cfg(feature = "async")]
+pub(crate) fn stop_progress() {
+    progress::stop();
+}
+
+/// Completion of the requests of `RequestFuture`s on a background thread
+#[cfg(feature = "async")]
+mod progress {
+    use std::mem::MaybeUninit;
+    use std::os::raw::c_int;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::Waker;
+    use std::thread::{self, JoinHandle};
+
+    use super::{count_of, is_null};
+    use crate::ffi;
+    use crate::ffi::{MPI_Request, MPI_Status};
+    use crate::point_to_point::Status;
+
+    /// The completion of an awaited request
+    pub(super) struct Watch(Mutex<WatchState>);
+
+    struct WatchState {
+        status: Option<MPI_Status>,
+        waker: Option<Waker>,
+        cancelled: bool,
+    }
+
+    impl Watch {
+        /// The status of the request if it has completed, otherwise `waker` is woken once it has.
+        pub(super) fn poll(&self, waker: &Waker) -> Option<Status> {
+            let mut state = self.0.lock().expect("Watch Mutex poisoned");
+            match state.status {
+                Some(status) => Some(Status::from_raw(status)),
+                None => {
+                    state.waker = Some(waker.clone());
+                    None
+                }
+            }
+        }
+
+        /// Ask the progress thread to cancel the request, as nobody awaits it anymore.
+        pub(super) fn cancel(&self) {
+            let mut state = self.0.lock().expect("Watch Mutex poisoned");
+            state.cancelled = true;
+            state.waker = None;
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.0.lock().expect("Watch Mutex poisoned").cancelled
+        }
+
+        fn complete(&self, status: MPI_Status) {
+            let waker = {
+                let mut state = self.0.lock().expect("Watch Mutex poisoned");
+                state.status = Some(status);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /// A request handed to the progress thread
+    struct Pending(MPI_Request, Arc<Watch>);
+
+    // SAFETY: Requests are only awaited under `Threading::Multiple`, where any thread may complete
+    // them, and only the progress thread uses them once they have been handed over.
+    unsafe impl Send for Pending {}
+
+    /// Requests that have been handed over since the progress thread last looked
+    static HANDED_OVER: Mutex<Vec<Pending>> = Mutex::new(Vec::new());
+
+    /// The progress thread, started on first use
+    static PROGRESS_THREAD: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+    /// Whether the progress thread should cancel all requests and stop once they have completed
+    static STOP: AtomicBool = AtomicBool::new(false);
+
+    /// Hand `request` over to the progress thread, which completes it.
+    pub(super) fn watch(request: MPI_Request) -> Arc<Watch> {
+        let watch = Arc::new(Watch(Mutex::new(WatchState {
+            status: None,
+            waker: None,
+            cancelled: false,
+        })));
+        HANDED_OVER
+            .lock()
+            .expect("HANDED_OVER Mutex poisoned")
+            .push(Pending(request, Arc::clone(&watch)));
+        PROGRESS_THREAD
+            .lock()
+            .expect("PROGRESS_THREAD Mutex poisoned")
+            .get_or_insert_with(|| {
+                thread::Builder::new()
+                    .name("rsmpi-progress".into())
+                    .spawn(run)
+                    .expect("Could not start the progress thread")
+            })
+            .thread()
+            .unpark();
+        watch
+    }
+
+    /// Cancel all pending requests, wait for them and stop the progress thread if it is running.
+    pub(super) fn stop() {
+        let handle = PROGRESS_THREAD
+            .lock()
+            .expect("PROGRESS_THREAD Mutex poisoned")
+            .take();
+        if let Some(handle) = handle {
+            STOP.store(true, Ordering::SeqCst);
+            handle.thread().unpark();
+            handle.join().expect("The progress thread panicked");
+        }
+    }
+
+    fn run() {
+        let mut requests: Vec<MPI_Request> = Vec::new();
+        let mut watches: Vec<Arc<Watch>> = Vec::new();
+        // Whether `MPI_Cancel()` has been called on the request at the same index already
+        let mut cancelled: Vec<bool> = Vec::new();
+        let mut indices: Vec<c_int> = Vec::new();
+        let mut statuses: Vec<MaybeUninit<MPI_Status>> = Vec::new();
+        loop {
+            for Pending(request, watch) in HANDED_OVER
+                .lock()
+                .expect("HANDED_OVER Mutex poisoned")
+                .drain(..)
+            {
+                requests.push(request);
+                watches.push(watch);
+                cancelled.push(false);
+            }
+            let stop = STOP.load(Ordering::SeqCst);
+            if requests.is_empty() {
+                if stop {
+                    return;
+                }
+                // Woken by `watch()`, which hands over requests before unparking the thread, or by
+                // `stop()`.
+                thread::park();
+                continue;
+            }
+
+            // The requests of dropped futures, and all requests once MPI is about to be
+            // finalized, are cancelled and only tested until they have completed.
+            for ((request, watch), cancelled) in
+                requests.iter_mut().zip(&watches).zip(&mut cancelled)
+            {
+                if !*cancelled && (stop || watch.is_cancelled()) {
+                    unsafe {
+                        ffi::MPI_Cancel(request);
+                    }
+                    *cancelled = true;
+                }
+            }
+
+            indices.resize(requests.len(), 0);
+            statuses.resize(requests.len(), MaybeUninit::uninit());
+            let mut completed: c_int = 0;
+            unsafe {
+                ffi::MPI_Testsome(
+                    count_of(&requests),
+                    requests.as_mut_ptr(),
+                    &mut completed,
+                    indices.as_mut_ptr(),
+                    statuses.as_mut_ptr() as *mut MPI_Status,
+                );
+            }
+            if completed == 0 {
+                thread::yield_now();
+                continue;
+            }
+            for (&index, status) in indices.iter().zip(&statuses).take(completed as usize) {
+                watches[index as usize].complete(unsafe { status.assume_init() });
+            }
+            // Completed requests have been set to `MPI_REQUEST_NULL`.
+            let mut i = 0;
+            while i < requests.len() {
+                if is_null(requests[i]) {
+                    requests.swap_remove(i);
+                    watches.swap_remove(i);
+                    cancelled.swap_remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Guard object that waits for the completion of an operation when it is dropped
+///
+/// The guard can be constructed or deconstructed using the `From` and `Into` traits.
+///
+/// # Examples
+///
+/// See `examples/immediate.rs`
+#[derive(Debug)]
+pub struct WaitGuard<'a, D: ?Sized, S: Scope<'a> = StaticScope>(Option<Request<'a, D, S>>);
+
+impl<'a, D: ?Sized, S: Scope<'a>> Drop for WaitGuard<'a, D, S> {
+    fn drop(&mut self) {
+        self.0.take().expect("invalid WaitGuard").wait();
+    }
+}
+
+unsafe impl<'a, D: ?Sized, S: Scope<'a>> AsRaw for WaitGuard<'a, D, S> {
+    type Raw = MPI_Request;
+    fn as_raw(&self) -> Self::Raw {
+        self.0.as_ref().expect("invalid WaitGuard").as_raw()
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> From<WaitGuard<'a, D, S>> for Request<'a, D, S> {
+    fn from(mut guard: WaitGuard<'a, D, S>) -> Self {
+        guard.0.take().expect("invalid WaitGuard")
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> From<Request<'a, D, S>> for WaitGuard<'a, D, S> {
+    fn from(req: Request<'a, D, S>) -> Self {
+        WaitGuard(Some(req))
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> WaitGuard<'a, D, S> {
+    fn cancel(&self) {
+        if let Some(ref req) = self.0 {
+            req.cancel();
+        }
+    }
+}
+
+/// Guard object that tries to cancel and waits for the completion of an operation when it is
+/// dropped
+///
+/// The guard can be constructed or deconstructed using the `From` and `Into` traits.
+///
+/// # Examples
+///
+/// See `examples/immediate.rs`
+#[derive(Debug)]
+pub struct CancelGuard<'a, D: ?Sized, S: Scope<'a> = StaticScope>(WaitGuard<'a, D, S>);
+
+impl<'a, D: ?Sized, S: Scope<'a>> Drop for CancelGuard<'a, D, S> {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> From<CancelGuard<'a, D, S>> for WaitGuard<'a, D, S> {
+    fn from(guard: CancelGuard<'a, D, S>) -> Self {
+        unsafe {
+            let inner = ptr::read(&guard.0);
+            mem::forget(guard);
+            inner
+        }
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> From<WaitGuard<'a, D, S>> for CancelGuard<'a, D, S> {
+    fn from(guard: WaitGuard<'a, D, S>) -> Self {
+        CancelGuard(guard)
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> From<Request<'a, D, S>> for CancelGuard<'a, D, S> {
+    fn from(req: Request<'a, D, S>) -> Self {
+        CancelGuard(WaitGuard::from(req))
+    }
+}
+
+/// A persistent request object for a communication operation registered with a `Scope` of
+/// lifetime `'a`
+///
+/// A persistent request binds the arguments of a communication operation once, after which the
+/// operation can be started and completed any number of times. The request starts out inactive,
+/// `start()` initiates the operation and a completion operation such as `wait()` or `test()` makes
+/// it inactive again.
+///
+/// While the request is inactive, the buffer bound to it can be accessed via `data()` and
+/// `data_mut()`, e.g. to fill in the next message between iterations.
+///
+/// When the request is dropped an active operation is waited for and the request is freed.
+///
+/// # Examples
+///
+/// See `examples/persistent.rs`
+///
+/// # Standard section(s)
+///
+/// 3.9
+#[must_use]
+pub struct PersistentRequest<'a, D: ?Sized, S: Scope<'a> = StaticScope> {
+    request: MPI_Request,
+    active: bool,
+    data: &'a mut D,
+    scope: S,
+    // Requests stay on the thread that created them, see `topology::SimpleCommunicator`.
+    phantom: PhantomData<(Cell<&'a ()>, *const ())>,
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> fmt::Debug for PersistentRequest<'a, D, S>
+where
+    D: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("PersistentRequest")
+            .field("request", &self.request)
+            .field("active", &self.active)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+unsafe impl<'a, D: ?Sized, S: Scope<'a>> AsRaw for PersistentRequest<'a, D, S> {
+    type Raw = MPI_Request;
+    fn as_raw(&self) -> Self::Raw {
+        self.request
+    }
+}
+
+// Only constructed under `Threading::Multiple`, like for `Request`. The buffer is owned by the
+// request between operations, so it moves along with it.
+unsafe impl<'a, D: ?Sized + Send, S: Scope<'a> + Send> Send
+    for ThreadSafe<PersistentRequest<'a, D, S>>
+{
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> Drop for PersistentRequest<'a, D, S> {
+    fn drop(&mut self) {
+        self.wait_without_status();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Request_free"));
+        unsafe {
+            ffi::MPI_Request_free(&mut self.request);
+            self.scope.unregister();
+        }
+    }
+}
+
+impl<'a, D: ?Sized, S: Scope<'a>> PersistentRequest<'a, D, S> {
+    /// Construct a persistent request object from the raw MPI type.
+    ///
+    /// # Safety
+    /// - `request` must be a live, inactive, persistent MPI request.
+    /// - `request` must not be used after calling `from_raw`.
+    /// - Any buffers bound to `request` must live longer than `scope`.
+    pub unsafe fn from_raw(request: MPI_Request, data: &'a mut D, scope: S) -> Self {
+        debug_assert!(!is_null(request));
+        scope.register();
+        Self {
+            request,
+            active: false,
+            data,
+            scope,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Whether the operation has been started and not yet completed.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Start the operation bound to the request.
+    ///
+    /// # Panics
+    ///
+    /// If the request is already active.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/persistent.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.9
+    pub fn start(&mut self) {
+        assert!(!self.active, "persistent request was started while active");
+        let _call = instrument::begin(|| Call::without_comm("MPI_Start"));
+        unsafe {
+            ffi::MPI_Start(&mut self.request);
+        }
+        self.active = true;
+    }
+
+    /// Wait for the started operation to finish, leaving the request inactive.
+    ///
+    /// Returns an empty `Status` if the request is not active.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/persistent.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3, 3.9
+    pub fn wait(&mut self) -> Status {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+        let status = unsafe {
+            Status::from_raw(
+                with_uninitialized(|status| ffi::MPI_Wait(&mut self.request, status)).1,
+            )
+        };
+        self.active = false;
+        status
+    }
+
+    /// Wait for the started operation to finish, but don’t bother retrieving the `Status`
+    /// information.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3, 3.9
+    pub fn wait_without_status(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+        unsafe {
+            ffi::MPI_Wait(&mut self.request, ffi::RSMPI_STATUS_IGNORE);
+        }
+        self.active = false;
+    }
+
+    /// Test whether the started operation has finished.
+    ///
+    /// If the operation has finished, the request becomes inactive and `Status` is returned.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.7.3, 3.9
+    pub fn test(&mut self) -> Option<Status> {
+        unsafe {
+            let mut status = MaybeUninit::uninit();
+            let _call = instrument::begin(|| Call::without_comm("MPI_Test"));
+            let (_, flag) = with_uninitialized(|flag| {
+                ffi::MPI_Test(&mut self.request, flag, status.as_mut_ptr())
+            });
+            if flag != 0 {
+                self.active = false;
+                Some(Status::from_raw(status.assume_init()))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// The buffer bound to the request.
+    ///
+    /// # Panics
+    ///
+    /// If the request is active.
+    pub fn data(&self) -> &D {
+        assert!(
+            !self.active,
+            "buffer of an active persistent request was accessed"
+        );
+        &*self.data
+    }
+
+    /// The contents of the buffer bound to the request, e.g. to prepare the next message to be
+    /// sent.
+    ///
+    /// Only the elements are handed out, so the buffer cannot be moved or resized while MPI
+    /// refers to it.
+    ///
+    /// # Panics
+    ///
+    /// If the request is active.
+    pub fn data_mut(&mut self) -> &mut D::Contents
+    where
+        D: PersistentBuffer,
+    {
+        assert!(
+            !self.active,
+            "buffer of an active persistent request was accessed"
+        );
+        self.data.contents_mut()
+    }
+}
+
+/// A buffer whose contents can be changed between runs of a `PersistentRequest`
+///
+/// # Safety
+///
+/// Modifying the contents must neither move nor resize the memory described by `pointer()` and
+/// `count()` of the buffer.
+pub unsafe trait PersistentBuffer {
+    /// The type of the contents
+    type Contents: ?Sized;
+
+    /// The contents of the buffer
+    fn contents_mut(&mut self) -> &mut Self::Contents;
+}
+
+unsafe impl<T: Equivalence> PersistentBuffer for T {
+    type Contents = T;
+    fn contents_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+unsafe impl<T: Equivalence> PersistentBuffer for [T] {
+    type Contents = [T];
+    fn contents_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+unsafe impl<T: Equivalence> PersistentBuffer for Vec<T> {
+    type Contents = [T];
+    fn contents_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+unsafe impl<T: Equivalence> PersistentBuffer for Box<[T]> {
+    type Contents = [T];
+    fn contents_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+unsafe impl<T: Equivalence, const D: usize> PersistentBuffer for [T; D] {
+    type Contents = [T; D];
+    fn contents_mut(&mut self) -> &mut [T; D] {
+        self
+    }
+}
+
+/// Start all of the persistent requests in the slice.
+///
+/// # Panics
+///
+/// If any of the requests is already active.
+///
+/// # Examples
+///
+/// See `examples/persistent.rs`
+///
+/// # Standard section(s)
+///
+/// 3.9
+pub fn start_all<'a, D: ?Sized, S: Scope<'a>>(requests: &mut [PersistentRequest<'a, D, S>]) {
+    assert!(
+        requests.iter().all(|r| !r.active),
+        "persistent request was started while active"
+    );
+    let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
+    let _call = instrument::begin(|| Call::without_comm("MPI_Startall"));
+    unsafe {
+        ffi::MPI_Startall(count_of(&mpi_requests), mpi_requests.as_mut_ptr());
+    }
+    for (request, &r) in requests.iter_mut().zip(&mpi_requests) {
+        request.request = r;
+        request.active = true;
+    }
+}
+
+/// A persistent request for partitioned communication registered with a `Scope` of lifetime `'a`
+///
+/// The buffer bound to a partitioned request is divided into equally sized partitions. Once a
+/// send has been started, `partitions_mut()` hands out the partitions separately, e.g. to be
+/// filled by different threads, and each partition is transferred as soon as it is marked ready.
+/// On the receiving side, `arrived()` gives access to the partitions that have arrived before the
+/// whole operation is complete.
+///
+/// Like a `PersistentRequest` the request can be started and completed any number of times and an
+/// active operation is waited for when the request is dropped.
+///
+/// Partitioned communication was introduced in MPI 4.0, creating a partitioned request fails with
+/// an error code on older MPI libraries.
+///
+/// # Examples
+///
+/// See `examples/partitioned.rs`
+///
+/// # Standard section(s)
+///
+/// 4.2
+#[must_use]
+pub struct PartitionedRequest<'a, T, S: Scope<'a> = StaticScope> {
+    request: MPI_Request,
+    active: bool,
+    send: bool,
+    ready: bool,
+    partitions: usize,
+    data: &'a mut [T],
+    scope: S,
+    // Requests stay on the thread that created them, see `topology::SimpleCommunicator`.
+    phantom: PhantomData<(Cell<&'a ()>, *const ())>,
+}
+
+impl<'a, T, S: Scope<'a>> fmt::Debug for PartitionedRequest<'a, T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("PartitionedRequest")
+            .field("request", &self.request)
+            .field("active", &self.active)
+            .field("partitions", &self.partitions)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+unsafe impl<'a, T, S: Scope<'a>> AsRaw for PartitionedRequest<'a, T, S> {
+    type Raw = MPI_Request;
+    fn as_raw(&self) -> Self::Raw {
+        self.request
+    }
+}
+
+impl<'a, T, S: Scope<'a>> Drop for PartitionedRequest<'a, T, S> {
+    fn drop(&mut self) {
+        self.wait_without_status();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Request_free"));
         unsafe {
-            r.into_raw();
+            ffi::MPI_Request_free(&mut self.request);
+            self.scope.unregister();
         }
-        Some((u_index, status))
-    } else {
-        None
     }
 }
 
-impl<'a, D: ?Sized, S: Scope<'a>> Request<'a, D, S> {
-    /// Construct a request object from the raw MPI type.
-    ///
-    /// # Requirements
-    ///
-    /// - The request is a valid, active request.  It must not be `MPI_REQUEST_NULL`.
-    /// - The request must not be persistent.
-    /// - All buffers associated with the request must outlive `'a`.
-    /// - The request must not be registered with the given scope.
+impl<'a, T, S: Scope<'a>> PartitionedRequest<'a, T, S> {
+    /// Construct a partitioned request object from the raw MPI type.
     ///
     /// # Safety
-    /// - `request` must be a live MPI object.
+    /// - `request` must be a live, inactive, partitioned MPI request, a send request if `send` is
+    ///   `true` and a receive request otherwise.
+    /// - `request` must be bound to `data` divided into `partitions` partitions.
     /// - `request` must not be used after calling `from_raw`.
-    /// - Any buffers owned by `request` must live longer than `scope`.
-    pub unsafe fn from_raw(request: MPI_Request, data: &'a D, scope: S) -> Self {
+    /// - Any buffers bound to `request` must live longer than `scope`.
+    pub unsafe fn from_raw(
+        request: MPI_Request,
+        data: &'a mut [T],
+        partitions: usize,
+        send: bool,
+        scope: S,
+    ) -> Self {
         debug_assert!(!is_null(request));
         scope.register();
         Self {
             request,
+            active: false,
+            send,
+            ready: false,
+            partitions,
             data,
             scope,
             phantom: Default::default(),
         }
     }
 
-    /// Unregister the request object from its scope and deconstruct it into its raw parts.
-    ///
-    /// This is unsafe because the request may outlive its associated buffers.
-    ///
-    /// # Safety
-    /// - The returned `MPI_Request` must be completed within the lifetime of the returned scope.
-    pub unsafe fn into_raw(self) -> (MPI_Request, &'a D, S) {
-        let request = ptr::read(&self.request);
-        let data = ptr::read(&self.data);
-        let scope = ptr::read(&self.scope);
-        let _ = ptr::read(&self.phantom);
-        mem::forget(self);
-        scope.unregister();
-        (request, data, scope)
+    /// Whether the operation has been started and not yet completed.
+    pub fn is_active(&self) -> bool {
+        self.active
     }
 
-    /// Wait for the request to finish and unregister the request object from its scope.
-    /// If provided, the status is written to the referent of the given reference.
-    /// The referent `MPI_Status` object is never read. Also returns the data
-    /// reference.
-    fn wait_with(self, status: *mut MPI_Status) -> &'a D {
-        unsafe {
-            let (mut request, data, _) = self.into_raw();
-            ffi::MPI_Wait(&mut request, status);
-            assert!(is_null(request)); // persistent requests are not supported
-            data
-        }
+    /// The number of partitions of the buffer.
+    pub fn partitions(&self) -> usize {
+        self.partitions
     }
 
-    /// Wait for the request to finish, unregister it, and return the data
-    /// reference.
-    pub fn wait_for_data(self) -> &'a D {
-        // TODO: Just ignores the status for now, but this info might be needed.
-        self.wait_with(unsafe { ffi::RSMPI_STATUS_IGNORE })
+    fn partition_len(&self) -> usize {
+        self.data.len() / self.partitions
     }
 
-    /// Wait for an operation to finish.
-    ///
-    /// Will block execution of the calling thread until the associated operation has finished.
+    /// Start the operation bound to the request.
     ///
-    /// # Examples
+    /// # Panics
     ///
-    /// See `examples/immediate.rs`
+    /// If the request is already active.
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.3
-    pub fn wait(self) -> Status {
-        unsafe { Status::from_raw(with_uninitialized(|status| self.wait_with(status)).1) }
+    /// 3.9, 4.2.1
+    pub fn start(&mut self) {
+        assert!(!self.active, "partitioned request was started while active");
+        let _call = instrument::begin(|| Call::without_comm("MPI_Start"));
+        unsafe {
+            ffi::MPI_Start(&mut self.request);
+        }
+        self.active = true;
+        self.ready = false;
     }
 
-    /// Wait for an operation to finish, but don’t bother retrieving the `Status` information.
+    /// Hand out the partitions of an active send, each of which is marked ready when it is
+    /// dropped.
     ///
-    /// Will block execution of the calling thread until the associated operation has finished.
+    /// The partitions can be sent to different threads, which is why this requires MPI to be
+    /// initialized with `Threading::Multiple`. At lower threading levels, the message is prepared
+    /// with `data_mut()` before the request is started and marked ready with `ready_all()`.
+    ///
+    /// # Panics
+    ///
+    /// If MPI has not been initialized with `Threading::Multiple`, the request is not an active
+    /// send or the partitions have already been handed out since the request was started.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/partitioned.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.3
-    pub fn wait_without_status(self) {
-        self.wait_with(unsafe { ffi::RSMPI_STATUS_IGNORE });
+    /// 4.2.2 (See: `MPI_Pready`)
+    pub fn partitions_mut(&mut self) -> Vec<SendPartition<'_, T>> {
+        assert_eq!(
+            threading_support(),
+            Threading::Multiple,
+            "partitions can only be handed out with Threading::Multiple"
+        );
+        assert!(
+            self.send,
+            "partitions of a partitioned receive cannot be marked ready"
+        );
+        assert!(
+            self.active,
+            "partitions of an inactive partitioned request were accessed"
+        );
+        assert!(
+            !self.ready,
+            "partitions were handed out twice for the same operation"
+        );
+        self.ready = true;
+        let request = self.request;
+        let len = self.partition_len();
+        let mut rest = &mut self.data[..];
+        (0..self.partitions)
+            .map(|index| {
+                let (data, tail) = mem::take(&mut rest).split_at_mut(len);
+                rest = tail;
+                SendPartition {
+                    request,
+                    index: index as c_int,
+                    data,
+                }
+            })
+            .collect()
     }
 
-    /// Test whether an operation has finished.
+    /// The contents of `partition` of an active receive if it has arrived.
     ///
-    /// If the operation has finished, `Status` is returned.  Otherwise returns the unfinished
-    /// `Request`.
+    /// # Panics
+    ///
+    /// If the request is not a receive or `partition` is out of bounds.
     ///
     /// # Examples
     ///
-    /// See `examples/immediate.rs`
+    /// See `examples/partitioned.rs`
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.3
-    pub fn test(self) -> Result<Status, Self> {
-        unsafe {
-            let mut status = MaybeUninit::uninit();
-            let mut request = self.as_raw();
+    /// 4.2.2 (See: `MPI_Parrived`)
+    pub fn arrived(&self, partition: usize) -> Option<&[T]> {
+        assert!(
+            !self.send,
+            "arrival of partitions can only be checked on a partitioned receive"
+        );
+        assert!(partition < self.partitions, "partition out of bounds");
+        let _call = instrument::begin(|| Call::without_comm("MPI_Parrived"));
+        let (_, flag) = unsafe {
+            with_uninitialized(|flag| ffi::RSMPI_Parrived(self.request, partition as c_int, flag))
+        };
+        if flag != 0 {
+            let len = self.partition_len();
+            Some(&self.data[partition * len..][..len])
+        } else {
+            None
+        }
+    }
 
-            let (_, flag) =
-                with_uninitialized(|flag| ffi::MPI_Test(&mut request, flag, status.as_mut_ptr()));
-            if flag != 0 {
-                assert!(is_null(request)); // persistent requests are not supported
-                let (_, _data, _) = self.into_raw();
-                Ok(Status::from_raw(status.assume_init()))
-            } else {
-                Err(self)
+    /// Mark all partitions of an active send ready unless they have been handed out.
+    ///
+    /// This sends a message that has been prepared with `data_mut()` before the request was
+    /// started. Completing the request does so as well.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.2.2 (See: `MPI_Pready_range`)
+    pub fn ready_all(&mut self) {
+        if self.active && self.send && !self.ready {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Pready_range"));
+            unsafe {
+                ffi::RSMPI_Pready_range(0, self.partitions as c_int - 1, self.request);
             }
+            self.ready = true;
         }
     }
 
-    /// Test whether an operation has finished.
+    /// Wait for the started operation to finish, leaving the request inactive.
     ///
-    /// If the operation has finished, a tuple (`Status`, saved_data) is returned.
-    /// Otherwise returns the unfinished `Request`.
+    /// # Standard section(s)
     ///
-    /// # Examples
+    /// 3.7.3, 4.2.2
+    pub fn wait(&mut self) -> Status {
+        self.ready_all();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+        let status = unsafe {
+            Status::from_raw(
+                with_uninitialized(|status| ffi::MPI_Wait(&mut self.request, status)).1,
+            )
+        };
+        self.active = false;
+        status
+    }
+
+    /// Wait for the started operation to finish, but don’t bother retrieving the `Status`
+    /// information.
     ///
-    /// See `examples/immediate.rs`
+    /// # Standard section(s)
+    ///
+    /// 3.7.3, 4.2.2
+    pub fn wait_without_status(&mut self) {
+        self.ready_all();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Wait"));
+        unsafe {
+            ffi::MPI_Wait(&mut self.request, ffi::RSMPI_STATUS_IGNORE);
+        }
+        self.active = false;
+    }
+
+    /// Test whether the started operation has finished.
+    ///
+    /// If the operation has finished, the request becomes inactive and `Status` is returned.
     ///
     /// # Standard section(s)
     ///
-    /// 3.7.3
-    pub fn test_with_data(self) -> Result<(Status, &'a D), Self> {
+    /// 3.7.3, 4.2.2
+    pub fn test(&mut self) -> Option<Status> {
+        self.ready_all();
         unsafe {
             let mut status = MaybeUninit::uninit();
-            let mut request = self.as_raw();
-
-            let (_, flag) =
-                with_uninitialized(|flag| ffi::MPI_Test(&mut request, flag, status.as_mut_ptr()));
+            let _call = instrument::begin(|| Call::without_comm("MPI_Test"));
+            let (_, flag) = with_uninitialized(|flag| {
+                ffi::MPI_Test(&mut self.request, flag, status.as_mut_ptr())
+            });
             if flag != 0 {
-                assert!(is_null(request)); // persistent requests are not supported
-                let (_, data, _) = self.into_raw();
-                Ok((Status::from_raw(status.assume_init()), data))
+                self.active = false;
+                Some(Status::from_raw(status.assume_init()))
             } else {
-                Err(self)
+                None
             }
         }
     }
 
-    /// Initiate cancellation of the request.
-    ///
-    /// The MPI implementation is not guaranteed to fulfill this operation.  It may not even be
-    /// valid for certain types of requests.  In the future, the MPI forum may [deprecate
-    /// cancellation of sends][mpi26] entirely.
+    /// The buffer bound to the request.
     ///
-    /// [mpi26]: https://github.com/mpi-forum/mpi-issues/issues/26
-    ///
-    /// # Examples
-    ///
-    /// See `examples/immediate.rs`
-    ///
-    /// # Standard section(s)
+    /// # Panics
     ///
-    /// 3.8.4
-    pub fn cancel(&self) {
-        let mut request = self.as_raw();
-        unsafe {
-            ffi::MPI_Cancel(&mut request);
-        }
+    /// If the request is active.
+    pub fn data(&self) -> &[T] {
+        assert!(
+            !self.active,
+            "buffer of an active partitioned request was accessed"
+        );
+        &*self.data
     }
 
-    /// Reduce the scope of a request.
-    pub fn shrink_scope_to<'b, S2>(self, scope: S2) -> Request<'b, D, S2>
-    where
-        'a: 'b,
-        S2: Scope<'b>,
-    {
-        unsafe {
-            let (request, data, _) = self.into_raw();
-            Request::from_raw(request, data, scope)
-        }
+    /// The buffer bound to the request, e.g. to prepare the next message to be sent.
+    ///
+    /// # Panics
+    ///
+    /// If the request is active.
+    pub fn data_mut(&mut self) -> &mut [T] {
+        assert!(
+            !self.active,
+            "buffer of an active partitioned request was accessed"
+        );
+        &mut *self.data
     }
 }
 
-/// Guard object that waits for the completion of an operation when it is dropped
+/// A partition of an active partitioned send, which is marked ready when it is dropped
 ///
-/// The guard can be constructed or deconstructed using the `From` and `Into` traits.
+/// See `PartitionedRequest::partitions_mut()`.
 ///
-/// # Examples
+/// # Standard section(s)
 ///
-/// See `examples/immediate.rs`
-#[derive(Debug)]
-pub struct WaitGuard<'a, D: ?Sized, S: Scope<'a> = StaticScope>(Option<Request<'a, D, S>>);
-
-impl<'a, D: ?Sized, S: Scope<'a>> Drop for WaitGuard<'a, D, S> {
-    fn drop(&mut self) {
-        self.0.take().expect("invalid WaitGuard").wait();
-    }
+/// 4.2.2 (See: `MPI_Pready`)
+pub struct SendPartition<'r, T> {
+    request: MPI_Request,
+    index: c_int,
+    data: &'r mut [T],
 }
 
-unsafe impl<'a, D: ?Sized, S: Scope<'a>> AsRaw for WaitGuard<'a, D, S> {
-    type Raw = MPI_Request;
-    fn as_raw(&self) -> Self::Raw {
-        self.0.as_ref().expect("invalid WaitGuard").as_raw()
-    }
-}
+// The request handle is only used to mark the partition ready, which MPI allows from any thread
+// with `Threading::Multiple`, as checked by `PartitionedRequest::partitions_mut()`.
+unsafe impl<'r, T: Send> Send for SendPartition<'r, T> {}
 
-impl<'a, D: ?Sized, S: Scope<'a>> From<WaitGuard<'a, D, S>> for Request<'a, D, S> {
-    fn from(mut guard: WaitGuard<'a, D, S>) -> Self {
-        guard.0.take().expect("invalid WaitGuard")
+impl<'r, T> SendPartition<'r, T> {
+    /// The index of the partition within the buffer of the request.
+    pub fn index(&self) -> usize {
+        self.index as usize
     }
-}
 
-impl<'a, D: ?Sized, S: Scope<'a>> From<Request<'a, D, S>> for WaitGuard<'a, D, S> {
-    fn from(req: Request<'a, D, S>) -> Self {
-        WaitGuard(Some(req))
-    }
+    /// Mark the partition ready to be sent, equivalent to dropping it.
+    pub fn ready(self) {}
 }
 
-impl<'a, D: ?Sized, S: Scope<'a>> WaitGuard<'a, D, S> {
-    fn cancel(&self) {
-        if let Some(ref req) = self.0 {
-            req.cancel();
-        }
+impl<'r, T> Deref for SendPartition<'r, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.data
     }
 }
 
-/// Guard object that tries to cancel and waits for the completion of an operation when it is
-/// dropped
-///
-/// The guard can be constructed or deconstructed using the `From` and `Into` traits.
-///
-/// # Examples
-///
-/// See `examples/immediate.rs`
-#[derive(Debug)]
-pub struct CancelGuard<'a, D: ?Sized, S: Scope<'a> = StaticScope>(WaitGuard<'a, D, S>);
-
-impl<'a, D: ?Sized, S: Scope<'a>> Drop for CancelGuard<'a, D, S> {
-    fn drop(&mut self) {
-        self.0.cancel();
+impl<'r, T> DerefMut for SendPartition<'r, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.data
     }
 }
 
-impl<'a, D: ?Sized, S: Scope<'a>> From<CancelGuard<'a, D, S>> for WaitGuard<'a, D, S> {
-    fn from(guard: CancelGuard<'a, D, S>) -> Self {
+impl<'r, T> Drop for SendPartition<'r, T> {
+    fn drop(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Pready"));
         unsafe {
-            let inner = ptr::read(&guard.0);
-            mem::forget(guard);
-            inner
+            ffi::RSMPI_Pready(self.index, self.request);
         }
     }
 }
 
-impl<'a, D: ?Sized, S: Scope<'a>> From<WaitGuard<'a, D, S>> for CancelGuard<'a, D, S> {
-    fn from(guard: WaitGuard<'a, D, S>) -> Self {
-        CancelGuard(guard)
-    }
-}
-
-impl<'a, D: ?Sized, S: Scope<'a>> From<Request<'a, D, S>> for CancelGuard<'a, D, S> {
-    fn from(req: Request<'a, D, S>) -> Self {
-        CancelGuard(WaitGuard::from(req))
-    }
-}
-
 /// A common interface for [`LocalScope`](struct.LocalScope.html) and
 /// [`StaticScope`](struct.StaticScope.html) used internally by the `request` module.
 ///
@@ -551,6 +1750,8 @@ pub struct RequestCollection<'a, D: ?Sized> {
     statuses: Vec<MaybeUninit<MPI_Status>>,
     /// Pre-allocated indices buffer for use with testsome(), waitsome(), etc.
     indices: Vec<c_int>,
+    /// Requests stay on the thread that created them, see `topology::SimpleCommunicator`.
+    phantom: PhantomData<*const ()>,
 }
 
 impl<'a, D: ?Sized> RequestCollection<'a, D> {
@@ -569,6 +1770,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
             data,
             statuses,
             indices,
+            phantom: PhantomData,
         }
     }
 
@@ -599,6 +1801,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     /// (request_index, status, saved_data).
     pub fn wait_any(&mut self) -> Option<(usize, Status, &'a D)> {
         let mut i: c_int = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Waitany"));
         let (_res, status) = unsafe {
             let count = self.requests.len() as c_int;
             with_uninitialized(|status| {
@@ -618,6 +1821,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     pub fn wait_some(&mut self, result: &mut Vec<(usize, Status, &'a D)>) {
         result.clear();
         let mut count = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Waitsome"));
         unsafe {
             let n = self.requests.len() as c_int;
             // NOTE: not using the return value here
@@ -639,16 +1843,36 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
             // Persistent requests check
             assert!(is_null(self.requests[idx]));
             if let Some(data) = self.data[idx].take() {
-                let status = unsafe { self.statuses[idx].assume_init() };
+                // The statuses are stored in the order of the indices
+                let status = unsafe { self.statuses[i].assume_init() };
                 let status = Status::from_raw(status);
                 result.push((idx, status, data));
             }
         }
     }
 
+    /// Iterate over the requests in the order in which they complete, yielding
+    /// (request_index, status, saved_data) for each of them.
+    ///
+    /// Completed requests are collected in batches with `MPI_Waitsome()`, the
+    /// iterator ends once all requests in the collection have completed. This is
+    /// the progress loop of a manager that hands out work as results come in:
+    /// stop iterating, `add()` new requests and call `completions()` again.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/immediate_multiple_requests.rs`
+    pub fn completions(&mut self) -> Completions<'_, 'a, D> {
+        Completions {
+            collection: self,
+            completed: vec![],
+        }
+    }
+
     /// Wait for all requests to complete, putting (request_index, status, saved_data)
     /// into result for every completed request.
     pub fn wait_all(&mut self, result: &mut Vec<(usize, Status, &'a D)>) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Waitall"));
         let _res = unsafe {
             ffi::MPI_Waitall(
                 self.requests
@@ -677,6 +1901,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
         let n = self.requests.len() as c_int;
         let mut i = 0;
         let mut flag = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Testany"));
         let (_, status) = unsafe {
             with_uninitialized(|status| {
                 ffi::MPI_Testany(n, self.requests.as_mut_ptr(), &mut i, &mut flag, status)
@@ -700,6 +1925,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
         result.clear();
         let n = self.requests.len() as c_int;
         let mut count = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Testsome"));
         unsafe {
             ffi::MPI_Testsome(
                 n,
@@ -717,7 +1943,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
                 .try_into()
                 .expect("could not cast c_int to usize");
             assert!(is_null(self.requests[idx]));
-            let status = unsafe { self.statuses[idx].assume_init() };
+            let status = unsafe { self.statuses[i].assume_init() };
             if let Some(data) = self.data[idx].take() {
                 result.push((idx, Status::from_raw(status), data));
             }
@@ -729,6 +1955,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     pub fn test_all(&mut self, result: &mut Vec<(usize, Status, &'a D)>) -> bool {
         let n = self.requests.len() as c_int;
         let mut flag = 0;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Testall"));
         unsafe {
             ffi::MPI_Testall(
                 n,
@@ -754,6 +1981,35 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     }
 }
 
+/// Iterator over the completing requests of a `RequestCollection`
+///
+/// See `RequestCollection::completions()`.
+pub struct Completions<'c, 'a, D: ?Sized> {
+    collection: &'c mut RequestCollection<'a, D>,
+    /// Completed requests that have not been yielded yet, in reverse order
+    completed: Vec<(usize, Status, &'a D)>,
+}
+
+impl<'c, 'a, D: ?Sized> Iterator for Completions<'c, 'a, D> {
+    type Item = (usize, Status, &'a D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // MPI_Waitsome() returns MPI_UNDEFINED instead of a count without active requests
+        while self.completed.is_empty() && self.collection.incomplete() > 0 {
+            self.collection.wait_some(&mut self.completed);
+            self.completed.reverse();
+        }
+        self.completed.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.completed.len() + self.collection.incomplete();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'c, 'a, D: ?Sized> ExactSizeIterator for Completions<'c, 'a, D> {}
+
 /// Drop implementation to ensure that all requests have actually completed.
 impl<'a, D: ?Sized> Drop for RequestCollection<'a, D> {
     fn drop(&mut self) {
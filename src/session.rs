@@ -0,0 +1,217 @@
+//! The sessions model of MPI 4.0
+//!
+//! Instead of initializing MPI once for the whole program via `mpi::initialize()`, every library
+//! can open its own `Session`. A session gives access to named process sets like `mpi://WORLD`
+//! and `mpi://SELF`, from which groups and then communicators are created, so that libraries can
+//! use MPI without depending on the application to initialize it.
+//!
+//! Creating a session fails if the MPI library does not support sessions.
+//!
+//! # Unfinished features
+//!
+//! - **11.3.1**: Requesting a thread support level via `MPI_Info`
+//! - **11.3.3**: `MPI_Session_get_info()`, `MPI_Session_get_pset_info()`
+
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Deref;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use conv::ConvUtil;
+
+use crate::ffi;
+use crate::instrument::{self, Call};
+use crate::raw::traits::*;
+use crate::topology::{sealed, Communicator, Group, Rank, SimpleCommunicator, UserGroup};
+use crate::{with_uninitialized, ErrorCode, MpiError};
+
+/// An MPI session
+///
+/// The session is finalized when it is dropped. The groups and communicators created from the
+/// session borrow it, so they are freed before that. Objects derived from them in turn, e.g. by
+/// `Communicator::duplicate()`, have to be freed before the session is finalized as well.
+///
+/// # Examples
+/// See `examples/session.rs`
+///
+/// # Standard section(s)
+///
+/// 11.3
+pub struct Session(ffi::RSMPI_Session);
+
+impl Session {
+    /// Initialize a new session.
+    ///
+    /// Errors of operations on the session are returned to the caller.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.1 (See: `MPI_Session_init`)
+    pub fn init() -> Result<Session, ErrorCode> {
+        let (code, session) = unsafe {
+            with_uninitialized(|session| {
+                ffi::RSMPI_Session_init(ffi::RSMPI_INFO_NULL, ffi::RSMPI_ERRORS_RETURN, session)
+            })
+        };
+        ErrorCode::check(code)?;
+        Ok(Session(session))
+    }
+
+    /// The names of the process sets known to the session.
+    ///
+    /// These include at least `mpi://WORLD` and `mpi://SELF`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.2, 11.3.3 (See: `MPI_Session_get_num_psets`, `MPI_Session_get_nth_pset`)
+    pub fn process_sets(&self) -> Result<Vec<String>, ErrorCode> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Session_get_num_psets"));
+        let (code, count) = unsafe {
+            with_uninitialized(|count| {
+                ffi::RSMPI_Session_get_num_psets(self.0, ffi::RSMPI_INFO_NULL, count)
+            })
+        };
+        ErrorCode::check(code)?;
+        (0..count).map(|n| self.process_set(n)).collect()
+    }
+
+    fn process_set(&self, n: c_int) -> Result<String, ErrorCode> {
+        // A length of zero queries the length of the name including the terminating null byte.
+        let mut len: c_int = 0;
+        ErrorCode::check(unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Session_get_nth_pset"));
+            ffi::RSMPI_Session_get_nth_pset(
+                self.0,
+                ffi::RSMPI_INFO_NULL,
+                n,
+                &mut len,
+                ptr::null_mut(),
+            )
+        })?;
+        let mut buf = vec![
+            0u8;
+            len.value_as()
+                .expect("Length of process set name cannot be expressed as a usize.")
+        ];
+        ErrorCode::check(unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Session_get_nth_pset"));
+            ffi::RSMPI_Session_get_nth_pset(
+                self.0,
+                ffi::RSMPI_INFO_NULL,
+                n,
+                &mut len,
+                buf.as_mut_ptr() as *mut c_char,
+            )
+        })?;
+        if let Some(end) = buf.iter().position(|&b| b == 0) {
+            buf.truncate(end);
+        }
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// The group of processes in the process set `name`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.3.2 (See: `MPI_Group_from_session_pset`)
+    pub fn process_set_group(&self, name: &str) -> Result<SessionBound<'_, UserGroup>, MpiError> {
+        let name = CString::new(name)?;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_from_session_pset"));
+        let (code, group) = unsafe {
+            with_uninitialized(|group| {
+                ffi::RSMPI_Group_from_session_pset(self.0, name.as_ptr(), group)
+            })
+        };
+        ErrorCode::check(code)?;
+        Ok(SessionBound::new(UserGroup(group)))
+    }
+
+    /// Create a communicator containing the processes in the process set `name`.
+    ///
+    /// This has to be called by all processes in the process set with the same `tag`, see
+    /// `SimpleCommunicator::from_group()`.
+    ///
+    /// # Examples
+    /// See `examples/session.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.3.2, 7.4.2
+    pub fn communicator(
+        &self,
+        name: &str,
+        tag: &str,
+    ) -> Result<SessionBound<'_, SimpleCommunicator>, MpiError> {
+        SimpleCommunicator::from_group(&self.process_set_group(name)?, tag).map(SessionBound::new)
+    }
+
+    /// Finalize the session, returning any error.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.1 (See: `MPI_Session_finalize`)
+    pub fn finalize(mut self) -> Result<(), ErrorCode> {
+        let code = unsafe { ffi::RSMPI_Session_finalize(&mut self.0) };
+        mem::forget(self);
+        ErrorCode::check(code)
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::RSMPI_Session_finalize(&mut self.0);
+        }
+    }
+}
+
+/// A group or communicator created from a `Session`, which cannot outlive it
+///
+/// Dereferences to the wrapped object and can be used as a `Group` or `Communicator` itself.
+///
+/// # Examples
+/// See `examples/session.rs`
+pub struct SessionBound<'s, T> {
+    object: T,
+    phantom: PhantomData<&'s Session>,
+}
+
+impl<'s, T> SessionBound<'s, T> {
+    fn new(object: T) -> Self {
+        SessionBound {
+            object,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> Deref for SessionBound<'s, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.object
+    }
+}
+
+unsafe impl<'s, T: AsRaw> AsRaw for SessionBound<'s, T> {
+    type Raw = T::Raw;
+    fn as_raw(&self) -> Self::Raw {
+        self.object.as_raw()
+    }
+}
+
+impl<'s, T: Group> Group for SessionBound<'s, T> {}
+
+impl<'s, T: Communicator> sealed::AsHandle for SessionBound<'s, T> {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        self.object.as_handle()
+    }
+}
+
+impl<'s, T: Communicator> Communicator for SessionBound<'s, T> {
+    fn target_size(&self) -> Rank {
+        self.object.target_size()
+    }
+}
@@ -0,0 +1,244 @@
+//! Node-local shared-memory transport
+//!
+//! The processes of a communicator obtained from `Communicator::split_shared()` can directly
+//! access each other's memory. A `SharedMemoryTransport` uses this to exchange buffers by copying
+//! them through a shared-memory window (`MPI_Win_allocate_shared()`) instead of going through
+//! point-to-point or collective operations of the MPI library. Buffers that do not fit into the
+//! window are transparently handed to the regular collective operations instead.
+//!
+//! Whether this is faster than the intra-node path of the MPI library depends on the
+//! implementation and the workload, so measure before using it.
+//!
+//! # Unfinished features
+//!
+//! - Operations other than broadcast and all-gather
+
+use std::mem::{self, MaybeUninit};
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+use conv::ConvUtil;
+
+use crate::collective::traits::*;
+use crate::datatype::traits::*;
+use crate::ffi;
+use crate::instrument::{self, Call};
+use crate::raw::traits::*;
+use crate::topology::traits::*;
+use crate::topology::{shares_memory, Rank, SimpleCommunicator};
+use crate::Address;
+
+/// Collective-like operations on a node-local communicator implemented via shared memory
+///
+/// Every process of the communicator owns a segment of `capacity` bytes in a shared-memory window.
+/// Data is exchanged by copying it into the segment of the sending process and out of it on the
+/// receiving processes, separated by memory synchronization and barriers.
+///
+/// # Examples
+///
+/// See `examples/shared_memory_transport.rs`
+///
+/// # Standard section(s)
+///
+/// 11.2.3, 11.5.4
+pub struct SharedMemoryTransport {
+    comm: SimpleCommunicator,
+    window: ffi::MPI_Win,
+    segments: Vec<*mut u8>,
+    capacity: usize,
+}
+
+impl SharedMemoryTransport {
+    /// Create a transport for the processes of `comm` with room for `capacity` bytes per process.
+    ///
+    /// This is a collective operation, all processes of `comm` have to pass the same `capacity`.
+    ///
+    /// # Panics
+    ///
+    /// On all processes of `comm` alike, if not all of them can share memory, i.e. `comm` does not
+    /// stem from `Communicator::split_shared()` or an equivalent construction.
+    pub fn new<C: Communicator + ?Sized>(comm: &C, capacity: usize) -> Self {
+        let comm = comm.duplicate();
+        assert!(
+            shares_memory(&comm),
+            "SharedMemoryTransport requires a communicator whose processes share memory"
+        );
+
+        let size: Address = capacity
+            .value_as()
+            .expect("Window capacity exceeds the range of MPI_Aint.");
+        let mut window = unsafe { ffi::RSMPI_WIN_NULL };
+        let mut base: *mut c_void = ptr::null_mut();
+        let base_ptr: *mut *mut c_void = &mut base;
+        unsafe {
+            let _call = instrument::begin(|| Call::new("MPI_Win_allocate_shared", comm.as_raw()));
+            ffi::MPI_Win_allocate_shared(
+                size,
+                1,
+                ffi::RSMPI_INFO_NULL,
+                comm.as_raw(),
+                base_ptr as *mut c_void,
+                &mut window,
+            );
+        }
+
+        let segments = (0..comm.size())
+            .map(|rank| unsafe {
+                let mut segment_size = MaybeUninit::<Address>::uninit();
+                let mut disp_unit = MaybeUninit::<c_int>::uninit();
+                let mut segment: *mut c_void = ptr::null_mut();
+                let segment_ptr: *mut *mut c_void = &mut segment;
+                let _call =
+                    instrument::begin(|| Call::without_comm("MPI_Win_shared_query").peer(rank));
+                ffi::MPI_Win_shared_query(
+                    window,
+                    rank,
+                    segment_size.as_mut_ptr(),
+                    disp_unit.as_mut_ptr(),
+                    segment_ptr as *mut c_void,
+                );
+                segment as *mut u8
+            })
+            .collect();
+
+        // Keep a passive target epoch open for the lifetime of the transport so that
+        // `MPI_Win_sync()` can be used to synchronize the public and private window copies.
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_lock_all"));
+            ffi::MPI_Win_lock_all(ffi::MPI_MODE_NOCHECK as c_int, window);
+        }
+
+        SharedMemoryTransport {
+            comm,
+            window,
+            segments,
+            capacity,
+        }
+    }
+
+    /// The number of bytes each process can contribute to an operation before the transport falls
+    /// back to the regular collective operations.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Broadcast the contents of `buf` on process `root` to all other processes.
+    ///
+    /// Equivalent to `broadcast_into()` on `root`.
+    pub fn broadcast_into<T>(&self, root: Rank, buf: &mut [T])
+    where
+        T: Equivalence + Copy,
+    {
+        let bytes = mem::size_of_val(buf);
+        if bytes > self.capacity {
+            self.comm.process_at_rank(root).broadcast_into(buf);
+            return;
+        }
+
+        let rank = self.comm.rank();
+        if rank == root {
+            unsafe { self.copy_to_segment(rank, buf) };
+        }
+        self.synchronize();
+        if rank != root {
+            unsafe { self.copy_from_segment(root, buf) };
+        }
+        self.synchronize();
+    }
+
+    /// Gather the contents of `sendbuf` on all processes into `recvbuf` on all processes.
+    ///
+    /// Equivalent to `all_gather_into()`. `recvbuf` has to hold exactly `size` times as many
+    /// elements as `sendbuf`.
+    pub fn all_gather_into<T>(&self, sendbuf: &[T], recvbuf: &mut [T])
+    where
+        T: Equivalence + Copy,
+    {
+        let size = self.comm.size();
+        let n = sendbuf.len();
+        assert_eq!(
+            n * size
+                .value_as::<usize>()
+                .expect("Communicator size is negative."),
+            recvbuf.len(),
+            "Receive buffer of all_gather_into must hold a contribution of every process"
+        );
+
+        if mem::size_of_val(sendbuf) > self.capacity {
+            self.comm.all_gather_into(sendbuf, recvbuf);
+            return;
+        }
+
+        let rank = self.comm.rank();
+        unsafe { self.copy_to_segment(rank, sendbuf) };
+        self.synchronize();
+        for (peer, chunk) in (0..size).zip(recvbuf.chunks_mut(n.max(1))) {
+            if peer == rank {
+                chunk.copy_from_slice(sendbuf);
+            } else {
+                unsafe { self.copy_from_segment(peer, chunk) };
+            }
+        }
+        self.synchronize();
+    }
+
+    /// Makes the writes of all processes to their segments visible to all other processes.
+    fn synchronize(&self) {
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_sync"));
+            ffi::MPI_Win_sync(self.window);
+        }
+        self.comm.barrier();
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_sync"));
+            ffi::MPI_Win_sync(self.window);
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `buf` must fit into the segment and no other process may access the segment concurrently.
+    unsafe fn copy_to_segment<T: Copy>(&self, rank: Rank, buf: &[T]) {
+        ptr::copy_nonoverlapping(
+            buf.as_ptr() as *const u8,
+            self.segment(rank),
+            mem::size_of_val(buf),
+        );
+    }
+
+    /// # Safety
+    ///
+    /// `buf` must fit into the segment and no other process may write to the segment
+    /// concurrently.
+    unsafe fn copy_from_segment<T: Copy>(&self, rank: Rank, buf: &mut [T]) {
+        ptr::copy_nonoverlapping(
+            self.segment(rank),
+            buf.as_mut_ptr() as *mut u8,
+            mem::size_of_val(buf),
+        );
+    }
+
+    fn segment(&self, rank: Rank) -> *mut u8 {
+        self.segments[rank.value_as::<usize>().expect("Rank is negative.")]
+    }
+}
+
+impl AsCommunicator for SharedMemoryTransport {
+    type Out = SimpleCommunicator;
+    fn as_communicator(&self) -> &Self::Out {
+        &self.comm
+    }
+}
+
+impl Drop for SharedMemoryTransport {
+    fn drop(&mut self) {
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_unlock_all"));
+            ffi::MPI_Win_unlock_all(self.window);
+        }
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_free"));
+            ffi::MPI_Win_free(&mut self.window);
+        }
+    }
+}
@@ -1,13 +1,17 @@
+use std::marker::PhantomData;
 use std::mem;
 
 use conv::ConvUtil;
 
-use super::{sealed, AsCommunicator, Communicator, IntoTopology, Rank};
+use super::{private_duplicate, sealed, AsCommunicator, Communicator, IntoTopology, Rank};
 use crate::ffi::MPI_Comm;
+use crate::instrument::{self, Call};
 use crate::topology::SimpleCommunicator;
 use crate::{
-    datatype::traits::*, ffi, raw::traits::*, with_uninitialized, with_uninitialized2, Count,
-    IntArray,
+    datatype::{traits::*, Order, UserDatatype},
+    ffi,
+    raw::traits::*,
+    with_uninitialized, with_uninitialized2, Count, IntArray, Tag,
 };
 
 /// Contains arrays describing the layout of the
@@ -71,6 +75,7 @@ impl CartesianCommunicator {
     /// # Standard section(s)
     /// 7.5.5 (MPI_Cartdim_get)
     pub fn num_dimensions(&self) -> Count {
+        let _call = instrument::begin(|| Call::new("MPI_Cartdim_get", self.as_raw()));
         unsafe { with_uninitialized(|count| ffi::MPI_Cartdim_get(self.as_raw(), count)).1 }
     }
 
@@ -99,6 +104,7 @@ impl CartesianCommunicator {
     ) {
         let mut periods_int: IntArray = smallvec::smallvec![0; periods.len()];
 
+        let _call = instrument::begin(|| Call::new("MPI_Cart_get", self.as_raw()));
         ffi::MPI_Cart_get(
             self.as_raw(),
             self.num_dimensions(),
@@ -198,6 +204,7 @@ impl CartesianCommunicator {
     /// - Behavior is undefined if any coordinates in non-periodic axes are outside the dimensions
     //    of the communicator.
     pub unsafe fn coordinates_to_rank_unchecked(&self, coords: &[Count]) -> Rank {
+        let _call = instrument::begin(|| Call::new("MPI_Cart_rank", self.as_raw()));
         with_uninitialized(|rank| ffi::MPI_Cart_rank(self.as_raw(), coords.as_ptr(), rank)).1
     }
 
@@ -269,6 +276,7 @@ impl CartesianCommunicator {
     /// Behavior is undefined if `rank` is not a non-negative value less than
     /// [`size`](trait.Communicator.html#method.size).
     pub unsafe fn rank_to_coordinates_into_unchecked(&self, rank: Rank, coords: &mut [Count]) {
+        let _call = instrument::begin(|| Call::new("MPI_Cart_coords", self.as_raw()));
         ffi::MPI_Cart_coords(self.as_raw(), rank, coords.count(), coords.as_mut_ptr());
     }
 
@@ -339,6 +347,7 @@ impl CartesianCommunicator {
         dimension: Count,
         displacement: Count,
     ) -> (Option<Rank>, Option<Rank>) {
+        let _call = instrument::begin(|| Call::new("MPI_Cart_shift", self.as_raw()));
         let (_, rank_source, rank_destination) =
             with_uninitialized2(|rank_source, rank_destination| {
                 ffi::MPI_Cart_shift(
@@ -411,6 +420,7 @@ impl CartesianCommunicator {
     pub unsafe fn subgroup_unchecked(&self, retain: &[bool]) -> CartesianCommunicator {
         let retain_int: IntArray = retain.iter().map(|b| *b as _).collect();
 
+        let _call = instrument::begin(|| Call::new("MPI_Cart_sub", self.as_raw()));
         CartesianCommunicator::from_raw(
             with_uninitialized(|new_comm| {
                 ffi::MPI_Cart_sub(self.as_raw(), retain_int.as_ptr(), new_comm)
@@ -438,6 +448,167 @@ impl CartesianCommunicator {
 
         unsafe { self.subgroup_unchecked(retain) }
     }
+
+    /// Exchanges the ghost cells of a local array with the neighbors in all dimensions.
+    ///
+    /// `buf` is an array of shape `shape` stored in row-major order, which has `ghost` layers of
+    /// ghost cells on both sides of every dimension around its interior. For every dimension, the
+    /// outermost `ghost` layers of the interior are sent to the neighbors in the negative and
+    /// positive direction, which store them in their opposite ghost layers. The faces include the
+    /// ghost cells of the other dimensions, so the corners are exchanged as well. Ghost cells at
+    /// the boundaries of non-periodic dimensions are left unchanged.
+    ///
+    /// This builds the datatypes of the faces for a single exchange, arrays that are exchanged
+    /// repeatedly should use a plan created by [`halo_exchange_plan`](#method.halo_exchange_plan)
+    /// instead.
+    ///
+    /// This function must be called on all processes of the communicator.
+    ///
+    /// Panics under the conditions of `halo_exchange_plan()` and `HaloExchange::exchange()`.
+    ///
+    /// # Examples
+    /// See `examples/halo_exchange.rs`
+    ///
+    /// # Standard section(s)
+    /// 3.10 (MPI_Sendrecv), 4.1.3 (MPI_Type_create_subarray), 6.4.2 (MPI_Comm_dup),
+    /// 7.5.6 (MPI_Cart_shift)
+    pub fn halo_exchange<T>(&self, buf: &mut [T], shape: &[Count], ghost: Count)
+    where
+        T: Equivalence,
+    {
+        self.halo_exchange_plan(shape, ghost).exchange(buf);
+    }
+
+    /// Prepares the exchange of the ghost cells of local arrays of shape `shape` with `ghost`
+    /// layers of ghost cells, see [`halo_exchange`](#method.halo_exchange).
+    ///
+    /// The plan holds the neighbors and the committed datatypes of the faces, so that they are
+    /// only determined once for all exchanges. The faces are exchanged on a private duplicate of
+    /// the communicator, so they cannot be confused with messages of the user that are still in
+    /// flight. The duplicate is created by the first plan and cached on the communicator.
+    ///
+    /// This function must be called on all processes of the communicator.
+    ///
+    /// Panics if `shape` is not of length [`num_dimensions`](#method.num_dimensions) or if the
+    /// interior of a dimension is narrower than `ghost`.
+    ///
+    /// # Examples
+    /// See `examples/halo_exchange.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.3 (MPI_Type_create_subarray), 6.4.2 (MPI_Comm_dup), 7.5.6 (MPI_Cart_shift)
+    pub fn halo_exchange_plan<T>(&self, shape: &[Count], ghost: Count) -> HaloExchange<T>
+    where
+        T: Equivalence,
+    {
+        assert_eq!(
+            self.num_dimensions(),
+            shape.count(),
+            "The length of the shape must be equal to the number of dimensions in the \
+             CartesianCommunicator"
+        );
+        assert!(ghost >= 0, "ghost width ({}) cannot be negative", ghost);
+        assert!(
+            shape.iter().all(|&n| n >= 3 * ghost),
+            "the interior of every dimension of {:?} must be at least {} wide",
+            shape,
+            ghost
+        );
+        let len = shape
+            .iter()
+            .map(|&n| n.value_as::<usize>().expect("shape cannot be negative"))
+            .product::<usize>();
+
+        let datatype = T::equivalent_datatype();
+        let mut exchanges = Vec::new();
+        if ghost > 0 {
+            for (dimension, &n) in shape.iter().enumerate() {
+                let face = |start: Count| {
+                    let mut subsizes = shape.to_vec();
+                    subsizes[dimension] = ghost;
+                    let mut starts = vec![0; shape.len()];
+                    starts[dimension] = start;
+                    UserDatatype::subarray(shape, &subsizes, &starts, Order::C, &datatype)
+                };
+                let (lower, upper) = self.shift(dimension as Count, 1);
+                let lower = lower.unwrap_or(unsafe { ffi::RSMPI_PROC_NULL });
+                let upper = upper.unwrap_or(unsafe { ffi::RSMPI_PROC_NULL });
+
+                // The first layers of the interior go down, the upper ghost layers come from
+                // above, then the other way around.
+                exchanges.push((face(ghost), lower, face(n - ghost), upper));
+                exchanges.push((face(n - 2 * ghost), upper, face(0), lower));
+            }
+        }
+
+        HaloExchange {
+            comm: private_duplicate(self),
+            shape: shape.to_vec(),
+            len,
+            exchanges,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An exchange of the ghost cells of local arrays of one shape, created by
+/// `CartesianCommunicator::halo_exchange_plan()`
+///
+/// # Examples
+/// See `examples/halo_exchange.rs`
+pub struct HaloExchange<'a, T> {
+    comm: &'a SimpleCommunicator,
+    shape: Vec<Count>,
+    len: usize,
+    /// The datatype sent, its destination, the datatype received and its source, in order
+    exchanges: Vec<(UserDatatype, Rank, UserDatatype, Rank)>,
+    phantom: PhantomData<fn(&mut [T])>,
+}
+
+impl<'a, T> HaloExchange<'a, T>
+where
+    T: Equivalence,
+{
+    /// Exchanges the ghost cells of `buf` with the neighbors in all dimensions.
+    ///
+    /// This function must be called on all processes of the communicator.
+    ///
+    /// Panics if `buf` does not hold an array of the shape of the plan.
+    ///
+    /// # Standard section(s)
+    /// 3.10 (MPI_Sendrecv)
+    pub fn exchange(&self, buf: &mut [T]) {
+        assert_eq!(
+            buf.len(),
+            self.len,
+            "buffer does not hold an array of shape {:?}",
+            self.shape
+        );
+        let ptr = buf.as_mut_ptr();
+        for (send, destination, receive, source) in &self.exchanges {
+            let _call = instrument::begin(|| {
+                Call::new("MPI_Sendrecv", self.comm.as_raw())
+                    .peer(*destination)
+                    .typed(1, send)
+            });
+            unsafe {
+                ffi::MPI_Sendrecv(
+                    ptr as *const _,
+                    1,
+                    send.as_raw(),
+                    *destination,
+                    Tag::default(),
+                    ptr as *mut _,
+                    1,
+                    receive.as_raw(),
+                    *source,
+                    Tag::default(),
+                    self.comm.as_raw(),
+                    ffi::RSMPI_STATUS_IGNORE,
+                );
+            }
+        }
+    }
 }
 
 impl Communicator for CartesianCommunicator {
@@ -0,0 +1,327 @@
+use std::mem;
+use std::os::raw::c_int;
+
+use conv::ConvUtil;
+
+use super::{sealed, AsCommunicator, Communicator, IntoTopology, Rank};
+use crate::ffi::MPI_Comm;
+use crate::instrument::{self, Call};
+use crate::topology::SimpleCommunicator;
+use crate::{ffi, raw::traits::*, with_uninitialized2, Count};
+
+/// Contains arrays describing the layout of a
+/// [`GraphCommunicator`](struct.GraphCommunicator.html).
+///
+/// The neighbors of node `i` are `edges[index[i - 1]..index[i]]`, with `index[-1]` taken to be 0.
+pub struct GraphLayout {
+    /// `index[i]` is the total number of neighbors of nodes 0 to `i`
+    pub index: Vec<Count>,
+    /// The concatenated lists of neighbors of all nodes
+    pub edges: Vec<Rank>,
+}
+
+/// A `GraphCommunicator` is an MPI communicator object where ranks are the nodes of a graph whose
+/// edges describe the communication pattern. Every process holds the full graph.
+///
+/// # Standard Section(s)
+///
+/// 7
+pub struct GraphCommunicator(pub(crate) SimpleCommunicator);
+
+impl GraphCommunicator {
+    /// Given a valid `MPI_Comm` handle in `raw`, returns a `GraphCommunicator` value if, and only
+    /// if:
+    /// - The handle is not `MPI_COMM_NULL`
+    /// - The topology of the communicator is `MPI_GRAPH`
+    ///
+    /// Otherwise returns None.
+    ///
+    /// # Safety
+    /// - `raw` must be a live MPI_Comm handle.
+    /// - `raw` must not be a system communicator handle.
+    /// - `raw` must not be a inter-communicator handle.
+    /// - `raw` must not be used after calling this function.
+    pub unsafe fn try_from_raw(raw: MPI_Comm) -> Option<GraphCommunicator> {
+        SimpleCommunicator::try_from_raw(raw).and_then(|comm| match comm.into_topology() {
+            IntoTopology::Graph(c) => Some(c),
+            incorrect => {
+                // Forget the comm object so it's not dropped
+                mem::forget(incorrect);
+
+                None
+            }
+        })
+    }
+
+    /// Returns the topological structure of the graph communicator
+    ///
+    /// # Standard section(s)
+    /// 7.5.5 (MPI_Graphdims_get, MPI_Graph_get)
+    pub fn get_layout(&self) -> GraphLayout {
+        let _call = instrument::begin(|| Call::new("MPI_Graphdims_get", self.as_raw()));
+        let (_, nnodes, nedges) = unsafe {
+            with_uninitialized2(|nnodes, nedges| {
+                ffi::MPI_Graphdims_get(self.as_raw(), nnodes, nedges)
+            })
+        };
+
+        let mut index = vec![
+            0;
+            nnodes
+                .value_as()
+                .expect("Received an invalid number of nodes")
+        ];
+        let mut edges = vec![
+            0;
+            nedges
+                .value_as()
+                .expect("Received an invalid number of edges")
+        ];
+        let _call = instrument::begin(|| Call::new("MPI_Graph_get", self.as_raw()));
+        unsafe {
+            ffi::MPI_Graph_get(
+                self.as_raw(),
+                nnodes,
+                nedges,
+                index.as_mut_ptr(),
+                edges.as_mut_ptr(),
+            );
+        }
+
+        GraphLayout { index, edges }
+    }
+
+    /// Returns the number of neighbors of `rank`.
+    ///
+    /// # Standard section(s)
+    /// 7.5.5 (MPI_Graph_neighbors_count)
+    pub fn neighbors_count(&self, rank: Rank) -> Count {
+        let mut nneighbors: Count = 0;
+        let _call = instrument::begin(|| Call::new("MPI_Graph_neighbors_count", self.as_raw()));
+        unsafe {
+            ffi::MPI_Graph_neighbors_count(self.as_raw(), rank, &mut nneighbors);
+        }
+        nneighbors
+    }
+
+    /// Returns the neighbors of `rank`.
+    ///
+    /// # Examples
+    /// See `examples/graph.rs`
+    ///
+    /// # Standard section(s)
+    /// 7.5.5 (MPI_Graph_neighbors)
+    pub fn neighbors(&self, rank: Rank) -> Vec<Rank> {
+        let nneighbors = self.neighbors_count(rank);
+        let mut neighbors = vec![
+            0;
+            nneighbors
+                .value_as()
+                .expect("Received an invalid number of neighbors")
+        ];
+        let _call = instrument::begin(|| Call::new("MPI_Graph_neighbors", self.as_raw()));
+        unsafe {
+            ffi::MPI_Graph_neighbors(self.as_raw(), rank, nneighbors, neighbors.as_mut_ptr());
+        }
+        neighbors
+    }
+}
+
+impl Communicator for GraphCommunicator {
+    fn target_size(&self) -> Rank {
+        self.size()
+    }
+}
+
+impl sealed::AsHandle for GraphCommunicator {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        self.0.as_handle()
+    }
+}
+
+impl AsCommunicator for GraphCommunicator {
+    type Out = GraphCommunicator;
+    fn as_communicator(&self) -> &Self::Out {
+        self
+    }
+}
+
+unsafe impl AsRaw for GraphCommunicator {
+    type Raw = MPI_Comm;
+    fn as_raw(&self) -> Self::Raw {
+        self.0.as_raw()
+    }
+}
+
+impl FromRaw for GraphCommunicator {
+    /// Creates a `GraphCommunicator` from `raw`.
+    ///
+    /// # Safety
+    /// - `raw` must be a live `MPI_GRAPH` MPI_Comm handle
+    /// - `raw` must not be an inter-comm handle, the parent handle, or a system handle
+    /// - `raw` must not be used after calling this function.
+    unsafe fn from_raw(raw: <Self as AsRaw>::Raw) -> Self {
+        debug_assert_ne!(raw, ffi::RSMPI_COMM_NULL);
+        GraphCommunicator(SimpleCommunicator::from_raw(raw))
+    }
+}
+
+/// The neighbors of the calling process in a
+/// [`DistributedGraphCommunicator`](struct.DistributedGraphCommunicator.html).
+pub struct DistributedGraphNeighbors {
+    /// The processes that have an edge to the calling process
+    pub sources: Vec<Rank>,
+    /// The weights of the edges from `sources`, `None` if the graph is unweighted
+    pub source_weights: Option<Vec<Count>>,
+    /// The processes the calling process has an edge to
+    pub destinations: Vec<Rank>,
+    /// The weights of the edges to `destinations`, `None` if the graph is unweighted
+    pub destination_weights: Option<Vec<Count>>,
+}
+
+/// A `DistributedGraphCommunicator` is an MPI communicator object where ranks are the nodes of a
+/// graph whose edges describe the communication pattern. Every process only knows its own
+/// incoming and outgoing edges, which makes it scale to large numbers of processes.
+///
+/// # Standard Section(s)
+///
+/// 7
+pub struct DistributedGraphCommunicator(pub(crate) SimpleCommunicator);
+
+impl DistributedGraphCommunicator {
+    /// Given a valid `MPI_Comm` handle in `raw`, returns a `DistributedGraphCommunicator` value
+    /// if, and only if:
+    /// - The handle is not `MPI_COMM_NULL`
+    /// - The topology of the communicator is `MPI_DIST_GRAPH`
+    ///
+    /// Otherwise returns None.
+    ///
+    /// # Safety
+    /// - `raw` must be a live MPI_Comm handle.
+    /// - `raw` must not be a system communicator handle.
+    /// - `raw` must not be a inter-communicator handle.
+    /// - `raw` must not be used after calling this function.
+    pub unsafe fn try_from_raw(raw: MPI_Comm) -> Option<DistributedGraphCommunicator> {
+        SimpleCommunicator::try_from_raw(raw).and_then(|comm| match comm.into_topology() {
+            IntoTopology::DistributedGraph(c) => Some(c),
+            incorrect => {
+                // Forget the comm object so it's not dropped
+                mem::forget(incorrect);
+
+                None
+            }
+        })
+    }
+
+    /// Returns the number of incoming and outgoing edges of the calling process and whether the
+    /// graph is weighted.
+    ///
+    /// # Standard section(s)
+    /// 7.5.5 (MPI_Dist_graph_neighbors_count)
+    pub fn neighbors_count(&self) -> (Count, Count, bool) {
+        let mut indegree: Count = 0;
+        let mut outdegree: Count = 0;
+        let mut weighted: c_int = 0;
+        let _call =
+            instrument::begin(|| Call::new("MPI_Dist_graph_neighbors_count", self.as_raw()));
+        unsafe {
+            ffi::MPI_Dist_graph_neighbors_count(
+                self.as_raw(),
+                &mut indegree,
+                &mut outdegree,
+                &mut weighted,
+            );
+        }
+        (indegree, outdegree, weighted != 0)
+    }
+
+    /// Returns the incoming and outgoing edges of the calling process.
+    ///
+    /// # Examples
+    /// See `examples/graph.rs`
+    ///
+    /// # Standard section(s)
+    /// 7.5.5 (MPI_Dist_graph_neighbors)
+    pub fn neighbors(&self) -> DistributedGraphNeighbors {
+        let (indegree, outdegree, weighted) = self.neighbors_count();
+        let indegree_len = indegree.value_as().expect("Received an invalid in-degree");
+        let outdegree_len = outdegree
+            .value_as()
+            .expect("Received an invalid out-degree");
+
+        let mut sources = vec![0; indegree_len];
+        let mut destinations = vec![0; outdegree_len];
+        let mut source_weights = vec![0; if weighted { indegree_len } else { 0 }];
+        let mut destination_weights = vec![0; if weighted { outdegree_len } else { 0 }];
+        let _call = instrument::begin(|| Call::new("MPI_Dist_graph_neighbors", self.as_raw()));
+        unsafe {
+            let (source_weights_ptr, destination_weights_ptr) = if weighted {
+                (
+                    source_weights.as_mut_ptr(),
+                    destination_weights.as_mut_ptr(),
+                )
+            } else {
+                (ffi::RSMPI_UNWEIGHTED(), ffi::RSMPI_UNWEIGHTED())
+            };
+            ffi::MPI_Dist_graph_neighbors(
+                self.as_raw(),
+                indegree,
+                sources.as_mut_ptr(),
+                source_weights_ptr,
+                outdegree,
+                destinations.as_mut_ptr(),
+                destination_weights_ptr,
+            );
+        }
+
+        DistributedGraphNeighbors {
+            sources,
+            source_weights: if weighted { Some(source_weights) } else { None },
+            destinations,
+            destination_weights: if weighted {
+                Some(destination_weights)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl Communicator for DistributedGraphCommunicator {
+    fn target_size(&self) -> Rank {
+        self.size()
+    }
+}
+
+impl sealed::AsHandle for DistributedGraphCommunicator {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        self.0.as_handle()
+    }
+}
+
+impl AsCommunicator for DistributedGraphCommunicator {
+    type Out = DistributedGraphCommunicator;
+    fn as_communicator(&self) -> &Self::Out {
+        self
+    }
+}
+
+unsafe impl AsRaw for DistributedGraphCommunicator {
+    type Raw = MPI_Comm;
+    fn as_raw(&self) -> Self::Raw {
+        self.0.as_raw()
+    }
+}
+
+impl FromRaw for DistributedGraphCommunicator {
+    /// Creates a `DistributedGraphCommunicator` from `raw`.
+    ///
+    /// # Safety
+    /// - `raw` must be a live `MPI_DIST_GRAPH` MPI_Comm handle
+    /// - `raw` must not be an inter-comm handle, the parent handle, or a system handle
+    /// - `raw` must not be used after calling this function.
+    unsafe fn from_raw(raw: <Self as AsRaw>::Raw) -> Self {
+        debug_assert_ne!(raw, ffi::RSMPI_COMM_NULL);
+        DistributedGraphCommunicator(SimpleCommunicator::from_raw(raw))
+    }
+}
@@ -11,33 +11,38 @@
 //! - **6.3**: Group management
 //!   - **6.3.2**: Constructors, `MPI_Group_range_incl()`, `MPI_Group_range_excl()`
 //! - **6.4**: Communicator management
-//!   - **6.4.2**: Constructors, `MPI_Comm_dup_with_info()`, `MPI_Comm_idup()`,
-//!     `MPI_Comm_split_type()`
+//!   - **6.4.2**: Constructors, `MPI_Comm_dup_with_info()`, `MPI_Comm_idup()`
 //!   - **6.4.4**: Info, `MPI_Comm_set_info()`, `MPI_Comm_get_info()`
-//! - **6.6**: Inter-communication
-//! - **6.7**: Caching
 //! - **6.8**: Naming objects
-//! - **7**: Process topologies
+//! - **7**: Process topologies, `MPI_Dist_graph_create()`
 //! - **Parts of sections**: 8, 10, 12
 use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::ops::{Deref, Range};
 use std::os::raw::{c_char, c_int, c_void};
 use std::process;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use conv::ConvUtil;
 
 #[cfg(not(msmpi))]
 use crate::Tag;
-use crate::{Count, IntArray};
+use crate::{Count, IntArray, MpiError};
 
 use crate::attribute::CommAttribute;
 use crate::datatype::traits::*;
+use crate::environment::{self, Threading};
+use crate::error_handler;
 use crate::ffi;
 use crate::ffi::{MPI_Comm, MPI_Group};
+use crate::instrument::{self, Call};
 use crate::raw::traits::*;
-use crate::with_uninitialized;
+use crate::{with_uninitialized, ErrorCode};
 
 mod cartesian;
+mod graph;
 
 /// Topology traits
 pub mod traits {
@@ -46,6 +51,7 @@ pub mod traits {
 
 // Re-export cartesian functions and types from topology modules.
 pub use self::cartesian::*;
+pub use self::graph::*;
 
 /// Something that has a communicator associated with it
 pub trait AsCommunicator {
@@ -63,7 +69,16 @@ pub(crate) mod sealed;
 
 /// A simple communicator, either a system-defined communicator like `MPI_COMM_WORLD` or a
 /// user-defined intra-communicator without a special topology.
-pub struct SimpleCommunicator(pub(crate) sealed::CommunicatorHandle);
+///
+/// Communicators, and the processes and requests derived from them, cannot be sent to or shared
+/// with other threads, regardless of whether the handles of the MPI library would allow it. MPI
+/// only permits calls from several threads under `Threading::Multiple`, where
+/// `Universe::thread_safe_world()` provides a world communicator that can be shared and
+/// `ThreadSafe` wraps other communicators and requests for sharing.
+pub struct SimpleCommunicator(
+    pub(crate) sealed::CommunicatorHandle,
+    PhantomData<*const ()>,
+);
 
 impl SimpleCommunicator {
     /// The 'world communicator'
@@ -73,14 +88,52 @@ impl SimpleCommunicator {
     /// # Examples
     /// See `examples/simple.rs`
     pub fn world() -> SimpleCommunicator {
-        SimpleCommunicator(sealed::CommunicatorHandle::World)
+        SimpleCommunicator(sealed::CommunicatorHandle::World, PhantomData)
     }
 
     /// The 'self communicator'
     ///
     /// Contains only the current process.
+    ///
+    /// # Examples
+    /// See `examples/self_comm.rs`
     pub fn self_comm() -> SimpleCommunicator {
-        SimpleCommunicator(sealed::CommunicatorHandle::SelfComm)
+        SimpleCommunicator(sealed::CommunicatorHandle::SelfComm, PhantomData)
+    }
+
+    /// Create a communicator containing the processes of `group` without a parent communicator.
+    ///
+    /// All processes of `group` have to call this with the same `tag`, which distinguishes
+    /// concurrent creations of communicators from the same group. This is how communicators are
+    /// created in the sessions model, see `session::Session`.
+    ///
+    /// Fails if the MPI library does not support this, it was introduced in MPI 4.0.
+    ///
+    /// # Examples
+    /// See `examples/session.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.4.2 (See: `MPI_Comm_create_from_group`)
+    pub fn from_group<G>(group: &G, tag: &str) -> Result<SimpleCommunicator, MpiError>
+    where
+        G: Group + ?Sized,
+    {
+        let tag = CString::new(tag)?;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Comm_create_from_group"));
+        let (code, newcomm) = unsafe {
+            with_uninitialized(|newcomm| {
+                ffi::RSMPI_Comm_create_from_group(
+                    group.as_raw(),
+                    tag.as_ptr(),
+                    ffi::RSMPI_INFO_NULL,
+                    ffi::RSMPI_ERRORS_ARE_FATAL,
+                    newcomm,
+                )
+            })
+        };
+        ErrorCode::check(code)?;
+        Ok(unsafe { SimpleCommunicator::from_raw(newcomm) })
     }
 
     /// If the raw value is the null handle returns `None`, otherwise it tries to create a
@@ -95,7 +148,7 @@ impl SimpleCommunicator {
     unsafe fn try_from_raw(raw: MPI_Comm) -> Option<SimpleCommunicator> {
         let handle = sealed::CommunicatorHandle::try_from_raw(raw)?;
         if let sealed::CommunicatorHandle::User(_) = handle {
-            Some(SimpleCommunicator(handle))
+            Some(SimpleCommunicator(handle, PhantomData))
         } else {
             None
         }
@@ -106,6 +159,7 @@ impl SimpleCommunicator {
     /// # Standard section(s)
     /// 7.5.5
     pub fn topology(&self) -> Topology {
+        let _call = instrument::begin(|| Call::new("MPI_Topo_test", self.as_raw()));
         unsafe {
             let (_, topology) =
                 with_uninitialized(|topology| ffi::MPI_Topo_test(self.as_raw(), topology));
@@ -130,9 +184,11 @@ impl SimpleCommunicator {
     /// 7.5.5
     pub fn into_topology(self) -> IntoTopology {
         match self.topology() {
-            Topology::Graph => unimplemented!(),
+            Topology::Graph => IntoTopology::Graph(GraphCommunicator(self)),
             Topology::Cartesian => IntoTopology::Cartesian(CartesianCommunicator(self)),
-            Topology::DistributedGraph => unimplemented!(),
+            Topology::DistributedGraph => {
+                IntoTopology::DistributedGraph(DistributedGraphCommunicator(self))
+            }
             Topology::Undefined => IntoTopology::Undefined(self),
         }
     }
@@ -161,7 +217,7 @@ impl FromRaw for SimpleCommunicator {
     /// - `handle` must not be used after calling this function.
     unsafe fn from_raw(handle: <Self as AsRaw>::Raw) -> Self {
         let handle = sealed::CommunicatorHandle::simple_comm_from_raw(handle);
-        SimpleCommunicator(handle)
+        SimpleCommunicator(handle, PhantomData)
     }
 }
 
@@ -178,6 +234,186 @@ impl AsCommunicator for SimpleCommunicator {
     }
 }
 
+impl<C: Communicator + ?Sized> sealed::AsHandle for Rc<C> {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        (**self).as_handle()
+    }
+}
+
+/// A communicator shared by reference counting, it is freed when the last reference is dropped.
+///
+/// # Examples
+/// See `examples/shared_handles.rs`
+impl<C: Communicator + ?Sized> Communicator for Rc<C> {
+    fn target_size(&self) -> Rank {
+        (**self).target_size()
+    }
+}
+
+impl<C: Communicator + ?Sized> sealed::AsHandle for Arc<C> {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        (**self).as_handle()
+    }
+}
+
+/// A communicator shared by atomic reference counting, it is freed when the last reference is
+/// dropped.
+///
+/// # Examples
+/// See `examples/shared_handles.rs`
+impl<C: Communicator + ?Sized> Communicator for Arc<C> {
+    fn target_size(&self) -> Rank {
+        (**self).target_size()
+    }
+}
+
+/// The 'world communicator' of a universe initialized with `Threading::Multiple`, which can be
+/// shared between threads
+///
+/// Any thread may call MPI at any time under `Threading::Multiple`, so unlike other communicators,
+/// the world communicator can then be sent to and shared with other threads.
+///
+/// # Examples
+/// See `examples/thread_safe_world.rs`
+///
+/// # Standard section(s)
+///
+/// 12.4
+pub struct ThreadSafeWorld(SimpleCommunicator);
+
+impl ThreadSafeWorld {
+    /// The world communicator, if MPI has been initialized with `Threading::Multiple`
+    pub(crate) fn new() -> Option<Self> {
+        (environment::threading_support() == Threading::Multiple)
+            .then(|| ThreadSafeWorld(SimpleCommunicator::world()))
+    }
+}
+
+impl Clone for ThreadSafeWorld {
+    fn clone(&self) -> Self {
+        ThreadSafeWorld(SimpleCommunicator::world())
+    }
+}
+
+// Only constructed under `Threading::Multiple`, and the world communicator is never freed.
+unsafe impl Send for ThreadSafeWorld {}
+unsafe impl Sync for ThreadSafeWorld {}
+
+unsafe impl AsRaw for ThreadSafeWorld {
+    type Raw = MPI_Comm;
+    fn as_raw(&self) -> Self::Raw {
+        self.0.as_raw()
+    }
+}
+
+impl sealed::AsHandle for ThreadSafeWorld {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        self.0.as_handle()
+    }
+}
+
+impl Communicator for ThreadSafeWorld {
+    fn target_size(&self) -> Rank {
+        self.size()
+    }
+}
+
+impl AsCommunicator for ThreadSafeWorld {
+    type Out = SimpleCommunicator;
+    fn as_communicator(&self) -> &Self::Out {
+        &self.0
+    }
+}
+
+/// A communicator, request or datatype of a universe initialized with `Threading::Multiple`, which
+/// can be shared between threads
+///
+/// Like `ThreadSafeWorld` for the world communicator, this allows sending communicators created
+/// by e.g. `duplicate()` or `split_by_color()` to other threads and sharing them. It dereferences
+/// to the wrapped communicator. Requests of the `StaticScope` can be wrapped as well, to complete
+/// them on another thread than the one that started them, and `UserDatatype`s, which are
+/// duplicated and freed by the threads they are cloned and dropped on.
+///
+/// # Examples
+/// See `examples/thread_safe_world.rs`
+///
+/// # Standard section(s)
+///
+/// 12.4
+pub struct ThreadSafe<T>(T);
+
+impl<T> ThreadSafe<T> {
+    /// Wrap `value` for sharing between threads, if MPI has been initialized with
+    /// `Threading::Multiple`
+    ///
+    /// Returns `value` as the error otherwise.
+    pub fn new(value: T) -> Result<Self, T> {
+        if environment::threading_support() == Threading::Multiple {
+            Ok(ThreadSafe(value))
+        } else {
+            Err(value)
+        }
+    }
+
+    /// The wrapped communicator, request or datatype, which stays on the current thread again
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ThreadSafe<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// Only constructed under `Threading::Multiple`. These communicators consist of nothing but their
+// handle, which any thread may use and free then.
+macro_rules! thread_safe_communicator {
+    ($($communicator:ty),*) => {
+        $(
+            unsafe impl Send for ThreadSafe<$communicator> {}
+            unsafe impl Sync for ThreadSafe<$communicator> {}
+        )*
+    };
+}
+
+thread_safe_communicator!(
+    SimpleCommunicator,
+    InterCommunicator,
+    CartesianCommunicator,
+    GraphCommunicator,
+    DistributedGraphCommunicator
+);
+
+unsafe impl<C: Communicator> AsRaw for ThreadSafe<C> {
+    type Raw = MPI_Comm;
+    fn as_raw(&self) -> Self::Raw {
+        self.0.as_raw()
+    }
+}
+
+impl<C: Communicator> sealed::AsHandle for ThreadSafe<C> {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        self.0.as_handle()
+    }
+}
+
+impl<C: Communicator> Communicator for ThreadSafe<C> {
+    fn target_size(&self) -> Rank {
+        self.0.target_size()
+    }
+}
+
+impl<C: AsCommunicator> AsCommunicator for ThreadSafe<C> {
+    type Out = C::Out;
+    fn as_communicator(&self) -> &Self::Out {
+        self.0.as_communicator()
+    }
+}
+
 /// An enum describing the topology of a communicator
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Topology {
@@ -208,13 +444,18 @@ pub enum IntoTopology {
 ///
 /// # Standard Sections
 /// 6.6
-pub struct InterCommunicator(pub(crate) sealed::CommunicatorHandle);
+pub struct InterCommunicator(
+    pub(crate) sealed::CommunicatorHandle,
+    PhantomData<*const ()>,
+);
 
 impl InterCommunicator {
     /// Construct an `InterCommunicator` from a raw handle
     pub unsafe fn try_from_raw(raw: MPI_Comm) -> Option<Self> {
         sealed::CommunicatorHandle::try_from_raw(raw).and_then(|handle| match handle {
-            sealed::CommunicatorHandle::InterComm(_) => Some(InterCommunicator(handle)),
+            sealed::CommunicatorHandle::InterComm(_) => {
+                Some(InterCommunicator(handle, PhantomData))
+            }
             _ => None,
         })
     }
@@ -226,6 +467,7 @@ impl InterCommunicator {
     /// 6.6.1, See MPI_Comm_remote_size
     pub fn remote_size(&self) -> Rank {
         let mut size = Rank::min_value();
+        let _call = instrument::begin(|| Call::new("MPI_Comm_remote_size", self.as_raw()));
         unsafe {
             ffi::MPI_Comm_remote_size(self.as_raw(), &mut size);
         }
@@ -237,6 +479,7 @@ impl InterCommunicator {
     /// # Standard Section(s)
     /// 6.6.1, See MPI_Comm_remote_group
     pub fn remote_group(&self) -> UserGroup {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_remote_group", self.as_raw()));
         unsafe {
             let (_, g) = with_uninitialized(|g| {
                 ffi::MPI_Comm_remote_group(self.as_raw(), g);
@@ -255,6 +498,7 @@ impl InterCommunicator {
     ///
     /// 7.6.2
     pub fn merge(&self, merge_order: MergeOrder) -> SimpleCommunicator {
+        let _call = instrument::begin(|| Call::new("MPI_Intercomm_merge", self.as_raw()));
         unsafe {
             SimpleCommunicator::try_from_raw(
                 with_uninitialized(|raw| {
@@ -264,6 +508,37 @@ impl InterCommunicator {
             )
         }.expect("rspmi internal error: MPI implementation return MPI_COMM_NULL from MPI_Intercomm_merge()")
     }
+
+    /// Identifies the calling process as the root of a rooted collective operation on the
+    /// inter-communicator, e.g. for broadcasting to all processes of the remote group.
+    ///
+    /// The other processes of the local group have to use `local_non_root()` while the processes
+    /// of the remote group identify the root by its rank via `process_at_rank()`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/intercommunicator.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.2.2, see MPI_ROOT
+    pub fn local_root(&self) -> Process {
+        Process::by_rank_unchecked(self, unsafe { ffi::RSMPI_ROOT })
+    }
+
+    /// Identifies the root of a rooted collective operation on the inter-communicator as another
+    /// process of the local group, i.e. the calling process does not take part in the operation.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/intercommunicator.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.2.2, see MPI_PROC_NULL
+    pub fn local_non_root(&self) -> Process {
+        Process::by_rank_unchecked(self, unsafe { ffi::RSMPI_PROC_NULL })
+    }
 }
 
 impl AsCommunicator for InterCommunicator {
@@ -294,7 +569,10 @@ impl FromRaw for InterCommunicator {
     /// - `handle` must be an inter-comms or inter-comm parent handle
     /// - `handle` must not be used after calling `from_raw`.
     unsafe fn from_raw(handle: <Self as AsRaw>::Raw) -> Self {
-        Self(sealed::CommunicatorHandle::inter_comm_from_raw(handle))
+        Self(
+            sealed::CommunicatorHandle::inter_comm_from_raw(handle),
+            PhantomData,
+        )
     }
 }
 
@@ -304,13 +582,22 @@ impl Communicator for InterCommunicator {
     }
 }
 
-/// Unimplemented
-#[allow(missing_copy_implementations)]
-pub struct GraphCommunicator;
-
-/// Unimplemented
-#[allow(missing_copy_implementations)]
-pub struct DistributedGraphCommunicator;
+/// Describes how the processes of a communicator are distributed over nodes, returned by
+/// [`Communicator::node_layout()`](trait.Communicator.html#method.node_layout).
+///
+/// A node is a group of processes that can create a shared memory region. The node-local rank
+/// and the number of processes on the node are the `rank()` and `size()` of `node`.
+pub struct NodeLayout {
+    /// The processes on the same node as the calling process, in the order of their ranks
+    pub node: SimpleCommunicator,
+    /// The processes with node-local rank 0, one per node, on those processes, `None` elsewhere
+    pub leaders: Option<SimpleCommunicator>,
+    /// The index of the node of the calling process, nodes are ordered by the ranks of their
+    /// leaders
+    pub node_index: Rank,
+    /// The number of nodes
+    pub num_nodes: Rank,
+}
 
 /// A color used in a communicator split
 #[derive(Copy, Clone, Debug)]
@@ -341,6 +628,25 @@ impl Color {
 /// A key used when determining the rank order of processes after a communicator split.
 pub type Key = c_int;
 
+/// The kind of resource shared by the processes of each communicator in a split by type
+///
+/// # Standard section(s)
+///
+/// 6.4.2 (See: `MPI_Comm_split_type`)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SplitType {
+    /// Processes that can create a shared memory region, `MPI_COMM_TYPE_SHARED`
+    Shared,
+    /// Processes that share a hardware resource, named by the `mpi_hw_resource_type` info key,
+    /// e.g. `"mpi_shared_memory"` or implementation specific names like `"NUMANode"` or
+    /// `"Package"`, see `MPI_COMM_TYPE_HW_GUIDED`.
+    ///
+    /// This requires an implementation of MPI 4.0 or later.
+    Hardware(String),
+    /// An implementation specific split type, e.g. `OMPI_COMM_TYPE_NUMA`, given as its raw value
+    Other(c_int),
+}
+
 /// Communicators are contexts for communication
 pub trait Communicator: sealed::AsHandle {
     /// Returns the number of processes available to communicate with in this `Communicator`. For
@@ -357,6 +663,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.1
     fn size(&self) -> Rank {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_size", self.as_raw()));
         unsafe { with_uninitialized(|size| ffi::MPI_Comm_size(self.as_raw(), size)).1 }
     }
 
@@ -369,6 +676,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.1
     fn rank(&self) -> Rank {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_rank", self.as_raw()));
         unsafe { with_uninitialized(|rank| ffi::MPI_Comm_rank(self.as_raw(), rank)).1 }
     }
 
@@ -384,7 +692,67 @@ pub trait Communicator: sealed::AsHandle {
     /// Returns an `AnyProcess` identifier that can be used, e.g. as a `Source` in point to point
     /// communication.
     fn any_process(&self) -> AnyProcess {
-        AnyProcess(self.as_handle())
+        AnyProcess(self.as_handle(), PhantomData)
+    }
+
+    /// Iterate over all processes available to communicate with, in the order of their ranks
+    ///
+    /// For inter-communicators these are the processes of the remote group.
+    ///
+    /// # Examples
+    /// See `examples/processes.rs`
+    fn processes(&self) -> Processes {
+        Processes {
+            comm: AnyProcess(self.as_handle(), PhantomData),
+            ranks: 0..self.target_size(),
+            skip: None,
+        }
+    }
+
+    /// Iterate over all processes available to communicate with except for the calling process
+    ///
+    /// For inter-communicators these are all processes of the remote group, like `processes()`.
+    ///
+    /// # Examples
+    /// See `examples/processes.rs`
+    fn other_processes(&self) -> Processes {
+        Processes {
+            skip: if self.test_inter() {
+                None
+            } else {
+                Some(self.rank())
+            },
+            ..self.processes()
+        }
+    }
+
+    /// A `Process` for the null process `MPI_PROC_NULL`
+    ///
+    /// Sending to the null process does nothing and receiving from it completes immediately
+    /// without changing the buffer, so processes at the boundary of a stencil can exchange with it
+    /// instead of branching. Receive operations that return the received value, like
+    /// `receive()`, panic since no message arrives.
+    ///
+    /// # Examples
+    /// See `examples/process_null.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.11
+    fn null_process(&self) -> Process {
+        Process::by_rank_unchecked(self, unsafe { ffi::RSMPI_PROC_NULL })
+    }
+
+    /// The `Process` at rank `r`, or the null process if there is no rank, e.g. a neighbor at the
+    /// boundary returned by `CartesianCommunicator::shift()`
+    ///
+    /// # Examples
+    /// See `examples/process_null.rs`
+    fn process_at_rank_or_null(&self, r: Option<Rank>) -> Process {
+        match r {
+            Some(r) => self.process_at_rank(r),
+            None => self.null_process(),
+        }
     }
 
     /// A `Process` for the calling process
@@ -401,6 +769,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.1
     fn compare(&self, other: &dyn Communicator) -> CommunicatorRelation {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_compare", self.as_raw()));
         unsafe {
             with_uninitialized(|cmp| ffi::MPI_Comm_compare(self.as_raw(), other.as_raw(), cmp))
                 .1
@@ -418,6 +787,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.2
     fn duplicate(&self) -> SimpleCommunicator {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_dup", self.as_raw()));
         unsafe {
             SimpleCommunicator::from_raw(
                 with_uninitialized(|newcomm| ffi::MPI_Comm_dup(self.as_raw(), newcomm)).1,
@@ -451,6 +821,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.2
     fn split_by_color_with_key(&self, color: Color, key: Key) -> Option<SimpleCommunicator> {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_split", self.as_raw()));
         unsafe {
             SimpleCommunicator::try_from_raw(
                 with_uninitialized(|newcomm| {
@@ -471,6 +842,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.2 (See: `MPI_Comm_split_type`)
     fn split_shared(&self, key: c_int) -> SimpleCommunicator {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_split_type", self.as_raw()));
         unsafe {
             SimpleCommunicator::try_from_raw(
                 with_uninitialized(|newcomm| {
@@ -487,6 +859,102 @@ pub trait Communicator: sealed::AsHandle {
         }
     }
 
+    /// Determine the node-local communicator, the communicator of node leaders and the index and
+    /// number of nodes, e.g. to pin threads or to stage data once per node.
+    ///
+    /// This function must be called on all processes of an intra-communicator.
+    ///
+    /// # Examples
+    /// See `examples/node_layout.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.4.2 (See: `MPI_Comm_split_type`)
+    fn node_layout(&self) -> NodeLayout {
+        let node = self.split_shared(self.rank());
+        let leaders = self.split_by_color(if node.rank() == 0 {
+            Color::with_value(0)
+        } else {
+            Color::undefined()
+        });
+        let mut nodes: [Rank; 2] = match &leaders {
+            Some(leaders) => [leaders.rank(), leaders.size()],
+            None => [0, 0],
+        };
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Bcast", node.as_raw())
+                .peer(0)
+                .typed(2, &Rank::equivalent_datatype())
+        });
+        unsafe {
+            ffi::MPI_Bcast(
+                nodes.as_mut_ptr() as _,
+                2,
+                Rank::equivalent_datatype().as_raw(),
+                0,
+                node.as_raw(),
+            );
+        }
+        NodeLayout {
+            node,
+            leaders,
+            node_index: nodes[0],
+            num_nodes: nodes[1],
+        }
+    }
+
+    /// Split the communicator into subcommunicators of processes which share a resource of
+    /// `split_type`, e.g. to build hierarchical on-node/off-node communication schemes.
+    ///
+    /// Within each subgroup, the processes are ranked in the order defined by the value of the
+    /// argument key, with ties broken according to their rank in the old group.
+    ///
+    /// Returns `None` if the implementation does not support `split_type` or if the process is
+    /// not part of any such resource.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/split_type.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.4.2 (See: `MPI_Comm_split_type`)
+    fn split_by_type(&self, split_type: SplitType, key: Key) -> Option<SimpleCommunicator> {
+        let (raw_type, resource) = match split_type {
+            SplitType::Shared => (unsafe { ffi::RSMPI_COMM_TYPE_SHARED }, None),
+            SplitType::Hardware(resource) => (
+                unsafe { ffi::RSMPI_COMM_TYPE_HW_GUIDED },
+                Some(
+                    CString::new(resource)
+                        .expect("Failed to convert the Rust string to a C string"),
+                ),
+            ),
+            SplitType::Other(raw_type) => (raw_type, None),
+        };
+        unsafe {
+            let mut info = ffi::RSMPI_INFO_NULL;
+            if let Some(ref resource) = resource {
+                ffi::MPI_Info_create(&mut info);
+                ffi::MPI_Info_set(
+                    info,
+                    b"mpi_hw_resource_type\0".as_ptr() as *const c_char,
+                    resource.as_ptr(),
+                );
+            }
+            let newcomm = {
+                let _call = instrument::begin(|| Call::new("MPI_Comm_split_type", self.as_raw()));
+                with_uninitialized(|newcomm| {
+                    ffi::MPI_Comm_split_type(self.as_raw(), raw_type, key, info, newcomm)
+                })
+                .1
+            };
+            if info != ffi::RSMPI_INFO_NULL {
+                ffi::MPI_Info_free(&mut info);
+            }
+            SimpleCommunicator::try_from_raw(newcomm)
+        }
+    }
+
     /// Split a communicator collectively by subgroup.
     ///
     /// Proceses pass in a group that is a subgroup of the group associated with the old
@@ -506,6 +974,7 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.2
     fn split_by_subgroup_collective(&self, group: &dyn Group) -> Option<SimpleCommunicator> {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_create", self.as_raw()));
         unsafe {
             SimpleCommunicator::try_from_raw(
                 with_uninitialized(|newcomm| {
@@ -546,6 +1015,7 @@ pub trait Communicator: sealed::AsHandle {
         group: &dyn Group,
         tag: Tag,
     ) -> Option<SimpleCommunicator> {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_create_group", self.as_raw()));
         unsafe {
             SimpleCommunicator::try_from_raw(
                 with_uninitialized(|newcomm| {
@@ -556,12 +1026,70 @@ pub trait Communicator: sealed::AsHandle {
         }
     }
 
+    /// Create an inter-communicator between the processes of this communicator and the processes
+    /// of another, disjoint communicator.
+    ///
+    /// `local_leader` is the rank of the leader of this group in this communicator. On the leader,
+    /// `remote_leader` identifies the leader of the other group in a communicator containing both
+    /// leaders, e.g. `MPI_COMM_WORLD`, on all other processes it is ignored.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/intercommunicator.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.6.2
+    fn create_intercommunicator(
+        &self,
+        local_leader: Rank,
+        remote_leader: &Process,
+    ) -> InterCommunicator {
+        self.create_intercommunicator_with_tag(local_leader, remote_leader, Tag::default())
+    }
+
+    /// Create an inter-communicator between the processes of this communicator and the processes
+    /// of another, disjoint communicator.
+    ///
+    /// Like `create_intercommunicator()` but uses `tag` for the communication between the leaders
+    /// in the communicator of `remote_leader`, which can avoid collision of concurrent calls.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.6.2
+    fn create_intercommunicator_with_tag(
+        &self,
+        local_leader: Rank,
+        remote_leader: &Process,
+        tag: Tag,
+    ) -> InterCommunicator {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Intercomm_create", self.as_raw()).peer(remote_leader.rank())
+        });
+        unsafe {
+            InterCommunicator::from_raw(
+                with_uninitialized(|newcomm| {
+                    ffi::MPI_Intercomm_create(
+                        self.as_raw(),
+                        local_leader,
+                        remote_leader.as_raw(),
+                        remote_leader.rank(),
+                        tag,
+                        newcomm,
+                    )
+                })
+                .1,
+            )
+        }
+    }
+
     /// The group associated with this communicator
     ///
     /// # Standard section(s)
     ///
     /// 6.3.2
     fn group(&self) -> UserGroup {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_group", self.as_raw()));
         unsafe {
             UserGroup(with_uninitialized(|group| ffi::MPI_Comm_group(self.as_raw(), group)).1)
         }
@@ -573,12 +1101,82 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 8.7
     fn abort(&self, errorcode: c_int) -> ! {
+        let _call = instrument::begin(|| Call::new("MPI_Abort", self.as_raw()));
         unsafe {
             ffi::MPI_Abort(self.as_raw(), errorcode);
         }
         process::abort();
     }
 
+    /// Make MPI functions called on this communicator return error codes instead of aborting
+    /// the program.
+    ///
+    /// The return values of the raw MPI functions can then be checked with `ErrorCode::check()`.
+    /// Methods of this crate that return a `Result` pass the error codes on, like the `try_`
+    /// variants of the blocking point to point and collective operations, e.g.
+    /// `Destination::try_send()` or `CommunicatorCollectives::try_all_reduce_into()`. All other
+    /// methods ignore them.
+    ///
+    /// # Safety
+    ///
+    /// Methods that ignore error codes read the output arguments of the MPI functions they call,
+    /// which a failing function may leave uninitialized. Until the communicator is set back with
+    /// `set_errors_are_fatal()`, no MPI function called by such a method on this communicator may
+    /// fail. The same holds for communicators created from it, for example with `duplicate()` or
+    /// `split_by_color()`, which inherit the setting.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/errors_return.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.3, see MPI_ERRORS_RETURN
+    unsafe fn set_errors_return(&self) {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_set_errhandler", self.as_raw()));
+        ffi::MPI_Comm_set_errhandler(self.as_raw(), ffi::RSMPI_ERRORS_RETURN);
+    }
+
+    /// Make errors in MPI functions called on this communicator abort the program, which is the
+    /// default.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.3, see MPI_ERRORS_ARE_FATAL
+    fn set_errors_are_fatal(&self) {
+        let _call = instrument::begin(|| Call::new("MPI_Comm_set_errhandler", self.as_raw()));
+        unsafe {
+            ffi::MPI_Comm_set_errhandler(self.as_raw(), ffi::RSMPI_ERRORS_ARE_FATAL);
+        }
+    }
+
+    /// Call `handler` with the error code when an MPI function called on this communicator
+    /// fails.
+    ///
+    /// Once `handler` returns, the MPI function returns the error code just like with
+    /// `set_errors_return()`. The handler is kept with the communicator and propagated to its
+    /// duplicates. It can, e.g., log the error or shut down with `abort()`. A panic inside of
+    /// `handler` aborts the program.
+    ///
+    /// # Safety
+    ///
+    /// Unless `handler` never returns, the requirements of `set_errors_return()` apply.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/error_handler.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.3.1 (MPI_Comm_create_errhandler, MPI_Comm_set_errhandler)
+    unsafe fn set_error_handler<F>(&self, handler: F)
+    where
+        F: Fn(ErrorCode) + Send + Sync + 'static,
+        Self: Sized,
+    {
+        error_handler::set_comm_error_handler(self.as_raw(), Arc::new(handler));
+    }
+
     /// Tests if the communicator is an inter-communicator.
     ///
     /// # Standard sections(s)
@@ -595,6 +1193,7 @@ pub trait Communicator: sealed::AsHandle {
     /// 6.8, see the `MPI_Comm_set_name` function
     fn set_name(&self, name: &str) {
         let c_name = CString::new(name).expect("Failed to convert the Rust string to a C string");
+        let _call = instrument::begin(|| Call::new("MPI_Comm_set_name", self.as_raw()));
         unsafe {
             ffi::MPI_Comm_set_name(self.as_raw(), c_name.as_ptr());
         }
@@ -608,6 +1207,7 @@ pub trait Communicator: sealed::AsHandle {
     fn get_name(&self) -> String {
         type BufType = [c_char; ffi::MPI_MAX_OBJECT_NAME as usize];
 
+        let _call = instrument::begin(|| Call::new("MPI_Comm_get_name", self.as_raw()));
         unsafe {
             let mut buf = MaybeUninit::<BufType>::uninit();
 
@@ -646,6 +1246,7 @@ pub trait Communicator: sealed::AsHandle {
 
         let periods: IntArray = periods.iter().map(|x| *x as i32).collect();
 
+        let _call = instrument::begin(|| Call::new("MPI_Cart_create", self.as_raw()));
         unsafe {
             let mut comm_cart = ffi::RSMPI_COMM_NULL;
             ffi::MPI_Cart_create(
@@ -682,6 +1283,7 @@ pub trait Communicator: sealed::AsHandle {
 
         let periods: IntArray = periods.iter().map(|x| *x as i32).collect();
 
+        let _call = instrument::begin(|| Call::new("MPI_Cart_map", self.as_raw()));
         unsafe {
             let mut new_rank = ffi::MPI_UNDEFINED;
             ffi::MPI_Cart_map(
@@ -699,6 +1301,178 @@ pub trait Communicator: sealed::AsHandle {
         }
     }
 
+    /// Creates a communicator whose ranks are the nodes of a graph, providing MPI with the
+    /// communication pattern of an application to allow it to better optimize the physical
+    /// locality of ranks that communicate with each other. All processes have to pass the same
+    /// graph.
+    ///
+    /// Returns `None` if the local process does not participate in the new communicator, i.e. if
+    /// its rank is greater than or equal to the number of nodes.
+    ///
+    /// * `index` - `index[i]` is the total number of neighbors of nodes 0 to `i`
+    /// * `edges` - the concatenated lists of neighbors of all nodes
+    /// * `reorder` - If true, MPI may re-order ranks in the new communicator.
+    ///
+    /// # Examples
+    /// See `examples/graph.rs`
+    ///
+    /// # Standard section(s)
+    /// 7.5.3 (MPI_Graph_create)
+    fn create_graph_communicator(
+        &self,
+        index: &[Count],
+        edges: &[Rank],
+        reorder: bool,
+    ) -> Option<GraphCommunicator> {
+        assert_eq!(
+            index.last().copied().unwrap_or(0),
+            edges.count(),
+            "the last entry of index must be the number of edges"
+        );
+
+        let _call = instrument::begin(|| Call::new("MPI_Graph_create", self.as_raw()));
+        unsafe {
+            let mut comm_graph = ffi::RSMPI_COMM_NULL;
+            ffi::MPI_Graph_create(
+                self.as_raw(),
+                index.count(),
+                index.as_ptr(),
+                edges.as_ptr(),
+                reorder as Count,
+                &mut comm_graph,
+            );
+            GraphCommunicator::try_from_raw(comm_graph)
+        }
+    }
+
+    /// Gets the target rank of this rank as-if
+    /// [`create_graph_communicator`](#method.create_graph_communicator) had been called with
+    /// `index`, `edges`, and `reorder = true`.
+    ///
+    /// Returns `None` if the local process would not participate in the new GraphCommunicator.
+    ///
+    /// * `index` - `index[i]` is the total number of neighbors of nodes 0 to `i`
+    /// * `edges` - the concatenated lists of neighbors of all nodes
+    ///
+    /// # Examples
+    /// See `examples/graph.rs`
+    ///
+    /// # Standard section
+    /// 7.5.8 (MPI_Graph_map)
+    fn graph_map(&self, index: &[Count], edges: &[Rank]) -> Option<Rank> {
+        assert_eq!(
+            index.last().copied().unwrap_or(0),
+            edges.count(),
+            "the last entry of index must be the number of edges"
+        );
+
+        let _call = instrument::begin(|| Call::new("MPI_Graph_map", self.as_raw()));
+        unsafe {
+            let mut new_rank = ffi::MPI_UNDEFINED;
+            ffi::MPI_Graph_map(
+                self.as_raw(),
+                index.count(),
+                index.as_ptr(),
+                edges.as_ptr(),
+                &mut new_rank,
+            );
+            if new_rank == ffi::MPI_UNDEFINED {
+                None
+            } else {
+                Some(new_rank)
+            }
+        }
+    }
+
+    /// Creates a communicator whose ranks are the nodes of a distributed graph, where every
+    /// process only specifies its own incoming and outgoing edges.
+    ///
+    /// * `sources` - ranks of the processes that have an edge to the calling process
+    /// * `destinations` - ranks of the processes the calling process has an edge to
+    /// * `reorder` - If true, MPI may re-order ranks in the new communicator.
+    ///
+    /// # Examples
+    /// See `examples/graph.rs`
+    ///
+    /// # Standard section(s)
+    /// 7.5.4 (MPI_Dist_graph_create_adjacent)
+    fn create_distributed_graph_communicator(
+        &self,
+        sources: &[Rank],
+        destinations: &[Rank],
+        reorder: bool,
+    ) -> DistributedGraphCommunicator {
+        let _call =
+            instrument::begin(|| Call::new("MPI_Dist_graph_create_adjacent", self.as_raw()));
+        unsafe {
+            let mut comm_dist_graph = ffi::RSMPI_COMM_NULL;
+            ffi::MPI_Dist_graph_create_adjacent(
+                self.as_raw(),
+                sources.count(),
+                sources.as_ptr(),
+                ffi::RSMPI_UNWEIGHTED(),
+                destinations.count(),
+                destinations.as_ptr(),
+                ffi::RSMPI_UNWEIGHTED(),
+                ffi::RSMPI_INFO_NULL,
+                reorder as Count,
+                &mut comm_dist_graph,
+            );
+            DistributedGraphCommunicator::from_raw(comm_dist_graph)
+        }
+    }
+
+    /// Creates a communicator whose ranks are the nodes of a distributed graph with weighted
+    /// edges, where every process only specifies its own incoming and outgoing edges.
+    ///
+    /// * `sources` - ranks of the processes that have an edge to the calling process
+    /// * `source_weights` - Must match length of `sources`, the weights of the incoming edges
+    /// * `destinations` - ranks of the processes the calling process has an edge to
+    /// * `destination_weights` - Must match length of `destinations`, the weights of the outgoing
+    ///     edges
+    /// * `reorder` - If true, MPI may re-order ranks in the new communicator.
+    ///
+    /// # Standard section(s)
+    /// 7.5.4 (MPI_Dist_graph_create_adjacent)
+    fn create_weighted_distributed_graph_communicator(
+        &self,
+        sources: &[Rank],
+        source_weights: &[Count],
+        destinations: &[Rank],
+        destination_weights: &[Count],
+        reorder: bool,
+    ) -> DistributedGraphCommunicator {
+        assert_eq!(
+            sources.len(),
+            source_weights.len(),
+            "sources and source_weights must be parallel, equal-sized arrays"
+        );
+        assert_eq!(
+            destinations.len(),
+            destination_weights.len(),
+            "destinations and destination_weights must be parallel, equal-sized arrays"
+        );
+
+        let _call =
+            instrument::begin(|| Call::new("MPI_Dist_graph_create_adjacent", self.as_raw()));
+        unsafe {
+            let mut comm_dist_graph = ffi::RSMPI_COMM_NULL;
+            ffi::MPI_Dist_graph_create_adjacent(
+                self.as_raw(),
+                sources.count(),
+                sources.as_ptr(),
+                source_weights.as_ptr(),
+                destinations.count(),
+                destinations.as_ptr(),
+                destination_weights.as_ptr(),
+                ffi::RSMPI_INFO_NULL,
+                reorder as Count,
+                &mut comm_dist_graph,
+            );
+            DistributedGraphCommunicator::from_raw(comm_dist_graph)
+        }
+    }
+
     /// Gets the implementation-defined buffer size required to pack 'incount' elements of type
     /// 'datatype'.
     ///
@@ -710,6 +1484,9 @@ pub trait Communicator: sealed::AsHandle {
         Dt: Datatype,
         Self: Sized,
     {
+        let _call = instrument::begin(|| {
+            Call::new("MPI_Pack_size", self.as_raw()).typed(incount, datatype)
+        });
         unsafe {
             with_uninitialized(|size| {
                 ffi::MPI_Pack_size(incount, datatype.as_raw(), self.as_raw(), size)
@@ -764,6 +1541,7 @@ pub trait Communicator: sealed::AsHandle {
         let inbuf_dt = inbuf.as_datatype();
 
         let mut position: Count = position;
+        let _call = instrument::begin(|| Call::new("MPI_Pack", self.as_raw()).buffer(inbuf));
         unsafe {
             ffi::MPI_Pack(
                 inbuf.pointer(),
@@ -792,6 +1570,7 @@ pub trait Communicator: sealed::AsHandle {
         let outbuf_dt = outbuf.as_datatype();
 
         let mut position: Count = position;
+        let _call = instrument::begin(|| Call::new("MPI_Unpack", self.as_raw()).buffer(outbuf));
         ffi::MPI_Unpack(
             inbuf.as_ptr() as *const _,
             inbuf.count(),
@@ -809,6 +1588,7 @@ pub trait Communicator: sealed::AsHandle {
     /// # Standard Sections
     /// 10.3.2, see MPI_Comm_get_parent
     fn parent(&self) -> Option<InterCommunicator> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Comm_get_parent"));
         unsafe {
             let mut comm = ffi::RSMPI_COMM_NULL;
             ffi::MPI_Comm_get_parent(&mut comm);
@@ -841,11 +1621,19 @@ pub trait AnyCommunicator: Communicator {
     ///
     /// 7.7.2
     fn set_attr<A: CommAttribute>(&mut self, val: A);
+
+    /// Delete `CommAttribute` from a communicator, if set
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.2
+    fn delete_attr<A: CommAttribute>(&mut self);
 }
 
 impl<C: ?Sized + Communicator> AnyCommunicator for C {
     fn get_attr<A: CommAttribute>(&self) -> Option<&A> {
         let key = A::get_key();
+        let _call = instrument::begin(|| Call::new("MPI_Comm_get_attr", self.as_raw()));
         let (val, flag) = unsafe {
             let mut ptr: MaybeUninit<*mut A> = MaybeUninit::uninit();
             let (_, flag) = with_uninitialized(|flag| {
@@ -874,6 +1662,7 @@ impl<C: ?Sized + Communicator> AnyCommunicator for C {
     fn set_attr<A: CommAttribute>(&mut self, val: A) {
         let key = A::get_key();
         let val = Box::new(val);
+        let _call = instrument::begin(|| Call::new("MPI_Comm_set_attr", self.as_raw()));
         unsafe {
             ffi::MPI_Comm_set_attr(
                 self.as_raw(),
@@ -882,6 +1671,61 @@ impl<C: ?Sized + Communicator> AnyCommunicator for C {
             )
         };
     }
+
+    fn delete_attr<A: CommAttribute>(&mut self) {
+        if self.get_attr::<A>().is_some() {
+            let key = A::get_key();
+            let _call = instrument::begin(|| Call::new("MPI_Comm_delete_attr", self.as_raw()));
+            unsafe {
+                ffi::MPI_Comm_delete_attr(self.as_raw(), key.as_raw());
+            }
+        }
+    }
+}
+
+/// A duplicate of a communicator for the messages that rsmpi exchanges internally, cached on the
+/// communicator by `private_duplicate()`
+#[derive(Clone)]
+struct PrivateDuplicate(Rc<SimpleCommunicator>);
+
+impl CommAttribute for PrivateDuplicate {}
+
+/// A duplicate of `comm` that is created on first use and freed together with `comm`.
+///
+/// Messages on the duplicate can neither match nor be matched by messages of the user on `comm`.
+/// This is collective on `comm` the first time it is called, so all processes have to call it
+/// whenever one of them does.
+pub(crate) fn private_duplicate<C: Communicator + ?Sized>(comm: &C) -> &SimpleCommunicator {
+    if comm.get_attr::<PrivateDuplicate>().is_none() {
+        let duplicate = Box::new(PrivateDuplicate(Rc::new(comm.duplicate())));
+        let _call = instrument::begin(|| Call::new("MPI_Comm_set_attr", comm.as_raw()));
+        unsafe {
+            ffi::MPI_Comm_set_attr(
+                comm.as_raw(),
+                PrivateDuplicate::get_key().as_raw(),
+                Box::into_raw(duplicate) as *mut c_void,
+            );
+        }
+    }
+    &comm
+        .get_attr::<PrivateDuplicate>()
+        .expect("The private duplicate has just been cached")
+        .0
+}
+
+/// Whether all processes of `comm` can share memory, i.e. `comm` stems from `split_shared()` or an
+/// equivalent construction.
+///
+/// Splitting by shared memory can give a different result on every process, so the processes agree
+/// on whether all of them share memory and the result is the same on all of them. This is
+/// collective on `comm`.
+pub(crate) fn shares_memory<C: Communicator + ?Sized>(comm: &C) -> bool {
+    use crate::collective::{traits::*, SystemOperation};
+
+    let local = u8::from(comm.split_shared(0).size() == comm.size());
+    let mut all = 0u8;
+    comm.all_reduce_into(&local, &mut all, SystemOperation::min());
+    all == 1
 }
 
 /// The relation between two communicators.
@@ -963,7 +1807,7 @@ impl<'a> Process<'a> {
 
     fn by_rank_unchecked<C: Communicator + ?Sized>(c: &'a C, r: Rank) -> Self {
         Process {
-            comm: AnyProcess(c.as_handle()),
+            comm: AnyProcess(c.as_handle(), PhantomData),
             rank: r,
         }
     }
@@ -973,6 +1817,11 @@ impl<'a> Process<'a> {
         self.rank
     }
 
+    /// Is this the null process, see `Communicator::null_process()`?
+    pub fn is_null(&self) -> bool {
+        self.rank == unsafe { ffi::RSMPI_PROC_NULL }
+    }
+
     /// Does the `Process` rank refer to the rank of this process in the
     /// associated communicator?
     pub fn is_self(&self) -> bool {
@@ -1008,10 +1857,43 @@ impl<'a> AsCommunicator for Process<'a> {
     }
 }
 
+/// An iterator over the processes of a communicator, see `Communicator::processes()` and
+/// `Communicator::other_processes()`
+#[derive(Clone)]
+pub struct Processes<'a> {
+    comm: AnyProcess<'a>,
+    ranks: Range<Rank>,
+    /// The rank of the calling process if it is left out
+    skip: Option<Rank>,
+}
+
+impl<'a> Iterator for Processes<'a> {
+    type Item = Process<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rank = self.ranks.find(|&rank| Some(rank) != self.skip)?;
+        Some(Process {
+            comm: self.comm,
+            rank,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let skipped = match self.skip {
+            Some(rank) if self.ranks.contains(&rank) => 1,
+            _ => 0,
+        };
+        let len = self.ranks.len() - skipped;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Processes<'a> {}
+
 /// Identifies an arbitrary process that is a member of a certain communicator, e.g. for use as a
 /// `Source` in point to point communication.
 #[derive(Copy, Clone)]
-pub struct AnyProcess<'a>(&'a sealed::CommunicatorHandle);
+pub struct AnyProcess<'a>(&'a sealed::CommunicatorHandle, PhantomData<*const ()>);
 
 unsafe impl<'a> AsRaw for AnyProcess<'a> {
     type Raw = MPI_Comm;
@@ -1069,10 +1951,11 @@ impl Group for SystemGroup {}
 /// # Standard section(s)
 ///
 /// 6.2.1
-pub struct UserGroup(MPI_Group);
+pub struct UserGroup(pub(crate) MPI_Group);
 
 impl Drop for UserGroup {
     fn drop(&mut self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_free"));
         unsafe {
             ffi::MPI_Group_free(&mut self.0);
         }
@@ -1104,6 +1987,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
         G: Group,
         Self: Sized,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_union"));
         unsafe {
             UserGroup(
                 with_uninitialized(|newgroup| {
@@ -1127,6 +2011,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
         G: Group,
         Self: Sized,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_intersection"));
         unsafe {
             UserGroup(
                 with_uninitialized(|newgroup| {
@@ -1150,6 +2035,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
         G: Group,
         Self: Sized,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_difference"));
         unsafe {
             UserGroup(
                 with_uninitialized(|newgroup| {
@@ -1169,6 +2055,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
     ///
     /// 6.3.2
     fn include(&self, ranks: &[Rank]) -> UserGroup {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_incl"));
         unsafe {
             UserGroup(
                 with_uninitialized(|newgroup| {
@@ -1188,6 +2075,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
     ///
     /// 6.3.2
     fn exclude(&self, ranks: &[Rank]) -> UserGroup {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_excl"));
         unsafe {
             UserGroup(
                 with_uninitialized(|newgroup| {
@@ -1204,6 +2092,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
     ///
     /// 6.3.1
     fn size(&self) -> Rank {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_size"));
         unsafe { with_uninitialized(|size| ffi::MPI_Group_size(self.as_raw(), size)).1 }
     }
 
@@ -1213,6 +2102,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
     ///
     /// 6.3.1
     fn rank(&self) -> Option<Rank> {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_rank"));
         unsafe {
             let (_, rank) = with_uninitialized(|rank| ffi::MPI_Group_rank(self.as_raw(), rank));
             if rank == ffi::RSMPI_UNDEFINED {
@@ -1235,6 +2125,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
         G: Group,
         Self: Sized,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_translate_ranks"));
         unsafe {
             let (_, translated) = with_uninitialized(|translated| {
                 ffi::MPI_Group_translate_ranks(self.as_raw(), 1, &rank, other.as_raw(), translated)
@@ -1275,6 +2166,7 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
         G: Group,
         Self: Sized,
     {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Group_compare"));
         unsafe {
             with_uninitialized(|relation| {
                 ffi::MPI_Group_compare(self.as_raw(), other.as_raw(), relation)
@@ -1315,6 +2207,7 @@ impl From<c_int> for GroupRelation {
 
 unsafe fn comm_is_inter(raw_comm: MPI_Comm) -> bool {
     let mut flag = c_int::min_value();
+    let _call = instrument::begin(|| Call::new("MPI_Comm_test_inter", raw_comm));
     unsafe {
         ffi::MPI_Comm_test_inter(raw_comm, &mut flag);
     }
@@ -1,4 +1,5 @@
 use crate::ffi;
+use crate::instrument::{self, Call};
 use crate::topology::comm_is_inter;
 use crate::traits::AsRaw;
 use mpi_sys::MPI_Comm;
@@ -69,6 +70,7 @@ impl CommunicatorHandle {
         } else {
             if comm_is_inter(raw) {
                 let mut parent_comm = ffi::RSMPI_COMM_NULL;
+                let _call = instrument::begin(|| Call::without_comm("MPI_Comm_get_parent"));
                 ffi::MPI_Comm_get_parent(&mut parent_comm);
                 if raw == parent_comm {
                     Some(CommunicatorHandle::Parent(raw))
@@ -131,7 +133,6 @@ impl CommunicatorHandle {
     }
 
     /// Returns true if the handle is of an inter-comm
-    #[allow(dead_code)]
     pub fn is_inter_comm(&self) -> bool {
         match self {
             CommunicatorHandle::SelfComm
@@ -150,10 +151,12 @@ impl Drop for CommunicatorHandle {
             CommunicatorHandle::Parent(_) => { /* not useful to drop (would nullify other references) */
             }
             CommunicatorHandle::User(handle) => unsafe {
+                let _call = instrument::begin(|| Call::new("MPI_Comm_free", *handle));
                 ffi::MPI_Comm_free(handle);
                 assert_eq!(*handle, ffi::RSMPI_COMM_NULL);
             },
             CommunicatorHandle::InterComm(handle) => unsafe {
+                let _call = instrument::begin(|| Call::new("MPI_Comm_disconnect", *handle));
                 ffi::MPI_Comm_disconnect(handle);
                 assert_eq!(*handle, ffi::RSMPI_COMM_NULL);
             },
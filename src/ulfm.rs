@@ -0,0 +1,103 @@
+//! User level failure mitigation
+//!
+//! The ULFM extension (`MPIX_`) lets a program continue after processes have failed: operations
+//! involving a failed process return an error of class `MPIX_ERR_PROC_FAILED` instead of aborting,
+//! a communicator can be revoked so that all pending and future operations on it fail, and the
+//! surviving processes can agree on a value and shrink the communicator to a working one.
+//!
+//! Errors are only reported if the communicator is set to `Communicator::set_errors_return()`,
+//! otherwise they abort the program as usual. After a failure, only the methods of this trait and
+//! other methods returning a `Result` may be called on such a communicator, see the safety
+//! requirements of `set_errors_return()`. The extension is not part of the MPI standard and
+//! is only available in some builds of some implementations, without it all operations fail with
+//! an error code.
+//!
+//! This module is available with the `ulfm` feature.
+
+use std::os::raw::c_int;
+
+use crate::ffi;
+use crate::instrument::{self, Call};
+use crate::raw::traits::*;
+use crate::topology::{Communicator, SimpleCommunicator, UserGroup};
+use crate::{with_uninitialized, ErrorCode};
+
+/// Fault tolerance traits
+pub mod traits {
+    pub use super::FaultTolerance;
+}
+
+/// Failure mitigation for the processes of a communicator
+///
+/// # Examples
+/// See `examples/ulfm.rs`
+pub trait FaultTolerance: Communicator {
+    /// Revoke the communicator on all processes.
+    ///
+    /// All pending and future operations on the communicator, except for `shrink()`, `agree()` and
+    /// the acknowledgement of failures, fail with an error of class `MPIX_ERR_REVOKED` on all
+    /// processes. This is used to notify the other processes of a failure detected locally.
+    fn revoke(&self) -> Result<(), ErrorCode> {
+        let _call = instrument::begin(|| Call::new("MPIX_Comm_revoke", self.as_raw()));
+        ErrorCode::check(unsafe { ffi::RSMPI_Comm_revoke(self.as_raw()) })
+    }
+
+    /// Create a new communicator out of the processes of this one that have not failed.
+    ///
+    /// This is collective over the surviving processes and also succeeds on a revoked
+    /// communicator, so it is the usual way to continue after a failure. The ranks of the
+    /// surviving processes keep their relative order.
+    fn shrink(&self) -> Result<SimpleCommunicator, ErrorCode> {
+        let _call = instrument::begin(|| Call::new("MPIX_Comm_shrink", self.as_raw()));
+        let (code, newcomm) =
+            unsafe { with_uninitialized(|newcomm| ffi::RSMPI_Comm_shrink(self.as_raw(), newcomm)) };
+        ErrorCode::check(code)?;
+        Ok(unsafe { SimpleCommunicator::from_raw(newcomm) })
+    }
+
+    /// Agree on the bitwise and of `flag` over all surviving processes.
+    ///
+    /// The result is the same on all processes that return from the call, even if processes fail
+    /// during it. If there are failed processes that have not been acknowledged with
+    /// `acknowledge_failures()`, an error of class `MPIX_ERR_PROC_FAILED` is returned, the
+    /// agreement is nonetheless carried out and its result can be obtained by calling `agree()`
+    /// again after acknowledging the failures.
+    fn agree(&self, flag: c_int) -> Result<c_int, ErrorCode> {
+        let mut flag = flag;
+        let _call = instrument::begin(|| Call::new("MPIX_Comm_agree", self.as_raw()));
+        ErrorCode::check(unsafe { ffi::RSMPI_Comm_agree(self.as_raw(), &mut flag) })?;
+        Ok(flag)
+    }
+
+    /// Acknowledge the failures of processes detected so far.
+    ///
+    /// Afterwards receives from any source and `agree()` do not report these failures any more.
+    fn acknowledge_failures(&self) -> Result<(), ErrorCode> {
+        let _call = instrument::begin(|| Call::new("MPIX_Comm_failure_ack", self.as_raw()));
+        ErrorCode::check(unsafe { ffi::RSMPI_Comm_failure_ack(self.as_raw()) })
+    }
+
+    /// The group of failed processes acknowledged by the last `acknowledge_failures()`.
+    fn acknowledged_failures(&self) -> Result<UserGroup, ErrorCode> {
+        let _call = instrument::begin(|| Call::new("MPIX_Comm_failure_get_acked", self.as_raw()));
+        let (code, group) = unsafe {
+            with_uninitialized(|group| ffi::RSMPI_Comm_failure_get_acked(self.as_raw(), group))
+        };
+        ErrorCode::check(code)?;
+        Ok(UserGroup(group))
+    }
+}
+
+impl<C: Communicator + ?Sized> FaultTolerance for C {}
+
+impl ErrorCode {
+    /// Whether the error was caused by the failure of a process (`MPIX_ERR_PROC_FAILED`).
+    pub fn is_process_failure(&self) -> bool {
+        self.class().raw() == unsafe { ffi::RSMPI_ERR_PROC_FAILED }
+    }
+
+    /// Whether the error was caused by using a revoked communicator (`MPIX_ERR_REVOKED`).
+    pub fn is_revoked(&self) -> bool {
+        self.class().raw() == unsafe { ffi::RSMPI_ERR_REVOKED }
+    }
+}
@@ -0,0 +1,937 @@
+//! One-sided communication
+//!
+//! A `Window` exposes a region of memory on every process of a communicator to remote memory
+//! access (RMA). Processes can then write data to (`put()`), read data from (`get()`) and combine
+//! data with (`accumulate()`, `fetch_and_op()`, ...) the memory of other processes without the
+//! target process taking part in the individual transfers.
+//!
+//! RMA operations happen inside access epochs which are delimited by synchronization calls. All
+//! buffers handed to RMA operations are borrowed by the epoch they are issued in and may only be
+//! reused once the epoch has completed.
+//!
+//! # Unfinished features
+//!
+//! - **11.2.4**: Dynamically attached memory, `MPI_Win_create_dynamic()`, `MPI_Win_attach()`,
+//! `MPI_Win_detach()`
+//! - **11.2.7**: Window info, `MPI_Win_set_info()`, `MPI_Win_get_info()`
+//! - **11.3.5**: Request-based RMA operations, `MPI_Rput()`, `MPI_Rget()`, `MPI_Raccumulate()`
+//! - **11.5.2**: General active target synchronization, `MPI_Win_start()`,
+//! `MPI_Win_complete()`, `MPI_Win_post()`, `MPI_Win_wait()`
+//! - **11.5.5**: Assertions
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::os::raw::{c_int, c_void};
+use std::sync::Arc;
+use std::{fmt, ptr, slice};
+
+use conv::ConvUtil;
+
+use crate::attribute::WindowAttribute;
+use crate::collective::SystemOperation;
+use crate::datatype::traits::*;
+use crate::error_handler;
+use crate::ffi;
+use crate::ffi::MPI_Win;
+use crate::instrument::{self, Call};
+use crate::raw::traits::*;
+use crate::request::{Scope, StaticScope};
+use crate::topology::{shares_memory, Communicator, Rank};
+use crate::{with_uninitialized, Address, ErrorCode};
+
+/// One-sided communication traits
+pub mod traits {
+    pub use super::{AccessEpoch, Origin, OriginMut};
+}
+
+/// A region of memory on every process of a communicator that is exposed to remote memory access
+///
+/// The memory is either borrowed from the caller (`Window::create()`) or allocated by the MPI
+/// library (`Window::allocate()`, `Window::allocate_shared()`). Displacements into the window of a
+/// target process are measured in elements of type `T`.
+///
+/// Like a request, a window over borrowed memory is registered with a `Scope`, so that the memory
+/// cannot be released while the window still exposes it even if the window is leaked.
+///
+/// Other processes can access the local memory through RMA operations at any time, so it is only
+/// exposed through the unsafe `local()` and `local_mut()`, whose callers have to synchronize with
+/// them.
+///
+/// Windows of zero-sized types are not supported, their constructors panic.
+///
+/// # Examples
+///
+/// See `examples/window.rs`
+///
+/// # Standard section(s)
+///
+/// 11.2
+pub struct Window<'a, T, S: Scope<'a> = StaticScope> {
+    window: MPI_Win,
+    base: *mut T,
+    len: usize,
+    shared: bool,
+    scope: S,
+    phantom: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T: Equivalence, S: Scope<'a>> Window<'a, T, S> {
+    /// Expose `buf` on every process of `comm` to remote memory access.
+    ///
+    /// This is a collective operation, the window memory can be of different size on every process.
+    /// The window is registered with `scope` until it is dropped, see `request::scope()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.1 (MPI_Win_create)
+    pub fn create<C: Communicator + ?Sized>(comm: &C, scope: S, buf: &'a mut [T]) -> Self {
+        let mut window = unsafe { ffi::RSMPI_WIN_NULL };
+        let _call = instrument::begin(|| Call::new("MPI_Win_create", comm.as_raw()));
+        unsafe {
+            ffi::MPI_Win_create(
+                buf.as_mut_ptr() as *mut c_void,
+                byte_size::<T>(buf.len()),
+                disp_unit::<T>(),
+                ffi::RSMPI_INFO_NULL,
+                comm.as_raw(),
+                &mut window,
+            );
+        }
+        scope.register();
+
+        Window {
+            window,
+            base: buf.as_mut_ptr(),
+            len: buf.len(),
+            shared: false,
+            scope,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The number of elements in the local memory of the window
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the local memory of the window is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The local memory of the window
+    ///
+    /// # Safety
+    ///
+    /// No other process may write to the local memory through RMA operations while the returned
+    /// slice is alive, i.e. it must only be used after the epochs of all such operations have
+    /// been closed, e.g. by `fence_epoch()` or a barrier after they have unlocked the window, and
+    /// before new ones are opened.
+    pub unsafe fn local(&self) -> &[T] {
+        if self.len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(self.base, self.len)
+        }
+    }
+
+    /// Mutable access to the local memory of the window
+    ///
+    /// # Safety
+    ///
+    /// No other process may access the local memory through RMA operations while the returned
+    /// slice is alive, see `local()`.
+    pub unsafe fn local_mut(&mut self) -> &mut [T] {
+        if self.len == 0 {
+            &mut []
+        } else {
+            slice::from_raw_parts_mut(self.base, self.len)
+        }
+    }
+
+    /// Get the `WindowAttribute` cached on this window, or `None` if not set.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/attributes_datatype_window.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.3 (MPI_Win_get_attr)
+    pub fn get_attr<A: WindowAttribute>(&self) -> Option<&A> {
+        let key = A::get_key();
+        let mut ptr: MaybeUninit<*mut A> = MaybeUninit::uninit();
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_get_attr"));
+        let (_, flag) = unsafe {
+            with_uninitialized(|flag| {
+                ffi::MPI_Win_get_attr(
+                    self.window,
+                    key.as_raw(),
+                    ptr.as_mut_ptr() as *mut c_void,
+                    flag,
+                )
+            })
+        };
+        if flag == 0 {
+            None
+        } else {
+            // The attribute was boxed by `set_attr()`
+            unsafe { ptr.assume_init().as_ref() }
+        }
+    }
+
+    /// Cache a `WindowAttribute` on this window, replacing a previous value.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.3 (MPI_Win_set_attr)
+    pub fn set_attr<A: WindowAttribute>(&mut self, val: A) {
+        let key = A::get_key();
+        let val = Box::new(val);
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_set_attr"));
+        unsafe {
+            ffi::MPI_Win_set_attr(self.window, key.as_raw(), Box::into_raw(val) as *mut c_void);
+        }
+    }
+
+    /// Delete the `WindowAttribute` cached on this window, if any.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 7.7.3 (MPI_Win_delete_attr)
+    pub fn delete_attr<A: WindowAttribute>(&mut self) {
+        if self.get_attr::<A>().is_some() {
+            let key = A::get_key();
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_delete_attr"));
+            unsafe {
+                ffi::MPI_Win_delete_attr(self.window, key.as_raw());
+            }
+        }
+    }
+
+    /// Call `handler` with the error code when an MPI function called on this window fails.
+    ///
+    /// Once `handler` returns, the MPI function returns the error code. A panic inside of
+    /// `handler` aborts the program.
+    ///
+    /// # Safety
+    ///
+    /// The methods of `Window` ignore error codes and may read output arguments that a failing
+    /// MPI function left uninitialized. Unless `handler` never returns, no MPI function called on
+    /// the window by a method of this crate may fail.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 9.3.2 (MPI_Win_create_errhandler, MPI_Win_set_errhandler)
+    pub unsafe fn set_error_handler<F>(&self, handler: F)
+    where
+        F: Fn(ErrorCode) + Send + Sync + 'static,
+    {
+        error_handler::set_window_error_handler(self.window, Arc::new(handler));
+    }
+
+    /// Open an access and exposure epoch on all processes of the window, perform RMA operations
+    /// via the `FenceEpoch` passed to `f` and close the epoch again.
+    ///
+    /// This is a collective operation. All RMA operations issued via the epoch have completed at
+    /// the origin and the target when the function returns, or when `f` panics.
+    ///
+    /// Buffers passed to RMA operations must be declared *outside* of `f`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.1 (MPI_Win_fence)
+    pub fn fence_epoch<'b, F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&FenceEpoch<'_, 'b, T>) -> R,
+    {
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_fence"));
+            ffi::MPI_Win_fence(0, self.window);
+        }
+        let _close = CloseEpoch {
+            window: self.window,
+            close: Close::Fence,
+        };
+        f(&FenceEpoch {
+            window: self.window,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Open an access epoch to the window of process `target` by locking it, perform RMA
+    /// operations via the `LockEpoch` passed to `f` and unlock the window again.
+    ///
+    /// Only the calling process takes part in the synchronization. All RMA operations issued via
+    /// the epoch have completed at the origin and the target when the function returns, or when
+    /// `f` panics.
+    ///
+    /// Buffers passed to RMA operations must be declared *outside* of `f`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window_lock.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.3 (MPI_Win_lock, MPI_Win_unlock)
+    pub fn lock_epoch<'b, F, R>(&mut self, lock_type: LockType, target: Rank, f: F) -> R
+    where
+        F: FnOnce(&LockEpoch<'_, 'b, T>) -> R,
+    {
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_lock").peer(target));
+            ffi::MPI_Win_lock(lock_type.as_raw(), target, 0, self.window);
+        }
+        let _close = CloseEpoch {
+            window: self.window,
+            close: Close::Unlock(target),
+        };
+        f(&LockEpoch {
+            window: self.window,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Open an access epoch to the windows of all processes by taking a shared lock on them,
+    /// perform RMA operations via the `LockEpoch` passed to `f` and unlock the windows again.
+    ///
+    /// Only the calling process takes part in the synchronization. All RMA operations issued via
+    /// the epoch have completed at the origin and the target when the function returns, or when
+    /// `f` panics.
+    ///
+    /// Buffers passed to RMA operations must be declared *outside* of `f`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window_lock.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.3 (MPI_Win_lock_all, MPI_Win_unlock_all)
+    pub fn lock_all_epoch<'b, F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&LockEpoch<'_, 'b, T>) -> R,
+    {
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_lock_all"));
+            ffi::MPI_Win_lock_all(0, self.window);
+        }
+        let _close = CloseEpoch {
+            window: self.window,
+            close: Close::UnlockAll,
+        };
+        f(&LockEpoch {
+            window: self.window,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<T: Equivalence + Copy + Default> Window<'static, T> {
+    /// Allocate memory for `len` elements on every process of `comm` and expose it to remote
+    /// memory access.
+    ///
+    /// This is a collective operation, `len` can differ between processes. The memory is
+    /// initialized to `T::default()` on all processes before the function returns, so RMA
+    /// operations issued right afterwards do not race with the initialization.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.2 (MPI_Win_allocate)
+    pub fn allocate<C: Communicator + ?Sized>(comm: &C, len: usize) -> Self {
+        let mut window = unsafe { ffi::RSMPI_WIN_NULL };
+        let mut base: *mut T = ptr::null_mut();
+        let base_ptr: *mut *mut T = &mut base;
+        let _call = instrument::begin(|| Call::new("MPI_Win_allocate", comm.as_raw()));
+        unsafe {
+            ffi::MPI_Win_allocate(
+                byte_size::<T>(len),
+                disp_unit::<T>(),
+                ffi::RSMPI_INFO_NULL,
+                comm.as_raw(),
+                base_ptr as *mut c_void,
+                &mut window,
+            );
+            for i in 0..len {
+                ptr::write(base.add(i), T::default());
+            }
+            let _call = instrument::begin(|| Call::new("MPI_Barrier", comm.as_raw()));
+            ffi::MPI_Barrier(comm.as_raw());
+        }
+
+        Window {
+            window,
+            base,
+            len,
+            shared: false,
+            scope: StaticScope,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Allocate memory for `len` elements on every process of `comm` that can be accessed
+    /// directly by all other processes of `comm` and expose it to remote memory access.
+    ///
+    /// This is a collective operation, `len` can differ between processes. The memory is
+    /// initialized to `T::default()` on all processes before the function returns.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window_shared.rs`
+    ///
+    /// # Panics
+    ///
+    /// On all processes of `comm` alike, if not all of them can share memory, i.e. `comm` does not
+    /// stem from `Communicator::split_shared()` or an equivalent construction.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.3 (MPI_Win_allocate_shared)
+    pub fn allocate_shared<C: Communicator + ?Sized>(comm: &C, len: usize) -> Self {
+        assert!(
+            shares_memory(comm),
+            "Shared memory windows require a communicator whose processes share memory"
+        );
+
+        let mut window = unsafe { ffi::RSMPI_WIN_NULL };
+        let mut base: *mut T = ptr::null_mut();
+        let base_ptr: *mut *mut T = &mut base;
+        let _call = instrument::begin(|| Call::new("MPI_Win_allocate_shared", comm.as_raw()));
+        unsafe {
+            ffi::MPI_Win_allocate_shared(
+                byte_size::<T>(len),
+                disp_unit::<T>(),
+                ffi::RSMPI_INFO_NULL,
+                comm.as_raw(),
+                base_ptr as *mut c_void,
+                &mut window,
+            );
+            for i in 0..len {
+                ptr::write(base.add(i), T::default());
+            }
+            let _call = instrument::begin(|| Call::new("MPI_Barrier", comm.as_raw()));
+            ffi::MPI_Barrier(comm.as_raw());
+        }
+
+        Window {
+            window,
+            base,
+            len,
+            shared: true,
+            scope: StaticScope,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The memory of process `rank` in a window created by `Window::allocate_shared()`
+    ///
+    /// # Safety
+    ///
+    /// No other process may write to the segment, directly or through RMA operations, while the
+    /// returned slice is alive. Accesses to the memory of other processes have to be synchronized
+    /// with them, e.g. via `fence_epoch()` or a barrier.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window_shared.rs`
+    ///
+    /// # Panics
+    ///
+    /// If the window is not a shared memory window.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.3 (MPI_Win_shared_query)
+    pub unsafe fn shared_segment(&self, rank: Rank) -> &[T] {
+        let (base, len) = self.shared_query(rank);
+        if len == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(base, len)
+        }
+    }
+
+    /// Mutable access to the memory of process `rank` in a window created by
+    /// `Window::allocate_shared()`
+    ///
+    /// # Safety
+    ///
+    /// No other process may access the segment, directly or through RMA operations, while the
+    /// returned slice is alive, see `shared_segment()`.
+    ///
+    /// # Panics
+    ///
+    /// If the window is not a shared memory window.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.3 (MPI_Win_shared_query)
+    pub unsafe fn shared_segment_mut(&mut self, rank: Rank) -> &mut [T] {
+        let (base, len) = self.shared_query(rank);
+        if len == 0 {
+            &mut []
+        } else {
+            slice::from_raw_parts_mut(base, len)
+        }
+    }
+
+    fn shared_query(&self, rank: Rank) -> (*mut T, usize) {
+        assert!(
+            self.shared,
+            "Only windows created by Window::allocate_shared() have shared segments"
+        );
+
+        let mut size: Address = 0;
+        let mut disp_unit: c_int = 0;
+        let mut base: *mut T = ptr::null_mut();
+        let base_ptr: *mut *mut T = &mut base;
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_shared_query").peer(rank));
+        unsafe {
+            ffi::MPI_Win_shared_query(
+                self.window,
+                rank,
+                &mut size,
+                &mut disp_unit,
+                base_ptr as *mut c_void,
+            );
+        }
+        let size: usize = size.value_as().expect("Received an invalid segment size");
+        (base, size / mem::size_of::<T>())
+    }
+}
+
+impl<'a, T, S: Scope<'a>> fmt::Debug for Window<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Window")
+            .field("window", &self.window)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+unsafe impl<'a, T, S: Scope<'a>> AsRaw for Window<'a, T, S> {
+    type Raw = MPI_Win;
+    fn as_raw(&self) -> Self::Raw {
+        self.window
+    }
+}
+
+impl<'a, T, S: Scope<'a>> Drop for Window<'a, T, S> {
+    fn drop(&mut self) {
+        unsafe {
+            let _call = instrument::begin(|| Call::without_comm("MPI_Win_free"));
+            ffi::MPI_Win_free(&mut self.window);
+            self.scope.unregister();
+        }
+    }
+}
+
+/// Closes an epoch when it goes out of scope, so that no RMA operations on buffers that are
+/// dropped during unwinding are left pending when the function passed to the epoch panics
+struct CloseEpoch {
+    window: MPI_Win,
+    close: Close,
+}
+
+/// The synchronization call that closes an epoch
+enum Close {
+    Fence,
+    Unlock(Rank),
+    UnlockAll,
+}
+
+impl Drop for CloseEpoch {
+    fn drop(&mut self) {
+        unsafe {
+            match self.close {
+                Close::Fence => {
+                    let _call = instrument::begin(|| Call::without_comm("MPI_Win_fence"));
+                    ffi::MPI_Win_fence(0, self.window);
+                }
+                Close::Unlock(target) => {
+                    let _call =
+                        instrument::begin(|| Call::without_comm("MPI_Win_unlock").peer(target));
+                    ffi::MPI_Win_unlock(target, self.window);
+                }
+                Close::UnlockAll => {
+                    let _call = instrument::begin(|| Call::without_comm("MPI_Win_unlock_all"));
+                    ffi::MPI_Win_unlock_all(self.window);
+                }
+            }
+        }
+    }
+}
+
+/// A buffer that consists of `count()` elements of type `T`, which can be the origin of RMA
+/// operations on a `Window<T>`
+///
+/// The memory of the target window is accessed as the same elements, so it only ever holds valid
+/// values of its element type.
+pub unsafe trait Origin<T>: Buffer {}
+unsafe impl<T> Origin<T> for T where T: Equivalence {}
+unsafe impl<T> Origin<T> for [T] where T: Equivalence {}
+unsafe impl<T> Origin<T> for Vec<T> where T: Equivalence {}
+unsafe impl<T> Origin<T> for Box<[T]> where T: Equivalence {}
+unsafe impl<T, const D: usize> Origin<T> for [T; D] where T: Equivalence {}
+
+/// A mutable buffer that consists of `count()` elements of type `T`, which RMA operations on a
+/// `Window<T>` can read into
+pub unsafe trait OriginMut<T>: BufferMut {}
+unsafe impl<T> OriginMut<T> for T where T: Equivalence {}
+unsafe impl<T> OriginMut<T> for [T] where T: Equivalence {}
+unsafe impl<T> OriginMut<T> for Vec<T> where T: Equivalence {}
+unsafe impl<T> OriginMut<T> for Box<[T]> where T: Equivalence {}
+unsafe impl<T, const D: usize> OriginMut<T> for [T; D] where T: Equivalence {}
+
+/// RMA operations that can be issued inside an access epoch of a `Window<T>`
+///
+/// All buffers passed to RMA operations stay borrowed for `'b`, i.e. until the epoch has been
+/// closed, and consist of elements of the type `T` of the window.
+pub trait AccessEpoch<'b, T>: AsRaw<Raw = MPI_Win> {
+    /// Write the contents of `origin` to the window of process `target` starting at element
+    /// `target_disp`.
+    ///
+    /// The data is laid out in the window of the target process in the same way as in `origin`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.1 (MPI_Put)
+    fn put<B: ?Sized>(&self, origin: &'b B, target: Rank, target_disp: Address)
+    where
+        B: Origin<T>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Put").peer(target).buffer(origin));
+        unsafe {
+            ffi::MPI_Put(
+                origin.pointer(),
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                target,
+                target_disp,
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Read data from the window of process `target` starting at element `target_disp` into
+    /// `origin`.
+    ///
+    /// The data is laid out in the window of the target process in the same way as in `origin`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.2 (MPI_Get)
+    fn get<B: ?Sized>(&self, origin: &'b mut B, target: Rank, target_disp: Address)
+    where
+        B: OriginMut<T>,
+    {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Get").peer(target).buffer(origin));
+        unsafe {
+            ffi::MPI_Get(
+                origin.pointer_mut(),
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                target,
+                target_disp,
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Combine the contents of `origin` with the window of process `target` starting at element
+    /// `target_disp` under the operation `op`.
+    ///
+    /// Only built-in operations can be used, `SystemOperation::replace()` turns the accumulate into
+    /// an atomic put.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.4 (MPI_Accumulate)
+    fn accumulate<B: ?Sized>(
+        &self,
+        origin: &'b B,
+        target: Rank,
+        target_disp: Address,
+        op: SystemOperation,
+    ) where
+        B: Origin<T>,
+    {
+        let _call = instrument::begin(|| {
+            Call::without_comm("MPI_Accumulate")
+                .peer(target)
+                .buffer(origin)
+        });
+        unsafe {
+            ffi::MPI_Accumulate(
+                origin.pointer(),
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                target,
+                target_disp,
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                op.as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Combine the contents of `origin` with the window of process `target` starting at element
+    /// `target_disp` under the operation `op` and return the previous contents of the window in
+    /// `result`.
+    ///
+    /// The data is laid out in the window of the target process in the same way as in `origin`.
+    /// Only built-in operations can be used, `SystemOperation::no_op()` turns the operation into an
+    /// atomic get.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.4 (MPI_Get_accumulate)
+    fn get_accumulate<S: ?Sized, R: ?Sized>(
+        &self,
+        origin: &'b S,
+        result: &'b mut R,
+        target: Rank,
+        target_disp: Address,
+        op: SystemOperation,
+    ) where
+        S: Origin<T>,
+        R: OriginMut<T>,
+    {
+        let _call = instrument::begin(|| {
+            Call::without_comm("MPI_Get_accumulate")
+                .peer(target)
+                .buffer(origin)
+        });
+        unsafe {
+            ffi::MPI_Get_accumulate(
+                origin.pointer(),
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                result.pointer_mut(),
+                result.count(),
+                result.as_datatype().as_raw(),
+                target,
+                target_disp,
+                origin.count(),
+                origin.as_datatype().as_raw(),
+                op.as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Atomically combine the value `origin` with the element `target_disp` in the window of
+    /// process `target` under the operation `op` and return the previous value in `result`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window_atomics.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.4 (MPI_Fetch_and_op)
+    fn fetch_and_op(
+        &self,
+        origin: &'b T,
+        result: &'b mut T,
+        target: Rank,
+        target_disp: Address,
+        op: SystemOperation,
+    ) where
+        T: Equivalence,
+    {
+        let _call = instrument::begin(|| {
+            Call::without_comm("MPI_Fetch_and_op")
+                .peer(target)
+                .buffer(origin)
+        });
+        unsafe {
+            ffi::MPI_Fetch_and_op(
+                origin.pointer(),
+                result.pointer_mut(),
+                T::equivalent_datatype().as_raw(),
+                target,
+                target_disp,
+                op.as_raw(),
+                self.as_raw(),
+            );
+        }
+    }
+
+    /// Atomically replace the element `target_disp` in the window of process `target` with
+    /// `origin` if it is equal to `compare` and return the previous value in `result`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/window_atomics.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.4 (MPI_Compare_and_swap)
+    fn compare_and_swap(
+        &self,
+        origin: &'b T,
+        compare: &'b T,
+        result: &'b mut T,
+        target: Rank,
+        target_disp: Address,
+    ) where
+        T: Equivalence,
+    {
+        let _call = instrument::begin(|| {
+            Call::without_comm("MPI_Compare_and_swap")
+                .peer(target)
+                .buffer(origin)
+        });
+        unsafe {
+            ffi::MPI_Compare_and_swap(
+                origin.pointer(),
+                compare.pointer(),
+                result.pointer_mut(),
+                T::equivalent_datatype().as_raw(),
+                target,
+                target_disp,
+                self.as_raw(),
+            );
+        }
+    }
+}
+
+/// An access epoch opened by `Window::fence_epoch()`
+pub struct FenceEpoch<'w, 'b, T> {
+    window: MPI_Win,
+    phantom: PhantomData<(&'w [T], Cell<&'b ()>)>, // Cell needed to ensure 'b is invariant
+}
+
+unsafe impl<'w, 'b, T> AsRaw for FenceEpoch<'w, 'b, T> {
+    type Raw = MPI_Win;
+    fn as_raw(&self) -> Self::Raw {
+        self.window
+    }
+}
+
+impl<'w, 'b, T> AccessEpoch<'b, T> for FenceEpoch<'w, 'b, T> {}
+
+/// The kind of lock taken on the window of a target process by `Window::lock_epoch()`
+///
+/// # Standard section(s)
+///
+/// 11.5.3
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LockType {
+    /// No other process may access the window of the target process during the epoch
+    Exclusive,
+    /// Other processes holding a shared lock may access the window of the target process
+    /// concurrently
+    Shared,
+}
+
+impl LockType {
+    fn as_raw(self) -> c_int {
+        match self {
+            LockType::Exclusive => ffi::MPI_LOCK_EXCLUSIVE as c_int,
+            LockType::Shared => ffi::MPI_LOCK_SHARED as c_int,
+        }
+    }
+}
+
+/// A passive target access epoch opened by `Window::lock_epoch()` or `Window::lock_all_epoch()`
+///
+/// In addition to wrapping all RMA operations up when the epoch is closed, operations targeting
+/// a process can be completed early by flushing them.
+pub struct LockEpoch<'w, 'b, T> {
+    window: MPI_Win,
+    phantom: PhantomData<(&'w [T], Cell<&'b ()>)>, // Cell needed to ensure 'b is invariant
+}
+
+impl<'w, 'b, T> LockEpoch<'w, 'b, T> {
+    /// Complete all RMA operations issued to process `target` so far at the origin and the
+    /// target.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.4 (MPI_Win_flush)
+    pub fn flush(&self, target: Rank) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_flush").peer(target));
+        unsafe {
+            ffi::MPI_Win_flush(target, self.window);
+        }
+    }
+
+    /// Complete all RMA operations issued so far at the origin and all targets.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.4 (MPI_Win_flush_all)
+    pub fn flush_all(&self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_flush_all"));
+        unsafe {
+            ffi::MPI_Win_flush_all(self.window);
+        }
+    }
+
+    /// Complete all RMA operations issued to process `target` so far at the origin, the target
+    /// might not have received the data yet.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.4 (MPI_Win_flush_local)
+    pub fn flush_local(&self, target: Rank) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_flush_local").peer(target));
+        unsafe {
+            ffi::MPI_Win_flush_local(target, self.window);
+        }
+    }
+
+    /// Complete all RMA operations issued so far at the origin, the targets might not have
+    /// received the data yet.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.4 (MPI_Win_flush_local_all)
+    pub fn flush_local_all(&self) {
+        let _call = instrument::begin(|| Call::without_comm("MPI_Win_flush_local_all"));
+        unsafe {
+            ffi::MPI_Win_flush_local_all(self.window);
+        }
+    }
+}
+
+unsafe impl<'w, 'b, T> AsRaw for LockEpoch<'w, 'b, T> {
+    type Raw = MPI_Win;
+    fn as_raw(&self) -> Self::Raw {
+        self.window
+    }
+}
+
+impl<'w, 'b, T> AccessEpoch<'b, T> for LockEpoch<'w, 'b, T> {}
+
+fn byte_size<T>(len: usize) -> Address {
+    (len * mem::size_of::<T>())
+        .value_as()
+        .expect("Window size exceeds the range of MPI_Aint.")
+}
+
+fn disp_unit<T>() -> c_int {
+    // MPI requires a positive displacement unit, and segment sizes could not be converted back
+    // to a number of elements.
+    assert_ne!(
+        mem::size_of::<T>(),
+        0,
+        "Windows of zero-sized types are not supported."
+    );
+    mem::size_of::<T>()
+        .value_as()
+        .expect("Element size exceeds the range of a displacement unit.")
+}
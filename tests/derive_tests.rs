@@ -18,3 +18,24 @@ fn derive_equivalence() {
         field: [usize; 7],
     }
 }
+
+/// Fieldless enums with an integer representation are sent as that integer type, received into
+/// it and checked afterwards.
+#[test]
+fn derive_equivalence_enum() {
+    #[derive(Equivalence, Debug, PartialEq)]
+    #[repr(i32)]
+    enum Direction {
+        North = 1,
+        East,
+        South = 10,
+        West,
+    }
+
+    assert_eq!(Direction::try_from(2), Ok(Direction::East));
+    assert_eq!(Direction::try_from(11), Ok(Direction::West));
+    assert_eq!(Direction::try_from(3), Err(3));
+    assert_eq!(mpi::datatype::Collection::count(&Direction::East), 1);
+    let _ = Direction::North;
+    let _ = Direction::South;
+}